@@ -0,0 +1,164 @@
+// Hours-based attendance analytics.
+// Sessions are summarized by hours attended against hours required (using the same missed-hours
+// rules as the attendance policy), broken down by attendance type and bucketed into a trend.
+
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+use sqlx::{Pool, Sqlite};
+
+use crate::attendance_policy::hours_missed;
+use crate::models::{
+    Attendance, AttendanceAnalytics, AttendanceAnalyticsFilters, AttendanceBreakdown, AttendanceTrendPoint,
+    StudentAttendanceAnalytics,
+};
+
+fn percent(attended: f64, required: f64) -> f64 {
+    if required > 0.0 {
+        (attended / required * 100.0).clamp(0.0, 100.0)
+    } else {
+        100.0
+    }
+}
+
+/// Totals for a set of sessions, labelled with `attendance_type`.
+pub fn summarize(attendance_type: &str, records: &[&Attendance]) -> AttendanceBreakdown {
+    let mut breakdown = AttendanceBreakdown {
+        attendance_type: attendance_type.to_string(),
+        ..Default::default()
+    };
+    for record in records {
+        breakdown.sessions += 1;
+        match record.status.as_str() {
+            "Present" => breakdown.present += 1,
+            "Absent" => breakdown.absent += 1,
+            "Tardy" => breakdown.tardy += 1,
+            "Excused" => breakdown.excused += 1,
+            "Partial" => breakdown.partial += 1,
+            _ => {}
+        }
+        let required = record.hours_required.unwrap_or(8.0);
+        let missed = hours_missed(record).min(required);
+        breakdown.hours_required += required;
+        breakdown.hours_missed += missed;
+        breakdown.hours_attended += required - missed;
+        breakdown.tardy_minutes += record.minutes_late.unwrap_or(0);
+    }
+    breakdown.attendance_percent = percent(breakdown.hours_attended, breakdown.hours_required);
+    breakdown
+}
+
+fn by_type(records: &[&Attendance]) -> Vec<AttendanceBreakdown> {
+    let mut groups: BTreeMap<&str, Vec<&Attendance>> = BTreeMap::new();
+    for record in records {
+        groups
+            .entry(record.attendance_type.as_deref().unwrap_or("classroom"))
+            .or_default()
+            .push(record);
+    }
+    groups.iter().map(|(t, group)| summarize(t, group)).collect()
+}
+
+fn period_of(date: &str, monthly: bool) -> Option<String> {
+    let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(if monthly {
+        date.format("%Y-%m").to_string()
+    } else {
+        // Weeks start on Monday
+        let start = date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64);
+        start.format("%Y-%m-%d").to_string()
+    })
+}
+
+/// Per-period totals in date order.
+pub fn trend(records: &[&Attendance], monthly: bool) -> Vec<AttendanceTrendPoint> {
+    let mut periods: BTreeMap<String, Vec<&Attendance>> = BTreeMap::new();
+    for record in records {
+        if let Some(period) = period_of(&record.date, monthly) {
+            periods.entry(period).or_default().push(record);
+        }
+    }
+    periods
+        .into_iter()
+        .map(|(period, group)| {
+            let totals = summarize("all", &group);
+            AttendanceTrendPoint {
+                period,
+                sessions: totals.sessions,
+                hours_required: totals.hours_required,
+                hours_missed: totals.hours_missed,
+                attendance_percent: totals.attendance_percent,
+                absences: totals.absent,
+                tardy_minutes: totals.tardy_minutes,
+            }
+        })
+        .collect()
+}
+
+/// Loads the filtered sessions and summarizes them for the group and for each student.
+pub async fn analyze(pool: &Pool<Sqlite>, filters: &AttendanceAnalyticsFilters) -> Result<AttendanceAnalytics, sqlx::Error> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, first_name || ' ' || last_name FROM students
+        WHERE (? IS NULL OR id = ?) AND (? IS NULL OR cohort = ?)"
+    )
+    .bind(&filters.student_id)
+    .bind(&filters.student_id)
+    .bind(&filters.cohort)
+    .bind(&filters.cohort)
+    .fetch_all(pool)
+    .await?;
+    let names: BTreeMap<String, String> = rows.into_iter().collect();
+
+    let records = sqlx::query_as::<_, Attendance>(
+        "SELECT a.* FROM attendance a
+        JOIN students s ON s.id = a.student_id
+        WHERE (? IS NULL OR a.student_id = ?)
+          AND (? IS NULL OR s.cohort = ?)
+          AND (? IS NULL OR a.course_id = ?)
+          AND (? IS NULL OR COALESCE(a.attendance_type, 'classroom') = ?)
+          AND (? IS NULL OR a.date >= ?)
+          AND (? IS NULL OR a.date <= ?)
+        ORDER BY a.date"
+    )
+    .bind(&filters.student_id)
+    .bind(&filters.student_id)
+    .bind(&filters.cohort)
+    .bind(&filters.cohort)
+    .bind(&filters.course_id)
+    .bind(&filters.course_id)
+    .bind(&filters.attendance_type)
+    .bind(&filters.attendance_type)
+    .bind(&filters.start_date)
+    .bind(&filters.start_date)
+    .bind(&filters.end_date)
+    .bind(&filters.end_date)
+    .fetch_all(pool)
+    .await?;
+
+    let monthly = filters.trend_period.as_deref() == Some("month");
+    let all: Vec<&Attendance> = records.iter().collect();
+
+    let mut per_student: BTreeMap<&str, Vec<&Attendance>> = BTreeMap::new();
+    for record in &records {
+        per_student.entry(record.student_id.as_str()).or_default().push(record);
+    }
+
+    let mut students: Vec<StudentAttendanceAnalytics> = per_student
+        .into_iter()
+        .map(|(student_id, group)| StudentAttendanceAnalytics {
+            student_id: student_id.to_string(),
+            student_name: names.get(student_id).cloned().unwrap_or_default(),
+            total: summarize("all", &group),
+            by_type: by_type(&group),
+            trend: trend(&group, monthly),
+        })
+        .collect();
+    students.sort_by(|a, b| a.total.attendance_percent.total_cmp(&b.total.attendance_percent));
+
+    Ok(AttendanceAnalytics {
+        total: summarize("all", &all),
+        by_type: by_type(&all),
+        trend: trend(&all, monthly),
+        students,
+    })
+}
@@ -0,0 +1,277 @@
+// Attendance policies per attendance type.
+// A policy decides which sessions count as absences (including converted tardies and
+// partial attendance) and keeps the makeup_hours obligations linked to each session in sync.
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{Attendance, AttendancePolicy, AttendancePolicyStatus, MakeupHours};
+
+pub const ATTENDANCE_TYPES: &[&str] = &["classroom", "clinical", "lab"];
+
+// Tolerance when comparing hour amounts
+const HOURS_EPSILON: f64 = 0.01;
+
+/// Policies used until a program configures its own.
+pub fn default_policy(attendance_type: &str) -> AttendancePolicy {
+    let (tardies_per_absence, absence_hours_threshold, max_absences, makeup_required) = match attendance_type {
+        "clinical" => (Some(2), Some(4.0), Some(2), 1),
+        "lab" => (Some(3), Some(2.0), Some(3), 1),
+        _ => (Some(3), None, Some(5), 0),
+    };
+    AttendancePolicy {
+        attendance_type: attendance_type.to_string(),
+        tardies_per_absence,
+        absence_hours_threshold,
+        max_absences,
+        makeup_required,
+        excused_requires_makeup: 0,
+        makeup_due_days: 30,
+        updated_at: String::new(),
+    }
+}
+
+fn attendance_type(record: &Attendance) -> &str {
+    record.attendance_type.as_deref().unwrap_or("classroom")
+}
+
+/// Hours a session fell short of what was required.
+pub fn hours_missed(record: &Attendance) -> f64 {
+    let required = record.hours_required.unwrap_or(8.0);
+    match record.status.as_str() {
+        "Absent" | "Excused" => required,
+        status => match (record.hours_attended, record.minutes_late) {
+            (Some(attended), _) => (required - attended).max(0.0),
+            (None, Some(minutes)) if status == "Tardy" => (minutes as f64 / 60.0).min(required),
+            _ => 0.0,
+        },
+    }
+}
+
+/// Counts a student's sessions of one attendance type against the policy.
+pub fn evaluate(policy: &AttendancePolicy, records: &[Attendance]) -> AttendancePolicyStatus {
+    let mut absences = 0;
+    let mut excused = 0;
+    let mut tardies = 0;
+    let mut convertible_tardies = 0;
+    let mut hours_based_absences = 0;
+    let mut total_missed = 0.0;
+
+    for record in records {
+        let missed = hours_missed(record);
+        total_missed += missed;
+        match record.status.as_str() {
+            "Absent" => absences += 1,
+            "Excused" => excused += 1,
+            status => {
+                if status == "Tardy" {
+                    tardies += 1;
+                }
+                let past_threshold = policy
+                    .absence_hours_threshold
+                    .map(|threshold| missed > 0.0 && missed + HOURS_EPSILON >= threshold)
+                    .unwrap_or(false);
+                if past_threshold {
+                    hours_based_absences += 1;
+                } else if status == "Tardy" {
+                    convertible_tardies += 1;
+                }
+            }
+        }
+    }
+
+    let converted_tardy_absences = policy
+        .tardies_per_absence
+        .filter(|n| *n > 0)
+        .map(|n| convertible_tardies / n)
+        .unwrap_or(0);
+    let effective_absences = absences + hours_based_absences + converted_tardy_absences;
+
+    AttendancePolicyStatus {
+        attendance_type: policy.attendance_type.clone(),
+        sessions: records.len() as i32,
+        absences,
+        excused,
+        tardies,
+        hours_based_absences,
+        converted_tardy_absences,
+        effective_absences,
+        max_absences: policy.max_absences,
+        hours_missed: total_missed,
+        over_limit: policy.max_absences.map(|max| effective_absences > max).unwrap_or(false),
+    }
+}
+
+/// Every policy, with defaults filled in for types that were never configured.
+pub async fn load_policies(pool: &Pool<Sqlite>) -> Result<Vec<AttendancePolicy>, sqlx::Error> {
+    let saved = sqlx::query_as::<_, AttendancePolicy>("SELECT * FROM attendance_policies")
+        .fetch_all(pool)
+        .await?;
+
+    let mut policies: Vec<AttendancePolicy> = ATTENDANCE_TYPES
+        .iter()
+        .map(|t| {
+            saved
+                .iter()
+                .find(|p| p.attendance_type == *t)
+                .cloned()
+                .unwrap_or_else(|| default_policy(t))
+        })
+        .collect();
+    // Custom types a program added beyond the built-in three
+    policies.extend(saved.into_iter().filter(|p| !ATTENDANCE_TYPES.contains(&p.attendance_type.as_str())));
+    Ok(policies)
+}
+
+/// Evaluates a student's records for every attendance type they have sessions in.
+pub fn evaluate_all(policies: &[AttendancePolicy], records: &[Attendance]) -> Vec<AttendancePolicyStatus> {
+    let mut statuses = Vec::new();
+    for policy in policies {
+        let of_type: Vec<Attendance> = records
+            .iter()
+            .filter(|r| attendance_type(r) == policy.attendance_type)
+            .cloned()
+            .collect();
+        if !of_type.is_empty() {
+            statuses.push(evaluate(policy, &of_type));
+        }
+    }
+    statuses
+}
+
+fn makeup_status(hours_owed: f64, hours_completed: f64) -> &'static str {
+    if hours_completed + HOURS_EPSILON >= hours_owed {
+        "completed"
+    } else if hours_completed > 0.0 {
+        "in_progress"
+    } else {
+        "pending"
+    }
+}
+
+fn makeup_reason(record: &Attendance) -> String {
+    match (record.status.as_str(), record.hours_attended) {
+        ("Tardy", None) => format!(
+            "Tardy for {} on {}: {} minutes late",
+            attendance_type(record),
+            record.date,
+            record.minutes_late.unwrap_or(0)
+        ),
+        ("Absent", _) | ("Excused", _) | (_, None) => {
+            format!("{} {} absence on {}", record.status, attendance_type(record), record.date)
+        }
+        (_, Some(attended)) => format!(
+            "Partial {} attendance on {}: {}/{} hours",
+            attendance_type(record),
+            record.date,
+            attended,
+            record.hours_required.unwrap_or(8.0)
+        ),
+    }
+}
+
+/// Applies the policy for one student and attendance type: creates, resizes, re-opens or cancels the
+/// makeup_hours rows linked to each session. Makeup rows entered by hand (no linked session) are left alone.
+pub async fn apply(
+    pool: &Pool<Sqlite>,
+    student_id: &str,
+    attendance_type: &str,
+) -> Result<AttendancePolicyStatus, sqlx::Error> {
+    let policy = load_policies(pool)
+        .await?
+        .into_iter()
+        .find(|p| p.attendance_type == attendance_type)
+        .unwrap_or_else(|| default_policy(attendance_type));
+
+    let records = sqlx::query_as::<_, Attendance>(
+        "SELECT * FROM attendance WHERE student_id = ? AND COALESCE(attendance_type, 'classroom') = ? ORDER BY date"
+    )
+    .bind(student_id)
+    .bind(attendance_type)
+    .fetch_all(pool)
+    .await?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for record in &records {
+        let owed = if policy.makeup_required == 1
+            && (record.status != "Excused" || policy.excused_requires_makeup == 1)
+        {
+            hours_missed(record)
+        } else {
+            0.0
+        };
+
+        let existing = sqlx::query_as::<_, MakeupHours>(
+            "SELECT * FROM makeup_hours WHERE original_absence_id = ? ORDER BY created_at LIMIT 1"
+        )
+        .bind(&record.id)
+        .fetch_optional(pool)
+        .await?;
+
+        match existing {
+            None if owed > HOURS_EPSILON => {
+                let due_date = chrono::NaiveDate::parse_from_str(&record.date, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|d| d.checked_add_signed(chrono::TimeDelta::try_days(policy.makeup_due_days as i64)?))
+                    .map(|d| d.format("%Y-%m-%d").to_string());
+
+                sqlx::query(
+                    "INSERT INTO makeup_hours (id, student_id, original_absence_id, hours_owed, hours_completed, reason, due_date, completion_date, status, notes, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, 0, ?, ?, NULL, 'pending', NULL, ?, ?)"
+                )
+                .bind(format!("MKP-{}", uuid::Uuid::new_v4()))
+                .bind(student_id)
+                .bind(&record.id)
+                .bind(owed)
+                .bind(makeup_reason(record))
+                .bind(&due_date)
+                .bind(&now)
+                .bind(&now)
+                .execute(pool)
+                .await?;
+            }
+            Some(makeup) if owed > HOURS_EPSILON => {
+                let resized = (makeup.hours_owed - owed).abs() > HOURS_EPSILON;
+                if resized || makeup.status == "cancelled" {
+                    // Allocated makeup logs are re-applied FIFO below against the new amount
+                    let mut conn = pool.acquire().await?;
+                    crate::makeup_ledger::release_obligation(&mut *conn, &makeup.id).await?;
+                    let makeup = sqlx::query_as::<_, MakeupHours>("SELECT * FROM makeup_hours WHERE id = ?")
+                        .bind(&makeup.id)
+                        .fetch_one(&mut *conn)
+                        .await?;
+                    let status = makeup_status(owed, makeup.hours_completed);
+                    sqlx::query(
+                        "UPDATE makeup_hours SET hours_owed = ?, reason = ?, status = ?,
+                            completion_date = CASE WHEN ? = 'completed' THEN COALESCE(completion_date, ?) ELSE NULL END,
+                            updated_at = ?
+                        WHERE id = ?"
+                    )
+                    .bind(owed)
+                    .bind(makeup_reason(record))
+                    .bind(status)
+                    .bind(status)
+                    .bind(chrono::Local::now().format("%Y-%m-%d").to_string())
+                    .bind(&now)
+                    .bind(&makeup.id)
+                    .execute(&mut *conn)
+                    .await?;
+                }
+            }
+            Some(makeup) if makeup.status != "completed" && makeup.status != "cancelled" => {
+                // The session no longer owes hours (e.g. corrected to Present); its allocated
+                // makeup hours go back to the student's other obligations
+                let mut conn = pool.acquire().await?;
+                crate::makeup_ledger::release_obligation(&mut *conn, &makeup.id).await?;
+                sqlx::query("UPDATE makeup_hours SET status = 'cancelled', updated_at = ? WHERE id = ?")
+                    .bind(&now)
+                    .bind(&makeup.id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+
+    crate::makeup_ledger::sync_student(pool, student_id).await?;
+    Ok(evaluate(&policy, &records))
+}
@@ -0,0 +1,320 @@
+// Date-derived certification statuses for students and instructors.
+// A daily sweep keeps the stored statuses current, opens renewal deadlines for certifications
+// entering their alert window and emits `certifications:expired` for items that expired since the last sweep.
+
+use serde::Serialize;
+use sqlx::{Pool, Sqlite};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::DbState;
+
+// Student certifications have no per-record alert window
+pub const STUDENT_ALERT_DAYS: i64 = 30;
+const SWEEP_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiredCertification {
+    pub kind: String, // "student" | "instructor"
+    pub id: String,
+    pub certification_name: String,
+    pub holder_name: String,
+    pub expiry_date: String,
+}
+
+/// Status implied by the expiry date. "Pending" (not yet verified) is kept until the certification expires.
+pub fn derive_status(current: &str, expiry_date: &str, alert_days: i64, today: chrono::NaiveDate) -> String {
+    let Ok(expiry) = chrono::NaiveDate::parse_from_str(expiry_date.get(..10).unwrap_or(expiry_date), "%Y-%m-%d") else {
+        return current.to_string();
+    };
+    let days_left = (expiry - today).num_days();
+    if days_left < 0 {
+        "Expired".to_string()
+    } else if current == "Pending" {
+        current.to_string()
+    } else if days_left <= alert_days {
+        "Expiring Soon".to_string()
+    } else {
+        "Active".to_string()
+    }
+}
+
+/// What ties a certification to its renewals: the same holder, type and name (case-insensitive).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificationGroup {
+    student_id: Option<String>, // None for instructor certifications
+    certification_type: String,
+    certification_name: String,
+}
+
+struct SweepRow {
+    id: String,
+    certification_name: String,
+    expiry_date: String,
+    status: String,
+    alert_days: i64,
+    renewal_deadline_id: Option<String>,
+    student_id: Option<String>,
+    holder_name: String,
+    superseded: bool, // A later certification of the same type and name exists for the same holder
+}
+
+/// Opens a renewal deadline while a certification is expiring or expired, and completes it once renewed,
+/// either by moving its expiry date or by recording the renewal as a newer certification.
+async fn sync_renewal_deadline(
+    pool: &Pool<Sqlite>,
+    table: &str,
+    row: &SweepRow,
+    status: &str,
+    today: chrono::NaiveDate,
+) -> Result<(), sqlx::Error> {
+    let needs_renewal = !row.superseded && (status == "Expiring Soon" || status == "Expired");
+    match (&row.renewal_deadline_id, needs_renewal) {
+        (None, true) => {
+            let id = format!("DL-{}", uuid::Uuid::new_v4());
+            let urgent = chrono::NaiveDate::parse_from_str(&row.expiry_date, "%Y-%m-%d")
+                .map(|expiry| (expiry - today).num_days() <= 7)
+                .unwrap_or(true);
+            sqlx::query(
+                "INSERT INTO deadlines (id, title, description, due_date, deadline_type, related_student_id, status, priority, created_at)
+                VALUES (?, ?, ?, ?, 'certification', ?, 'Pending', ?, ?)"
+            )
+            .bind(&id)
+            .bind(format!("Renew {} ({})", row.certification_name, row.holder_name))
+            .bind(format!("{} expires {}", row.certification_name, row.expiry_date))
+            .bind(&row.expiry_date)
+            .bind(&row.student_id)
+            .bind(if urgent { "High" } else { "Medium" })
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+            sqlx::query(&format!("UPDATE {} SET renewal_deadline_id = ? WHERE id = ?", table))
+                .bind(&id)
+                .bind(&row.id)
+                .execute(pool)
+                .await?;
+        }
+        (Some(deadline_id), false) => {
+            // Renewed: the expiry date moved out of the alert window, or a newer certification replaced it
+            sqlx::query("UPDATE deadlines SET status = 'Completed' WHERE id = ? AND status != 'Completed'")
+                .bind(deadline_id)
+                .execute(pool)
+                .await?;
+            sqlx::query(&format!("UPDATE {} SET renewal_deadline_id = NULL WHERE id = ?", table))
+                .bind(&row.id)
+                .execute(pool)
+                .await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn sweep_rows(
+    pool: &Pool<Sqlite>,
+    table: &str,
+    kind: &str,
+    rows: Vec<SweepRow>,
+    today: chrono::NaiveDate,
+    expired: &mut Vec<ExpiredCertification>,
+) -> Result<(), sqlx::Error> {
+    for row in rows {
+        let status = derive_status(&row.status, &row.expiry_date, row.alert_days, today);
+        if status != row.status {
+            sqlx::query(&format!("UPDATE {} SET status = ? WHERE id = ?", table))
+                .bind(&status)
+                .bind(&row.id)
+                .execute(pool)
+                .await?;
+            if status == "Expired" {
+                expired.push(ExpiredCertification {
+                    kind: kind.to_string(),
+                    id: row.id.clone(),
+                    certification_name: row.certification_name.clone(),
+                    holder_name: row.holder_name.clone(),
+                    expiry_date: row.expiry_date.clone(),
+                });
+            }
+        }
+        sync_renewal_deadline(pool, table, &row, &status, today).await?;
+    }
+    Ok(())
+}
+
+// Student certifications, optionally only those in `group`
+async fn student_rows(pool: &Pool<Sqlite>, group: Option<&CertificationGroup>) -> Result<Vec<SweepRow>, sqlx::Error> {
+    let filter = if group.is_some() {
+        "WHERE c.student_id = ? AND c.certification_type = ? AND lower(c.certification_name) = lower(?)"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT c.id, c.certification_name, c.expiry_date, c.status, c.renewal_deadline_id, c.student_id,
+                s.first_name || ' ' || s.last_name,
+                EXISTS (
+                    SELECT 1 FROM student_certifications n
+                    WHERE n.student_id = c.student_id AND n.certification_type = c.certification_type
+                      AND lower(n.certification_name) = lower(c.certification_name)
+                      AND n.expiry_date > c.expiry_date AND n.id != c.id
+                )
+        FROM student_certifications c
+        JOIN students s ON s.id = c.student_id
+        {}",
+        filter
+    );
+    let mut query = sqlx::query_as::<_, (String, String, String, String, Option<String>, String, String, bool)>(&sql);
+    if let Some(group) = group {
+        query = query
+            .bind(&group.student_id)
+            .bind(&group.certification_type)
+            .bind(&group.certification_name);
+    }
+    let rows = query.fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, certification_name, expiry_date, status, renewal_deadline_id, student_id, holder_name, superseded)| SweepRow {
+            id,
+            certification_name,
+            expiry_date,
+            status,
+            alert_days: STUDENT_ALERT_DAYS,
+            renewal_deadline_id,
+            student_id: Some(student_id),
+            holder_name,
+            superseded,
+        })
+        .collect())
+}
+
+// Instructor certifications, optionally only those in `group`
+async fn instructor_rows(pool: &Pool<Sqlite>, group: Option<&CertificationGroup>) -> Result<Vec<SweepRow>, sqlx::Error> {
+    let filter = if group.is_some() {
+        "WHERE c.certification_type = ? AND lower(c.certification_name) = lower(?)"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT c.id, c.certification_name, c.expiry_date, c.status, c.alert_days, c.renewal_deadline_id,
+                EXISTS (
+                    SELECT 1 FROM instructor_certifications n
+                    WHERE n.certification_type = c.certification_type
+                      AND lower(n.certification_name) = lower(c.certification_name)
+                      AND n.expiry_date > c.expiry_date AND n.id != c.id
+                )
+        FROM instructor_certifications c
+        {}",
+        filter
+    );
+    let mut query = sqlx::query_as::<_, (String, String, String, String, i32, Option<String>, bool)>(&sql);
+    if let Some(group) = group {
+        query = query.bind(&group.certification_type).bind(&group.certification_name);
+    }
+    let rows = query.fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, certification_name, expiry_date, status, alert_days, renewal_deadline_id, superseded)| SweepRow {
+            id,
+            certification_name,
+            expiry_date,
+            status,
+            alert_days: alert_days as i64,
+            renewal_deadline_id,
+            student_id: None,
+            holder_name: "Instructor".to_string(),
+            superseded,
+        })
+        .collect())
+}
+
+/// Updates every stored status and renewal deadline. Returns the certifications that became expired.
+pub async fn sweep(pool: &Pool<Sqlite>, today: chrono::NaiveDate) -> Result<Vec<ExpiredCertification>, sqlx::Error> {
+    let mut expired = Vec::new();
+    let rows = student_rows(pool, None).await?;
+    sweep_rows(pool, "student_certifications", "student", rows, today, &mut expired).await?;
+    let rows = instructor_rows(pool, None).await?;
+    sweep_rows(pool, "instructor_certifications", "instructor", rows, today, &mut expired).await?;
+    Ok(expired)
+}
+
+/// The group of a stored student certification. Read it before an edit or delete so the
+/// certifications left behind in the old group can be synced afterwards.
+pub async fn student_group(pool: &Pool<Sqlite>, id: &str) -> Result<Option<CertificationGroup>, sqlx::Error> {
+    let row: Option<(String, String, String)> = sqlx::query_as(
+        "SELECT student_id, certification_type, certification_name FROM student_certifications WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(student_id, certification_type, certification_name)| CertificationGroup {
+        student_id: Some(student_id),
+        certification_type,
+        certification_name,
+    }))
+}
+
+/// Instructor counterpart of `student_group`.
+pub async fn instructor_group(pool: &Pool<Sqlite>, id: &str) -> Result<Option<CertificationGroup>, sqlx::Error> {
+    let row: Option<(String, String)> = sqlx::query_as(
+        "SELECT certification_type, certification_name FROM instructor_certifications WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|(certification_type, certification_name)| CertificationGroup {
+        student_id: None,
+        certification_type,
+        certification_name,
+    }))
+}
+
+/// Brings the statuses and renewal deadlines of every student certification in `group` up to date
+/// (a renewal completes the deadlines of the certifications it replaces).
+pub async fn sync_student_group(pool: &Pool<Sqlite>, group: &CertificationGroup) -> Result<(), sqlx::Error> {
+    let rows = student_rows(pool, Some(group)).await?;
+    sweep_rows(pool, "student_certifications", "student", rows, chrono::Local::now().date_naive(), &mut Vec::new()).await
+}
+
+/// Instructor counterpart of `sync_student_group`.
+pub async fn sync_instructor_group(pool: &Pool<Sqlite>, group: &CertificationGroup) -> Result<(), sqlx::Error> {
+    let rows = instructor_rows(pool, Some(group)).await?;
+    sweep_rows(pool, "instructor_certifications", "instructor", rows, chrono::Local::now().date_naive(), &mut Vec::new()).await
+}
+
+/// Brings a just-saved student certification's status and renewal deadline up to date, along with
+/// the student's other certifications of the same type and name.
+pub async fn sync_student_certification(pool: &Pool<Sqlite>, id: &str) -> Result<(), sqlx::Error> {
+    match student_group(pool, id).await? {
+        Some(group) => sync_student_group(pool, &group).await,
+        None => Ok(()),
+    }
+}
+
+/// Instructor counterpart of `sync_student_certification`.
+pub async fn sync_instructor_certification(pool: &Pool<Sqlite>, id: &str) -> Result<(), sqlx::Error> {
+    match instructor_group(pool, id).await? {
+        Some(group) => sync_instructor_group(pool, &group).await,
+        None => Ok(()),
+    }
+}
+
+/// Sweeps as of today and emits `certifications:expired` when anything newly expired.
+pub async fn run_sweep(app: &AppHandle, pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let expired = sweep(pool, chrono::Local::now().date_naive()).await?;
+    if !expired.is_empty() {
+        let _ = app.emit("certifications:expired", expired);
+    }
+    Ok(())
+}
+
+/// Runs the sweep at startup and then once a day for as long as the app is open.
+pub fn spawn_daily_sweep(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let pool = app.state::<DbState>().db.clone();
+            if let Err(e) = run_sweep(&app, &pool).await {
+                log::error!("Certification sweep failed: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(SWEEP_INTERVAL_SECS)).await;
+        }
+    });
+}
@@ -0,0 +1,175 @@
+// Clinical clearance against the certification requirements catalog.
+// A requirement applies to a student when its cohort and site (if set) match; it is met on a date
+// when a matching certification was issued on or before that date and is still valid on it.
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{CertificationRequirement, ClinicalClearance, RequirementClearance, Student, StudentCertification};
+
+/// `(name, certification_type, name_keyword, validity_months)` seeded into an empty catalog. They start
+/// inactive so existing schedules keep working until the program turns on the requirements it enforces.
+const DEFAULT_REQUIREMENTS: &[(&str, &str, Option<&str>, Option<i32>)] = &[
+    ("BLS certification", "BLS", None, Some(24)),
+    ("TB screening", "Immunization", Some("TB"), Some(12)),
+    ("Influenza vaccine", "Immunization", Some("flu"), Some(12)),
+    ("Hepatitis B series", "Immunization", Some("Hep"), None),
+    ("Background check", "Background Check", None, None),
+    ("Drug screen", "Drug Screen", None, Some(12)),
+];
+
+pub async fn seed_default_requirements(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let count: (i32,) = sqlx::query_as("SELECT COUNT(*) FROM certification_requirements")
+        .fetch_one(pool)
+        .await?;
+    if count.0 > 0 {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for (name, certification_type, name_keyword, validity_months) in DEFAULT_REQUIREMENTS {
+        sqlx::query(
+            "INSERT INTO certification_requirements (id, name, certification_type, name_keyword, cohort, site_id, validity_months, is_active, notes, created_at)
+            VALUES (?, ?, ?, ?, NULL, NULL, ?, 0, NULL, ?)"
+        )
+        .bind(format!("CRQ-{}", uuid::Uuid::new_v4()))
+        .bind(name)
+        .bind(certification_type)
+        .bind(name_keyword)
+        .bind(validity_months)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+fn parse_date(date: &str) -> Option<chrono::NaiveDate> {
+    // Certification dates are sometimes stored with a time component
+    chrono::NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d").ok()
+}
+
+fn matches(requirement: &CertificationRequirement, cert: &StudentCertification) -> bool {
+    cert.certification_type.eq_ignore_ascii_case(&requirement.certification_type)
+        && requirement
+            .name_keyword
+            .as_deref()
+            .map(|keyword| cert.certification_name.to_lowercase().contains(&keyword.to_lowercase()))
+            .unwrap_or(true)
+}
+
+/// Last date a certification satisfies the requirement: its expiry date, or earlier when the
+/// requirement's validity period from the issue date runs out first.
+pub fn valid_until(requirement: &CertificationRequirement, cert: &StudentCertification) -> Option<chrono::NaiveDate> {
+    let expiry = parse_date(&cert.expiry_date);
+    let by_validity = requirement.validity_months.zip(cert.issue_date.as_deref().and_then(parse_date)).and_then(
+        |(months, issued)| issued.checked_add_months(chrono::Months::new(months.max(0) as u32)),
+    );
+    match (expiry, by_validity) {
+        (Some(expiry), Some(limit)) => Some(expiry.min(limit)),
+        (expiry, limit) => expiry.or(limit),
+    }
+}
+
+fn in_force(cert: &StudentCertification, until: Option<chrono::NaiveDate>, date: chrono::NaiveDate) -> bool {
+    let issued = cert.issue_date.as_deref().and_then(parse_date).map(|d| d <= date).unwrap_or(true);
+    // No expiry date and no validity period: the certification doesn't lapse
+    issued && until.map(|u| u >= date).unwrap_or(true)
+}
+
+/// Checks one requirement against a student's certifications on `date`.
+pub fn check_requirement(
+    requirement: &CertificationRequirement,
+    certifications: &[StudentCertification],
+    date: chrono::NaiveDate,
+) -> RequirementClearance {
+    let mut candidates: Vec<(&StudentCertification, Option<chrono::NaiveDate>)> = certifications
+        .iter()
+        .filter(|cert| matches(requirement, cert))
+        .map(|cert| (cert, valid_until(requirement, cert)))
+        .collect();
+    // Longest-valid certification first
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let (status, chosen) = if let Some(found) =
+        candidates.iter().find(|(c, u)| in_force(c, *u, date) && c.status != "Pending")
+    {
+        ("met", Some(found))
+    } else if let Some(found) = candidates.iter().find(|(c, u)| in_force(c, *u, date)) {
+        ("pending", Some(found))
+    } else if let Some(found) = candidates.first() {
+        ("expired", Some(found))
+    } else {
+        ("missing", None)
+    };
+
+    RequirementClearance {
+        requirement_id: requirement.id.clone(),
+        requirement_name: requirement.name.clone(),
+        status: status.to_string(),
+        certification_id: chosen.map(|(cert, _)| cert.id.clone()),
+        valid_until: chosen.and_then(|(_, until)| until.map(|d| d.format("%Y-%m-%d").to_string())),
+    }
+}
+
+/// Active requirements that apply to a cohort at a site.
+pub async fn applicable_requirements(
+    pool: &Pool<Sqlite>,
+    cohort: &str,
+    site_id: &str,
+) -> Result<Vec<CertificationRequirement>, sqlx::Error> {
+    sqlx::query_as::<_, CertificationRequirement>(
+        "SELECT * FROM certification_requirements
+        WHERE is_active = 1 AND (cohort IS NULL OR cohort = ?) AND (site_id IS NULL OR site_id = ?)
+        ORDER BY name"
+    )
+    .bind(cohort)
+    .bind(site_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Whether a student is cleared for clinical at `site_id` on `date` (YYYY-MM-DD).
+pub async fn check(pool: &Pool<Sqlite>, student: &Student, site_id: &str, date: &str) -> Result<ClinicalClearance, String> {
+    let on = parse_date(date).ok_or_else(|| format!("Invalid date '{}'", date))?;
+
+    let requirements = applicable_requirements(pool, &student.cohort, site_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let certifications = sqlx::query_as::<_, StudentCertification>(
+        "SELECT * FROM student_certifications WHERE student_id = ?"
+    )
+    .bind(&student.id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let requirements: Vec<RequirementClearance> = requirements
+        .iter()
+        .map(|requirement| check_requirement(requirement, &certifications, on))
+        .collect();
+
+    Ok(ClinicalClearance {
+        student_id: student.id.clone(),
+        student_name: format!("{} {}", student.first_name, student.last_name),
+        site_id: site_id.to_string(),
+        date: date.to_string(),
+        cleared: requirements.iter().all(|r| r.status == "met"),
+        requirements,
+    })
+}
+
+/// Error message listing what keeps a student from being cleared.
+pub fn describe_blockers(clearance: &ClinicalClearance) -> String {
+    let blockers: Vec<String> = clearance
+        .requirements
+        .iter()
+        .filter(|r| r.status != "met")
+        .map(|r| format!("{} ({})", r.requirement_name, r.status))
+        .collect();
+    format!(
+        "{} is not cleared for clinical on {}: {}",
+        clearance.student_name,
+        clearance.date,
+        blockers.join(", ")
+    )
+}
@@ -5,11 +5,23 @@ use crate::models::{
     LessonPlan, TeachingMaterial, Attendance, StudentCertification,
     PreceptorEvaluation, Deadline, LessonPlanWithMaterials, AttendanceSummary, CertificationAlert,
     SkillValidation, StudentHoursBySite, StudentSimulationSummary, StudentFlag,
-    MakeupHours, MakeupHoursSummary,
+    MakeupHours, MakeupHoursSummary, MakeupAllocation,
     InstructorCertification, InstructorCertificationAlert,
-    CompHoursEarned, CompHoursUsed, CompHoursSummary, CompHoursExpirationWarning,
+    CompHoursEarned, CompHoursUsed, CompHoursSummary, CompHoursExpirationWarning, CompHoursLedger,
+    CompHoursPolicy, CompTimesheet, CompTimesheetRequest,
     Course,
-    VBONRegulation, VBONMapping, VBONRegulationWithMapping, VBONComplianceSummary, VBONCategoryStats
+    RegulatoryFramework, FrameworkComplianceSummary,
+    RegulationCatalogEdition, RegulationCatalogDiff, RegulationCatalogImportRequest, RegulationCatalogImportResult,
+    VBONRegulation, VBONMapping, VBONRegulationWithMapping, VBONComplianceSummary,
+    FlagRule, FlagRuleSet, FlagRulePreview,
+    CohortFlagFilters, CohortFlagReport, FlagTypeCount, FlagSeverityCounts, AtRiskStudent,
+    StudentFlagRecord, FlagTimelineEvent,
+    NclexOutcome, NclexModelInfo, NclexPrediction,
+    RemediationPlan, RemediationActivity, RemediationCheckpoint, RemediationPlanWithDetails,
+    AttendancePolicy, AttendancePolicyStatus, AttendanceAnalytics, AttendanceAnalyticsFilters,
+    CertificationRequirement, ClinicalClearance,
+    AcademicHoliday, CourseSchedule, SemesterScheduleRequest, SemesterScheduleResult, RescheduleRequest, RescheduleResult,
+    CourseCloneRequest, CourseCloneResult
 };
 use crate::db::DbState;
 use std::collections::HashMap;
@@ -176,11 +188,16 @@ pub async fn update_student_notes(state: State<'_, DbState>, id: String, notes:
 
 #[tauri::command]
 pub async fn add_clinical_log(state: State<'_, DbState>, log: ClinicalLog) -> Result<(), String> {
+    let site_id = match log.site_id {
+        Some(ref id) => Some(id.clone()),
+        None => crate::db::resolve_site_id(&state.db, &log.site_name).await.map_err(|e| e.to_string())?,
+    };
+
     sqlx::query(
         "INSERT INTO clinical_logs (
             id, student_id, date, site_name, patient_diagnosis, mapped_competencies, status, instructor_feedback,
-            hours, is_simulation, is_makeup
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            hours, is_simulation, is_makeup, site_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&log.id)
     .bind(&log.student_id)
@@ -193,10 +210,14 @@ pub async fn add_clinical_log(state: State<'_, DbState>, log: ClinicalLog) -> Re
     .bind(&log.hours.unwrap_or(8.0))
     .bind(&log.is_simulation.unwrap_or(0))
     .bind(&log.is_makeup.unwrap_or(0))
+    .bind(&site_id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    crate::makeup_ledger::sync_log(&state.db, &log.id, &[&log.student_id])
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -322,10 +343,22 @@ pub async fn update_student(state: State<'_, DbState>, student: Student) -> Resu
 #[tauri::command]
 pub async fn approve_clinical_log(state: State<'_, DbState>, log_id: String) -> Result<(), String> {
     sqlx::query("UPDATE clinical_logs SET status = 'Approved' WHERE id = ?")
-        .bind(log_id)
+        .bind(&log_id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+
+    // Approved makeup logs pay down the student's open makeup hours
+    let student: Option<(String,)> = sqlx::query_as("SELECT student_id FROM clinical_logs WHERE id = ?")
+        .bind(&log_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some((student_id,)) = student {
+        crate::makeup_ledger::sync_log(&state.db, &log_id, &[&student_id])
+            .await
+            .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
@@ -350,8 +383,8 @@ pub async fn add_grade(state: State<'_, DbState>, grade: Grade) -> Result<(), St
 #[tauri::command]
 pub async fn add_event(state: State<'_, DbState>, event: CalendarEvent) -> Result<(), String> {
     sqlx::query(
-        "INSERT INTO calendar_events (id, date, title, event_type, location, proctor, status, description)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO calendar_events (id, date, title, event_type, location, proctor, status, description, lesson_plan_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&event.id)
     .bind(&event.date)
@@ -361,6 +394,7 @@ pub async fn add_event(state: State<'_, DbState>, event: CalendarEvent) -> Resul
     .bind(&event.proctor)
     .bind(&event.status)
     .bind(&event.description)
+    .bind(&event.lesson_plan_id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
@@ -415,6 +449,15 @@ pub async fn delete_student(state: State<'_, DbState>, id: String) -> Result<(),
         .await
         .map_err(|e| e.to_string())?;
     
+    // Delete makeup allocations made from the student's clinical logs
+    sqlx::query(
+        "DELETE FROM makeup_allocations WHERE clinical_log_id IN (SELECT id FROM clinical_logs WHERE student_id = ?)"
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
     // Delete associated clinical logs
     sqlx::query("DELETE FROM clinical_logs WHERE student_id = ?")
         .bind(&id)
@@ -422,6 +465,30 @@ pub async fn delete_student(state: State<'_, DbState>, id: String) -> Result<(),
         .await
         .map_err(|e| e.to_string())?;
     
+    // Delete remediation plans with their activities, checkpoints and deadlines
+    let plan_ids: Vec<(String,)> = sqlx::query_as("SELECT id FROM remediation_plans WHERE student_id = ?")
+        .bind(&id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    for (plan_id,) in plan_ids {
+        delete_remediation_plan(state.clone(), plan_id).await?;
+    }
+
+    // Delete NCLEX outcomes
+    sqlx::query("DELETE FROM nclex_outcomes WHERE student_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Delete flag history
+    sqlx::query("DELETE FROM student_flag_history WHERE student_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
     // Delete student
     sqlx::query("DELETE FROM students WHERE id = ?")
         .bind(id)
@@ -433,20 +500,54 @@ pub async fn delete_student(state: State<'_, DbState>, id: String) -> Result<(),
 
 #[tauri::command]
 pub async fn delete_clinical_log(state: State<'_, DbState>, log_id: String) -> Result<(), String> {
+    let student: Option<(String,)> = sqlx::query_as("SELECT student_id FROM clinical_logs WHERE id = ?")
+        .bind(&log_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    crate::makeup_ledger::release_log(&mut *tx, &log_id).await.map_err(|e| e.to_string())?;
     sqlx::query("DELETE FROM clinical_logs WHERE id = ?")
-        .bind(log_id)
-        .execute(&state.db)
+        .bind(&log_id)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    // Hours the log had covered may be picked up by the student's other makeup logs
+    if let Some((student_id,)) = student {
+        crate::makeup_ledger::sync_student(&state.db, &student_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub async fn update_clinical_log(state: State<'_, DbState>, log: ClinicalLog) -> Result<(), String> {
+    let previous: Option<(String, String, Option<String>)> =
+        sqlx::query_as("SELECT student_id, site_name, site_id FROM clinical_logs WHERE id = ?")
+            .bind(&log.id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    // When the site name changed, the site is matched again unless the caller picked the new site id
+    // too; the edited log otherwise still carries the old site's id
+    let site_name_changed = previous
+        .as_ref()
+        .map(|(_, site_name, site_id)| *site_name != log.site_name && *site_id == log.site_id)
+        .unwrap_or(false);
+    let site_id = match log.site_id {
+        Some(ref id) if !site_name_changed => Some(id.clone()),
+        _ => crate::db::resolve_site_id(&state.db, &log.site_name).await.map_err(|e| e.to_string())?,
+    };
+
     sqlx::query(
         "UPDATE clinical_logs SET
         student_id = ?, date = ?, site_name = ?, patient_diagnosis = ?, mapped_competencies = ?, status = ?, instructor_feedback = ?,
-        hours = ?, is_simulation = ?, is_makeup = ?
+        hours = ?, is_simulation = ?, is_makeup = ?, site_id = ?
         WHERE id = ?"
     )
     .bind(&log.student_id)
@@ -459,11 +560,20 @@ pub async fn update_clinical_log(state: State<'_, DbState>, log: ClinicalLog) ->
     .bind(&log.hours.unwrap_or(8.0))
     .bind(&log.is_simulation.unwrap_or(0))
     .bind(&log.is_makeup.unwrap_or(0))
+    .bind(&site_id)
     .bind(&log.id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    // Re-allocate in case the hours, approval, makeup flag or student changed
+    let mut students = vec![log.student_id.as_str()];
+    if let Some((previous_student, _, _)) = previous.as_ref().filter(|(id, _, _)| *id != log.student_id) {
+        students.insert(0, previous_student.as_str());
+    }
+    crate::makeup_ledger::sync_log(&state.db, &log.id, &students)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -571,6 +681,10 @@ pub async fn create_lesson_plan(state: State<'_, DbState>, lesson_plan: LessonPl
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    crate::regulation_links::save_lesson_plan_tags(&state.db, &lesson_plan.id, lesson_plan.vbon_tags.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -598,19 +712,68 @@ pub async fn update_lesson_plan(state: State<'_, DbState>, lesson_plan: LessonPl
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    // Callers that don't edit tags leave them out, which keeps the plan's regulation links
+    crate::regulation_links::save_lesson_plan_tags(&state.db, &lesson_plan.id, lesson_plan.vbon_tags.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_lesson_plan(state: State<'_, DbState>, id: String) -> Result<(), String> {
-    // Delete associated materials first
-    sqlx::query("DELETE FROM teaching_materials WHERE lesson_plan_id = ?")
-        .bind(&id)
-        .execute(&state.db)
+    // Regulation links, materials, scheduled calendar events and the plan go together or not at all
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    crate::course_schedule::delete_session(&mut *tx, &id)
         .await
         .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+// ==================== SEMESTER SCHEDULING ====================
+
+#[tauri::command]
+pub async fn generate_semester_schedule(
+    state: State<'_, DbState>,
+    request: SemesterScheduleRequest,
+) -> Result<SemesterScheduleResult, String> {
+    crate::course_schedule::generate(&state.db, &request).await
+}
+
+#[tauri::command]
+pub async fn reschedule_course_session(
+    state: State<'_, DbState>,
+    request: RescheduleRequest,
+) -> Result<RescheduleResult, String> {
+    crate::course_schedule::reschedule(&state.db, &request).await
+}
+
+#[tauri::command]
+pub async fn get_course_schedule(state: State<'_, DbState>, course_id: String) -> Result<Option<CourseSchedule>, String> {
+    sqlx::query_as::<_, CourseSchedule>("SELECT * FROM course_schedules WHERE course_id = ?")
+        .bind(course_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_academic_holidays(state: State<'_, DbState>) -> Result<Vec<AcademicHoliday>, String> {
+    sqlx::query_as::<_, AcademicHoliday>("SELECT * FROM academic_holidays ORDER BY date")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_academic_holiday(state: State<'_, DbState>, holiday: AcademicHoliday) -> Result<(), String> {
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    crate::course_schedule::save_holiday(&mut *conn, &holiday).await
+}
 
-    sqlx::query("DELETE FROM lesson_plans WHERE id = ?")
+#[tauri::command]
+pub async fn delete_academic_holiday(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM academic_holidays WHERE id = ?")
         .bind(id)
         .execute(&state.db)
         .await
@@ -682,48 +845,150 @@ pub async fn get_materials_for_lesson(state: State<'_, DbState>, lesson_plan_id:
 
 // ==================== ATTENDANCE ====================
 
+// Re-recording the same student/date/type updates the row in place so makeup_hours rows
+// linked through original_absence_id keep pointing at it.
+const UPSERT_ATTENDANCE: &str =
+    "INSERT INTO attendance (id, student_id, date, status, notes, recorded_at, attendance_type, hours_attended, hours_required, minutes_late, course_id)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(student_id, date, attendance_type) DO UPDATE SET
+        status = excluded.status, notes = excluded.notes, recorded_at = excluded.recorded_at,
+        hours_attended = excluded.hours_attended, hours_required = excluded.hours_required,
+        minutes_late = excluded.minutes_late, course_id = excluded.course_id";
+
+async fn upsert_attendance(pool: &sqlx::Pool<sqlx::Sqlite>, record: &Attendance) -> Result<(), sqlx::Error> {
+    sqlx::query(UPSERT_ATTENDANCE)
+        .bind(&record.id)
+        .bind(&record.student_id)
+        .bind(&record.date)
+        .bind(&record.status)
+        .bind(&record.notes)
+        .bind(&record.recorded_at)
+        .bind(record.attendance_type.as_deref().unwrap_or("classroom"))
+        .bind(&record.hours_attended)
+        .bind(record.hours_required.unwrap_or(8.0))
+        .bind(record.minutes_late)
+        .bind(&record.course_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Applies the attendance policy for each `(student_id, attendance_type)` pair, then refreshes
+/// the flags of every affected student.
+async fn apply_attendance_policies(pool: &sqlx::Pool<sqlx::Sqlite>, pairs: Vec<(String, String)>) -> Result<(), String> {
+    let pairs: std::collections::BTreeSet<(String, String)> = pairs.into_iter().collect();
+    for (student_id, attendance_type) in &pairs {
+        crate::attendance_policy::apply(pool, student_id, attendance_type)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let students: std::collections::BTreeSet<&String> = pairs.iter().map(|(student_id, _)| student_id).collect();
+    for student_id in students {
+        crate::flag_rules::refresh_student_flags(pool, student_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn attendance_key(record: &Attendance) -> (String, String) {
+    (
+        record.student_id.clone(),
+        record.attendance_type.clone().unwrap_or_else(|| "classroom".to_string()),
+    )
+}
+
 #[tauri::command]
 pub async fn record_attendance(state: State<'_, DbState>, attendance: Attendance) -> Result<(), String> {
+    upsert_attendance(&state.db, &attendance).await.map_err(|e| e.to_string())?;
+    apply_attendance_policies(&state.db, vec![attendance_key(&attendance)]).await
+}
+
+#[tauri::command]
+pub async fn bulk_record_attendance(state: State<'_, DbState>, records: Vec<Attendance>) -> Result<(), String> {
+    for record in &records {
+        upsert_attendance(&state.db, record).await.map_err(|e| e.to_string())?;
+    }
+    apply_attendance_policies(&state.db, records.iter().map(attendance_key).collect()).await
+}
+
+#[tauri::command]
+pub async fn get_attendance_policies(state: State<'_, DbState>) -> Result<Vec<AttendancePolicy>, String> {
+    crate::attendance_policy::load_policies(&state.db).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_attendance_policy(state: State<'_, DbState>, policy: AttendancePolicy) -> Result<AttendancePolicy, String> {
+    if policy.attendance_type.trim().is_empty() {
+        return Err("Attendance type is required".to_string());
+    }
+    if policy.tardies_per_absence.is_some_and(|n| n < 1) {
+        return Err("Tardies per absence must be at least 1".to_string());
+    }
+    if policy.absence_hours_threshold.is_some_and(|h| h <= 0.0) {
+        return Err("Absence hours threshold must be greater than 0".to_string());
+    }
+    if policy.max_absences.is_some_and(|n| n < 0) || policy.makeup_due_days < 0 {
+        return Err("Maximum absences and makeup due days cannot be negative".to_string());
+    }
+
+    let pool = &state.db;
+    let saved = AttendancePolicy {
+        updated_at: chrono::Utc::now().to_rfc3339(),
+        ..policy
+    };
     sqlx::query(
-        "INSERT OR REPLACE INTO attendance (id, student_id, date, status, notes, recorded_at, attendance_type, hours_attended, hours_required)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO attendance_policies (attendance_type, tardies_per_absence, absence_hours_threshold, max_absences, makeup_required, excused_requires_makeup, makeup_due_days, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(attendance_type) DO UPDATE SET
+            tardies_per_absence = excluded.tardies_per_absence, absence_hours_threshold = excluded.absence_hours_threshold,
+            max_absences = excluded.max_absences, makeup_required = excluded.makeup_required,
+            excused_requires_makeup = excluded.excused_requires_makeup, makeup_due_days = excluded.makeup_due_days,
+            updated_at = excluded.updated_at"
     )
-    .bind(&attendance.id)
-    .bind(&attendance.student_id)
-    .bind(&attendance.date)
-    .bind(&attendance.status)
-    .bind(&attendance.notes)
-    .bind(&attendance.recorded_at)
-    .bind(&attendance.attendance_type.as_deref().unwrap_or("classroom"))
-    .bind(&attendance.hours_attended)
-    .bind(&attendance.hours_required.unwrap_or(8.0))
-    .execute(&state.db)
+    .bind(&saved.attendance_type)
+    .bind(saved.tardies_per_absence)
+    .bind(saved.absence_hours_threshold)
+    .bind(saved.max_absences)
+    .bind(saved.makeup_required)
+    .bind(saved.excused_requires_makeup)
+    .bind(saved.makeup_due_days)
+    .bind(&saved.updated_at)
+    .execute(pool)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    // Re-apply to every student with sessions of this type so makeup obligations and flags follow the new policy
+    let students: Vec<(String,)> = sqlx::query_as(
+        "SELECT DISTINCT student_id FROM attendance WHERE COALESCE(attendance_type, 'classroom') = ?"
+    )
+    .bind(&saved.attendance_type)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    apply_attendance_policies(
+        pool,
+        students.into_iter().map(|(id,)| (id, saved.attendance_type.clone())).collect(),
+    )
+    .await?;
+
+    Ok(saved)
 }
 
 #[tauri::command]
-pub async fn bulk_record_attendance(state: State<'_, DbState>, records: Vec<Attendance>) -> Result<(), String> {
-    for record in records {
-        sqlx::query(
-            "INSERT OR REPLACE INTO attendance (id, student_id, date, status, notes, recorded_at, attendance_type, hours_attended, hours_required)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
-        )
-        .bind(&record.id)
-        .bind(&record.student_id)
-        .bind(&record.date)
-        .bind(&record.status)
-        .bind(&record.notes)
-        .bind(&record.recorded_at)
-        .bind(&record.attendance_type.as_deref().unwrap_or("classroom"))
-        .bind(&record.hours_attended)
-        .bind(&record.hours_required.unwrap_or(8.0))
-        .execute(&state.db)
+pub async fn get_student_attendance_policy_status(
+    state: State<'_, DbState>,
+    student_id: String
+) -> Result<Vec<AttendancePolicyStatus>, String> {
+    let pool = &state.db;
+    let policies = crate::attendance_policy::load_policies(pool).await.map_err(|e| e.to_string())?;
+    let records = sqlx::query_as::<_, Attendance>("SELECT * FROM attendance WHERE student_id = ?")
+        .bind(&student_id)
+        .fetch_all(pool)
         .await
         .map_err(|e| e.to_string())?;
-    }
-    Ok(())
+    Ok(crate::attendance_policy::evaluate_all(&policies, &records))
 }
 
 #[tauri::command]
@@ -752,56 +1017,74 @@ pub async fn get_student_attendance(state: State<'_, DbState>, student_id: Strin
 pub async fn get_students_with_attendance_issues(state: State<'_, DbState>, min_absences: i32) -> Result<Vec<AttendanceSummary>, String> {
     let pool = &state.db;
 
-    // Get all students first
     let students = sqlx::query_as::<_, Student>("SELECT * FROM students")
         .fetch_all(pool)
         .await
         .map_err(|e| e.to_string())?;
 
-    let mut summaries = Vec::new();
-
-    for student in students {
-        let absences: (i32,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM attendance WHERE student_id = ? AND status = 'Absent'"
-        )
-        .bind(&student.id)
-        .fetch_one(pool)
+    let mut records: HashMap<String, Vec<Attendance>> = HashMap::new();
+    for record in sqlx::query_as::<_, Attendance>("SELECT * FROM attendance")
+        .fetch_all(pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())?
+    {
+        records.entry(record.student_id.clone()).or_default().push(record);
+    }
 
-        let tardies: (i32,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM attendance WHERE student_id = ? AND status = 'Tardy'"
-        )
-        .bind(&student.id)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut summaries = Vec::new();
 
-        let present: (i32,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM attendance WHERE student_id = ? AND status = 'Present'"
-        )
-        .bind(&student.id)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| e.to_string())?;
+    for student in students {
+        let student_records: Vec<&Attendance> = records.get(&student.id).map(|r| r.iter().collect()).unwrap_or_default();
+        let total = crate::attendance_analytics::summarize("all", &student_records);
+        let of_type = |attendance_type: &str| {
+            let group: Vec<&Attendance> = student_records
+                .iter()
+                .copied()
+                .filter(|r| r.attendance_type.as_deref().unwrap_or("classroom") == attendance_type)
+                .collect();
+            crate::attendance_analytics::summarize(attendance_type, &group)
+        };
+        let classroom = of_type("classroom");
+        let clinical = of_type("clinical");
 
-        if absences.0 >= min_absences {
+        if total.absent >= min_absences {
             summaries.push(AttendanceSummary {
                 student_id: student.id.clone(),
                 student_name: format!("{} {}", student.first_name, student.last_name),
-                total_absences: absences.0,
-                total_tardies: tardies.0,
-                total_present: present.0,
+                total_absences: total.absent,
+                total_tardies: total.tardy,
+                total_present: total.present,
+                classroom_absences: classroom.absent,
+                classroom_tardies: classroom.tardy,
+                clinical_absences: clinical.absent,
+                clinical_tardies: clinical.tardy,
+                hours_required: total.hours_required,
+                hours_missed: total.hours_missed,
+                attendance_percent: total.attendance_percent,
             });
         }
     }
 
-    // Sort by total_absences descending
-    summaries.sort_by(|a, b| b.total_absences.cmp(&a.total_absences));
+    // Sort by total_absences descending, then by hours missed
+    summaries.sort_by(|a, b| {
+        b.total_absences
+            .cmp(&a.total_absences)
+            .then(b.hours_missed.total_cmp(&a.hours_missed))
+    });
 
     Ok(summaries)
 }
 
+#[tauri::command]
+pub async fn get_attendance_analytics(
+    state: State<'_, DbState>,
+    filters: AttendanceAnalyticsFilters
+) -> Result<AttendanceAnalytics, String> {
+    crate::attendance_analytics::analyze(&state.db, &filters)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_student_attendance_detail(state: State<'_, DbState>, student_id: String) -> Result<Vec<Attendance>, String> {
     sqlx::query_as::<_, Attendance>(
@@ -815,47 +1098,64 @@ pub async fn get_student_attendance_detail(state: State<'_, DbState>, student_id
 
 // ==================== MAKEUP HOURS ====================
 
+/// New obligations start with no hours completed; the makeup ledger credits them from approved makeup logs.
 #[tauri::command]
 pub async fn add_makeup_hours(state: State<'_, DbState>, record: MakeupHours) -> Result<(), String> {
     sqlx::query(
         "INSERT INTO makeup_hours (id, student_id, original_absence_id, hours_owed, hours_completed, reason, due_date, completion_date, status, notes, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        VALUES (?, ?, ?, ?, 0, ?, ?, NULL, ?, ?, ?, ?)"
     )
     .bind(&record.id)
     .bind(&record.student_id)
     .bind(&record.original_absence_id)
     .bind(&record.hours_owed)
-    .bind(&record.hours_completed)
     .bind(&record.reason)
     .bind(&record.due_date)
-    .bind(&record.completion_date)
-    .bind(&record.status)
+    .bind(if record.status == "cancelled" { "cancelled" } else { "pending" })
     .bind(&record.notes)
     .bind(&record.created_at)
     .bind(&record.updated_at)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    crate::makeup_ledger::sync_student(&state.db, &record.student_id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
+/// Edits the due date and notes, and cancels or reopens the obligation. Completed hours and the
+/// rest of the status are owned by the makeup ledger, so they are ignored here.
 #[tauri::command]
 pub async fn update_makeup_hours(state: State<'_, DbState>, record: MakeupHours) -> Result<(), String> {
-    sqlx::query(
-        "UPDATE makeup_hours SET
-        hours_completed = ?, completion_date = ?, status = ?, notes = ?, updated_at = ?
-        WHERE id = ?"
-    )
-    .bind(&record.hours_completed)
-    .bind(&record.completion_date)
-    .bind(&record.status)
-    .bind(&record.notes)
-    .bind(&record.updated_at)
-    .bind(&record.id)
-    .execute(&state.db)
-    .await
-    .map_err(|e| e.to_string())?;
-    Ok(())
+    let existing = sqlx::query_as::<_, MakeupHours>("SELECT * FROM makeup_hours WHERE id = ?")
+        .bind(&record.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Makeup hours record not found".to_string())?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("UPDATE makeup_hours SET due_date = ?, notes = ?, updated_at = ? WHERE id = ?")
+        .bind(&record.due_date)
+        .bind(&record.notes)
+        .bind(&record.updated_at)
+        .bind(&record.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let cancelled = record.status == "cancelled";
+    if cancelled != (existing.status == "cancelled") {
+        crate::makeup_ledger::set_cancelled(&mut *tx, &record.id, cancelled)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::makeup_ledger::sync_student(&state.db, &existing.student_id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -913,14 +1213,51 @@ pub async fn get_all_makeup_hours_summaries(state: State<'_, DbState>) -> Result
 
 #[tauri::command]
 pub async fn delete_makeup_hours(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let student: Option<(String,)> = sqlx::query_as("SELECT student_id FROM makeup_hours WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM makeup_allocations WHERE makeup_hours_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
     sqlx::query("DELETE FROM makeup_hours WHERE id = ?")
-        .bind(id)
-        .execute(&state.db)
+        .bind(&id)
+        .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    // Freed makeup log hours move on to the student's next open obligation
+    if let Some((student_id,)) = student {
+        crate::makeup_ledger::sync_student(&state.db, &student_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_makeup_allocations(
+    state: State<'_, DbState>,
+    makeup_hours_id: String
+) -> Result<Vec<MakeupAllocation>, String> {
+    sqlx::query_as::<_, MakeupAllocation>(
+        "SELECT a.*, l.date AS log_date, l.site_name FROM makeup_allocations a
+        JOIN clinical_logs l ON l.id = a.clinical_log_id
+        WHERE a.makeup_hours_id = ?
+        ORDER BY l.date, a.allocated_at"
+    )
+    .bind(makeup_hours_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn auto_create_makeup_hours(
     state: State<'_, DbState>,
@@ -972,13 +1309,29 @@ pub async fn auto_create_makeup_hours(
     .await
     .map_err(|e| e.to_string())?;
 
-    Ok(record)
-}
+    crate::makeup_ledger::sync_student(&state.db, &record.student_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Re-read so the returned record includes any hours allocated from makeup logs
+    sqlx::query_as::<_, MakeupHours>("SELECT * FROM makeup_hours WHERE id = ?")
+        .bind(&record.id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
 
 // ==================== CERTIFICATIONS ====================
 
 #[tauri::command]
 pub async fn add_certification(state: State<'_, DbState>, cert: StudentCertification) -> Result<(), String> {
+    let status = crate::cert_status::derive_status(
+        &cert.status,
+        &cert.expiry_date,
+        crate::cert_status::STUDENT_ALERT_DAYS,
+        chrono::Local::now().date_naive()
+    );
+
     sqlx::query(
         "INSERT INTO student_certifications (id, student_id, certification_type, certification_name, issue_date, expiry_date, status, document_url, notes)
         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
@@ -989,17 +1342,27 @@ pub async fn add_certification(state: State<'_, DbState>, cert: StudentCertifica
     .bind(&cert.certification_name)
     .bind(&cert.issue_date)
     .bind(&cert.expiry_date)
-    .bind(&cert.status)
+    .bind(&status)
     .bind(&cert.document_url)
     .bind(&cert.notes)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    // Opens or completes the renewal deadline right away instead of at the next daily sweep
+    crate::cert_status::sync_student_certification(&state.db, &cert.id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn update_certification(state: State<'_, DbState>, cert: StudentCertification) -> Result<(), String> {
+    let previous_group = crate::cert_status::student_group(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
+    let status = crate::cert_status::derive_status(
+        &cert.status,
+        &cert.expiry_date,
+        crate::cert_status::STUDENT_ALERT_DAYS,
+        chrono::Local::now().date_naive()
+    );
+
     sqlx::query(
         "UPDATE student_certifications SET
         student_id = ?, certification_type = ?, certification_name = ?, issue_date = ?, expiry_date = ?, status = ?, document_url = ?, notes = ?
@@ -1010,23 +1373,49 @@ pub async fn update_certification(state: State<'_, DbState>, cert: StudentCertif
     .bind(&cert.certification_name)
     .bind(&cert.issue_date)
     .bind(&cert.expiry_date)
-    .bind(&cert.status)
+    .bind(&status)
     .bind(&cert.document_url)
     .bind(&cert.notes)
     .bind(&cert.id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    // Opens or completes the renewal deadline right away instead of at the next daily sweep
+    crate::cert_status::sync_student_certification(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
+
+    // If the student, type or name changed, the certifications it used to renew may need their deadlines back
+    let current_group = crate::cert_status::student_group(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
+    if let Some(group) = previous_group.filter(|group| Some(group) != current_group.as_ref()) {
+        crate::cert_status::sync_student_group(&state.db, &group).await.map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_certification(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let group = crate::cert_status::student_group(&state.db, &id).await.map_err(|e| e.to_string())?;
+
+    // Drop the open renewal deadline along with the certification
+    sqlx::query(
+        "DELETE FROM deadlines WHERE status != 'Completed'
+        AND id = (SELECT renewal_deadline_id FROM student_certifications WHERE id = ?)"
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
     sqlx::query("DELETE FROM student_certifications WHERE id = ?")
-        .bind(id)
+        .bind(&id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+
+    // Deleting a renewal leaves the certification it replaced needing renewal again
+    if let Some(group) = group {
+        crate::cert_status::sync_student_group(&state.db, &group).await.map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
@@ -1042,8 +1431,13 @@ pub async fn get_student_certifications(state: State<'_, DbState>, student_id: S
 }
 
 #[tauri::command]
-pub async fn get_expiring_certifications(state: State<'_, DbState>, days_ahead: i32) -> Result<Vec<CertificationAlert>, String> {
+pub async fn get_expiring_certifications(
+    state: State<'_, DbState>,
+    days_ahead: i32,
+    include_expired: Option<bool>
+) -> Result<Vec<CertificationAlert>, String> {
     let pool = &state.db;
+    let include_expired = include_expired.unwrap_or(false);
 
     // Get current date in ISO format
     let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
@@ -1055,16 +1449,38 @@ pub async fn get_expiring_certifications(state: State<'_, DbState>, days_ahead:
         .format("%Y-%m-%d")
         .to_string();
 
-    let certs = sqlx::query_as::<_, StudentCertification>(
-        "SELECT * FROM student_certifications
-         WHERE expiry_date >= ? AND expiry_date <= ?
-         ORDER BY expiry_date ASC"
-    )
-    .bind(&today)
-    .bind(&future_date)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    // With include_expired, already-expired certifications are listed too, except ones a later
+    // certification of the same name has replaced (the same test the certification sweep uses)
+    let certs = if include_expired {
+        sqlx::query_as::<_, StudentCertification>(
+            "SELECT * FROM student_certifications c
+             WHERE c.expiry_date <= ?
+               AND NOT EXISTS (
+                   SELECT 1 FROM student_certifications n
+                   WHERE n.student_id = c.student_id
+                     AND n.certification_type = c.certification_type
+                     AND lower(n.certification_name) = lower(c.certification_name)
+                     AND n.expiry_date > c.expiry_date
+                     AND n.id != c.id
+               )
+             ORDER BY c.expiry_date ASC"
+        )
+        .bind(&future_date)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+    } else {
+        sqlx::query_as::<_, StudentCertification>(
+            "SELECT * FROM student_certifications
+             WHERE expiry_date >= ? AND expiry_date <= ?
+             ORDER BY expiry_date ASC"
+        )
+        .bind(&today)
+        .bind(&future_date)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+    };
 
     let mut alerts = Vec::new();
 
@@ -1096,13 +1512,122 @@ pub async fn get_expiring_certifications(state: State<'_, DbState>, days_ahead:
     Ok(alerts)
 }
 
+#[tauri::command]
+pub async fn get_certification_requirements(state: State<'_, DbState>) -> Result<Vec<CertificationRequirement>, String> {
+    sqlx::query_as::<_, CertificationRequirement>(
+        "SELECT * FROM certification_requirements ORDER BY name"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_certification_requirement(
+    state: State<'_, DbState>,
+    requirement: CertificationRequirement
+) -> Result<CertificationRequirement, String> {
+    if requirement.name.trim().is_empty() || requirement.certification_type.trim().is_empty() {
+        return Err("Requirement name and certification type are required".to_string());
+    }
+    if requirement.validity_months.is_some_and(|m| m < 1) {
+        return Err("Validity period must be at least 1 month".to_string());
+    }
+
+    let saved = CertificationRequirement {
+        id: if requirement.id.is_empty() { format!("CRQ-{}", uuid::Uuid::new_v4()) } else { requirement.id.clone() },
+        created_at: if requirement.created_at.is_empty() { chrono::Utc::now().to_rfc3339() } else { requirement.created_at.clone() },
+        ..requirement
+    };
+
+    sqlx::query(
+        "INSERT INTO certification_requirements (id, name, certification_type, name_keyword, cohort, site_id, validity_months, is_active, notes, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+            name = excluded.name, certification_type = excluded.certification_type, name_keyword = excluded.name_keyword,
+            cohort = excluded.cohort, site_id = excluded.site_id, validity_months = excluded.validity_months,
+            is_active = excluded.is_active, notes = excluded.notes"
+    )
+    .bind(&saved.id)
+    .bind(&saved.name)
+    .bind(&saved.certification_type)
+    .bind(&saved.name_keyword)
+    .bind(&saved.cohort)
+    .bind(&saved.site_id)
+    .bind(saved.validity_months)
+    .bind(saved.is_active)
+    .bind(&saved.notes)
+    .bind(&saved.created_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn delete_certification_requirement(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM certification_requirements WHERE id = ?")
+        .bind(id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_clinical_clearance(
+    state: State<'_, DbState>,
+    student_id: String,
+    site_id: String,
+    date: String
+) -> Result<ClinicalClearance, String> {
+    let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
+        .bind(&student_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Student not found".to_string())?;
+    crate::clearance::check(&state.db, &student, &site_id, &date).await
+}
+
+/// Clearance of every active student (optionally in one cohort) for a site on a date, uncleared first.
+#[tauri::command]
+pub async fn get_site_clearance(
+    state: State<'_, DbState>,
+    site_id: String,
+    date: String,
+    cohort: Option<String>
+) -> Result<Vec<ClinicalClearance>, String> {
+    let students = sqlx::query_as::<_, Student>(
+        "SELECT * FROM students WHERE status != 'Graduated' AND (? IS NULL OR cohort = ?) ORDER BY last_name, first_name"
+    )
+    .bind(&cohort)
+    .bind(&cohort)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for student in &students {
+        results.push(crate::clearance::check(&state.db, student, &site_id, &date).await?);
+    }
+    results.sort_by_key(|c| c.cleared);
+    Ok(results)
+}
+
 // ==================== PRECEPTOR EVALUATIONS ====================
 
 #[tauri::command]
 pub async fn add_preceptor_evaluation(state: State<'_, DbState>, eval: PreceptorEvaluation) -> Result<(), String> {
+    let preceptor_id = match eval.preceptor_id {
+        Some(ref id) => Some(id.clone()),
+        None => crate::db::resolve_preceptor_id(&state.db, &eval.preceptor_name).await.map_err(|e| e.to_string())?,
+    };
+
     sqlx::query(
-        "INSERT INTO preceptor_evaluations (id, student_id, clinical_log_id, preceptor_name, evaluation_date, overall_rating, clinical_skills_rating, professionalism_rating, communication_rating, comments, areas_for_improvement, strengths, status, submitted_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO preceptor_evaluations (id, student_id, clinical_log_id, preceptor_name, evaluation_date, overall_rating, clinical_skills_rating, professionalism_rating, communication_rating, comments, areas_for_improvement, strengths, status, submitted_at, preceptor_id, response_timestamp, response_key)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&eval.id)
     .bind(&eval.student_id)
@@ -1118,6 +1643,9 @@ pub async fn add_preceptor_evaluation(state: State<'_, DbState>, eval: Preceptor
     .bind(&eval.strengths)
     .bind(&eval.status)
     .bind(&eval.submitted_at)
+    .bind(&preceptor_id)
+    .bind(&eval.response_timestamp)
+    .bind(&eval.response_key)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
@@ -1126,9 +1654,14 @@ pub async fn add_preceptor_evaluation(state: State<'_, DbState>, eval: Preceptor
 
 #[tauri::command]
 pub async fn update_preceptor_evaluation(state: State<'_, DbState>, eval: PreceptorEvaluation) -> Result<(), String> {
+    let preceptor_id = match eval.preceptor_id {
+        Some(ref id) => Some(id.clone()),
+        None => crate::db::resolve_preceptor_id(&state.db, &eval.preceptor_name).await.map_err(|e| e.to_string())?,
+    };
+
     sqlx::query(
         "UPDATE preceptor_evaluations SET
-        student_id = ?, clinical_log_id = ?, preceptor_name = ?, evaluation_date = ?, overall_rating = ?, clinical_skills_rating = ?, professionalism_rating = ?, communication_rating = ?, comments = ?, areas_for_improvement = ?, strengths = ?, status = ?
+        student_id = ?, clinical_log_id = ?, preceptor_name = ?, evaluation_date = ?, overall_rating = ?, clinical_skills_rating = ?, professionalism_rating = ?, communication_rating = ?, comments = ?, areas_for_improvement = ?, strengths = ?, status = ?, preceptor_id = ?
         WHERE id = ?"
     )
     .bind(&eval.student_id)
@@ -1143,6 +1676,7 @@ pub async fn update_preceptor_evaluation(state: State<'_, DbState>, eval: Precep
     .bind(&eval.areas_for_improvement)
     .bind(&eval.strengths)
     .bind(&eval.status)
+    .bind(&preceptor_id)
     .bind(&eval.id)
     .execute(&state.db)
     .await
@@ -1182,6 +1716,60 @@ pub async fn review_evaluation(state: State<'_, DbState>, id: String, status: St
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_preceptor_evaluations(state: State<'_, DbState>, preceptor_id: String) -> Result<Vec<PreceptorEvaluation>, String> {
+    sqlx::query_as::<_, PreceptorEvaluation>(
+        "SELECT * FROM preceptor_evaluations WHERE preceptor_id = ? ORDER BY evaluation_date DESC"
+    )
+    .bind(preceptor_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+const PRECEPTOR_EVALUATION_STATS_QUERY: &str =
+    "SELECT p.id AS preceptor_id,
+            p.first_name || ' ' || p.last_name AS preceptor_name,
+            s.name AS site_name,
+            COUNT(e.id) AS evaluation_count,
+            COUNT(DISTINCT e.student_id) AS students_evaluated,
+            AVG(e.overall_rating) AS avg_overall_rating,
+            AVG(e.clinical_skills_rating) AS avg_clinical_skills_rating,
+            AVG(e.professionalism_rating) AS avg_professionalism_rating,
+            AVG(e.communication_rating) AS avg_communication_rating,
+            MIN(e.evaluation_date) AS first_evaluation_date,
+            MAX(e.evaluation_date) AS last_evaluation_date
+     FROM preceptors p
+     LEFT JOIN clinical_sites s ON s.id = p.site_id
+     LEFT JOIN preceptor_evaluations e ON e.preceptor_id = p.id";
+
+#[tauri::command]
+pub async fn get_preceptor_evaluation_stats(
+    state: State<'_, DbState>,
+    preceptor_id: String
+) -> Result<Option<crate::models::PreceptorEvaluationStats>, String> {
+    sqlx::query_as::<_, crate::models::PreceptorEvaluationStats>(
+        &format!("{} WHERE p.id = ? GROUP BY p.id", PRECEPTOR_EVALUATION_STATS_QUERY)
+    )
+    .bind(&preceptor_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_all_preceptor_evaluation_stats(
+    state: State<'_, DbState>
+) -> Result<Vec<crate::models::PreceptorEvaluationStats>, String> {
+    // Used to compare preceptors side by side, so only active preceptors are included
+    sqlx::query_as::<_, crate::models::PreceptorEvaluationStats>(
+        &format!("{} WHERE p.is_active = 1 GROUP BY p.id ORDER BY p.last_name, p.first_name", PRECEPTOR_EVALUATION_STATS_QUERY)
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
 // ==================== DEADLINES ====================
 
 #[tauri::command]
@@ -1237,7 +1825,11 @@ pub async fn delete_deadline(state: State<'_, DbState>, id: String) -> Result<()
 }
 
 #[tauri::command]
-pub async fn get_upcoming_deadlines(state: State<'_, DbState>, days_ahead: i32) -> Result<Vec<Deadline>, String> {
+pub async fn get_upcoming_deadlines(
+    state: State<'_, DbState>,
+    days_ahead: i32,
+    include_overdue: Option<bool>
+) -> Result<Vec<Deadline>, String> {
     let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
     let future_date = chrono::Utc::now()
         .checked_add_signed(chrono::TimeDelta::try_days(days_ahead as i64).unwrap())
@@ -1245,12 +1837,15 @@ pub async fn get_upcoming_deadlines(state: State<'_, DbState>, days_ahead: i32)
         .format("%Y-%m-%d")
         .to_string();
 
+    // Overdue deadlines have no lower bound on the due date
+    let lower_bound = if include_overdue.unwrap_or(false) { String::new() } else { today };
+
     sqlx::query_as::<_, Deadline>(
         "SELECT * FROM deadlines
          WHERE due_date >= ? AND due_date <= ? AND status != 'Completed'
          ORDER BY due_date ASC, priority DESC"
     )
-    .bind(today)
+    .bind(lower_bound)
     .bind(future_date)
     .fetch_all(&state.db)
     .await
@@ -1325,13 +1920,27 @@ pub async fn get_student_hours_by_site(state: State<'_, DbState>, student_id: St
     .await
     .map_err(|e| e.to_string())?;
 
-    // Group hours by site
-    let mut site_hours: HashMap<String, (f64, f64)> = HashMap::new(); // (direct_hours, sim_hours)
+    // Current site names, so renamed sites report under their new name
+    let site_names: HashMap<String, String> = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, name FROM clinical_sites"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .collect();
+
+    // Group hours by site id, falling back to the free-text name for unlinked logs
+    let mut site_hours: HashMap<(Option<String>, String), (f64, f64)> = HashMap::new(); // (direct_hours, sim_hours)
 
     for log in logs {
         let hours = log.hours.unwrap_or(8.0);
         let is_sim = log.is_simulation.unwrap_or(0) == 1;
-        let entry = site_hours.entry(log.site_name.clone()).or_insert((0.0, 0.0));
+        let key = match log.site_id {
+            Some(site_id) => (Some(site_id), String::new()),
+            None => (None, log.site_name.clone()),
+        };
+        let entry = site_hours.entry(key).or_insert((0.0, 0.0));
 
         if is_sim {
             entry.1 += hours;
@@ -1343,8 +1952,12 @@ pub async fn get_student_hours_by_site(state: State<'_, DbState>, student_id: St
     // Convert to result format
     let result: Vec<StudentHoursBySite> = site_hours
         .into_iter()
-        .map(|(site_name, (direct_hours, sim_hours))| StudentHoursBySite {
-            site_name,
+        .map(|((site_id, unlinked_name), (direct_hours, sim_hours))| StudentHoursBySite {
+            site_name: site_id
+                .as_ref()
+                .and_then(|id| site_names.get(id).cloned())
+                .unwrap_or(unlinked_name),
+            site_id,
             direct_hours,
             sim_hours,
             total_hours: direct_hours + sim_hours,
@@ -1389,8 +2002,11 @@ pub async fn get_student_simulation_summary(state: State<'_, DbState>, student_i
         0.0
     };
 
-    // Compliance check: simulation hours should not exceed 50% of total clinical hours
-    let is_compliant = sim_percentage <= 50.0;
+    // Compliance check against the program's simulation cap (no cap when the rule is disabled)
+    let rule_set = crate::flag_rules::active_rule_set(pool).await.map_err(|e| e.to_string())?;
+    let is_compliant = crate::flag_rules::simulation_cap(&rule_set.rules)
+        .map(|cap| sim_percentage <= cap)
+        .unwrap_or(true);
 
     Ok(StudentSimulationSummary {
         total_hours,
@@ -1406,187 +2022,1088 @@ pub async fn get_student_simulation_summary(state: State<'_, DbState>, student_i
 #[tauri::command]
 pub async fn get_student_flags(state: State<'_, DbState>, student_id: String) -> Result<Vec<StudentFlag>, String> {
     let pool = &state.db;
-    let mut flags: Vec<StudentFlag> = Vec::new();
 
-    // Get student details
     let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
         .bind(&student_id)
         .fetch_optional(pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Student not found".to_string())?;
 
-    let student = match student {
-        Some(s) => s,
-        None => return Err("Student not found".to_string()),
-    };
+    let rule_set = crate::flag_rules::active_rule_set(pool).await.map_err(|e| e.to_string())?;
+    let metrics = crate::flag_rules::load_metrics(pool, student).await.map_err(|e| e.to_string())?;
 
-    // Check clinical hours progress
-    let hours_remaining = student.clinical_hours_required - student.clinical_hours_completed;
-    if hours_remaining > 0.0 && student.clinical_hours_completed < student.clinical_hours_required * 0.25 {
-        flags.push(StudentFlag {
-            flag_type: "clinical_hours".to_string(),
-            message: format!("Clinical hours behind schedule: {:.1}/{:.1} hours completed",
-                student.clinical_hours_completed, student.clinical_hours_required),
-            severity: "warning".to_string(),
-            related_id: None,
-        });
-    }
+    // Read-only: history is recorded by write paths and `snapshot_student_flags`
+    let mut flags = crate::flag_rules::evaluate(&rule_set.rules, &metrics, chrono::Utc::now().date_naive());
+    crate::flag_rules::attach_history(pool, &student_id, &mut flags).await.map_err(|e| e.to_string())?;
+    Ok(flags)
+}
 
-    // Check simulation compliance
-    let sim_summary = get_student_simulation_summary(state.clone(), student_id.clone()).await?;
-    if !sim_summary.is_compliant {
-        flags.push(StudentFlag {
-            flag_type: "simulation_compliance".to_string(),
-            message: format!("Simulation hours exceed 50% of total: {:.1}%", sim_summary.sim_percentage),
-            severity: "error".to_string(),
-            related_id: None,
-        });
+#[tauri::command]
+pub async fn acknowledge_student_flag(
+    state: State<'_, DbState>,
+    history_id: String,
+    note: String,
+    action_plan: Option<String>,
+    acknowledged_by: Option<String>
+) -> Result<StudentFlagRecord, String> {
+    if note.trim().is_empty() {
+        return Err("An acknowledgement note is required".to_string());
     }
 
-    // Check GPA
-    if let Some(gpa) = student.gpa {
-        if gpa < 2.5 {
-            flags.push(StudentFlag {
-                flag_type: "gpa".to_string(),
-                message: format!("GPA below minimum requirement: {:.2}", gpa),
-                severity: "error".to_string(),
-                related_id: None,
-            });
-        } else if gpa < 3.0 {
-            flags.push(StudentFlag {
-                flag_type: "gpa".to_string(),
-                message: format!("GPA approaching minimum requirement: {:.2}", gpa),
-                severity: "warning".to_string(),
-                related_id: None,
+    sqlx::query(
+        "UPDATE student_flag_history
+        SET acknowledged_at = ?, acknowledged_by = ?, acknowledgement_note = ?, action_plan = ?
+        WHERE id = ?"
+    )
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&acknowledged_by)
+    .bind(note.trim())
+    .bind(&action_plan)
+    .bind(&history_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query_as::<_, StudentFlagRecord>("SELECT * FROM student_flag_history WHERE id = ?")
+        .bind(&history_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Flag not found".to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_flag_history(
+    state: State<'_, DbState>,
+    student_id: String,
+    include_resolved: Option<bool>
+) -> Result<Vec<StudentFlagRecord>, String> {
+    sqlx::query_as::<_, StudentFlagRecord>(
+        "SELECT * FROM student_flag_history
+        WHERE student_id = ? AND (? = 1 OR resolved_at IS NULL)
+        ORDER BY first_seen DESC"
+    )
+    .bind(&student_id)
+    .bind(include_resolved.unwrap_or(true) as i32)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Raised, acknowledged and resolved events for a student's flags, oldest first.
+#[tauri::command]
+pub async fn get_student_flag_timeline(state: State<'_, DbState>, student_id: String) -> Result<Vec<FlagTimelineEvent>, String> {
+    let records = get_student_flag_history(state.clone(), student_id, Some(true)).await?;
+
+    let mut events = Vec::new();
+    for record in records {
+        let event = |date: &str, kind: &str| FlagTimelineEvent {
+            date: date.to_string(),
+            event: kind.to_string(),
+            history_id: record.id.clone(),
+            flag_type: record.flag_type.clone(),
+            severity: record.severity.clone(),
+            message: record.message.clone(),
+            note: None,
+            action_plan: None,
+            acknowledged_by: None,
+        };
+
+        events.push(event(&record.first_seen, "raised"));
+        if let Some(ref acknowledged_at) = record.acknowledged_at {
+            events.push(FlagTimelineEvent {
+                note: record.acknowledgement_note.clone(),
+                action_plan: record.action_plan.clone(),
+                acknowledged_by: record.acknowledged_by.clone(),
+                ..event(acknowledged_at, "acknowledged")
             });
         }
+        if let Some(ref resolved_at) = record.resolved_at {
+            events.push(event(resolved_at, "resolved"));
+        }
     }
 
-    // Check NCLEX predictor score
-    if let Some(nclex_score) = student.nclex_predictor_score {
-        if nclex_score < 60.0 {
-            flags.push(StudentFlag {
-                flag_type: "nclex_predictor".to_string(),
-                message: format!("NCLEX predictor score below threshold: {:.1}%", nclex_score),
-                severity: "error".to_string(),
-                related_id: None,
-            });
-        } else if nclex_score < 70.0 {
-            flags.push(StudentFlag {
-                flag_type: "nclex_predictor".to_string(),
-                message: format!("NCLEX predictor score needs improvement: {:.1}%", nclex_score),
-                severity: "warning".to_string(),
-                related_id: None,
-            });
+    events.sort_by(|a, b| a.date.cmp(&b.date));
+    Ok(events)
+}
+
+/// Flags for every student in a cohort, with totals and students ranked by risk.
+#[tauri::command]
+pub async fn get_cohort_flags(
+    state: State<'_, DbState>,
+    cohort: String,
+    filters: Option<CohortFlagFilters>
+) -> Result<CohortFlagReport, String> {
+    let pool = &state.db;
+    let filters = filters.unwrap_or_default();
+
+    let rule_set = crate::flag_rules::active_rule_set(pool).await.map_err(|e| e.to_string())?;
+    let cohort_metrics = crate::flag_rules::load_cohort_metrics(pool, Some(&cohort))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let open_history = crate::flag_rules::open_history_for_cohort(pool, &cohort)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let today = chrono::Utc::now().date_naive();
+    let mut student_count = 0;
+    let mut by_type: HashMap<String, FlagTypeCount> = HashMap::new();
+    let mut by_severity = FlagSeverityCounts::default();
+    let mut at_risk: Vec<AtRiskStudent> = Vec::new();
+
+    for metrics in cohort_metrics {
+        if let Some(ref status) = filters.student_status {
+            if !metrics.student.status.eq_ignore_ascii_case(status) {
+                continue;
+            }
         }
-    }
+        student_count += 1;
 
-    // Check remediation status
-    if let Some(ref status) = student.remediation_status {
-        if status == "Required" || status == "In Progress" {
-            flags.push(StudentFlag {
-                flag_type: "remediation".to_string(),
-                message: format!("Remediation {}: {}", status.to_lowercase(),
-                    student.remediation_topic.as_deref().unwrap_or("Unknown topic")),
-                severity: if status == "Required" { "error" } else { "warning" }.to_string(),
-                related_id: None,
-            });
+        let mut flags = crate::flag_rules::evaluate(&rule_set.rules, &metrics, today);
+        if let Some(open) = open_history.get(&metrics.student.id) {
+            crate::flag_rules::apply_history(open, &mut flags);
         }
-    }
 
-    // Check attendance issues
-    let absences: (i32,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM attendance WHERE student_id = ? AND status = 'Absent'"
-    )
-    .bind(&student_id)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+        let flags: Vec<StudentFlag> = flags
+            .into_iter()
+            .filter(|f| filters.flag_types.as_ref().map(|t| t.contains(&f.flag_type)).unwrap_or(true))
+            .filter(|f| filters.severities.as_ref().map(|s| s.contains(&f.severity)).unwrap_or(true))
+            .collect();
+        if flags.is_empty() {
+            continue;
+        }
 
-    if absences.0 >= 5 {
-        flags.push(StudentFlag {
-            flag_type: "attendance".to_string(),
-            message: format!("Excessive absences: {} absences recorded", absences.0),
-            severity: "error".to_string(),
-            related_id: None,
-        });
-    } else if absences.0 >= 3 {
-        flags.push(StudentFlag {
-            flag_type: "attendance".to_string(),
-            message: format!("Multiple absences: {} absences recorded", absences.0),
-            severity: "warning".to_string(),
-            related_id: None,
+        for flag in &flags {
+            let count = by_type.entry(flag.flag_type.clone()).or_insert_with(|| FlagTypeCount {
+                flag_type: flag.flag_type.clone(),
+                errors: 0,
+                warnings: 0,
+                info: 0,
+                total: 0,
+            });
+            count.total += 1;
+            match flag.severity.as_str() {
+                "error" => {
+                    count.errors += 1;
+                    by_severity.error += 1;
+                }
+                "warning" => {
+                    count.warnings += 1;
+                    by_severity.warning += 1;
+                }
+                _ => {
+                    count.info += 1;
+                    by_severity.info += 1;
+                }
+            }
+        }
+
+        at_risk.push(AtRiskStudent {
+            student_id: metrics.student.id.clone(),
+            student_name: format!("{} {}", metrics.student.first_name, metrics.student.last_name),
+            status: metrics.student.status.clone(),
+            risk_score: flags.iter().map(|f| crate::flag_rules::severity_weight(&f.severity)).sum(),
+            error_count: flags.iter().filter(|f| f.severity == "error").count() as i32,
+            warning_count: flags.iter().filter(|f| f.severity == "warning").count() as i32,
+            flags,
         });
     }
 
-    // Check for expiring certifications (within 30 days)
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let future_date = chrono::Utc::now()
-        .checked_add_signed(chrono::TimeDelta::try_days(30).unwrap())
-        .unwrap()
-        .format("%Y-%m-%d")
-        .to_string();
+    let flagged_student_count = at_risk.len() as i32;
+    at_risk.sort_by(|a, b| {
+        b.risk_score
+            .cmp(&a.risk_score)
+            .then(b.error_count.cmp(&a.error_count))
+            .then(a.student_name.cmp(&b.student_name))
+    });
+    if let Some(limit) = filters.limit {
+        at_risk.truncate(limit.max(0) as usize);
+    }
 
-    let expiring_certs = sqlx::query_as::<_, StudentCertification>(
-        "SELECT * FROM student_certifications
-         WHERE student_id = ? AND expiry_date >= ? AND expiry_date <= ?"
-    )
-    .bind(&student_id)
-    .bind(&today)
-    .bind(&future_date)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    let mut by_type: Vec<FlagTypeCount> = by_type.into_values().collect();
+    by_type.sort_by(|a, b| b.total.cmp(&a.total).then(a.flag_type.cmp(&b.flag_type)));
 
-    for cert in expiring_certs {
-        let expiry = chrono::NaiveDate::parse_from_str(&cert.expiry_date, "%Y-%m-%d")
-            .unwrap_or_else(|_| chrono::Utc::now().date_naive());
-        let today_date = chrono::Utc::now().date_naive();
-        let days_until = (expiry - today_date).num_days();
+    Ok(CohortFlagReport {
+        cohort,
+        student_count,
+        flagged_student_count,
+        by_type,
+        by_severity,
+        at_risk,
+    })
+}
 
-        flags.push(StudentFlag {
-            flag_type: "certification".to_string(),
-            message: format!("{} expires in {} days", cert.certification_name, days_until),
-            severity: if days_until <= 7 { "error" } else { "warning" }.to_string(),
-            related_id: Some(cert.id),
-        });
-    }
+// ==================== FLAG RULES ====================
+
+#[tauri::command]
+pub async fn get_flag_rules(state: State<'_, DbState>) -> Result<FlagRuleSet, String> {
+    crate::flag_rules::active_rule_set(&state.db).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_flag_rule_versions(state: State<'_, DbState>) -> Result<Vec<FlagRuleSet>, String> {
+    sqlx::query_as::<_, FlagRuleSet>("SELECT * FROM flag_rule_sets ORDER BY version DESC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_flag_rules(
+    state: State<'_, DbState>,
+    rules: Vec<FlagRule>,
+    notes: Option<String>
+) -> Result<FlagRuleSet, String> {
+    crate::flag_rules::validate(&rules)?;
 
-    // Check for expired certifications
-    let expired_certs = sqlx::query_as::<_, StudentCertification>(
-        "SELECT * FROM student_certifications
-         WHERE student_id = ? AND expiry_date < ?"
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+
+    let next_version: (i32,) = sqlx::query_as("SELECT COALESCE(MAX(version), 0) + 1 FROM flag_rule_sets")
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE flag_rule_sets SET is_active = 0")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let rule_set = FlagRuleSet {
+        version: next_version.0,
+        rules: sqlx::types::Json(rules),
+        notes,
+        is_active: 1,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    sqlx::query(
+        "INSERT INTO flag_rule_sets (version, rules, notes, is_active, created_at) VALUES (?, ?, ?, 1, ?)"
     )
-    .bind(&student_id)
-    .bind(&today)
-    .fetch_all(pool)
+    .bind(rule_set.version)
+    .bind(&rule_set.rules)
+    .bind(&rule_set.notes)
+    .bind(&rule_set.created_at)
+    .execute(&mut *tx)
     .await
     .map_err(|e| e.to_string())?;
 
-    for cert in expired_certs {
-        flags.push(StudentFlag {
-            flag_type: "certification".to_string(),
-            message: format!("{} has expired", cert.certification_name),
-            severity: "error".to_string(),
-            related_id: Some(cert.id),
-        });
-    }
+    tx.commit().await.map_err(|e| e.to_string())?;
 
-    Ok(flags)
+    // The new rules raise and clear flags for everyone
+    crate::flag_rules::snapshot_flags(&state.db, None).await.map_err(|e| e.to_string())?;
+    Ok(rule_set)
 }
 
-// ==================== CLINICAL TRACKING SYSTEM ====================
+#[tauri::command]
+pub async fn activate_flag_rule_version(state: State<'_, DbState>, version: i32) -> Result<FlagRuleSet, String> {
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
 
-// Clinical Sites Commands
+    let rule_set = sqlx::query_as::<_, FlagRuleSet>("SELECT * FROM flag_rule_sets WHERE version = ?")
+        .bind(version)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Rule set version {} not found", version))?;
+
+    sqlx::query("UPDATE flag_rule_sets SET is_active = CASE WHEN version = ? THEN 1 ELSE 0 END")
+        .bind(version)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    crate::flag_rules::snapshot_flags(&state.db, None).await.map_err(|e| e.to_string())?;
+    Ok(FlagRuleSet { is_active: 1, ..rule_set })
+}
 
+/// Records every student's current flags (or one cohort's) into the flag history, so flags raised
+/// since the last write or daily snapshot get a history record that can be acknowledged.
 #[tauri::command]
-pub async fn add_clinical_site(state: State<'_, DbState>, site: crate::models::ClinicalSite) -> Result<(), String> {
-    sqlx::query(
-        "INSERT INTO clinical_sites (
-            id, name, address, contact_name, contact_phone, contact_email,
-            site_type, is_active, notes, created_at,
-            unit_name, contact_title, accrediting_body, last_accreditation_date,
-            contract_start_date, contract_expiration_date, last_used_date,
+pub async fn snapshot_student_flags(state: State<'_, DbState>, cohort: Option<String>) -> Result<i32, String> {
+    crate::flag_rules::snapshot_flags(&state.db, cohort.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Shows which students would gain, lose or change flags under `rules` compared with the active rule set.
+#[tauri::command]
+pub async fn preview_flag_rules(
+    state: State<'_, DbState>,
+    rules: Vec<FlagRule>,
+    cohort: Option<String>
+) -> Result<Vec<FlagRulePreview>, String> {
+    crate::flag_rules::validate(&rules)?;
+    let pool = &state.db;
+
+    let active = crate::flag_rules::active_rule_set(pool).await.map_err(|e| e.to_string())?;
+    let cohort_metrics = crate::flag_rules::load_cohort_metrics(pool, cohort.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let today = chrono::Utc::now().date_naive();
+    let mut previews = Vec::new();
+    for metrics in cohort_metrics {
+        let student_id = metrics.student.id.clone();
+        let student_name = format!("{} {}", metrics.student.first_name, metrics.student.last_name);
+
+        let current = crate::flag_rules::evaluate(&active.rules, &metrics, today);
+        let proposed = crate::flag_rules::evaluate(&rules, &metrics, today);
+        let (added, removed, changed) = crate::flag_rules::diff_flags(&current, &proposed);
+
+        if !added.is_empty() || !removed.is_empty() || !changed.is_empty() {
+            previews.push(FlagRulePreview { student_id, student_name, added, removed, changed });
+        }
+    }
+
+    Ok(previews)
+}
+
+// ==================== REMEDIATION PLANS ====================
+
+/// Creates, moves or removes the deadline backing a remediation due date and returns its id.
+async fn sync_remediation_deadline(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    deadline_id: Option<&str>,
+    student_id: &str,
+    title: &str,
+    description: Option<&str>,
+    due_date: Option<&str>
+) -> Result<Option<String>, String> {
+    match (deadline_id, due_date) {
+        (Some(id), None) => {
+            sqlx::query("DELETE FROM deadlines WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(None)
+        }
+        (Some(id), Some(due_date)) => {
+            sqlx::query("UPDATE deadlines SET title = ?, description = ?, due_date = ? WHERE id = ?")
+                .bind(title)
+                .bind(description)
+                .bind(due_date)
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Some(id.to_string()))
+        }
+        (None, Some(due_date)) => {
+            let id = format!("DL-{}", uuid::Uuid::new_v4());
+            sqlx::query(
+                "INSERT INTO deadlines (id, title, description, due_date, deadline_type, related_student_id, status, priority, created_at)
+                VALUES (?, ?, ?, ?, 'remediation', ?, 'Pending', 'High', ?)"
+            )
+            .bind(&id)
+            .bind(title)
+            .bind(description)
+            .bind(due_date)
+            .bind(student_id)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(Some(id))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+/// Mirrors a student's active plans into the legacy remediation columns shown on the student record.
+async fn sync_student_remediation_fields(pool: &sqlx::Pool<sqlx::Sqlite>, student_id: &str) -> Result<(), String> {
+    let plans: Vec<(String, String)> = sqlx::query_as(
+        "SELECT topic, status FROM remediation_plans WHERE student_id = ? ORDER BY start_date"
+    )
+    .bind(student_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if plans.is_empty() {
+        return Ok(());
+    }
+
+    let active: Vec<&str> = plans.iter().filter(|(_, status)| status == "active").map(|(topic, _)| topic.as_str()).collect();
+    let (status, topic) = if active.is_empty() {
+        ("Completed".to_string(), plans.last().map(|(topic, _)| topic.clone()))
+    } else {
+        ("In Progress".to_string(), Some(active.join(", ")))
+    };
+
+    sqlx::query("UPDATE students SET remediation_status = ?, remediation_topic = ? WHERE id = ?")
+        .bind(&status)
+        .bind(&topic)
+        .bind(student_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn get_remediation_plan_details(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    plan: RemediationPlan
+) -> Result<RemediationPlanWithDetails, String> {
+    let student_name: Option<(String, String)> = sqlx::query_as("SELECT first_name, last_name FROM students WHERE id = ?")
+        .bind(&plan.student_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let activities = sqlx::query_as::<_, RemediationActivity>(
+        "SELECT * FROM remediation_activities WHERE plan_id = ? ORDER BY due_date IS NULL, due_date, created_at"
+    )
+    .bind(&plan.id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let checkpoints = sqlx::query_as::<_, RemediationCheckpoint>(
+        "SELECT * FROM remediation_checkpoints WHERE plan_id = ? ORDER BY checkpoint_date"
+    )
+    .bind(&plan.id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut triggering_grades = Vec::new();
+    for grade_id in plan.triggering_grade_ids.iter() {
+        if let Some(grade) = sqlx::query_as::<_, Grade>("SELECT * FROM grades WHERE id = ?")
+            .bind(grade_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            triggering_grades.push(grade);
+        }
+    }
+
+    let mut triggering_evaluations = Vec::new();
+    for eval_id in plan.triggering_evaluation_ids.iter() {
+        if let Some(eval) = sqlx::query_as::<_, PreceptorEvaluation>("SELECT * FROM preceptor_evaluations WHERE id = ?")
+            .bind(eval_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            triggering_evaluations.push(eval);
+        }
+    }
+
+    Ok(RemediationPlanWithDetails {
+        student_name: student_name.map(|(first, last)| format!("{} {}", first, last)).unwrap_or_default(),
+        plan,
+        activities,
+        checkpoints,
+        triggering_grades,
+        triggering_evaluations,
+    })
+}
+
+#[tauri::command]
+pub async fn create_remediation_plan(
+    state: State<'_, DbState>,
+    plan: RemediationPlan,
+    activities: Vec<RemediationActivity>
+) -> Result<RemediationPlanWithDetails, String> {
+    let pool = &state.db;
+
+    let review_deadline_id = sync_remediation_deadline(
+        pool,
+        None,
+        &plan.student_id,
+        &format!("Remediation review: {}", plan.topic),
+        plan.reason.as_deref(),
+        plan.target_date.as_deref(),
+    ).await?;
+
+    sqlx::query(
+        "INSERT INTO remediation_plans (id, student_id, topic, reason, status, start_date, target_date, triggering_grade_ids, triggering_evaluation_ids, outcome, outcome_notes, created_by, created_at, updated_at, closed_at, review_deadline_id)
+        VALUES (?, ?, ?, ?, 'active', ?, ?, ?, ?, NULL, NULL, ?, ?, ?, NULL, ?)"
+    )
+    .bind(&plan.id)
+    .bind(&plan.student_id)
+    .bind(&plan.topic)
+    .bind(&plan.reason)
+    .bind(&plan.start_date)
+    .bind(&plan.target_date)
+    .bind(&plan.triggering_grade_ids)
+    .bind(&plan.triggering_evaluation_ids)
+    .bind(&plan.created_by)
+    .bind(&plan.created_at)
+    .bind(&plan.updated_at)
+    .bind(&review_deadline_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for activity in activities {
+        add_remediation_activity(state.clone(), RemediationActivity { plan_id: plan.id.clone(), ..activity }).await?;
+    }
+
+    sync_student_remediation_fields(pool, &plan.student_id).await?;
+
+    let plan = sqlx::query_as::<_, RemediationPlan>("SELECT * FROM remediation_plans WHERE id = ?")
+        .bind(&plan.id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    get_remediation_plan_details(pool, plan).await
+}
+
+#[tauri::command]
+pub async fn update_remediation_plan(state: State<'_, DbState>, plan: RemediationPlan) -> Result<(), String> {
+    let pool = &state.db;
+
+    let review_deadline_id = if plan.status == "active" {
+        sync_remediation_deadline(
+            pool,
+            plan.review_deadline_id.as_deref(),
+            &plan.student_id,
+            &format!("Remediation review: {}", plan.topic),
+            plan.reason.as_deref(),
+            plan.target_date.as_deref(),
+        ).await?
+    } else {
+        plan.review_deadline_id.clone()
+    };
+
+    sqlx::query(
+        "UPDATE remediation_plans SET
+            topic = ?, reason = ?, start_date = ?, target_date = ?,
+            triggering_grade_ids = ?, triggering_evaluation_ids = ?,
+            outcome_notes = ?, updated_at = ?, review_deadline_id = ?
+        WHERE id = ?"
+    )
+    .bind(&plan.topic)
+    .bind(&plan.reason)
+    .bind(&plan.start_date)
+    .bind(&plan.target_date)
+    .bind(&plan.triggering_grade_ids)
+    .bind(&plan.triggering_evaluation_ids)
+    .bind(&plan.outcome_notes)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&review_deadline_id)
+    .bind(&plan.id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sync_student_remediation_fields(pool, &plan.student_id).await
+}
+
+const REMEDIATION_OUTCOMES: &[&str] = &["successful", "unsuccessful", "withdrawn"];
+
+/// Closes a plan with its outcome. Deadlines for unfinished activities are removed.
+#[tauri::command]
+pub async fn close_remediation_plan(
+    state: State<'_, DbState>,
+    plan_id: String,
+    outcome: String,
+    outcome_notes: Option<String>
+) -> Result<(), String> {
+    if !REMEDIATION_OUTCOMES.contains(&outcome.as_str()) {
+        return Err(format!(
+            "Unknown outcome '{}'; expected one of: {}",
+            outcome,
+            REMEDIATION_OUTCOMES.join(", ")
+        ));
+    }
+    let pool = &state.db;
+    let status = if outcome == "withdrawn" { "cancelled" } else { "completed" };
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let plan = sqlx::query_as::<_, RemediationPlan>("SELECT * FROM remediation_plans WHERE id = ?")
+        .bind(&plan_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Remediation plan not found".to_string())?;
+
+    sqlx::query(
+        "DELETE FROM deadlines WHERE id IN (
+            SELECT deadline_id FROM remediation_activities WHERE plan_id = ? AND status != 'completed'
+        )"
+    )
+    .bind(&plan_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE remediation_activities SET deadline_id = NULL WHERE plan_id = ? AND status != 'completed'")
+        .bind(&plan_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(ref deadline_id) = plan.review_deadline_id {
+        sqlx::query("UPDATE deadlines SET status = 'Completed' WHERE id = ?")
+            .bind(deadline_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    sqlx::query(
+        "UPDATE remediation_plans SET status = ?, outcome = ?, outcome_notes = ?, closed_at = ?, updated_at = ? WHERE id = ?"
+    )
+    .bind(status)
+    .bind(&outcome)
+    .bind(&outcome_notes)
+    .bind(&now)
+    .bind(&now)
+    .bind(&plan_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sync_student_remediation_fields(pool, &plan.student_id).await
+}
+
+#[tauri::command]
+pub async fn delete_remediation_plan(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let pool = &state.db;
+
+    let student_id: Option<(String,)> = sqlx::query_as("SELECT student_id FROM remediation_plans WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "DELETE FROM deadlines WHERE id IN (
+            SELECT deadline_id FROM remediation_activities WHERE plan_id = ?
+            UNION SELECT review_deadline_id FROM remediation_plans WHERE id = ?
+        )"
+    )
+    .bind(&id)
+    .bind(&id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for table in ["remediation_activities", "remediation_checkpoints"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE plan_id = ?", table))
+            .bind(&id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    sqlx::query("DELETE FROM remediation_plans WHERE id = ?")
+        .bind(&id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some((student_id,)) = student_id {
+        sync_student_remediation_fields(pool, &student_id).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_student_remediation_plans(
+    state: State<'_, DbState>,
+    student_id: String
+) -> Result<Vec<RemediationPlanWithDetails>, String> {
+    let plans = sqlx::query_as::<_, RemediationPlan>(
+        "SELECT * FROM remediation_plans WHERE student_id = ? ORDER BY start_date DESC"
+    )
+    .bind(&student_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for plan in plans {
+        results.push(get_remediation_plan_details(&state.db, plan).await?);
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn get_active_remediation_plans(state: State<'_, DbState>) -> Result<Vec<RemediationPlanWithDetails>, String> {
+    let plans = sqlx::query_as::<_, RemediationPlan>(
+        "SELECT * FROM remediation_plans WHERE status = 'active' ORDER BY target_date IS NULL, target_date"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for plan in plans {
+        results.push(get_remediation_plan_details(&state.db, plan).await?);
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn add_remediation_activity(state: State<'_, DbState>, activity: RemediationActivity) -> Result<(), String> {
+    let pool = &state.db;
+
+    let student_id: (String,) = sqlx::query_as("SELECT student_id FROM remediation_plans WHERE id = ?")
+        .bind(&activity.plan_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Remediation plan not found".to_string())?;
+
+    let deadline_id = if activity.status == "completed" {
+        None
+    } else {
+        sync_remediation_deadline(
+            pool,
+            None,
+            &student_id.0,
+            &format!("Remediation: {}", activity.title),
+            activity.description.as_deref(),
+            activity.due_date.as_deref(),
+        ).await?
+    };
+
+    sqlx::query(
+        "INSERT INTO remediation_activities (id, plan_id, title, description, due_date, status, completed_date, deadline_id, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&activity.id)
+    .bind(&activity.plan_id)
+    .bind(&activity.title)
+    .bind(&activity.description)
+    .bind(&activity.due_date)
+    .bind(&activity.status)
+    .bind(&activity.completed_date)
+    .bind(&deadline_id)
+    .bind(&activity.created_at)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn update_remediation_activity(state: State<'_, DbState>, activity: RemediationActivity) -> Result<(), String> {
+    let pool = &state.db;
+
+    let existing = sqlx::query_as::<_, RemediationActivity>("SELECT * FROM remediation_activities WHERE id = ?")
+        .bind(&activity.id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Remediation activity not found".to_string())?;
+
+    let student_id: (String,) = sqlx::query_as("SELECT student_id FROM remediation_plans WHERE id = ?")
+        .bind(&existing.plan_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let deadline_id = if activity.status == "completed" {
+        if let Some(ref id) = existing.deadline_id {
+            complete_deadline(state.clone(), id.clone()).await?;
+        }
+        existing.deadline_id.clone()
+    } else {
+        sync_remediation_deadline(
+            pool,
+            existing.deadline_id.as_deref(),
+            &student_id.0,
+            &format!("Remediation: {}", activity.title),
+            activity.description.as_deref(),
+            activity.due_date.as_deref(),
+        ).await?
+    };
+
+    // Reopened: the deadline was marked done when the activity was completed
+    if existing.status == "completed" && activity.status != "completed" {
+        if let Some(ref id) = deadline_id {
+            sqlx::query("UPDATE deadlines SET status = 'Pending' WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let completed_date = match activity.status.as_str() {
+        "completed" => activity
+            .completed_date
+            .clone()
+            .or_else(|| Some(chrono::Local::now().format("%Y-%m-%d").to_string())),
+        _ => None,
+    };
+
+    sqlx::query(
+        "UPDATE remediation_activities SET title = ?, description = ?, due_date = ?, status = ?, completed_date = ?, deadline_id = ?
+        WHERE id = ?"
+    )
+    .bind(&activity.title)
+    .bind(&activity.description)
+    .bind(&activity.due_date)
+    .bind(&activity.status)
+    .bind(&completed_date)
+    .bind(&deadline_id)
+    .bind(&activity.id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_remediation_activity(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM deadlines WHERE id = (SELECT deadline_id FROM remediation_activities WHERE id = ?)")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query("DELETE FROM remediation_activities WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_remediation_checkpoint(state: State<'_, DbState>, checkpoint: RemediationCheckpoint) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO remediation_checkpoints (id, plan_id, checkpoint_date, progress, score, notes, recorded_by, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&checkpoint.id)
+    .bind(&checkpoint.plan_id)
+    .bind(&checkpoint.checkpoint_date)
+    .bind(&checkpoint.progress)
+    .bind(checkpoint.score)
+    .bind(&checkpoint.notes)
+    .bind(&checkpoint.recorded_by)
+    .bind(&checkpoint.created_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query("UPDATE remediation_plans SET updated_at = ? WHERE id = ?")
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(&checkpoint.plan_id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_remediation_checkpoint(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM remediation_checkpoints WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ==================== NCLEX READINESS ====================
+
+#[tauri::command]
+pub async fn record_nclex_outcome(state: State<'_, DbState>, outcome: NclexOutcome) -> Result<(), String> {
+    // One result per attempt; recording the same attempt again replaces it
+    sqlx::query(
+        "INSERT INTO nclex_outcomes (id, student_id, exam_date, attempt_number, passed, notes, recorded_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(student_id, attempt_number) DO UPDATE SET
+            exam_date = excluded.exam_date,
+            passed = excluded.passed,
+            notes = excluded.notes,
+            recorded_at = excluded.recorded_at"
+    )
+    .bind(&outcome.id)
+    .bind(&outcome.student_id)
+    .bind(&outcome.exam_date)
+    .bind(outcome.attempt_number.max(1))
+    .bind(outcome.passed)
+    .bind(&outcome.notes)
+    .bind(&outcome.recorded_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_nclex_outcomes(state: State<'_, DbState>) -> Result<Vec<NclexOutcome>, String> {
+    sqlx::query_as::<_, NclexOutcome>("SELECT * FROM nclex_outcomes ORDER BY exam_date DESC")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_nclex_outcome(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM nclex_outcomes WHERE id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_nclex_model(state: State<'_, DbState>) -> Result<Option<NclexModelInfo>, String> {
+    sqlx::query_as::<_, NclexModelInfo>("SELECT * FROM nclex_models WHERE is_active = 1")
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Retrains the readiness model on every student's first-attempt outcome, makes it active and
+/// stores every student's new probability as their win probability (percent).
+#[tauri::command]
+pub async fn train_nclex_model(state: State<'_, DbState>) -> Result<NclexModelInfo, String> {
+    let pool = &state.db;
+
+    let first_attempts: Vec<(String, i32)> = sqlx::query_as(
+        "SELECT o.student_id, o.passed FROM nclex_outcomes o
+        WHERE o.attempt_number = (SELECT MIN(attempt_number) FROM nclex_outcomes WHERE student_id = o.student_id)"
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let features = crate::nclex_model::load_features(pool).await.map_err(|e| e.to_string())?;
+    let samples: Vec<(Vec<Option<f64>>, bool)> = first_attempts
+        .into_iter()
+        .filter_map(|(student_id, passed)| features.get(&student_id).map(|values| (values.clone(), passed == 1)))
+        .collect();
+
+    let (model, training_accuracy) = crate::nclex_model::train(&samples)?;
+    let info = NclexModelInfo {
+        id: format!("NCLEXM-{}", uuid::Uuid::new_v4()),
+        trained_at: chrono::Utc::now().to_rfc3339(),
+        sample_count: samples.len() as i32,
+        pass_count: samples.iter().filter(|(_, passed)| *passed).count() as i32,
+        training_accuracy,
+        model: sqlx::types::Json(model),
+        is_active: 1,
+    };
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("UPDATE nclex_models SET is_active = 0")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO nclex_models (id, trained_at, sample_count, pass_count, training_accuracy, model, is_active)
+        VALUES (?, ?, ?, ?, ?, ?, 1)"
+    )
+    .bind(&info.id)
+    .bind(&info.trained_at)
+    .bind(info.sample_count)
+    .bind(info.pass_count)
+    .bind(info.training_accuracy)
+    .bind(&info.model)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    // Predictions are read-only, so stored scores are only refreshed here
+    let students = sqlx::query_as::<_, Student>("SELECT * FROM students")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let predictions = score_students(pool, students).await?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    for prediction in &predictions {
+        sqlx::query("UPDATE students SET win_probability = ? WHERE id = ?")
+            .bind((prediction.probability * 1000.0).round() / 10.0)
+            .bind(&prediction.student_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(info)
+}
+
+/// Scores students with the active model without storing anything.
+async fn score_students(pool: &sqlx::Pool<sqlx::Sqlite>, students: Vec<Student>) -> Result<Vec<NclexPrediction>, String> {
+    let info = sqlx::query_as::<_, NclexModelInfo>("SELECT * FROM nclex_models WHERE is_active = 1")
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No NCLEX model has been trained yet".to_string())?;
+    if !crate::nclex_model::is_compatible(&info.model) {
+        return Err("The NCLEX model was trained on an older feature set; retrain it".to_string());
+    }
+
+    let features = crate::nclex_model::load_features(pool).await.map_err(|e| e.to_string())?;
+
+    let mut predictions = Vec::new();
+    for student in students {
+        let Some(values) = features.get(&student.id) else { continue };
+        let (probability, top_factors) = crate::nclex_model::predict(&info.model, values);
+        predictions.push(NclexPrediction {
+            student_name: format!("{} {}", student.first_name, student.last_name),
+            student_id: student.id,
+            probability,
+            top_factors,
+            model_id: info.id.clone(),
+            model_trained_at: info.trained_at.clone(),
+        });
+    }
+
+    Ok(predictions)
+}
+
+#[tauri::command]
+pub async fn predict_nclex_readiness(state: State<'_, DbState>, student_id: String) -> Result<NclexPrediction, String> {
+    let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
+        .bind(&student_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Student not found".to_string())?;
+
+    score_students(&state.db, vec![student])
+        .await?
+        .pop()
+        .ok_or_else(|| "Student not found".to_string())
+}
+
+#[tauri::command]
+pub async fn predict_cohort_nclex_readiness(state: State<'_, DbState>, cohort: String) -> Result<Vec<NclexPrediction>, String> {
+    let students = sqlx::query_as::<_, Student>(
+        "SELECT * FROM students WHERE cohort = ? ORDER BY last_name, first_name"
+    )
+    .bind(&cohort)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut predictions = score_students(&state.db, students).await?;
+    predictions.sort_by(|a, b| a.probability.total_cmp(&b.probability));
+    Ok(predictions)
+}
+
+// ==================== CLINICAL TRACKING SYSTEM ====================
+
+// Clinical Sites Commands
+
+#[tauri::command]
+pub async fn add_clinical_site(state: State<'_, DbState>, site: crate::models::ClinicalSite) -> Result<(), String> {
+    sqlx::query(
+        "INSERT INTO clinical_sites (
+            id, name, address, contact_name, contact_phone, contact_email,
+            site_type, is_active, notes, created_at,
+            unit_name, contact_title, accrediting_body, last_accreditation_date,
+            contract_start_date, contract_expiration_date, last_used_date,
             max_students_per_day, parking_info, dress_code
         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
@@ -1613,6 +3130,9 @@ pub async fn add_clinical_site(state: State<'_, DbState>, site: crate::models::C
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    // Link any logs that were recorded against this site before it existed
+    crate::db::backfill_site_ids(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1702,7 +3222,7 @@ pub async fn get_site_usage_stats(state: State<'_, DbState>, site_id: String) ->
     .unwrap_or((0,));
 
     let total_hours: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM clinical_logs WHERE site_name = (SELECT name FROM clinical_sites WHERE id = ?)"
+        "SELECT COALESCE(SUM(hours), 0) FROM clinical_logs WHERE site_id = ?"
     )
     .bind(&site_id)
     .fetch_one(&state.db)
@@ -1710,7 +3230,7 @@ pub async fn get_site_usage_stats(state: State<'_, DbState>, site_id: String) ->
     .unwrap_or((0.0,));
 
     let last_used: Option<(String,)> = sqlx::query_as(
-        "SELECT MAX(date) FROM clinical_logs WHERE site_name = (SELECT name FROM clinical_sites WHERE id = ?)"
+        "SELECT MAX(date) FROM clinical_logs WHERE site_id = ?"
     )
     .bind(&site_id)
     .fetch_optional(&state.db)
@@ -1742,6 +3262,12 @@ pub async fn update_site_last_used(state: State<'_, DbState>, site_id: String) -
 
 #[tauri::command]
 pub async fn add_preceptor(state: State<'_, DbState>, preceptor: crate::models::Preceptor) -> Result<(), String> {
+    // Unless given, preceptors are licensed in the state of the current board
+    let license_state = match preceptor.license_state.clone().filter(|s| !s.trim().is_empty()) {
+        Some(license_state) => Some(license_state),
+        None => crate::regulations::default_license_state(&state.db).await,
+    };
+
     sqlx::query(
         "INSERT INTO preceptors (
             id, first_name, last_name, credentials, email, phone,
@@ -1761,7 +3287,7 @@ pub async fn add_preceptor(state: State<'_, DbState>, preceptor: crate::models::
     .bind(&preceptor.notes)
     .bind(&preceptor.created_at)
     .bind(&preceptor.license_number)
-    .bind(&preceptor.license_state)
+    .bind(&license_state)
     .bind(&preceptor.license_expiration_date)
     .bind(&preceptor.last_verification_date)
     .bind(&preceptor.next_verification_due)
@@ -1769,6 +3295,9 @@ pub async fn add_preceptor(state: State<'_, DbState>, preceptor: crate::models::
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    // Link any evaluations that name this preceptor
+    crate::db::backfill_preceptor_ids(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -2006,11 +3535,23 @@ pub async fn get_preceptor_by_id(state: State<'_, DbState>, id: String) -> Resul
 
 // Clinical Assignments Commands
 
-#[tauri::command]
-pub async fn create_clinical_assignment(
-    state: State<'_, DbState>,
-    assignment: crate::models::ClinicalAssignment
-) -> Result<(), String> {
+async fn assignment_clearance(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    assignment: &crate::models::ClinicalAssignment
+) -> Result<ClinicalClearance, String> {
+    let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
+        .bind(&assignment.student_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Student not found".to_string())?;
+    crate::clearance::check(pool, &student, &assignment.site_id, &assignment.date).await
+}
+
+async fn insert_assignment(
+    conn: &mut sqlx::SqliteConnection,
+    assignment: &crate::models::ClinicalAssignment
+) -> Result<(), sqlx::Error> {
     sqlx::query(
         "INSERT INTO clinical_assignments (
             id, student_id, site_id, preceptor_id, date, start_time, end_time,
@@ -2030,20 +3571,54 @@ pub async fn create_clinical_assignment(
     .bind(&assignment.status)
     .bind(&assignment.notes)
     .bind(&assignment.created_at)
-    .execute(&state.db)
-    .await
-    .map_err(|e| e.to_string())?;
+    .execute(&mut *conn)
+    .await?;
     Ok(())
 }
 
+/// Students must be cleared for the site on the assignment date unless `allow_uncleared` is set.
+#[tauri::command]
+pub async fn create_clinical_assignment(
+    state: State<'_, DbState>,
+    assignment: crate::models::ClinicalAssignment,
+    allow_uncleared: Option<bool>
+) -> Result<(), String> {
+    if !allow_uncleared.unwrap_or(false) {
+        let clearance = assignment_clearance(&state.db, &assignment).await?;
+        if !clearance.cleared {
+            return Err(crate::clearance::describe_blockers(&clearance));
+        }
+    }
+
+    let mut conn = state.db.acquire().await.map_err(|e| e.to_string())?;
+    insert_assignment(&mut conn, &assignment).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn bulk_create_assignments(
     state: State<'_, DbState>,
-    assignments: Vec<crate::models::ClinicalAssignment>
+    assignments: Vec<crate::models::ClinicalAssignment>,
+    allow_uncleared: Option<bool>
 ) -> Result<(), String> {
-    for assignment in assignments {
-        create_clinical_assignment(state.clone(), assignment).await?;
+    // Check every student up front, then insert the batch in one transaction so it is never left half-created
+    if !allow_uncleared.unwrap_or(false) {
+        let mut blockers = Vec::new();
+        for assignment in &assignments {
+            let clearance = assignment_clearance(&state.db, assignment).await?;
+            if !clearance.cleared {
+                blockers.push(crate::clearance::describe_blockers(&clearance));
+            }
+        }
+        if !blockers.is_empty() {
+            return Err(blockers.join("\n"));
+        }
+    }
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    for assignment in &assignments {
+        insert_assignment(&mut tx, assignment).await.map_err(|e| e.to_string())?;
     }
+    tx.commit().await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -2165,11 +3740,30 @@ pub async fn get_student_assignments(
     .map_err(|e| e.to_string())
 }
 
+/// Moving an assignment re-checks clearance for the new site and date unless `allow_uncleared` is set.
 #[tauri::command]
 pub async fn update_assignment(
     state: State<'_, DbState>,
-    assignment: crate::models::ClinicalAssignment
+    assignment: crate::models::ClinicalAssignment,
+    allow_uncleared: Option<bool>
 ) -> Result<(), String> {
+    if !allow_uncleared.unwrap_or(false) {
+        // The student can't change on update, so check the one already on the assignment
+        let (student_id,): (String,) = sqlx::query_as("SELECT student_id FROM clinical_assignments WHERE id = ?")
+            .bind(&assignment.id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Assignment not found".to_string())?;
+        let clearance = assignment_clearance(
+            &state.db,
+            &crate::models::ClinicalAssignment { student_id, ..assignment.clone() }
+        ).await?;
+        if !clearance.cleared {
+            return Err(crate::clearance::describe_blockers(&clearance));
+        }
+    }
+
     sqlx::query(
         "UPDATE clinical_assignments SET
             site_id = ?, preceptor_id = ?, date = ?, start_time = ?, end_time = ?,
@@ -2401,12 +3995,31 @@ pub async fn submit_hours(
     state: State<'_, DbState>,
     submission: crate::models::StudentHourSubmission
 ) -> Result<(), String> {
+    // Prefer the assignment's site, then fall back to matching the typed site name
+    let site_id = match submission.site_id {
+        Some(ref id) => Some(id.clone()),
+        None => {
+            let assignment_site: Option<(String,)> = match submission.assignment_id {
+                Some(ref assignment_id) => sqlx::query_as("SELECT site_id FROM clinical_assignments WHERE id = ?")
+                    .bind(assignment_id)
+                    .fetch_optional(&state.db)
+                    .await
+                    .map_err(|e| e.to_string())?,
+                None => None,
+            };
+            match assignment_site {
+                Some((id,)) => Some(id),
+                None => crate::db::resolve_site_id(&state.db, &submission.site_name).await.map_err(|e| e.to_string())?,
+            }
+        }
+    };
+
     sqlx::query(
         "INSERT INTO student_hour_submissions (
             id, student_id, assignment_id, date, site_name, start_time, end_time,
             hours, activities, skills_practiced, reflection, status,
-            reviewer_feedback, reviewed_at, reviewed_by, submitted_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            reviewer_feedback, reviewed_at, reviewed_by, submitted_at, site_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&submission.id)
     .bind(&submission.student_id)
@@ -2424,6 +4037,7 @@ pub async fn submit_hours(
     .bind(&submission.reviewed_at)
     .bind(&submission.reviewed_by)
     .bind(&submission.submitted_at)
+    .bind(&site_id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
@@ -2531,6 +4145,13 @@ pub async fn add_instructor_certification(
     state: State<'_, DbState>,
     cert: InstructorCertification
 ) -> Result<(), String> {
+    let status = crate::cert_status::derive_status(
+        &cert.status,
+        &cert.expiry_date,
+        cert.alert_days as i64,
+        chrono::Local::now().date_naive()
+    );
+
     sqlx::query(
         "INSERT INTO instructor_certifications (
             id, certification_type, certification_name, license_number,
@@ -2546,7 +4167,7 @@ pub async fn add_instructor_certification(
     .bind(&cert.issue_date)
     .bind(&cert.expiry_date)
     .bind(cert.alert_days)
-    .bind(&cert.status)
+    .bind(&status)
     .bind(&cert.document_path)
     .bind(&cert.notes)
     .bind(&cert.created_at)
@@ -2554,7 +4175,9 @@ pub async fn add_instructor_certification(
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
-    Ok(())
+
+    // Opens or completes the renewal deadline right away instead of at the next daily sweep
+    crate::cert_status::sync_instructor_certification(&state.db, &cert.id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -2562,7 +4185,14 @@ pub async fn update_instructor_certification(
     state: State<'_, DbState>,
     cert: InstructorCertification
 ) -> Result<(), String> {
+    let previous_group = crate::cert_status::instructor_group(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
     let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    let status = crate::cert_status::derive_status(
+        &cert.status,
+        &cert.expiry_date,
+        cert.alert_days as i64,
+        chrono::Local::now().date_naive()
+    );
 
     sqlx::query(
         "UPDATE instructor_certifications SET
@@ -2578,7 +4208,7 @@ pub async fn update_instructor_certification(
     .bind(&cert.issue_date)
     .bind(&cert.expiry_date)
     .bind(cert.alert_days)
-    .bind(&cert.status)
+    .bind(&status)
     .bind(&cert.document_path)
     .bind(&cert.notes)
     .bind(&now)
@@ -2586,16 +4216,42 @@ pub async fn update_instructor_certification(
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+
+    // Opens or completes the renewal deadline right away instead of at the next daily sweep
+    crate::cert_status::sync_instructor_certification(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
+
+    // If the type or name changed, the certifications it used to renew may need their deadlines back
+    let current_group = crate::cert_status::instructor_group(&state.db, &cert.id).await.map_err(|e| e.to_string())?;
+    if let Some(group) = previous_group.filter(|group| Some(group) != current_group.as_ref()) {
+        crate::cert_status::sync_instructor_group(&state.db, &group).await.map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_instructor_certification(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let group = crate::cert_status::instructor_group(&state.db, &id).await.map_err(|e| e.to_string())?;
+
+    // Drop the open renewal deadline along with the certification
+    sqlx::query(
+        "DELETE FROM deadlines WHERE status != 'Completed'
+        AND id = (SELECT renewal_deadline_id FROM instructor_certifications WHERE id = ?)"
+    )
+    .bind(&id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
     sqlx::query("DELETE FROM instructor_certifications WHERE id = ?")
         .bind(&id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+
+    // Deleting a renewal leaves the certification it replaced needing renewal again
+    if let Some(group) = group {
+        crate::cert_status::sync_instructor_group(&state.db, &group).await.map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
@@ -2658,15 +4314,20 @@ pub async fn get_instructor_certification_alerts(
 
 // Comp Hours Commands
 
+/// Records earned hours under the comp hours policy and returns the entry as stored
+/// (expiration date, cap-trimmed hours and approval status may differ from what was sent).
 #[tauri::command]
 pub async fn add_comp_hours_earned(
     state: State<'_, DbState>,
     entry: CompHoursEarned
-) -> Result<(), String> {
+) -> Result<CompHoursEarned, String> {
+    let mut entry = entry;
+    crate::comp_ledger::apply_policy(&state.db, &mut entry).await?;
+
     sqlx::query(
         "INSERT INTO comp_hours_earned (
-            id, date, activity_type, hours, notes, expiration_date, created_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            id, date, activity_type, hours, notes, expiration_date, created_at, approval_status, approved_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&entry.id)
     .bind(&entry.date)
@@ -2675,12 +4336,51 @@ pub async fn add_comp_hours_earned(
     .bind(&entry.notes)
     .bind(&entry.expiration_date)
     .bind(&entry.created_at)
+    .bind(&entry.approval_status)
+    .bind(&entry.approved_at)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    crate::comp_ledger::sync(&state.db).await.map_err(|e| e.to_string())?;
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn approve_comp_hours_earned(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let result = sqlx::query(
+        "UPDATE comp_hours_earned SET approval_status = 'approved', approved_at = ? WHERE id = ? AND approval_status = 'pending'"
+    )
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&id)
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+    if result.rows_affected() == 0 {
+        return Err("No pending comp hours entry with that id".to_string());
+    }
+    crate::comp_ledger::sync(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_comp_hours_policy(state: State<'_, DbState>) -> Result<CompHoursPolicy, String> {
+    crate::comp_ledger::load_policy(&state.db).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_comp_hours_policy(state: State<'_, DbState>, policy: CompHoursPolicy) -> Result<(), String> {
+    if policy.accrual_cap.map(|cap| cap <= 0.0).unwrap_or(false) {
+        return Err("The accrual cap must be greater than zero".to_string());
+    }
+    if policy.expiration_months.map(|months| months < 1).unwrap_or(false) {
+        return Err("The expiration period must be at least 1 month".to_string());
+    }
+    if policy.warning_days_red < 0 || policy.warning_days_yellow < policy.warning_days_red {
+        return Err("Warning tiers must satisfy 0 <= red days <= yellow days".to_string());
+    }
+    crate::comp_ledger::save_policy(&state.db, &policy).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_all_comp_hours_earned(state: State<'_, DbState>) -> Result<Vec<CompHoursEarned>, String> {
     sqlx::query_as::<_, CompHoursEarned>(
@@ -2693,11 +4393,18 @@ pub async fn get_all_comp_hours_earned(state: State<'_, DbState>) -> Result<Vec<
 
 #[tauri::command]
 pub async fn delete_comp_hours_earned(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM comp_hours_allocations WHERE earned_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
     sqlx::query("DELETE FROM comp_hours_earned WHERE id = ?")
         .bind(&id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+    // Uses drawn from this lot move to the next eligible one
+    crate::comp_ledger::sync(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -2720,6 +4427,7 @@ pub async fn add_comp_hours_used(
     .execute(&state.db)
     .await
     .map_err(|e| e.to_string())?;
+    crate::comp_ledger::sync(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -2735,102 +4443,57 @@ pub async fn get_all_comp_hours_used(state: State<'_, DbState>) -> Result<Vec<Co
 
 #[tauri::command]
 pub async fn delete_comp_hours_used(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    sqlx::query("DELETE FROM comp_hours_allocations WHERE used_id = ?")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
     sqlx::query("DELETE FROM comp_hours_used WHERE id = ?")
         .bind(&id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
+    crate::comp_ledger::sync(&state.db).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
 #[tauri::command]
 pub async fn get_comp_hours_summary(state: State<'_, DbState>) -> Result<CompHoursSummary, String> {
-    let pool = &state.db;
-
-    // Get total earned
-    let total_earned: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_earned"
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Get total used
-    let total_used: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_used"
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Get earned this year
-    let year_start = chrono::Utc::now().format("%Y-01-01").to_string();
-    let earned_this_year: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_earned WHERE date >= ?"
-    )
-    .bind(&year_start)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Get used this year
-    let used_this_year: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_used WHERE date >= ?"
-    )
-    .bind(&year_start)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Calculate hours expiring within 6 months
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let six_months = chrono::Utc::now()
-        .checked_add_signed(chrono::TimeDelta::try_days(180).unwrap())
-        .map(|d| d.format("%Y-%m-%d").to_string())
-        .unwrap_or_default();
-
-    let expiring: (f64,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_earned
-         WHERE expiration_date IS NOT NULL AND expiration_date >= ? AND expiration_date <= ?"
-    )
-    .bind(&today)
-    .bind(&six_months)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Get nearest expiration date
-    let nearest_expiry: Option<(String,)> = sqlx::query_as(
-        "SELECT expiration_date FROM comp_hours_earned
-         WHERE expiration_date IS NOT NULL AND expiration_date >= ?
-         ORDER BY expiration_date LIMIT 1"
-    )
-    .bind(&today)
-    .fetch_optional(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    let today = chrono::Local::now().date_naive();
+    let ledger = crate::comp_ledger::load(&state.db, today).await.map_err(|e| e.to_string())?;
+    let used = sqlx::query_as::<_, CompHoursUsed>("SELECT * FROM comp_hours_used")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
 
-    let (expiring_date, days_until_expiry) = if let Some((date,)) = nearest_expiry {
-        let today_date = chrono::Utc::now().date_naive();
-        if let Ok(exp_date) = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
-            let days = (exp_date - today_date).num_days() as i32;
-            (Some(date), Some(days))
-        } else {
-            (Some(date), None)
-        }
-    } else {
-        (None, None)
-    };
+    let year_start = today.format("%Y-01-01").to_string();
+    let total_earned: f64 = ledger.lots.iter().map(|l| l.earned.hours).sum();
+    let total_used: f64 = used.iter().map(|u| u.hours).sum();
+    let earned_this_year: f64 = ledger.lots.iter().filter(|l| l.earned.date >= year_start).map(|l| l.earned.hours).sum();
+    let used_this_year: f64 = used.iter().filter(|u| u.date >= year_start).map(|u| u.hours).sum();
+
+    // Unspent hours inside the policy's warning window
+    let policy = crate::comp_ledger::load_policy(&state.db).await.map_err(|e| e.to_string())?;
+    let expiring_soon: f64 = ledger
+        .forfeiture_schedule
+        .iter()
+        .filter(|w| w.days_until_expiry <= policy.warning_days_yellow)
+        .map(|w| w.hours)
+        .sum();
+    let nearest = ledger.forfeiture_schedule.first();
 
     Ok(CompHoursSummary {
-        total_earned: total_earned.0,
-        total_used: total_used.0,
-        balance: total_earned.0 - total_used.0,
-        earned_this_year: earned_this_year.0,
-        used_this_year: used_this_year.0,
-        expiring_soon: expiring.0,
-        expiring_date,
-        days_until_expiry,
+        total_earned,
+        total_used,
+        // Uses with nothing left to draw from put the balance below zero
+        balance: ledger.available - ledger.unallocated_used,
+        earned_this_year,
+        used_this_year,
+        expiring_soon,
+        expiring_date: nearest.map(|w| w.expiration_date.clone()),
+        days_until_expiry: nearest.map(|w| w.days_until_expiry),
+        forfeited: ledger.forfeited,
+        unallocated_used: ledger.unallocated_used,
     })
 }
 
@@ -2838,56 +4501,56 @@ pub async fn get_comp_hours_summary(state: State<'_, DbState>) -> Result<CompHou
 pub async fn get_comp_hours_expiration_warnings(
     state: State<'_, DbState>
 ) -> Result<Vec<CompHoursExpirationWarning>, String> {
-    let pool = &state.db;
-
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let today_date = chrono::Utc::now().date_naive();
-
-    // Get all entries with expiration dates in the future
-    let entries = sqlx::query_as::<_, CompHoursEarned>(
-        "SELECT * FROM comp_hours_earned
-         WHERE expiration_date IS NOT NULL AND expiration_date >= ?
-         ORDER BY expiration_date"
-    )
-    .bind(&today)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+    let ledger = crate::comp_ledger::load(&state.db, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ledger.forfeiture_schedule)
+}
 
-    // Group by expiration date and calculate totals
-    let mut warnings: HashMap<String, f64> = HashMap::new();
-    for entry in entries {
-        if let Some(exp_date) = entry.expiration_date {
-            *warnings.entry(exp_date).or_insert(0.0) += entry.hours;
-        }
-    }
+/// Earned lots with what was consumed and forfeited from each, the use-to-lot allocations,
+/// and the projected forfeiture schedule. Read-only; allocations are rebuilt when entries change.
+#[tauri::command]
+pub async fn get_comp_hours_ledger(state: State<'_, DbState>) -> Result<CompHoursLedger, String> {
+    crate::comp_ledger::load(&state.db, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    let mut result = Vec::new();
-    for (exp_date, hours) in warnings {
-        if let Ok(expiry) = chrono::NaiveDate::parse_from_str(&exp_date, "%Y-%m-%d") {
-            let days = (expiry - today_date).num_days() as i32;
+#[tauri::command]
+pub async fn get_comp_timesheet(state: State<'_, DbState>, start_date: String, end_date: String) -> Result<CompTimesheet, String> {
+    crate::comp_ledger::timesheet(&state.db, &start_date, &end_date).await
+}
 
-            let alert_level = if days <= 90 {
-                "red" // < 3 months
-            } else if days <= 180 {
-                "yellow" // 3-6 months
-            } else {
-                "green" // > 6 months
-            };
+/// Writes the pay period timesheet as CSV or PDF and returns the path written.
+#[tauri::command]
+pub async fn export_comp_timesheet(state: State<'_, DbState>, request: CompTimesheetRequest) -> Result<String, String> {
+    let timesheet = crate::comp_ledger::timesheet(&state.db, &request.start_date, &request.end_date).await?;
 
-            result.push(CompHoursExpirationWarning {
-                hours,
-                expiration_date: exp_date,
-                days_until_expiry: days,
-                alert_level: alert_level.to_string(),
-            });
+    let extension = match request.format.as_str() {
+        "csv" => "csv",
+        "pdf" => "pdf",
+        other => return Err(format!("Unsupported timesheet format '{}'", other)),
+    };
+    let mut output_path = std::path::PathBuf::from(&request.output_path);
+    if output_path.extension().map(|e| !e.eq_ignore_ascii_case(extension)).unwrap_or(true) {
+        output_path.set_extension(extension);
+    }
+    if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if !parent.is_dir() {
+            return Err(format!("Folder does not exist: {}", parent.display()));
         }
     }
 
-    // Sort by days until expiry
-    result.sort_by_key(|w| w.days_until_expiry);
+    if extension == "csv" {
+        crate::reports::comp_timesheet_csv(&output_path, &request.employee_name, &timesheet)?;
+    } else {
+        let mut writer = crate::reports::ReportWriter::new("Compensatory Time Timesheet", &request.branding)?;
+        crate::reports::comp_timesheet(&mut writer, &request.employee_name, &timesheet);
+        writer.signature_block(&[request.employee_name.clone(), "Supervisor".to_string()]);
+        writer.save(&output_path)?;
+    }
 
-    Ok(result)
+    Ok(output_path.to_string_lossy().to_string())
 }
 
 // ==================== COURSES ====================
@@ -2976,6 +4639,12 @@ pub async fn delete_course(state: State<'_, DbState>, id: String) -> Result<(),
     Ok(())
 }
 
+/// Copies a course with its lesson plans, materials and regulation links into a new semester.
+#[tauri::command]
+pub async fn clone_course(state: State<'_, DbState>, request: CourseCloneRequest) -> Result<CourseCloneResult, String> {
+    crate::course_schedule::clone_course(&state.db, &request).await
+}
+
 #[tauri::command]
 pub async fn get_lesson_plans_by_course(state: State<'_, DbState>, course_id: String) -> Result<Vec<LessonPlan>, String> {
     sqlx::query_as::<_, LessonPlan>(
@@ -3080,10 +4749,109 @@ pub async fn get_lesson_plan_with_materials(
 // ==================== VBON COMPLIANCE MAPPING ====================
 
 #[tauri::command]
-pub async fn get_all_vbon_regulations(state: State<'_, DbState>) -> Result<Vec<VBONRegulation>, String> {
+pub async fn get_regulatory_frameworks(state: State<'_, DbState>) -> Result<Vec<RegulatoryFramework>, String> {
+    sqlx::query_as::<_, RegulatoryFramework>(
+        "SELECT * FROM regulatory_frameworks ORDER BY is_current DESC, framework_type DESC, code"
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Adds a framework or updates its details. Whether it is current is changed only by `set_current_regulatory_framework`.
+#[tauri::command]
+pub async fn save_regulatory_framework(state: State<'_, DbState>, framework: RegulatoryFramework) -> Result<(), String> {
+    let id = framework.id.trim().to_lowercase();
+    if id.is_empty() || framework.code.trim().is_empty() || framework.name.trim().is_empty() {
+        return Err("A framework needs an id, code and name".to_string());
+    }
+    if !matches!(framework.framework_type.as_str(), "state_board" | "accreditor") {
+        return Err(format!("Unknown framework type '{}'", framework.framework_type));
+    }
+    let jurisdiction = framework
+        .jurisdiction
+        .as_deref()
+        .map(|j| j.trim().to_uppercase())
+        .filter(|j| !j.is_empty());
+
+    sqlx::query(
+        "INSERT INTO regulatory_frameworks (id, code, name, framework_type, jurisdiction, description, is_current, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, 0, ?)
+        ON CONFLICT(id) DO UPDATE SET
+            code = excluded.code, name = excluded.name, framework_type = excluded.framework_type,
+            jurisdiction = excluded.jurisdiction, description = excluded.description"
+    )
+    .bind(&id)
+    .bind(framework.code.trim())
+    .bind(framework.name.trim())
+    .bind(&framework.framework_type)
+    .bind(&jurisdiction)
+    .bind(&framework.description)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Deletes a framework with its regulation catalog and mappings. The current framework cannot be deleted.
+#[tauri::command]
+pub async fn delete_regulatory_framework(state: State<'_, DbState>, id: String) -> Result<(), String> {
+    let framework = crate::regulations::resolve(&state.db, Some(&id)).await?;
+    if framework.is_current {
+        return Err("Switch to another framework before deleting the current one".to_string());
+    }
+
+    let regulation_ids: Vec<String> = sqlx::query_scalar("SELECT id FROM vbon_regulations WHERE framework_id = ?")
+        .bind(&id)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    crate::regulation_links::remove_regulations(&mut *tx, &regulation_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query(
+        "DELETE FROM vbon_mappings WHERE regulation_id IN (SELECT id FROM vbon_regulations WHERE framework_id = ?)"
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM vbon_regulations WHERE framework_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM regulatory_frameworks WHERE id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Makes `id` the framework used when a command is not given one.
+#[tauri::command]
+pub async fn set_current_regulatory_framework(state: State<'_, DbState>, id: String) -> Result<RegulatoryFramework, String> {
+    crate::regulations::resolve(&state.db, Some(&id)).await?;
+    sqlx::query("UPDATE regulatory_frameworks SET is_current = (id = ?)")
+        .bind(&id)
+        .execute(&state.db)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::regulations::current(&state.db).await
+}
+
+#[tauri::command]
+pub async fn get_all_vbon_regulations(state: State<'_, DbState>, framework_id: Option<String>) -> Result<Vec<VBONRegulation>, String> {
+    let framework = crate::regulations::resolve(&state.db, framework_id.as_deref()).await?;
     sqlx::query_as::<_, VBONRegulation>(
-        "SELECT * FROM vbon_regulations WHERE is_active = 1 ORDER BY sort_order"
+        "SELECT * FROM vbon_regulations WHERE is_active = 1 AND framework_id = ? ORDER BY sort_order"
     )
+    .bind(&framework.id)
     .fetch_all(&state.db)
     .await
     .map_err(|e| e.to_string())
@@ -3091,12 +4859,14 @@ pub async fn get_all_vbon_regulations(state: State<'_, DbState>) -> Result<Vec<V
 
 #[tauri::command]
 pub async fn add_vbon_regulation(state: State<'_, DbState>, regulation: VBONRegulation) -> Result<(), String> {
+    crate::regulations::resolve(&state.db, Some(&regulation.framework_id)).await?;
     sqlx::query(
         "INSERT INTO vbon_regulations (
-            id, code, section, category, title, description, sort_order, is_active, created_at, updated_at
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            id, framework_id, code, section, category, title, description, sort_order, is_active, created_at, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&regulation.id)
+    .bind(&regulation.framework_id)
     .bind(&regulation.code)
     .bind(&regulation.section)
     .bind(&regulation.category)
@@ -3114,14 +4884,25 @@ pub async fn add_vbon_regulation(state: State<'_, DbState>, regulation: VBONRegu
 
 #[tauri::command]
 pub async fn seed_vbon_regulations(state: State<'_, DbState>, regulations: Vec<VBONRegulation>) -> Result<(), String> {
+    // Built-in regulations never overwrite a catalog edition the user imported
+    let mut imported: HashMap<String, bool> = HashMap::new();
     for regulation in regulations {
+        if !imported.contains_key(&regulation.framework_id) {
+            let current = crate::regulation_catalog::current_edition(&state.db, &regulation.framework_id).await?;
+            imported.insert(regulation.framework_id.clone(), current.is_some());
+        }
+        if imported[&regulation.framework_id] {
+            continue;
+        }
+
         // Use INSERT OR REPLACE to handle existing regulations
         sqlx::query(
             "INSERT OR REPLACE INTO vbon_regulations (
-                id, code, section, category, title, description, sort_order, is_active, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                id, framework_id, code, section, category, title, description, sort_order, is_active, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&regulation.id)
+        .bind(&regulation.framework_id)
         .bind(&regulation.code)
         .bind(&regulation.section)
         .bind(&regulation.category)
@@ -3139,33 +4920,56 @@ pub async fn seed_vbon_regulations(state: State<'_, DbState>, regulations: Vec<V
 }
 
 #[tauri::command]
-pub async fn get_vbon_regulations_with_mappings(state: State<'_, DbState>) -> Result<Vec<VBONRegulationWithMapping>, String> {
-    let pool = &state.db;
+pub async fn get_vbon_regulations_with_mappings(
+    state: State<'_, DbState>,
+    framework_id: Option<String>,
+) -> Result<Vec<VBONRegulationWithMapping>, String> {
+    let framework = crate::regulations::resolve(&state.db, framework_id.as_deref()).await?;
+    crate::regulations::regulations_with_mappings(&state.db, &framework.id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    let regulations = sqlx::query_as::<_, VBONRegulation>(
-        "SELECT * FROM vbon_regulations WHERE is_active = 1 ORDER BY sort_order"
+#[tauri::command]
+pub async fn get_lesson_plan_regulations(state: State<'_, DbState>, lesson_plan_id: String) -> Result<Vec<VBONRegulation>, String> {
+    sqlx::query_as::<_, VBONRegulation>(
+        "SELECT r.* FROM lesson_plan_regulations l
+        JOIN vbon_regulations r ON r.id = l.regulation_id
+        WHERE l.lesson_plan_id = ?
+        ORDER BY r.framework_id, r.sort_order"
     )
-    .fetch_all(pool)
+    .bind(&lesson_plan_id)
+    .fetch_all(&state.db)
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| e.to_string())
+}
 
-    let mut results = Vec::new();
-    for reg in regulations {
-        let mapping = sqlx::query_as::<_, VBONMapping>(
-            "SELECT * FROM vbon_mappings WHERE regulation_id = ?"
-        )
-        .bind(&reg.id)
-        .fetch_optional(pool)
+/// Replaces the regulations a lesson plan teaches; the regulations' mappings are updated to match.
+#[tauri::command]
+pub async fn set_lesson_plan_regulations(
+    state: State<'_, DbState>,
+    lesson_plan_id: String,
+    regulation_ids: Vec<String>,
+) -> Result<(), String> {
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    crate::regulation_links::set_for_lesson_plan(&mut *tx, &lesson_plan_id, &regulation_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+/// Replaces the lesson plans that teach a regulation; the plans' tags are updated to match.
+#[tauri::command]
+pub async fn set_regulation_lesson_plans(
+    state: State<'_, DbState>,
+    regulation_id: String,
+    lesson_plan_ids: Vec<String>,
+) -> Result<(), String> {
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    crate::regulation_links::set_for_regulation(&mut *tx, &regulation_id, &lesson_plan_ids)
         .await
         .map_err(|e| e.to_string())?;
-
-        results.push(VBONRegulationWithMapping {
-            regulation: reg,
-            mapping,
-        });
-    }
-
-    Ok(results)
+    tx.commit().await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -3188,7 +4992,9 @@ pub async fn upsert_vbon_mapping(state: State<'_, DbState>, mapping: VBONMapping
         "UPDATE vbon_mappings SET
             syllabus_reference = ?, lesson_plan_ids = ?, material_links = ?,
             assessment_method = ?, clinical_experience = ?, notes = ?,
-            coverage_status = ?, last_reviewed_date = ?, reviewed_by = ?, updated_at = ?
+            coverage_status = ?, last_reviewed_date = ?, reviewed_by = ?, updated_at = ?,
+            reviewed_edition_id = (SELECT edition_id FROM vbon_regulations WHERE id = vbon_mappings.regulation_id),
+            needs_review = 0, review_reason = NULL
         WHERE regulation_id = ?"
     )
     .bind(&mapping.syllabus_reference)
@@ -3212,8 +5018,9 @@ pub async fn upsert_vbon_mapping(state: State<'_, DbState>, mapping: VBONMapping
             "INSERT INTO vbon_mappings (
                 id, regulation_id, syllabus_reference, lesson_plan_ids, material_links,
                 assessment_method, clinical_experience, notes, coverage_status,
-                last_reviewed_date, reviewed_by, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                last_reviewed_date, reviewed_by, created_at, updated_at, reviewed_edition_id
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+                (SELECT edition_id FROM vbon_regulations WHERE id = ?))"
         )
         .bind(&mapping.id)
         .bind(&mapping.regulation_id)
@@ -3228,107 +5035,132 @@ pub async fn upsert_vbon_mapping(state: State<'_, DbState>, mapping: VBONMapping
         .bind(&mapping.reviewed_by)
         .bind(&now)
         .bind(&now)
+        .bind(&mapping.regulation_id)
         .execute(&state.db)
         .await
         .map_err(|e| e.to_string())?;
     }
 
+    crate::regulation_links::save_mapping_lesson_plans(&state.db, &mapping.regulation_id, mapping.lesson_plan_ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
 #[tauri::command]
-pub async fn get_vbon_compliance_summary(state: State<'_, DbState>) -> Result<VBONComplianceSummary, String> {
-    let pool = &state.db;
-
-    // Get all regulations
-    let regulations = sqlx::query_as::<_, VBONRegulation>(
-        "SELECT * FROM vbon_regulations WHERE is_active = 1"
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+pub async fn get_vbon_compliance_summary(
+    state: State<'_, DbState>,
+    framework_id: Option<String>,
+) -> Result<VBONComplianceSummary, String> {
+    let framework = crate::regulations::resolve(&state.db, framework_id.as_deref()).await?;
+    crate::regulations::compliance_summary(&state.db, &framework.id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    // Get all mappings
-    let mappings = sqlx::query_as::<_, VBONMapping>(
-        "SELECT * FROM vbon_mappings"
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| e.to_string())?;
+/// Compliance summary for every framework, current first.
+#[tauri::command]
+pub async fn get_compliance_by_framework(state: State<'_, DbState>) -> Result<Vec<FrameworkComplianceSummary>, String> {
+    let frameworks = get_regulatory_frameworks(state.clone()).await?;
+    let mut results = Vec::new();
+    for framework in frameworks {
+        let summary = crate::regulations::compliance_summary(&state.db, &framework.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        results.push(FrameworkComplianceSummary { framework, summary });
+    }
+    Ok(results)
+}
 
-    // Create a map of regulation_id to mapping
-    let mapping_map: HashMap<String, VBONMapping> = mappings
-        .into_iter()
-        .map(|m| (m.regulation_id.clone(), m))
-        .collect();
+/// Per-regulation evidence with gaps and stale reviews (older than `review_interval_days`, default a year).
+#[tauri::command]
+pub async fn get_vbon_evidence_report(
+    state: State<'_, DbState>,
+    framework_id: Option<String>,
+    review_interval_days: Option<i64>,
+) -> Result<crate::models::VBONEvidenceReport, String> {
+    let framework = crate::regulations::resolve(&state.db, framework_id.as_deref()).await?;
+    let interval = review_interval_days.unwrap_or(crate::vbon_evidence::DEFAULT_REVIEW_INTERVAL_DAYS);
+    crate::vbon_evidence::build(&state.db, &framework, interval, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    // Calculate stats
-    let total_regulations = regulations.len() as i32;
-    let mut covered_count = 0;
-    let mut partial_count = 0;
-    let mut not_covered_count = 0;
+/// Writes the evidence package (report PDF, CSV index and referenced local files) as a folder or zip.
+#[tauri::command]
+pub async fn export_vbon_evidence_package(
+    state: State<'_, DbState>,
+    request: crate::models::VBONEvidenceExportRequest,
+) -> Result<String, String> {
+    let framework = crate::regulations::resolve(&state.db, request.framework_id.as_deref()).await?;
+    let interval = request.review_interval_days.unwrap_or(crate::vbon_evidence::DEFAULT_REVIEW_INTERVAL_DAYS);
+    let report = crate::vbon_evidence::build(&state.db, &framework, interval, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::vbon_evidence::export(report, &request.output_path, request.zip, &request.branding)
+}
 
-    // Group by category
-    let mut category_map: HashMap<String, VBONCategoryStats> = HashMap::new();
+#[tauri::command]
+pub async fn get_regulation_catalog_editions(
+    state: State<'_, DbState>,
+    framework_id: Option<String>,
+) -> Result<Vec<RegulationCatalogEdition>, String> {
+    let framework = crate::regulations::resolve(&state.db, framework_id.as_deref()).await?;
+    sqlx::query_as::<_, RegulationCatalogEdition>(
+        "SELECT * FROM regulation_catalog_editions WHERE framework_id = ? ORDER BY imported_at DESC"
+    )
+    .bind(&framework.id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
 
-    for reg in &regulations {
-        let status = mapping_map.get(&reg.id)
-            .map(|m| m.coverage_status.as_str())
-            .unwrap_or("not_covered");
+/// Imports a JSON or CSV catalog as a new edition, returning its diff against the live catalog.
+#[tauri::command]
+pub async fn import_regulation_catalog(
+    state: State<'_, DbState>,
+    request: RegulationCatalogImportRequest,
+) -> Result<RegulationCatalogImportResult, String> {
+    let framework = crate::regulations::resolve(&state.db, request.framework_id.as_deref()).await?;
+    crate::regulation_catalog::import(&state.db, &framework, &request).await
+}
 
-        match status {
-            "covered" => covered_count += 1,
-            "partial" => partial_count += 1,
-            _ => not_covered_count += 1,
-        }
+/// Added, removed and changed regulations between two editions; without `from_edition_id`, against the live catalog.
+#[tauri::command]
+pub async fn diff_regulation_catalog_editions(
+    state: State<'_, DbState>,
+    from_edition_id: Option<String>,
+    to_edition_id: String,
+) -> Result<RegulationCatalogDiff, String> {
+    crate::regulation_catalog::diff_editions(&state.db, from_edition_id.as_deref(), &to_edition_id).await
+}
 
-        // Update category stats
-        let category_stat = category_map
-            .entry(reg.category.clone())
-            .or_insert_with(|| VBONCategoryStats {
-                category: reg.category.clone(),
-                total: 0,
-                covered: 0,
-                partial: 0,
-                not_covered: 0,
-            });
+/// Makes an imported edition the live catalog; returns the number of mappings flagged for re-review.
+#[tauri::command]
+pub async fn apply_regulation_catalog_edition(state: State<'_, DbState>, edition_id: String) -> Result<i32, String> {
+    crate::regulation_catalog::apply(&state.db, &edition_id).await
+}
 
-        category_stat.total += 1;
-        match status {
-            "covered" => category_stat.covered += 1,
-            "partial" => category_stat.partial += 1,
-            _ => category_stat.not_covered += 1,
-        }
+#[tauri::command]
+pub async fn delete_regulation_catalog_edition(state: State<'_, DbState>, edition_id: String) -> Result<(), String> {
+    let edition = crate::regulation_catalog::load_edition(&state.db, &edition_id).await?;
+    if edition.is_current {
+        return Err("The live edition cannot be deleted".to_string());
     }
-
-    // Calculate percentage (count covered as 100%, partial as 50%)
-    let percentage_complete = if total_regulations > 0 {
-        ((covered_count as f64 * 100.0) + (partial_count as f64 * 50.0)) / (total_regulations as f64)
-    } else {
-        0.0
-    };
-
-    // Get last audit date (most recent last_reviewed_date from mappings)
-    let last_audit_date: Option<String> = sqlx::query_scalar(
-        "SELECT MAX(last_reviewed_date) FROM vbon_mappings WHERE last_reviewed_date IS NOT NULL"
-    )
-    .fetch_one(pool)
-    .await
-    .map_err(|e| e.to_string())?;
-
-    // Convert category map to vec
-    let mut regulations_by_category: Vec<VBONCategoryStats> = category_map.into_values().collect();
-    regulations_by_category.sort_by(|a, b| a.category.cmp(&b.category));
-
-    Ok(VBONComplianceSummary {
-        total_regulations,
-        covered_count,
-        partial_count,
-        not_covered_count,
-        percentage_complete,
-        last_audit_date,
-        regulations_by_category,
-    })
+    let mut tx = state.db.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM regulation_edition_entries WHERE edition_id = ?")
+        .bind(&edition_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM regulation_catalog_editions WHERE id = ?")
+        .bind(&edition_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
 }
 
 #[tauri::command]
@@ -3501,6 +5333,14 @@ pub struct ImportResult {
     pub imported: i32,
     pub failed: i32,
     pub errors: Vec<String>,
+    #[serde(default)]
+    pub unmatched_preceptors: Vec<String>,
+    #[serde(default)]
+    pub updated: i32,
+    #[serde(default)]
+    pub skipped: i32,
+    #[serde(default)]
+    pub queued: i32,
 }
 
 #[tauri::command]
@@ -3511,11 +5351,29 @@ pub async fn import_preceptor_evaluations(
     let mut imported = 0;
     let mut failed = 0;
     let mut errors: Vec<String> = vec![];
+    let mut unmatched_preceptors: Vec<String> = vec![];
 
     for eval in evaluations {
+        // Match the imported name to a preceptor record; unmatched names are still imported
+        // A lookup failure fails this row only; earlier rows are already saved
+        let preceptor_id = match eval.preceptor_id {
+            Some(ref id) => Some(id.clone()),
+            None => match crate::db::resolve_preceptor_id(&state.db, &eval.preceptor_name).await {
+                Ok(id) => id,
+                Err(e) => {
+                    failed += 1;
+                    errors.push(format!("Failed to match preceptor {}: {}", eval.preceptor_name, e));
+                    continue;
+                }
+            },
+        };
+        if preceptor_id.is_none() && !unmatched_preceptors.contains(&eval.preceptor_name) {
+            unmatched_preceptors.push(eval.preceptor_name.clone());
+        }
+
         let result = sqlx::query(
-            "INSERT INTO preceptor_evaluations (id, student_id, clinical_log_id, preceptor_name, evaluation_date, overall_rating, clinical_skills_rating, professionalism_rating, communication_rating, comments, areas_for_improvement, strengths, status, submitted_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO preceptor_evaluations (id, student_id, clinical_log_id, preceptor_name, evaluation_date, overall_rating, clinical_skills_rating, professionalism_rating, communication_rating, comments, areas_for_improvement, strengths, status, submitted_at, preceptor_id, response_timestamp, response_key)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&eval.id)
         .bind(&eval.student_id)
@@ -3531,6 +5389,9 @@ pub async fn import_preceptor_evaluations(
         .bind(&eval.strengths)
         .bind(&eval.status)
         .bind(&eval.submitted_at)
+        .bind(&preceptor_id)
+        .bind(&eval.response_timestamp)
+        .bind(&eval.response_key)
         .execute(&state.db)
         .await;
 
@@ -3543,5 +5404,438 @@ pub async fn import_preceptor_evaluations(
         }
     }
 
-    Ok(ImportResult { imported, failed, errors })
+    Ok(ImportResult { imported, failed, errors, unmatched_preceptors, updated: 0, skipped: 0, queued: 0 })
+}
+
+#[tauri::command]
+pub async fn get_import_fields(target: crate::models::ImportTarget) -> Result<Vec<crate::models::ImportField>, String> {
+    Ok(crate::importer::import_fields(target))
+}
+
+#[tauri::command]
+pub async fn inspect_spreadsheet(
+    file_path: String,
+    sheet_name: Option<String>
+) -> Result<crate::models::SpreadsheetInfo, String> {
+    let table = crate::importer::read_table(std::path::Path::new(&file_path), sheet_name.as_deref())?;
+
+    Ok(crate::models::SpreadsheetInfo {
+        sheet_names: table.sheet_names,
+        headers: table.headers,
+        row_count: table.rows.len() as i32,
+        sample_rows: table.rows.into_iter().take(5).collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn preview_spreadsheet_import(
+    state: State<'_, DbState>,
+    file_path: String,
+    mapping: crate::models::ImportMapping
+) -> Result<crate::models::ImportPreview, String> {
+    let table = crate::importer::read_table(std::path::Path::new(&file_path), mapping.sheet_name.as_deref())?;
+    crate::importer::build_preview(&state.db, &table, &mapping).await
+}
+
+#[tauri::command]
+pub async fn commit_spreadsheet_import(
+    state: State<'_, DbState>,
+    file_path: String,
+    mapping: crate::models::ImportMapping,
+    update_duplicates: bool
+) -> Result<ImportResult, String> {
+    // Re-validate against the current database rather than trusting an earlier preview
+    let table = crate::importer::read_table(std::path::Path::new(&file_path), mapping.sheet_name.as_deref())?;
+    let preview = crate::importer::build_preview(&state.db, &table, &mapping).await?;
+    let outcome = crate::importer::commit_preview(&state.db, &preview, update_duplicates).await?;
+
+    if preview.target == crate::models::ImportTarget::Attendance {
+        let pairs = preview
+            .rows
+            .iter()
+            .filter(|row| row.errors.is_empty())
+            .filter_map(|row| {
+                let attendance_type = row.values.get("attendance_type").map(|t| t.as_str()).unwrap_or("classroom");
+                Some((row.student_id.clone()?, attendance_type.to_string()))
+            })
+            .collect();
+        apply_attendance_policies(&state.db, pairs).await?;
+    }
+
+    Ok(ImportResult {
+        imported: outcome.imported,
+        failed: outcome.failed,
+        errors: outcome.errors,
+        unmatched_preceptors: vec![],
+        updated: outcome.updated,
+        skipped: outcome.skipped,
+        queued: 0,
+    })
+}
+
+#[tauri::command]
+pub async fn suggest_evaluation_form_mapping(file_path: String) -> Result<HashMap<String, String>, String> {
+    let table = crate::importer::read_table(std::path::Path::new(&file_path), None)?;
+    Ok(crate::evaluation_forms::suggest_mapping(&table.headers))
+}
+
+#[tauri::command]
+pub async fn import_evaluation_form_csv(
+    state: State<'_, DbState>,
+    file_path: String,
+    mapping: HashMap<String, String>
+) -> Result<ImportResult, String> {
+    let pool = &state.db;
+    let table = crate::importer::read_table(std::path::Path::new(&file_path), None)?;
+    let responses = crate::evaluation_forms::parse_responses(&table, &mapping)?;
+
+    let students = sqlx::query_as::<_, Student>("SELECT * FROM students")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut result = ImportResult {
+        imported: 0,
+        failed: 0,
+        errors: vec![],
+        unmatched_preceptors: vec![],
+        updated: 0,
+        skipped: 0,
+        queued: 0,
+    };
+
+    for (row_number, parsed) in responses {
+        let response = match parsed {
+            Ok(response) => response,
+            Err(e) => {
+                result.failed += 1;
+                result.errors.push(format!("Row {}: {}", row_number, e));
+                continue;
+            }
+        };
+
+        // Responses are deduplicated by timestamp and respondent, whether they were imported or queued
+        // last time. Evaluations imported before keys were stored match on timestamp and preceptor.
+        let response_key = crate::evaluation_forms::response_key(&response);
+        let seen: Result<(i32,), sqlx::Error> = sqlx::query_as(
+            "SELECT (SELECT COUNT(*) FROM preceptor_evaluations
+                     WHERE response_key = ?
+                        OR (response_key IS NULL AND response_timestamp = ? AND lower(preceptor_name) = ?))
+                  + (SELECT COUNT(*) FROM evaluation_import_queue WHERE response_key = ?)"
+        )
+        .bind(&response_key)
+        .bind(&response.response_timestamp)
+        .bind(response.preceptor_name.as_deref().map(|p| p.to_ascii_lowercase()).unwrap_or_default())
+        .bind(&response_key)
+        .fetch_one(pool)
+        .await;
+
+        let seen = match seen {
+            Ok(seen) => seen,
+            Err(e) => {
+                result.failed += 1;
+                result.errors.push(format!("Row {}: {}", row_number, e));
+                continue;
+            }
+        };
+
+        if seen.0 > 0 {
+            result.skipped += 1;
+            continue;
+        }
+
+        let matched = match response.preceptor_name {
+            Some(ref preceptor_name) => crate::evaluation_forms::match_student(&students, &response)
+                .map(|student_id| (student_id, preceptor_name.clone())),
+            None => Err("Response has no preceptor name".to_string()),
+        };
+
+        match matched {
+            Ok((student_id, preceptor_name)) => {
+                let preceptor_id = match crate::db::resolve_preceptor_id(pool, &preceptor_name).await {
+                    Ok(preceptor_id) => preceptor_id,
+                    Err(e) => {
+                        result.failed += 1;
+                        result.errors.push(format!("Row {}: {}", row_number, e));
+                        continue;
+                    }
+                };
+                if preceptor_id.is_none() && !result.unmatched_preceptors.contains(&preceptor_name) {
+                    result.unmatched_preceptors.push(preceptor_name.clone());
+                }
+
+                let eval = crate::evaluation_forms::to_evaluation(&response, student_id, preceptor_name, preceptor_id);
+                match add_preceptor_evaluation(state.clone(), eval).await {
+                    Ok(_) => result.imported += 1,
+                    Err(e) => {
+                        result.failed += 1;
+                        result.errors.push(format!("Row {}: {}", row_number, e));
+                    }
+                }
+            }
+            Err(reason) => {
+                // Unmatched responses wait in the resolution queue instead of failing the import
+                let queued = sqlx::query(
+                    "INSERT INTO evaluation_import_queue (id, response_timestamp, response_key, response, reason, status, created_at)
+                    VALUES (?, ?, ?, ?, ?, 'pending', ?)"
+                )
+                .bind(format!("EVQ-{}", uuid::Uuid::new_v4()))
+                .bind(&response.response_timestamp)
+                .bind(&response_key)
+                .bind(sqlx::types::Json(&response))
+                .bind(&reason)
+                .bind(chrono::Utc::now().to_rfc3339())
+                .execute(pool)
+                .await;
+
+                match queued {
+                    Ok(_) => result.queued += 1,
+                    Err(e) => {
+                        result.failed += 1;
+                        result.errors.push(format!("Row {}: {}", row_number, e));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_evaluation_import_queue(
+    state: State<'_, DbState>,
+    status: Option<String>
+) -> Result<Vec<crate::models::EvaluationImportQueueItem>, String> {
+    sqlx::query_as::<_, crate::models::EvaluationImportQueueItem>(
+        "SELECT * FROM evaluation_import_queue WHERE status = ? ORDER BY response_timestamp"
+    )
+    .bind(status.unwrap_or_else(|| "pending".to_string()))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resolve_evaluation_import(
+    state: State<'_, DbState>,
+    queue_id: String,
+    student_id: String,
+    preceptor_name: Option<String>
+) -> Result<PreceptorEvaluation, String> {
+    let item = sqlx::query_as::<_, crate::models::EvaluationImportQueueItem>(
+        "SELECT * FROM evaluation_import_queue WHERE id = ? AND status = 'pending'"
+    )
+    .bind(&queue_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Queued response not found or already resolved".to_string())?;
+
+    let response = item.response.0;
+    let preceptor_name = preceptor_name
+        .or_else(|| response.preceptor_name.clone())
+        .ok_or_else(|| "A preceptor name is required".to_string())?;
+
+    let eval = crate::evaluation_forms::to_evaluation(&response, student_id, preceptor_name, None);
+    add_preceptor_evaluation(state.clone(), eval.clone()).await?;
+
+    sqlx::query(
+        "UPDATE evaluation_import_queue SET status = 'resolved', resolved_evaluation_id = ?, resolved_at = ? WHERE id = ?"
+    )
+    .bind(&eval.id)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&queue_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(eval)
+}
+
+#[tauri::command]
+pub async fn dismiss_evaluation_import(state: State<'_, DbState>, queue_id: String) -> Result<(), String> {
+    // Dismissed responses stay in the queue so re-imports still skip them
+    sqlx::query(
+        "UPDATE evaluation_import_queue SET status = 'dismissed', resolved_at = ? WHERE id = ? AND status = 'pending'"
+    )
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&queue_id)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ============ REPORT EXPORT ============
+
+#[tauri::command]
+pub async fn export_report(state: State<'_, DbState>, request: crate::models::ReportRequest) -> Result<String, String> {
+    use crate::models::ReportKind;
+    use crate::reports::{self, ReportWriter};
+
+    let pool = &state.db;
+
+    let mut output_path = std::path::PathBuf::from(&request.output_path);
+    if output_path.extension().map(|e| !e.eq_ignore_ascii_case("pdf")).unwrap_or(true) {
+        output_path.set_extension("pdf");
+    }
+    if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if !parent.is_dir() {
+            return Err(format!("Folder does not exist: {}", parent.display()));
+        }
+    }
+
+    let student = match request.kind {
+        ReportKind::StudentTranscript | ReportKind::SkillsChecklist | ReportKind::AttendanceRecord => {
+            let student_id = request.student_id.clone().ok_or("A student is required for this report")?;
+            let student = get_student_details(state.clone(), student_id).await?.ok_or("Student not found")?;
+            Some(student)
+        }
+        ReportKind::VbonCompliance | ReportKind::SiteRoster => None,
+    };
+
+    let framework = match request.kind {
+        ReportKind::VbonCompliance => Some(crate::regulations::resolve(pool, request.framework_id.as_deref()).await?),
+        _ => None,
+    };
+    let compliance_title = framework.as_ref().map(|f| format!("{} Compliance Summary", f.code)).unwrap_or_default();
+
+    let (title, default_signers) = match request.kind {
+        ReportKind::StudentTranscript => ("Clinical Hours Transcript", vec!["Clinical Coordinator", "Program Director"]),
+        ReportKind::SkillsChecklist => ("Skills Validation Checklist", vec!["Student", "Clinical Instructor"]),
+        ReportKind::AttendanceRecord => ("Attendance Record", vec!["Student", "Instructor"]),
+        ReportKind::VbonCompliance => (compliance_title.as_str(), vec!["Program Director"]),
+        ReportKind::SiteRoster => ("Clinical Site Roster", vec!["Clinical Coordinator"]),
+    };
+    let mut writer = ReportWriter::new(title, &request.branding)?;
+
+    match request.kind {
+        ReportKind::StudentTranscript => {
+            let student = student.ok_or("A student is required for this report")?;
+            let hours_by_site = get_student_hours_by_site(state.clone(), student.id.clone()).await?;
+            let logs = get_clinical_logs(state.clone(), student.id.clone()).await?;
+            let simulation = get_student_simulation_summary(state.clone(), student.id.clone()).await?;
+            reports::student_transcript(&mut writer, &student, &hours_by_site, &logs, &simulation);
+        }
+        ReportKind::SkillsChecklist => {
+            let student = student.ok_or("A student is required for this report")?;
+            let validations = get_skill_validations(state.clone(), student.id.clone()).await?;
+            reports::skills_checklist(&mut writer, &student, &validations);
+        }
+        ReportKind::AttendanceRecord => {
+            let student = student.ok_or("A student is required for this report")?;
+            let records = get_student_attendance(state.clone(), student.id.clone()).await?;
+            reports::attendance_record(&mut writer, &student, &records);
+        }
+        ReportKind::VbonCompliance => {
+            let framework_id = framework.map(|f| f.id);
+            let summary = get_vbon_compliance_summary(state.clone(), framework_id.clone()).await?;
+            let regulations = get_vbon_regulations_with_mappings(state.clone(), framework_id).await?;
+            reports::vbon_compliance(&mut writer, &summary, &regulations);
+        }
+        ReportKind::SiteRoster => {
+            let sites = sqlx::query_as::<_, crate::models::ClinicalSite>(
+                "SELECT * FROM clinical_sites WHERE COALESCE(is_active, 1) = 1 ORDER BY name"
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            // Students with upcoming (not cancelled) assignments at each site
+            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let assigned: Vec<(String, String)> = sqlx::query_as(
+                "SELECT DISTINCT a.site_id, s.last_name || ', ' || s.first_name
+                FROM clinical_assignments a
+                JOIN students s ON s.id = a.student_id
+                WHERE a.date >= ? AND a.status != 'cancelled'
+                ORDER BY s.last_name, s.first_name"
+            )
+            .bind(&today)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            let mut students_by_site: HashMap<String, Vec<String>> = HashMap::new();
+            for (site_id, student_name) in assigned {
+                students_by_site.entry(site_id).or_default().push(student_name);
+            }
+            reports::site_roster(&mut writer, &sites, &students_by_site);
+        }
+    }
+
+    let signers = request
+        .signature_lines
+        .unwrap_or_else(|| default_signers.into_iter().map(String::from).collect());
+    writer.signature_block(&signers);
+    writer.save(&output_path)?;
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+// ==================== REMINDERS ====================
+
+#[tauri::command]
+pub async fn get_pending_reminders(app: AppHandle) -> Result<Vec<crate::models::Reminder>, String> {
+    crate::reminders::collect(&app).await
+}
+
+#[tauri::command]
+pub async fn check_reminders_now(app: AppHandle) -> Result<usize, String> {
+    crate::reminders::run_once(&app).await
+}
+
+/// Hides a reminder for `minutes`; it is notified again once the snooze runs out.
+#[tauri::command]
+pub async fn snooze_reminder(state: State<'_, DbState>, key: String, category: String, minutes: i64) -> Result<(), String> {
+    if minutes < 1 {
+        return Err("Snooze must be at least 1 minute".to_string());
+    }
+    let until = chrono::Utc::now()
+        .checked_add_signed(chrono::TimeDelta::try_minutes(minutes).ok_or("Snooze is too long")?)
+        .ok_or("Snooze is too long")?
+        .to_rfc3339();
+
+    sqlx::query(
+        "INSERT INTO reminder_log (key, category, notified_at, snoozed_until) VALUES (?, ?, NULL, ?)
+        ON CONFLICT(key) DO UPDATE SET notified_at = NULL, snoozed_until = excluded.snoozed_until"
+    )
+    .bind(&key)
+    .bind(&category)
+    .bind(&until)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_reminder_settings(state: State<'_, DbState>) -> Result<Vec<crate::models::ReminderSetting>, String> {
+    crate::reminders::load_settings(&state.db).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_reminder_setting(state: State<'_, DbState>, category: String, enabled: bool) -> Result<(), String> {
+    if !crate::reminders::CATEGORIES.iter().any(|(c, _)| *c == category) {
+        return Err(format!("Unknown reminder category '{}'", category));
+    }
+    sqlx::query(
+        "INSERT INTO reminder_settings (category, enabled) VALUES (?, ?)
+        ON CONFLICT(category) DO UPDATE SET enabled = excluded.enabled"
+    )
+    .bind(&category)
+    .bind(enabled as i32)
+    .execute(&state.db)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// ==================== TRAY ====================
+
+/// Recounts pending work for the tray now instead of waiting for the next periodic refresh.
+#[tauri::command]
+pub async fn refresh_tray(app: AppHandle) -> Result<(), String> {
+    crate::tray::refresh(&app).await?;
+    Ok(())
 }
@@ -0,0 +1,369 @@
+// Comp hours ledger.
+// Earned entries are lots that can expire. Each use is drawn FIFO from the oldest lots that were earned
+// on or before the use and had not expired by then; whatever a lot still holds after its expiration
+// date is forfeited. The ledger is rebuilt from scratch whenever entries change. Earned entries waiting
+// for approval under the employer policy are left out until approved.
+
+use std::collections::BTreeMap;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{
+    CompHoursAllocation, CompHoursEarned, CompHoursExpirationWarning, CompHoursLedger, CompHoursLot, CompHoursPolicy,
+    CompHoursUsed, CompTimesheet, CompTimesheetEntry,
+};
+
+const EPSILON: f64 = 1e-6;
+const POLICY_ID: &str = "default";
+
+fn day(date: &str) -> &str {
+    date.get(..10).unwrap_or(date)
+}
+
+impl Default for CompHoursPolicy {
+    fn default() -> Self {
+        CompHoursPolicy {
+            accrual_cap: None,
+            expiration_months: None,
+            requires_approval: false,
+            warning_days_red: 90,
+            warning_days_yellow: 180,
+            updated_at: None,
+        }
+    }
+}
+
+pub async fn load_policy(pool: &Pool<Sqlite>) -> Result<CompHoursPolicy, sqlx::Error> {
+    let policy = sqlx::query_as::<_, CompHoursPolicy>("SELECT * FROM comp_hours_policy WHERE id = ?")
+        .bind(POLICY_ID)
+        .fetch_optional(pool)
+        .await?;
+    Ok(policy.unwrap_or_default())
+}
+
+pub async fn save_policy(pool: &Pool<Sqlite>, policy: &CompHoursPolicy) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO comp_hours_policy (id, accrual_cap, expiration_months, requires_approval, warning_days_red, warning_days_yellow, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET
+            accrual_cap = excluded.accrual_cap,
+            expiration_months = excluded.expiration_months,
+            requires_approval = excluded.requires_approval,
+            warning_days_red = excluded.warning_days_red,
+            warning_days_yellow = excluded.warning_days_yellow,
+            updated_at = excluded.updated_at"
+    )
+    .bind(POLICY_ID)
+    .bind(policy.accrual_cap)
+    .bind(policy.expiration_months)
+    .bind(policy.requires_approval)
+    .bind(policy.warning_days_red)
+    .bind(policy.warning_days_yellow)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub fn alert_level(days_until_expiry: i32, policy: &CompHoursPolicy) -> &'static str {
+    if days_until_expiry <= policy.warning_days_red {
+        "red"
+    } else if days_until_expiry <= policy.warning_days_yellow {
+        "yellow"
+    } else {
+        "green"
+    }
+}
+
+/// Fills in the policy's expiration date and approval status on a new entry, trimming it to the
+/// accrual cap. Fails when the cap leaves no room at all.
+pub async fn apply_policy(pool: &Pool<Sqlite>, entry: &mut CompHoursEarned) -> Result<(), String> {
+    if entry.hours <= 0.0 {
+        return Err("Hours earned must be greater than zero".to_string());
+    }
+    let policy = load_policy(pool).await.map_err(|e| e.to_string())?;
+
+    if let Some(months) = policy.expiration_months {
+        let earned_on = chrono::NaiveDate::parse_from_str(day(&entry.date), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}'", entry.date))?;
+        let expires = earned_on
+            .checked_add_months(chrono::Months::new(months.max(0) as u32))
+            .ok_or("Expiration date is out of range")?;
+        entry.expiration_date = Some(expires.format("%Y-%m-%d").to_string());
+    }
+
+    if let Some(cap) = policy.accrual_cap {
+        let ledger = load(pool, chrono::Local::now().date_naive()).await.map_err(|e| e.to_string())?;
+        let (pending,): (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(hours), 0) FROM comp_hours_earned WHERE approval_status = 'pending'"
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        let room = cap - (ledger.available - ledger.unallocated_used + pending);
+        if room <= EPSILON {
+            return Err(format!("The accrual cap of {:.1} hours has been reached", cap));
+        }
+        if entry.hours > room {
+            let note = format!("Reduced from {:.1} hours by the {:.1} hour accrual cap", entry.hours, cap);
+            entry.notes = Some(match entry.notes.take().filter(|n| !n.trim().is_empty()) {
+                Some(notes) => format!("{} ({})", notes, note),
+                None => note,
+            });
+            entry.hours = room;
+        }
+    }
+
+    if policy.requires_approval {
+        entry.approval_status = Some("pending".to_string());
+        entry.approved_at = None;
+    } else {
+        entry.approval_status = Some("approved".to_string());
+        entry.approved_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    Ok(())
+}
+
+/// Allocates every use against the earned lots as of `today`.
+pub fn compute(
+    earned: Vec<CompHoursEarned>,
+    used: Vec<CompHoursUsed>,
+    today: chrono::NaiveDate,
+    policy: &CompHoursPolicy,
+) -> CompHoursLedger {
+    let today_str = today.format("%Y-%m-%d").to_string();
+
+    let mut lots: Vec<CompHoursLot> = earned
+        .into_iter()
+        .map(|earned| CompHoursLot {
+            consumed: 0.0,
+            forfeited: 0.0,
+            remaining: earned.hours.max(0.0),
+            status: String::new(),
+            earned,
+        })
+        .collect();
+    // Oldest first; lots earned the same day are spent soonest-expiring first
+    lots.sort_by(|a, b| {
+        let key = |lot: &CompHoursLot| {
+            (
+                day(&lot.earned.date).to_string(),
+                lot.earned.expiration_date.clone().unwrap_or_else(|| "9999-12-31".to_string()),
+                lot.earned.created_at.clone(),
+                lot.earned.id.clone(),
+            )
+        };
+        key(a).cmp(&key(b))
+    });
+
+    let mut used = used;
+    used.sort_by(|a, b| (day(&a.date), &a.created_at, &a.id).cmp(&(day(&b.date), &b.created_at, &b.id)));
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut allocations = Vec::new();
+    let mut unallocated_used = 0.0;
+    for entry in &used {
+        let on = day(&entry.date);
+        let mut needed = entry.hours.max(0.0);
+        for lot in lots.iter_mut() {
+            if needed <= EPSILON {
+                break;
+            }
+            let usable = lot.remaining > EPSILON
+                && day(&lot.earned.date) <= on
+                && lot.earned.expiration_date.as_deref().map(|e| day(e) >= on).unwrap_or(true);
+            if !usable {
+                continue;
+            }
+            let take = needed.min(lot.remaining);
+            lot.remaining -= take;
+            lot.consumed += take;
+            needed -= take;
+            allocations.push(CompHoursAllocation {
+                id: format!("CHA-{}", uuid::Uuid::new_v4()),
+                used_id: entry.id.clone(),
+                earned_id: lot.earned.id.clone(),
+                hours: take,
+                created_at: now.clone(),
+            });
+        }
+        if needed > EPSILON {
+            unallocated_used += needed;
+        }
+    }
+
+    let mut schedule: BTreeMap<String, f64> = BTreeMap::new();
+    for lot in lots.iter_mut() {
+        match lot.earned.expiration_date.as_deref().map(day) {
+            Some(expiry) if expiry < today_str.as_str() => {
+                lot.forfeited = lot.remaining;
+                lot.remaining = 0.0;
+            }
+            Some(expiry) if lot.remaining > EPSILON => {
+                *schedule.entry(expiry.to_string()).or_insert(0.0) += lot.remaining;
+            }
+            _ => {}
+        }
+        lot.status = if lot.remaining > EPSILON {
+            "available"
+        } else if lot.forfeited > EPSILON {
+            "forfeited"
+        } else {
+            "consumed"
+        }
+        .to_string();
+    }
+
+    let forfeiture_schedule = schedule
+        .into_iter()
+        .filter_map(|(expiration_date, hours)| {
+            let expiry = chrono::NaiveDate::parse_from_str(&expiration_date, "%Y-%m-%d").ok()?;
+            let days = (expiry - today).num_days() as i32;
+            Some(CompHoursExpirationWarning {
+                hours,
+                expiration_date,
+                days_until_expiry: days,
+                alert_level: alert_level(days, policy).to_string(),
+            })
+        })
+        .collect();
+
+    CompHoursLedger {
+        available: lots.iter().map(|l| l.remaining).sum(),
+        forfeited: lots.iter().map(|l| l.forfeited).sum(),
+        lots,
+        allocations,
+        unallocated_used,
+        forfeiture_schedule,
+    }
+}
+
+async fn load_entries(pool: &Pool<Sqlite>) -> Result<(Vec<CompHoursEarned>, Vec<CompHoursUsed>), sqlx::Error> {
+    let earned = sqlx::query_as::<_, CompHoursEarned>("SELECT * FROM comp_hours_earned")
+        .fetch_all(pool)
+        .await?;
+    let used = sqlx::query_as::<_, CompHoursUsed>("SELECT * FROM comp_hours_used")
+        .fetch_all(pool)
+        .await?;
+    Ok((earned, used))
+}
+
+fn is_approved(entry: &CompHoursEarned) -> bool {
+    entry.approval_status.as_deref().unwrap_or("approved") == "approved"
+}
+
+/// Current ledger without touching the stored allocations.
+pub async fn load(pool: &Pool<Sqlite>, today: chrono::NaiveDate) -> Result<CompHoursLedger, sqlx::Error> {
+    let policy = load_policy(pool).await?;
+    let (earned, used) = load_entries(pool).await?;
+    let earned = earned.into_iter().filter(is_approved).collect();
+    Ok(compute(earned, used, today, &policy))
+}
+
+/// Rebuilds the stored allocations after entries change and returns the ledger.
+pub async fn sync(pool: &Pool<Sqlite>) -> Result<CompHoursLedger, sqlx::Error> {
+    let ledger = load(pool, chrono::Local::now().date_naive()).await?;
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM comp_hours_allocations").execute(&mut *tx).await?;
+    for allocation in &ledger.allocations {
+        sqlx::query(
+            "INSERT INTO comp_hours_allocations (id, used_id, earned_id, hours, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(&allocation.id)
+        .bind(&allocation.used_id)
+        .bind(&allocation.earned_id)
+        .bind(allocation.hours)
+        .bind(&allocation.created_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(ledger)
+}
+
+/// Activity and balances for a pay period (inclusive dates). Entries still pending approval are listed
+/// but left out of the totals.
+pub async fn timesheet(pool: &Pool<Sqlite>, start_date: &str, end_date: &str) -> Result<CompTimesheet, String> {
+    let start = chrono::NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid start date '{}'", start_date))?;
+    let end = chrono::NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid end date '{}'", end_date))?;
+    if end < start {
+        return Err("The pay period ends before it starts".to_string());
+    }
+
+    let policy = load_policy(pool).await.map_err(|e| e.to_string())?;
+    let (earned, used) = load_entries(pool).await.map_err(|e| e.to_string())?;
+    let approved: Vec<CompHoursEarned> = earned.iter().filter(|e| is_approved(e)).cloned().collect();
+
+    // Ledger as it stood at the start of the period and the day after it ended
+    let before = |date: chrono::NaiveDate| {
+        let cutoff = date.format("%Y-%m-%d").to_string();
+        compute(
+            approved.iter().filter(|e| day(&e.date) < cutoff.as_str()).cloned().collect(),
+            used.iter().filter(|u| day(&u.date) < cutoff.as_str()).cloned().collect(),
+            date,
+            &policy,
+        )
+    };
+    let opening = before(start);
+    let closing = before(end + chrono::Duration::days(1));
+    let balance = |ledger: &CompHoursLedger| ledger.available - ledger.unallocated_used;
+
+    let in_period = |date: &str| day(date) >= start_date && day(date) <= end_date;
+    let mut entries: Vec<CompTimesheetEntry> = Vec::new();
+    for entry in earned.iter().filter(|e| in_period(&e.date)) {
+        entries.push(CompTimesheetEntry {
+            date: day(&entry.date).to_string(),
+            kind: "Earned".to_string(),
+            description: entry.activity_type.clone(),
+            hours: entry.hours,
+            expiration_date: entry.expiration_date.clone(),
+            status: entry.approval_status.clone().unwrap_or_else(|| "approved".to_string()),
+            notes: entry.notes.clone(),
+        });
+    }
+    for entry in used.iter().filter(|u| in_period(&u.date)) {
+        entries.push(CompTimesheetEntry {
+            date: day(&entry.date).to_string(),
+            kind: "Used".to_string(),
+            description: entry.reason.clone(),
+            hours: entry.hours,
+            expiration_date: None,
+            status: "approved".to_string(),
+            notes: entry.notes.clone(),
+        });
+    }
+    for lot in &closing.lots {
+        let Some(expiry) = lot.earned.expiration_date.as_deref() else { continue };
+        if lot.forfeited > EPSILON && in_period(expiry) {
+            entries.push(CompTimesheetEntry {
+                date: day(expiry).to_string(),
+                kind: "Forfeited".to_string(),
+                description: format!("Expired unused ({} on {})", lot.earned.activity_type, day(&lot.earned.date)),
+                hours: lot.forfeited,
+                expiration_date: Some(day(expiry).to_string()),
+                status: "approved".to_string(),
+                notes: None,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let total = |kind: &str| -> f64 {
+        entries.iter().filter(|e| e.kind == kind && e.status == "approved").map(|e| e.hours).sum()
+    };
+    Ok(CompTimesheet {
+        start_date: start_date.to_string(),
+        end_date: end_date.to_string(),
+        opening_balance: balance(&opening),
+        earned: total("Earned"),
+        used: total("Used"),
+        forfeited: closing.forfeited - opening.forfeited,
+        closing_balance: balance(&closing),
+        pending_approval: entries.iter().filter(|e| e.status == "pending").map(|e| e.hours).sum(),
+        entries,
+    })
+}
@@ -0,0 +1,634 @@
+// Semester scheduling.
+// A course outline (one row per class session, grouped by week) is laid onto the course's meeting days from
+// a start date, skipping the holiday calendar in `academic_holidays`. The meeting pattern is kept in
+// `course_schedules` so a cancelled class (a snow day) can push that session and every later one to the next
+// meeting day, moving the generated `calendar_events` along with them. A course can also be copied forward
+// to a new term, its lesson plans re-dated by week number.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::models::{
+    AcademicHoliday, Course, CourseCloneRequest, CourseCloneResult, CourseSchedule, LessonPlan, RescheduleRequest,
+    RescheduleResult, ScheduledSession, SemesterScheduleRequest, SemesterScheduleResult, SessionMove, SkippedDate,
+    TeachingMaterial,
+};
+
+/// How far past the last candidate day to look for a meeting day before giving up.
+const MAX_SEARCH_DAYS: i64 = 366;
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.get(..10).unwrap_or(date), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", date))
+}
+
+/// Weekday names ("Mon", "monday", ...) to weekdays, in calendar order without duplicates.
+pub fn parse_meeting_days(days: &[String]) -> Result<Vec<Weekday>, String> {
+    let mut parsed = Vec::new();
+    for day in days.iter().map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        let weekday = day
+            .parse::<Weekday>()
+            .map_err(|_| format!("Unknown meeting day '{}'", day))?;
+        if !parsed.contains(&weekday) {
+            parsed.push(weekday);
+        }
+    }
+    if parsed.is_empty() {
+        return Err("Choose at least one meeting day".to_string());
+    }
+    parsed.sort_by_key(|d| d.num_days_from_monday());
+    Ok(parsed)
+}
+
+fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn format_meeting_days(days: &[Weekday]) -> String {
+    days.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",")
+}
+
+/// Calendar events store a timestamp; noon keeps the day stable in any local time zone.
+fn event_date(date: NaiveDate) -> String {
+    format!("{}T12:00:00", date.format("%Y-%m-%d"))
+}
+
+/// The first meeting day on or after `from` that is not closed.
+fn next_meeting(from: NaiveDate, days: &[Weekday], closed: &HashMap<NaiveDate, String>) -> Result<NaiveDate, String> {
+    (0..MAX_SEARCH_DAYS)
+        .map(|offset| from + Duration::days(offset))
+        .find(|date| days.contains(&date.weekday()) && !closed.contains_key(date))
+        .ok_or_else(|| format!("No open meeting day within a year of {}", from))
+}
+
+async fn holiday_calendar(conn: &mut SqliteConnection) -> Result<HashMap<NaiveDate, String>, String> {
+    let holidays = sqlx::query_as::<_, AcademicHoliday>("SELECT * FROM academic_holidays")
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(holidays
+        .into_iter()
+        .filter_map(|h| parse_date(&h.date).ok().map(|date| (date, h.name)))
+        .collect())
+}
+
+async fn load_course(conn: &mut SqliteConnection, course_id: &str) -> Result<Course, String> {
+    sqlx::query_as::<_, Course>("SELECT * FROM courses WHERE id = ?")
+        .bind(course_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Course '{}' not found", course_id))
+}
+
+/// Deletes a lesson plan with its materials, regulation links and generated calendar events.
+pub async fn delete_session(conn: &mut SqliteConnection, lesson_plan_id: &str) -> Result<(), sqlx::Error> {
+    crate::regulation_links::remove_lesson_plan(&mut *conn, lesson_plan_id).await?;
+    sqlx::query("DELETE FROM teaching_materials WHERE lesson_plan_id = ?")
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM calendar_events WHERE lesson_plan_id = ?")
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM lesson_plans WHERE id = ?")
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+// ==================== GENERATION ====================
+
+/// Lays the outline onto the meeting days. Each session goes on the first open meeting day after the
+/// previous session, but never before the Monday of its outline week, so a holiday pushes later sessions
+/// back while a short week leaves the rest of that week free.
+pub async fn generate(pool: &Pool<Sqlite>, request: &SemesterScheduleRequest) -> Result<SemesterScheduleResult, String> {
+    let start = parse_date(&request.start_date)?;
+    let end = request.end_date.as_deref().filter(|d| !d.is_empty()).map(parse_date).transpose()?;
+    if end.is_some_and(|end| end < start) {
+        return Err("The end date is before the start date".to_string());
+    }
+    let meeting_days = parse_meeting_days(&request.meeting_days)?;
+
+    let mut outline = request.outline.clone();
+    if outline.is_empty() {
+        return Err("The course outline has no sessions".to_string());
+    }
+    for (index, entry) in outline.iter().enumerate() {
+        if entry.topic.trim().is_empty() {
+            return Err(format!("Outline row {} has no topic", index + 1));
+        }
+        if entry.week_number < 1 {
+            return Err(format!("Outline row {} has an invalid week number {}", index + 1, entry.week_number));
+        }
+    }
+    // Stable, so sessions keep their order within a week
+    outline.sort_by_key(|entry| entry.week_number);
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let course = load_course(&mut *tx, &request.course_id).await?;
+
+    let mut closed = holiday_calendar(&mut *tx).await?;
+    for holiday in &request.holidays {
+        closed.insert(parse_date(&holiday.date)?, holiday.name.clone());
+    }
+
+    let first_monday = monday_of(start);
+    let mut sessions = Vec::new();
+    let mut cursor = start;
+    for entry in &outline {
+        let week_start = first_monday + Duration::weeks((entry.week_number - 1) as i64);
+        let date = next_meeting(cursor.max(week_start), &meeting_days, &closed)?;
+        sessions.push(ScheduledSession {
+            lesson_plan_id: uuid::Uuid::new_v4().to_string(),
+            date: date.format("%Y-%m-%d").to_string(),
+            week_number: entry.week_number,
+            chapter: entry.chapter.clone().filter(|c| !c.trim().is_empty()),
+            topic: entry.topic.trim().to_string(),
+        });
+        cursor = date + Duration::days(1);
+    }
+
+    // Meeting days lost to the holiday calendar while the course runs
+    let last = cursor - Duration::days(1);
+    let skipped_dates: Vec<SkippedDate> = closed
+        .iter()
+        .filter(|(date, _)| **date >= start && **date <= last && meeting_days.contains(&date.weekday()))
+        .map(|(date, name)| (*date, name.clone()))
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(date, reason)| SkippedDate { date: date.format("%Y-%m-%d").to_string(), reason })
+        .collect();
+
+    let mut warnings = Vec::new();
+    if let Some(end) = end {
+        let late = sessions.iter().filter(|s| parse_date(&s.date).is_ok_and(|d| d > end)).count();
+        if late > 0 {
+            warnings.push(format!(
+                "{} session{} fall after the end date {}",
+                late,
+                if late == 1 { "" } else { "s" },
+                end.format("%Y-%m-%d")
+            ));
+        }
+    }
+
+    let existing: Vec<(String,)> = sqlx::query_as(
+        "SELECT id FROM lesson_plans WHERE course_id = ? AND date >= ?"
+    )
+    .bind(&course.id)
+    .bind(start.format("%Y-%m-%d").to_string())
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let replaced_count = if request.replace_existing { existing.len() as i32 } else { 0 };
+    if !request.replace_existing && !existing.is_empty() {
+        warnings.push(format!(
+            "{} already has {} lesson plan{} from {} on; they were kept",
+            course.code,
+            existing.len(),
+            if existing.len() == 1 { "" } else { "s" },
+            start.format("%Y-%m-%d")
+        ));
+    }
+
+    let mut result = SemesterScheduleResult {
+        sessions,
+        skipped_dates,
+        replaced_count,
+        events_created: 0,
+        warnings,
+    };
+    if request.preview {
+        return Ok(result);
+    }
+
+    if request.replace_existing {
+        for (id,) in &existing {
+            delete_session(&mut *tx, id).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    // Calendar event ids are numeric on the frontend
+    let event_base = chrono::Utc::now().timestamp_millis();
+    let mut events_created = 0;
+    for (index, (entry, session)) in outline.iter().zip(&result.sessions).enumerate() {
+        sqlx::query(
+            "INSERT INTO lesson_plans (id, date, course_id, course_name, week_number, chapter, topic, topics_covered, assessment_method, vbon_tags, notes, last_taught_notes, notes_for_next_time, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, NULL, NULL, NULL, ?, ?)"
+        )
+        .bind(&session.lesson_plan_id)
+        .bind(&session.date)
+        .bind(&course.id)
+        .bind(&course.name)
+        .bind(session.week_number)
+        .bind(&session.chapter)
+        .bind(&session.topic)
+        .bind(entry.topics_covered.as_deref().filter(|t| !t.trim().is_empty()))
+        .bind(entry.assessment_method.as_deref().filter(|a| !a.trim().is_empty()))
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if request.create_calendar_events {
+            sqlx::query(
+                "INSERT INTO calendar_events (id, date, title, event_type, location, proctor, status, description, lesson_plan_id)
+                VALUES (?, ?, ?, 'school', NULL, NULL, NULL, ?, ?)"
+            )
+            .bind((event_base + index as i64).to_string())
+            .bind(event_date(parse_date(&session.date)?))
+            .bind(format!("{}: {}", course.code, session.topic))
+            .bind(session.chapter.as_ref().map(|c| format!("Week {} · Chapter {}", session.week_number, c)))
+            .bind(&session.lesson_plan_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+            events_created += 1;
+        }
+    }
+    result.events_created = events_created;
+
+    sqlx::query(
+        "INSERT INTO course_schedules (course_id, start_date, end_date, meeting_days, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(course_id) DO UPDATE SET
+            start_date = excluded.start_date,
+            end_date = excluded.end_date,
+            meeting_days = excluded.meeting_days,
+            updated_at = excluded.updated_at"
+    )
+    .bind(&course.id)
+    .bind(start.format("%Y-%m-%d").to_string())
+    .bind(end.map(|d| d.format("%Y-%m-%d").to_string()))
+    .bind(format_meeting_days(&meeting_days))
+    .bind(&now)
+    .bind(&now)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+// ==================== RESCHEDULING ====================
+
+/// Cancels the course's class on `request.date` and shifts that session and every later one to the next
+/// open meeting day after its current date, so the semester keeps its order and its gaps. Meeting days come
+/// from the saved course schedule, or from the weekdays the course's lesson plans fall on.
+pub async fn reschedule(pool: &Pool<Sqlite>, request: &RescheduleRequest) -> Result<RescheduleResult, String> {
+    let cancelled = parse_date(&request.date)?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let course = load_course(&mut *tx, &request.course_id).await?;
+    let schedule = sqlx::query_as::<_, CourseSchedule>("SELECT * FROM course_schedules WHERE course_id = ?")
+        .bind(&course.id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let plans = sqlx::query_as::<_, LessonPlan>(
+        "SELECT * FROM lesson_plans WHERE course_id = ? ORDER BY date, id"
+    )
+    .bind(&course.id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let meeting_days = match &schedule {
+        Some(schedule) => {
+            let days: Vec<String> = schedule.meeting_days.split(',').map(str::to_string).collect();
+            parse_meeting_days(&days)?
+        }
+        None => {
+            let mut days: Vec<Weekday> = plans.iter().filter_map(|p| parse_date(&p.date).ok()).map(|d| d.weekday()).collect();
+            days.sort_by_key(|d| d.num_days_from_monday());
+            days.dedup();
+            days
+        }
+    };
+
+    // Sessions from the cancelled day on, grouped by date
+    let mut sessions: BTreeMap<NaiveDate, Vec<&LessonPlan>> = BTreeMap::new();
+    for plan in &plans {
+        if let Ok(date) = parse_date(&plan.date) {
+            if date >= cancelled {
+                sessions.entry(date).or_default().push(plan);
+            }
+        }
+    }
+    if !sessions.contains_key(&cancelled) {
+        return Err(format!("{} has no class on {}", course.code, cancelled.format("%Y-%m-%d")));
+    }
+
+    let reason = request
+        .reason
+        .clone()
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| "Class cancelled".to_string());
+    let mut closed = holiday_calendar(&mut *tx).await?;
+    closed.insert(cancelled, reason.clone());
+
+    let mut moves = Vec::new();
+    let mut previous: Option<NaiveDate> = None;
+    for (date, group) in &sessions {
+        let earliest = (*date + Duration::days(1)).max(previous.map(|p| p + Duration::days(1)).unwrap_or(*date));
+        let new_date = next_meeting(earliest, &meeting_days, &closed)?;
+        for plan in group {
+            moves.push(SessionMove {
+                lesson_plan_id: plan.id.clone(),
+                topic: plan.topic.clone(),
+                from_date: plan.date.clone(),
+                to_date: new_date.format("%Y-%m-%d").to_string(),
+            });
+        }
+        previous = Some(new_date);
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(end) = schedule.as_ref().and_then(|s| s.end_date.as_deref()).and_then(|d| parse_date(d).ok()) {
+        let late = moves.iter().filter(|m| parse_date(&m.to_date).is_ok_and(|d| d > end)).count();
+        if late > 0 {
+            warnings.push(format!(
+                "{} session{} now fall after the end date {}",
+                late,
+                if late == 1 { "" } else { "s" },
+                end.format("%Y-%m-%d")
+            ));
+        }
+    }
+
+    if request.preview {
+        return Ok(RescheduleResult { moves, events_updated: 0, warnings });
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut events_updated = 0;
+    for session_move in &moves {
+        sqlx::query("UPDATE lesson_plans SET date = ?, updated_at = ? WHERE id = ?")
+            .bind(&session_move.to_date)
+            .bind(&now)
+            .bind(&session_move.lesson_plan_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        let updated = sqlx::query("UPDATE calendar_events SET date = ? WHERE lesson_plan_id = ?")
+            .bind(event_date(parse_date(&session_move.to_date)?))
+            .bind(&session_move.lesson_plan_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        events_updated += updated.rows_affected() as i32;
+    }
+
+    if request.record_closure {
+        save_holiday(&mut *tx, &AcademicHoliday {
+            id: String::new(),
+            date: cancelled.format("%Y-%m-%d").to_string(),
+            name: reason,
+            created_at: now,
+        })
+        .await?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(RescheduleResult { moves, events_updated, warnings })
+}
+
+// ==================== COPYING FORWARD ====================
+
+/// Copies a course into a new term. Each lesson plan keeps its week number and weekday, counted from the
+/// week the new term starts in (plans without a week number get the week they fell in last term). Last
+/// term's notes for next time become the copy's last-taught notes; materials and regulation links come along.
+pub async fn clone_course(pool: &Pool<Sqlite>, request: &CourseCloneRequest) -> Result<CourseCloneResult, String> {
+    let new_start = parse_date(&request.start_date)?;
+    if request.semester.trim().is_empty() {
+        return Err("Choose the semester to copy the course into".to_string());
+    }
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let source = load_course(&mut *tx, &request.course_id).await?;
+
+    let code = request.code.clone().filter(|c| !c.trim().is_empty()).unwrap_or_else(|| source.code.clone());
+    let name = request.name.clone().filter(|n| !n.trim().is_empty()).unwrap_or_else(|| source.name.clone());
+    let (duplicates,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM courses WHERE code = ? AND semester = ? AND year = ? AND COALESCE(is_active, 1) = 1"
+    )
+    .bind(&code)
+    .bind(request.semester.trim())
+    .bind(request.year)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if duplicates > 0 {
+        return Err(format!("{} already exists for {} {}", code, request.semester.trim(), request.year));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let course = Course {
+        id: uuid::Uuid::new_v4().to_string(),
+        code,
+        name,
+        description: source.description.clone(),
+        syllabus_url: source.syllabus_url.clone(),
+        content_outline_url: source.content_outline_url.clone(),
+        clinical_manual_url: source.clinical_manual_url.clone(),
+        semester: Some(request.semester.trim().to_string()),
+        year: Some(request.year),
+        is_active: Some(1),
+        created_at: now.clone(),
+        updated_at: now.clone(),
+    };
+    sqlx::query(
+        "INSERT INTO courses (id, code, name, description, syllabus_url, content_outline_url, clinical_manual_url, semester, year, is_active, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&course.id)
+    .bind(&course.code)
+    .bind(&course.name)
+    .bind(&course.description)
+    .bind(&course.syllabus_url)
+    .bind(&course.content_outline_url)
+    .bind(&course.clinical_manual_url)
+    .bind(&course.semester)
+    .bind(course.year)
+    .bind(course.is_active)
+    .bind(&course.created_at)
+    .bind(&course.updated_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let plans = sqlx::query_as::<_, LessonPlan>(
+        "SELECT * FROM lesson_plans WHERE course_id = ? ORDER BY date, id"
+    )
+    .bind(&source.id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let schedule = sqlx::query_as::<_, CourseSchedule>("SELECT * FROM course_schedules WHERE course_id = ?")
+        .bind(&source.id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Last term's week 1, for plans that were never given a week number
+    let old_start = schedule
+        .as_ref()
+        .and_then(|s| parse_date(&s.start_date).ok())
+        .or_else(|| plans.iter().filter_map(|p| parse_date(&p.date).ok()).min())
+        .unwrap_or(new_start);
+    let old_monday = monday_of(old_start);
+    let new_monday = monday_of(new_start);
+    let closed = holiday_calendar(&mut *tx).await?;
+
+    let mut warnings = Vec::new();
+    let mut on_holidays = Vec::new();
+    let mut material_count = 0;
+    let mut regulation_link_count = 0;
+    for plan in &plans {
+        let old_date = parse_date(&plan.date).ok();
+        let week_number = plan.week_number.filter(|w| *w >= 1).unwrap_or_else(|| {
+            old_date.map(|d| ((d - old_monday).num_days().div_euclid(7) + 1).max(1) as i32).unwrap_or(1)
+        });
+        let weekday_offset = old_date.map(|d| d.weekday().num_days_from_monday() as i64).unwrap_or(0);
+        let new_date = new_monday + Duration::weeks((week_number - 1) as i64) + Duration::days(weekday_offset);
+        if let Some(holiday) = closed.get(&new_date) {
+            on_holidays.push(format!("{} ({})", new_date.format("%Y-%m-%d"), holiday));
+        }
+
+        let new_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO lesson_plans (id, date, course_id, course_name, week_number, chapter, topic, topics_covered, assessment_method, vbon_tags, notes, last_taught_notes, notes_for_next_time, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?, NULL, ?, ?)"
+        )
+        .bind(&new_id)
+        .bind(new_date.format("%Y-%m-%d").to_string())
+        .bind(&course.id)
+        .bind(&course.name)
+        .bind(week_number)
+        .bind(&plan.chapter)
+        .bind(&plan.topic)
+        .bind(&plan.topics_covered)
+        .bind(&plan.assessment_method)
+        .bind(&plan.notes)
+        .bind(&plan.notes_for_next_time)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let materials = sqlx::query_as::<_, TeachingMaterial>(
+            "SELECT * FROM teaching_materials WHERE lesson_plan_id = ? ORDER BY sort_order"
+        )
+        .bind(&plan.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        for material in &materials {
+            sqlx::query(
+                "INSERT INTO teaching_materials (id, lesson_plan_id, material_type, title, url, description, sort_order)
+                VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(uuid::Uuid::new_v4().to_string())
+            .bind(&new_id)
+            .bind(&material.material_type)
+            .bind(&material.title)
+            .bind(&material.url)
+            .bind(&material.description)
+            .bind(material.sort_order)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+        material_count += materials.len() as i32;
+
+        let regulation_ids: Vec<(String,)> = sqlx::query_as(
+            "SELECT regulation_id FROM lesson_plan_regulations WHERE lesson_plan_id = ? ORDER BY regulation_id"
+        )
+        .bind(&plan.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        if !regulation_ids.is_empty() {
+            let regulation_ids: Vec<String> = regulation_ids.into_iter().map(|(id,)| id).collect();
+            crate::regulation_links::set_for_lesson_plan(&mut *tx, &new_id, &regulation_ids)
+                .await
+                .map_err(|e| e.to_string())?;
+            regulation_link_count += regulation_ids.len() as i32;
+        }
+    }
+
+    if !on_holidays.is_empty() {
+        warnings.push(format!(
+            "{} session{} land on a holiday: {}",
+            on_holidays.len(),
+            if on_holidays.len() == 1 { "" } else { "s" },
+            on_holidays.join(", ")
+        ));
+    }
+
+    // Carry the meeting pattern over so the new term can be rescheduled the same way
+    if let Some(schedule) = &schedule {
+        let new_end = schedule
+            .end_date
+            .as_deref()
+            .and_then(|d| parse_date(d).ok())
+            .map(|end| (new_start + (end - old_start)).format("%Y-%m-%d").to_string());
+        sqlx::query(
+            "INSERT INTO course_schedules (course_id, start_date, end_date, meeting_days, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&course.id)
+        .bind(new_start.format("%Y-%m-%d").to_string())
+        .bind(new_end)
+        .bind(&schedule.meeting_days)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(CourseCloneResult {
+        course,
+        lesson_plan_count: plans.len() as i32,
+        material_count,
+        regulation_link_count,
+        warnings,
+    })
+}
+
+// ==================== HOLIDAY CALENDAR ====================
+
+/// Adds or edits a holiday. A new holiday on a date that already has one renames it instead.
+pub async fn save_holiday(conn: &mut SqliteConnection, holiday: &AcademicHoliday) -> Result<(), String> {
+    let date = parse_date(&holiday.date)?;
+    if holiday.name.trim().is_empty() {
+        return Err("The holiday needs a name".to_string());
+    }
+    let id = if holiday.id.is_empty() { format!("HOL-{}", uuid::Uuid::new_v4()) } else { holiday.id.clone() };
+    let created_at = if holiday.created_at.is_empty() { chrono::Utc::now().to_rfc3339() } else { holiday.created_at.clone() };
+    sqlx::query(
+        "INSERT INTO academic_holidays (id, date, name, created_at) VALUES (?, ?, ?, ?)
+        ON CONFLICT(id) DO UPDATE SET date = excluded.date, name = excluded.name
+        ON CONFLICT(date) DO UPDATE SET name = excluded.name"
+    )
+    .bind(id)
+    .bind(date.format("%Y-%m-%d").to_string())
+    .bind(holiday.name.trim())
+    .bind(created_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use sqlx::migrate::MigrateDatabase;
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use crate::matching;
 use std::fs;
 use tauri::AppHandle;
 use tauri::Manager;
@@ -82,7 +83,9 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
             hours REAL DEFAULT 8.0,
             is_simulation INTEGER DEFAULT 0,
             is_makeup INTEGER DEFAULT 0,
-            FOREIGN KEY(student_id) REFERENCES students(id)
+            site_id TEXT,
+            FOREIGN KEY(student_id) REFERENCES students(id),
+            FOREIGN KEY(site_id) REFERENCES clinical_sites(id)
         );"
     ).execute(&pool).await?;
 
@@ -93,6 +96,8 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
         .execute(&pool).await;
     let _ = sqlx::query("ALTER TABLE clinical_logs ADD COLUMN is_makeup INTEGER DEFAULT 0")
         .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE clinical_logs ADD COLUMN site_id TEXT REFERENCES clinical_sites(id)")
+        .execute(&pool).await;
 
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS calendar_events (
@@ -107,6 +112,10 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
         );"
     ).execute(&pool).await?;
 
+    // Class sessions generated from a lesson plan carry its id so rescheduling can move them
+    let _ = sqlx::query("ALTER TABLE calendar_events ADD COLUMN lesson_plan_id TEXT")
+        .execute(&pool).await;
+
     // Courses table for course-level information
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS courses (
@@ -161,6 +170,29 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
     let _ = sqlx::query("ALTER TABLE lesson_plans ADD COLUMN notes_for_next_time TEXT")
         .execute(&pool).await;
 
+    // Meeting pattern used to generate a course's semester of lesson plans
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS course_schedules (
+            course_id TEXT PRIMARY KEY,
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            meeting_days TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(course_id) REFERENCES courses(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Holiday calendar: days without classes (breaks, holidays, snow days)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS academic_holidays (
+            id TEXT PRIMARY KEY,
+            date TEXT NOT NULL UNIQUE,
+            name TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );"
+    ).execute(&pool).await?;
+
     // Teaching materials (links to Google Drive, YouTube, ATI, etc.)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS teaching_materials (
@@ -199,6 +231,10 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
         .execute(&pool).await;
     let _ = sqlx::query("ALTER TABLE attendance ADD COLUMN hours_required REAL DEFAULT 8.0")
         .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE attendance ADD COLUMN minutes_late INTEGER")
+        .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE attendance ADD COLUMN course_id TEXT REFERENCES courses(id)")
+        .execute(&pool).await;
 
     // Make-up hours tracking
     sqlx::query(
@@ -220,6 +256,36 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
         );"
     ).execute(&pool).await?;
 
+    // Databases created before attendance_type was part of the table's UNIQUE constraint can hold several
+    // records for one student, day and type, and lack the index the attendance upsert conflicts on.
+    // Keep the latest record of each, move make-up hours onto it, then add the index.
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE attendance SET attendance_type = 'classroom' WHERE attendance_type IS NULL")
+        .execute(&mut *tx).await?;
+    sqlx::query(
+        "UPDATE makeup_hours SET original_absence_id = (
+            SELECT k.id FROM attendance a
+            JOIN attendance k ON k.student_id = a.student_id AND k.date = a.date AND k.attendance_type = a.attendance_type
+            WHERE a.id = makeup_hours.original_absence_id
+            ORDER BY k.recorded_at DESC, k.id DESC LIMIT 1
+        )
+        WHERE original_absence_id IN (SELECT id FROM attendance)"
+    ).execute(&mut *tx).await?;
+    sqlx::query(
+        "DELETE FROM attendance WHERE id IN (
+            SELECT id FROM (
+                SELECT id, ROW_NUMBER() OVER (
+                    PARTITION BY student_id, date, attendance_type ORDER BY recorded_at DESC, id DESC
+                ) AS rn
+                FROM attendance
+            ) WHERE rn > 1
+        )"
+    ).execute(&mut *tx).await?;
+    sqlx::query(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_attendance_student_date_type ON attendance(student_id, date, attendance_type)"
+    ).execute(&mut *tx).await?;
+    tx.commit().await?;
+
     // Student certifications (BLS, immunizations, etc.)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS student_certifications (
@@ -253,11 +319,48 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
             strengths TEXT,
             status TEXT NOT NULL,
             submitted_at TEXT NOT NULL,
+            preceptor_id TEXT,
+            response_timestamp TEXT,
             FOREIGN KEY(student_id) REFERENCES students(id),
-            FOREIGN KEY(clinical_log_id) REFERENCES clinical_logs(id)
+            FOREIGN KEY(clinical_log_id) REFERENCES clinical_logs(id),
+            FOREIGN KEY(preceptor_id) REFERENCES preceptors(id)
         );"
     ).execute(&pool).await?;
 
+    // Add preceptor_id to preceptor_evaluations if it doesn't exist (for existing databases)
+    let _ = sqlx::query("ALTER TABLE preceptor_evaluations ADD COLUMN preceptor_id TEXT REFERENCES preceptors(id)")
+        .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE preceptor_evaluations ADD COLUMN response_timestamp TEXT")
+        .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE preceptor_evaluations ADD COLUMN response_key TEXT")
+        .execute(&pool).await;
+
+    // Google Forms responses that couldn't be matched to a student, awaiting manual resolution
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS evaluation_import_queue (
+            id TEXT PRIMARY KEY,
+            response_timestamp TEXT NOT NULL,
+            response TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            resolved_evaluation_id TEXT,
+            created_at TEXT NOT NULL,
+            resolved_at TEXT,
+            FOREIGN KEY(resolved_evaluation_id) REFERENCES preceptor_evaluations(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Re-imports are deduplicated on timestamp plus respondent; queued responses keep their answers,
+    // so older rows can be keyed the same way `evaluation_forms::response_key` does
+    let _ = sqlx::query("ALTER TABLE evaluation_import_queue ADD COLUMN response_key TEXT")
+        .execute(&pool).await;
+    sqlx::query(
+        "UPDATE evaluation_import_queue SET response_key = response_timestamp
+            || '|' || lower(COALESCE(json_extract(response, '$.studentEmail'), json_extract(response, '$.studentName'), ''))
+            || '|' || lower(COALESCE(json_extract(response, '$.preceptorName'), ''))
+        WHERE response_key IS NULL"
+    ).execute(&pool).await?;
+
     // Upcoming deadlines
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS deadlines (
@@ -378,6 +481,9 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
     let _ = sqlx::query("ALTER TABLE preceptors ADD COLUMN specialties TEXT")
         .execute(&pool).await;
 
+    // Link existing evaluations to preceptor records by name
+    backfill_preceptor_ids(&pool).await?;
+
     // Clinical Assignments (scheduled clinical rotations)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS clinical_assignments (
@@ -454,11 +560,20 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
             reviewed_at TEXT,
             reviewed_by TEXT,
             submitted_at TEXT NOT NULL,
+            site_id TEXT,
             FOREIGN KEY(student_id) REFERENCES students(id),
-            FOREIGN KEY(assignment_id) REFERENCES clinical_assignments(id)
+            FOREIGN KEY(assignment_id) REFERENCES clinical_assignments(id),
+            FOREIGN KEY(site_id) REFERENCES clinical_sites(id)
         );"
     ).execute(&pool).await?;
 
+    // Add site_id to student_hour_submissions if it doesn't exist (for existing databases)
+    let _ = sqlx::query("ALTER TABLE student_hour_submissions ADD COLUMN site_id TEXT REFERENCES clinical_sites(id)")
+        .execute(&pool).await;
+
+    // Link existing free-text site names to clinical_sites records
+    backfill_site_ids(&pool).await?;
+
     // ==================== INSTRUCTOR CREDENTIALS & COMP HOURS ====================
 
     // Instructor certifications (BLS, RN License, etc.)
@@ -507,7 +622,23 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
 
     // ==================== VBON COMPLIANCE MAPPING ====================
 
-    // VBON Regulations (Virginia Board of Nursing requirements)
+    // Regulatory Frameworks (state boards and accreditors; each has its own regulation catalog)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS regulatory_frameworks (
+            id TEXT PRIMARY KEY,
+            code TEXT NOT NULL,
+            name TEXT NOT NULL,
+            framework_type TEXT NOT NULL DEFAULT 'state_board',
+            jurisdiction TEXT,
+            description TEXT,
+            is_current INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );"
+    ).execute(&pool).await?;
+
+    crate::regulations::seed_frameworks(&pool).await?;
+
+    // VBON Regulations (Virginia Board of Nursing requirements, and other frameworks' by framework_id)
     sqlx::query(
         "CREATE TABLE IF NOT EXISTS vbon_regulations (
             id TEXT PRIMARY KEY,
@@ -544,5 +675,424 @@ pub async fn init_db(app: &AppHandle) -> Result<Pool<Sqlite>, Box<dyn std::error
         );"
     ).execute(&pool).await?;
 
+    // Regulations predating frameworks belong to VBON
+    let _ = sqlx::query("ALTER TABLE vbon_regulations ADD COLUMN framework_id TEXT NOT NULL DEFAULT 'vbon'")
+        .execute(&pool)
+        .await;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_vbon_regulations_framework ON vbon_regulations(framework_id, sort_order)")
+        .execute(&pool)
+        .await?;
+
+    // Regulation Catalog Editions (imported versions of a framework's catalog; the current one is live)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS regulation_catalog_editions (
+            id TEXT PRIMARY KEY,
+            framework_id TEXT NOT NULL,
+            edition TEXT NOT NULL,
+            effective_date TEXT,
+            source_file TEXT,
+            notes TEXT,
+            regulation_count INTEGER NOT NULL DEFAULT 0,
+            is_current INTEGER NOT NULL DEFAULT 0,
+            imported_at TEXT NOT NULL,
+            FOREIGN KEY(framework_id) REFERENCES regulatory_frameworks(id),
+            UNIQUE(framework_id, edition)
+        );"
+    ).execute(&pool).await?;
+
+    // Regulation Edition Entries (each regulation as worded in an edition)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS regulation_edition_entries (
+            edition_id TEXT NOT NULL,
+            id TEXT NOT NULL,
+            code TEXT NOT NULL,
+            section TEXT NOT NULL,
+            category TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY(edition_id, id),
+            FOREIGN KEY(edition_id) REFERENCES regulation_catalog_editions(id)
+        );"
+    ).execute(&pool).await?;
+
+    let _ = sqlx::query("ALTER TABLE vbon_regulations ADD COLUMN edition_id TEXT")
+        .execute(&pool)
+        .await;
+
+    // Which edition a mapping was reviewed against, and whether a later edition needs it looked at again
+    let _ = sqlx::query("ALTER TABLE vbon_mappings ADD COLUMN reviewed_edition_id TEXT")
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE vbon_mappings ADD COLUMN needs_review INTEGER NOT NULL DEFAULT 0")
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE vbon_mappings ADD COLUMN review_reason TEXT")
+        .execute(&pool)
+        .await;
+
+    // Lesson Plan Regulations (which regulations each lesson plan teaches; replaces vbon_tags and lesson_plan_ids)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS lesson_plan_regulations (
+            lesson_plan_id TEXT NOT NULL,
+            regulation_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY(lesson_plan_id, regulation_id),
+            FOREIGN KEY(lesson_plan_id) REFERENCES lesson_plans(id),
+            FOREIGN KEY(regulation_id) REFERENCES vbon_regulations(id)
+        );"
+    ).execute(&pool).await?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_lesson_plan_regulations_regulation ON lesson_plan_regulations(regulation_id)")
+        .execute(&pool)
+        .await?;
+
+    crate::regulation_links::backfill(&pool).await?;
+
+    // Flag Rule Sets (versioned thresholds for student flags; one version is active)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS flag_rule_sets (
+            version INTEGER PRIMARY KEY,
+            rules TEXT NOT NULL,
+            notes TEXT,
+            is_active INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );"
+    ).execute(&pool).await?;
+
+    // Remediation Plans (structured remediation, several per student)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS remediation_plans (
+            id TEXT PRIMARY KEY,
+            student_id TEXT NOT NULL,
+            topic TEXT NOT NULL,
+            reason TEXT,
+            status TEXT NOT NULL DEFAULT 'active',
+            start_date TEXT NOT NULL,
+            target_date TEXT,
+            triggering_grade_ids TEXT NOT NULL DEFAULT '[]',
+            triggering_evaluation_ids TEXT NOT NULL DEFAULT '[]',
+            outcome TEXT,
+            outcome_notes TEXT,
+            created_by TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            closed_at TEXT,
+            review_deadline_id TEXT,
+            FOREIGN KEY(student_id) REFERENCES students(id),
+            FOREIGN KEY(review_deadline_id) REFERENCES deadlines(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Remediation Activities (assigned work within a plan)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS remediation_activities (
+            id TEXT PRIMARY KEY,
+            plan_id TEXT NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            due_date TEXT,
+            status TEXT NOT NULL DEFAULT 'pending',
+            completed_date TEXT,
+            deadline_id TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY(plan_id) REFERENCES remediation_plans(id),
+            FOREIGN KEY(deadline_id) REFERENCES deadlines(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Remediation Checkpoints (progress reviews within a plan)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS remediation_checkpoints (
+            id TEXT PRIMARY KEY,
+            plan_id TEXT NOT NULL,
+            checkpoint_date TEXT NOT NULL,
+            progress TEXT NOT NULL,
+            score REAL,
+            notes TEXT,
+            recorded_by TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY(plan_id) REFERENCES remediation_plans(id)
+        );"
+    ).execute(&pool).await?;
+
+    crate::flag_rules::seed_default_rules(&pool).await?;
+    crate::flag_rules::add_new_default_rules(&pool).await?;
+
+    // Student Flag History (flags as they were raised, acknowledged and resolved over time)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS student_flag_history (
+            id TEXT PRIMARY KEY,
+            student_id TEXT NOT NULL,
+            flag_type TEXT NOT NULL,
+            related_id TEXT,
+            severity TEXT NOT NULL,
+            message TEXT NOT NULL,
+            first_seen TEXT NOT NULL,
+            last_seen TEXT NOT NULL,
+            resolved_at TEXT,
+            acknowledged_at TEXT,
+            acknowledged_by TEXT,
+            acknowledgement_note TEXT,
+            action_plan TEXT,
+            FOREIGN KEY(student_id) REFERENCES students(id)
+        );"
+    ).execute(&pool).await?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_student_flag_history_student ON student_flag_history(student_id, resolved_at)"
+    ).execute(&pool).await?;
+
+    // Flags are keyed by the rule that raised them, since several rules may share a flag type
+    let _ = sqlx::query("ALTER TABLE student_flag_history ADD COLUMN rule_id TEXT").execute(&pool).await;
+
+    // NCLEX Outcomes (actual exam results, used to train the readiness model)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS nclex_outcomes (
+            id TEXT PRIMARY KEY,
+            student_id TEXT NOT NULL,
+            exam_date TEXT NOT NULL,
+            attempt_number INTEGER NOT NULL DEFAULT 1,
+            passed INTEGER NOT NULL,
+            notes TEXT,
+            recorded_at TEXT NOT NULL,
+            FOREIGN KEY(student_id) REFERENCES students(id),
+            UNIQUE(student_id, attempt_number)
+        );"
+    ).execute(&pool).await?;
+
+    // NCLEX Models (trained readiness models; the active one scores students)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS nclex_models (
+            id TEXT PRIMARY KEY,
+            trained_at TEXT NOT NULL,
+            sample_count INTEGER NOT NULL,
+            pass_count INTEGER NOT NULL,
+            training_accuracy REAL NOT NULL,
+            model TEXT NOT NULL,
+            is_active INTEGER NOT NULL DEFAULT 0
+        );"
+    ).execute(&pool).await?;
+
+    // Attendance Policies (one per attendance type; types without a row use the built-in defaults)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS attendance_policies (
+            attendance_type TEXT PRIMARY KEY,
+            tardies_per_absence INTEGER,
+            absence_hours_threshold REAL,
+            max_absences INTEGER,
+            makeup_required INTEGER NOT NULL DEFAULT 0,
+            excused_requires_makeup INTEGER NOT NULL DEFAULT 0,
+            makeup_due_days INTEGER NOT NULL DEFAULT 30,
+            updated_at TEXT NOT NULL
+        );"
+    ).execute(&pool).await?;
+
+    let _ = sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_makeup_hours_absence ON makeup_hours(original_absence_id)"
+    ).execute(&pool).await;
+
+    // Renewal deadlines opened by the daily certification sweep
+    let _ = sqlx::query("ALTER TABLE student_certifications ADD COLUMN renewal_deadline_id TEXT")
+        .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE instructor_certifications ADD COLUMN renewal_deadline_id TEXT")
+        .execute(&pool).await;
+
+    // Certification Requirements (what each cohort/site needs for clinical clearance)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS certification_requirements (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            certification_type TEXT NOT NULL,
+            name_keyword TEXT,
+            cohort TEXT,
+            site_id TEXT,
+            validity_months INTEGER,
+            is_active INTEGER NOT NULL DEFAULT 1,
+            notes TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY(site_id) REFERENCES clinical_sites(id)
+        );"
+    ).execute(&pool).await?;
+
+    crate::clearance::seed_default_requirements(&pool).await?;
+
+    // Reminder Log (which reminders were notified or snoozed, keyed by item and date)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS reminder_log (
+            key TEXT PRIMARY KEY,
+            category TEXT NOT NULL,
+            notified_at TEXT,
+            snoozed_until TEXT
+        );"
+    ).execute(&pool).await?;
+
+    // Reminder Settings (per-category opt-out; categories without a row are enabled)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS reminder_settings (
+            category TEXT PRIMARY KEY,
+            enabled INTEGER NOT NULL DEFAULT 1
+        );"
+    ).execute(&pool).await?;
+
+    // Makeup Allocations (hours of approved makeup clinical logs applied to makeup_hours, FIFO)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS makeup_allocations (
+            id TEXT PRIMARY KEY,
+            makeup_hours_id TEXT NOT NULL,
+            clinical_log_id TEXT NOT NULL,
+            hours REAL NOT NULL,
+            allocated_at TEXT NOT NULL,
+            FOREIGN KEY(makeup_hours_id) REFERENCES makeup_hours(id),
+            FOREIGN KEY(clinical_log_id) REFERENCES clinical_logs(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Comp hours ledger: each use drawn against specific earned lots (oldest unexpired first)
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS comp_hours_allocations (
+            id TEXT PRIMARY KEY,
+            used_id TEXT NOT NULL,
+            earned_id TEXT NOT NULL,
+            hours REAL NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY(used_id) REFERENCES comp_hours_used(id),
+            FOREIGN KEY(earned_id) REFERENCES comp_hours_earned(id)
+        );"
+    ).execute(&pool).await?;
+
+    // Forfeited hours depend on today's date, so the ledger computes them rather than storing a copy
+    // that goes stale; drop the column earlier versions added
+    let _ = sqlx::query("ALTER TABLE comp_hours_earned DROP COLUMN forfeited_hours")
+        .execute(&pool).await;
+
+    // Earned hours entered while the policy requires approval wait as 'pending' and don't count yet
+    let _ = sqlx::query("ALTER TABLE comp_hours_earned ADD COLUMN approval_status TEXT NOT NULL DEFAULT 'approved'")
+        .execute(&pool).await;
+    let _ = sqlx::query("ALTER TABLE comp_hours_earned ADD COLUMN approved_at TEXT")
+        .execute(&pool).await;
+
+    // Employer comp hours policy (single row, id 'default')
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS comp_hours_policy (
+            id TEXT PRIMARY KEY,
+            accrual_cap REAL,
+            expiration_months INTEGER,
+            requires_approval INTEGER NOT NULL DEFAULT 0,
+            warning_days_red INTEGER NOT NULL DEFAULT 90,
+            warning_days_yellow INTEGER NOT NULL DEFAULT 180,
+            updated_at TEXT
+        );"
+    ).execute(&pool).await?;
+
     Ok(pool)
 }
+
+/// Loads `(id, label)` pairs for every clinical site, including "name unit" labels
+/// so logs written as e.g. "Riverside ICU" can still be matched.
+async fn site_match_candidates(pool: &Pool<Sqlite>) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let sites: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, name, unit_name FROM clinical_sites"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut candidates = Vec::new();
+    for (id, name, unit_name) in sites {
+        if let Some(unit) = unit_name.filter(|u| !u.trim().is_empty()) {
+            candidates.push((id.clone(), format!("{} {}", name, unit)));
+        }
+        candidates.push((id, name));
+    }
+    Ok(candidates)
+}
+
+/// Finds the clinical site whose name best matches a free-text site name.
+pub async fn resolve_site_id(pool: &Pool<Sqlite>, site_name: &str) -> Result<Option<String>, sqlx::Error> {
+    let candidates = site_match_candidates(pool).await?;
+    Ok(matching::best_match(site_name, &candidates, matching::DEFAULT_MATCH_THRESHOLD).map(|id| id.to_string()))
+}
+
+/// Fills in `site_id` on clinical logs and hour submissions that only have a free-text `site_name`.
+/// Runs on every startup; rows that can't be matched are left NULL and retried next time.
+pub async fn backfill_site_ids(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let candidates = site_match_candidates(pool).await?;
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    for table in ["clinical_logs", "student_hour_submissions"] {
+        let names: Vec<(String,)> = sqlx::query_as(&format!(
+            "SELECT DISTINCT site_name FROM {} WHERE site_id IS NULL", table
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        for (site_name,) in names {
+            if let Some(site_id) = matching::best_match(&site_name, &candidates, matching::DEFAULT_MATCH_THRESHOLD) {
+                sqlx::query(&format!(
+                    "UPDATE {} SET site_id = ? WHERE site_id IS NULL AND site_name = ?", table
+                ))
+                .bind(site_id)
+                .bind(&site_name)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads `(id, label)` pairs for every preceptor in both "First Last" and "Last, First" order,
+/// plus the credentialed form ("First Last, RN") that evaluation forms often use.
+async fn preceptor_match_candidates(pool: &Pool<Sqlite>) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let preceptors: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, first_name, last_name, credentials FROM preceptors"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut candidates = Vec::new();
+    for (id, first_name, last_name, credentials) in preceptors {
+        if let Some(creds) = credentials.filter(|c| !c.trim().is_empty()) {
+            candidates.push((id.clone(), format!("{} {} {}", first_name, last_name, creds)));
+        }
+        candidates.push((id.clone(), format!("{} {}", last_name, first_name)));
+        candidates.push((id, format!("{} {}", first_name, last_name)));
+    }
+    Ok(candidates)
+}
+
+/// Finds the preceptor whose name best matches a free-text preceptor name.
+pub async fn resolve_preceptor_id(pool: &Pool<Sqlite>, preceptor_name: &str) -> Result<Option<String>, sqlx::Error> {
+    let candidates = preceptor_match_candidates(pool).await?;
+    Ok(matching::best_match(preceptor_name, &candidates, matching::DEFAULT_MATCH_THRESHOLD).map(|id| id.to_string()))
+}
+
+/// Fills in `preceptor_id` on evaluations that only have a free-text `preceptor_name`.
+pub async fn backfill_preceptor_ids(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let candidates = preceptor_match_candidates(pool).await?;
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<(String,)> = sqlx::query_as(
+        "SELECT DISTINCT preceptor_name FROM preceptor_evaluations WHERE preceptor_id IS NULL"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (preceptor_name,) in names {
+        if let Some(preceptor_id) = matching::best_match(&preceptor_name, &candidates, matching::DEFAULT_MATCH_THRESHOLD) {
+            sqlx::query("UPDATE preceptor_evaluations SET preceptor_id = ? WHERE preceptor_id IS NULL AND preceptor_name = ?")
+                .bind(preceptor_id)
+                .bind(&preceptor_name)
+                .execute(pool)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
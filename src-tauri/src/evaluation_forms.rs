@@ -0,0 +1,280 @@
+// Parsing for Google Forms CSV exports of preceptor evaluations.
+// Question columns are mapped to evaluation fields, ratings are read from linear-scale
+// or worded answers, and respondents are matched back to students.
+
+use std::collections::HashMap;
+
+use crate::importer::{self, SpreadsheetTable};
+use crate::matching;
+use crate::models::{EvaluationFormResponse, PreceptorEvaluation, Student};
+
+// Evaluation fields a form column can be mapped to
+pub const FORM_FIELDS: &[&str] = &[
+    "response_timestamp",
+    "student_name",
+    "student_email",
+    "preceptor_name",
+    "evaluation_date",
+    "overall_rating",
+    "clinical_skills_rating",
+    "professionalism_rating",
+    "communication_rating",
+    "comments",
+    "areas_for_improvement",
+    "strengths",
+];
+
+// Students are matched more strictly than sites or preceptors since a wrong match
+// attaches an evaluation to the wrong student record
+const STUDENT_MATCH_THRESHOLD: f64 = 0.85;
+
+/// Guesses a column mapping from the question text in the header row.
+pub fn suggest_mapping(headers: &[String]) -> HashMap<String, String> {
+    let rules: &[(&str, &[&str])] = &[
+        ("response_timestamp", &["timestamp"]),
+        ("student_email", &["student email", "student's email"]),
+        ("student_name", &["student name", "student's name", "name of student", "student"]),
+        ("preceptor_name", &["preceptor name", "your name", "preceptor", "evaluator"]),
+        ("evaluation_date", &["date of clinical", "clinical date", "shift date", "date"]),
+        ("overall_rating", &["overall"]),
+        ("clinical_skills_rating", &["clinical skill", "skills"]),
+        ("professionalism_rating", &["professionalism", "professional"]),
+        ("communication_rating", &["communication", "communicat"]),
+        ("strengths", &["strength"]),
+        ("areas_for_improvement", &["improvement", "improve", "growth"]),
+        ("comments", &["comment", "additional"]),
+    ];
+
+    let mut mapping = HashMap::new();
+    let mut used: Vec<usize> = Vec::new();
+
+    for (field, keywords) in rules {
+        // Keywords are ordered most to least specific
+        let found = keywords.iter().find_map(|keyword| {
+            headers
+                .iter()
+                .enumerate()
+                .find(|(i, h)| !used.contains(i) && h.to_lowercase().contains(keyword))
+        });
+        if let Some((i, header)) = found {
+            used.push(i);
+            mapping.insert(field.to_string(), header.clone());
+        }
+    }
+
+    mapping
+}
+
+/// Reads a rating from a linear-scale answer ("4", "4 - Exceeds expectations") or a worded one.
+pub fn parse_rating(raw: &str) -> Option<i32> {
+    let raw = raw.trim();
+    let leading: String = raw.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if let Ok(value) = leading.parse::<i32>() {
+        return Some(value.clamp(1, 5));
+    }
+
+    let lower = raw.to_lowercase();
+    let words: &[(&str, i32)] = &[
+        ("unsatisfactory", 1),
+        ("poor", 1),
+        ("needs improvement", 2),
+        ("below", 2),
+        ("fair", 2),
+        ("satisfactory", 3),
+        ("meets", 3),
+        ("good", 3),
+        ("very good", 4),
+        ("above", 4),
+        ("exceeds", 4),
+        ("excellent", 5),
+        ("outstanding", 5),
+    ];
+    // Prefer the longest phrase that matches ("very good" over "good", "unsatisfactory" over "satisfactory")
+    words
+        .iter()
+        .filter(|(phrase, _)| lower.contains(phrase))
+        .max_by_key(|(phrase, _)| phrase.len())
+        .map(|(_, value)| *value)
+}
+
+/// Normalizes a Google Forms timestamp ("1/15/2025 9:41:02") to `YYYY-MM-DDTHH:MM:SS`.
+pub fn normalize_timestamp(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let formats = [
+        "%m/%d/%Y %H:%M:%S",
+        "%m/%d/%Y %H:%M",
+        "%Y/%m/%d %I:%M:%S %p",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ];
+    for format in formats {
+        if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(raw, format) {
+            return Some(datetime.format("%Y-%m-%dT%H:%M:%S").to_string());
+        }
+    }
+    // Some locales export the date only
+    importer::parse_date(raw, None).map(|date| format!("{}T00:00:00", date))
+}
+
+/// Parses every non-empty row into a response. Rows that can't be parsed at all
+/// (no usable timestamp) come back as errors with their spreadsheet row number.
+pub fn parse_responses(
+    table: &SpreadsheetTable,
+    mapping: &HashMap<String, String>,
+) -> Result<Vec<(i32, Result<EvaluationFormResponse, String>)>, String> {
+    let mut indexes: HashMap<&str, usize> = HashMap::new();
+    for (field, column) in mapping {
+        let field = FORM_FIELDS
+            .iter()
+            .find(|f| **f == field.as_str())
+            .ok_or_else(|| format!("'{}' is not an evaluation field", field))?;
+        let index = table
+            .headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(column.trim()))
+            .ok_or_else(|| format!("Column '{}' not found in form export", column))?;
+        indexes.insert(*field, index);
+    }
+
+    if !indexes.contains_key("response_timestamp") {
+        return Err("The Timestamp column must be mapped to deduplicate responses".to_string());
+    }
+
+    let mut responses = Vec::new();
+    for (i, row) in table.rows.iter().enumerate() {
+        if row.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        let row_number = i as i32 + 2;
+
+        let value = |field: &str| -> Option<String> {
+            indexes
+                .get(field)
+                .and_then(|&idx| row.get(idx))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let response_timestamp = match value("response_timestamp").and_then(|t| normalize_timestamp(&t)) {
+            Some(ts) => ts,
+            None => {
+                responses.push((row_number, Err("Missing or unreadable timestamp".to_string())));
+                continue;
+            }
+        };
+
+        let evaluation_date = value("evaluation_date")
+            .and_then(|d| importer::parse_date(&d, None))
+            .unwrap_or_else(|| response_timestamp[..10].to_string());
+
+        responses.push((
+            row_number,
+            Ok(EvaluationFormResponse {
+                evaluation_date,
+                student_name: value("student_name").map(|n| importer::normalize_person_name(&n)),
+                student_email: value("student_email").map(|e| e.to_lowercase()),
+                preceptor_name: value("preceptor_name").map(|n| importer::normalize_person_name(&n)),
+                overall_rating: value("overall_rating").and_then(|r| parse_rating(&r)),
+                clinical_skills_rating: value("clinical_skills_rating").and_then(|r| parse_rating(&r)),
+                professionalism_rating: value("professionalism_rating").and_then(|r| parse_rating(&r)),
+                communication_rating: value("communication_rating").and_then(|r| parse_rating(&r)),
+                comments: value("comments"),
+                areas_for_improvement: value("areas_for_improvement"),
+                strengths: value("strengths"),
+                response_timestamp,
+            }),
+        ));
+    }
+
+    Ok(responses)
+}
+
+/// Matches a response to a student by email, then exact name, then a close fuzzy name match.
+/// The error describes why the response needs manual resolution.
+pub fn match_student(students: &[Student], response: &EvaluationFormResponse) -> Result<String, String> {
+    if let Some(ref email) = response.student_email {
+        if let Some(s) = students
+            .iter()
+            .find(|s| s.email.as_deref().map(|e| e.trim().eq_ignore_ascii_case(email)).unwrap_or(false))
+        {
+            return Ok(s.id.clone());
+        }
+    }
+
+    let name = match response.student_name {
+        Some(ref name) => name,
+        None => {
+            return Err(match response.student_email {
+                Some(ref email) => format!("No student with email '{}'", email),
+                None => "Response has no student name or email".to_string(),
+            })
+        }
+    };
+
+    let candidates: Vec<(String, String)> = students
+        .iter()
+        .flat_map(|s| {
+            vec![
+                (s.id.clone(), format!("{} {}", s.first_name, s.last_name)),
+                (s.id.clone(), format!("{} {}", s.last_name, s.first_name)),
+            ]
+        })
+        .collect();
+
+    let exact: Vec<&str> = candidates
+        .iter()
+        .filter(|(_, candidate)| matching::normalize_name(candidate) == matching::normalize_name(name))
+        .map(|(id, _)| id.as_str())
+        .collect();
+    match exact.as_slice() {
+        [id] => return Ok(id.to_string()),
+        [first, rest @ ..] if rest.iter().all(|id| id == first) => return Ok(first.to_string()),
+        [_, ..] => return Err(format!("More than one student is named '{}'", name)),
+        [] => {}
+    }
+
+    matching::best_match(name, &candidates, STUDENT_MATCH_THRESHOLD)
+        .map(|id| id.to_string())
+        .ok_or_else(|| format!("No student matching '{}'", name))
+}
+
+/// Identifies a response across re-imports: its timestamp plus who it is about and who wrote it.
+/// Two preceptors can submit in the same second, so the timestamp alone is not enough.
+/// ASCII lowercasing keeps the key reproducible in SQL (see the queue backfill in `db::init_db`).
+pub fn response_key(response: &EvaluationFormResponse) -> String {
+    let student = response.student_email.as_ref().or(response.student_name.as_ref());
+    format!(
+        "{}|{}|{}",
+        response.response_timestamp,
+        student.map(|s| s.to_ascii_lowercase()).unwrap_or_default(),
+        response.preceptor_name.as_deref().map(|p| p.to_ascii_lowercase()).unwrap_or_default(),
+    )
+}
+
+/// Builds a pending evaluation from a matched form response.
+pub fn to_evaluation(
+    response: &EvaluationFormResponse,
+    student_id: String,
+    preceptor_name: String,
+    preceptor_id: Option<String>,
+) -> PreceptorEvaluation {
+    PreceptorEvaluation {
+        id: format!("EVAL-{}", uuid::Uuid::new_v4()),
+        student_id,
+        clinical_log_id: None,
+        preceptor_name,
+        evaluation_date: response.evaluation_date.clone(),
+        overall_rating: response.overall_rating,
+        clinical_skills_rating: response.clinical_skills_rating,
+        professionalism_rating: response.professionalism_rating,
+        communication_rating: response.communication_rating,
+        comments: response.comments.clone(),
+        areas_for_improvement: response.areas_for_improvement.clone(),
+        strengths: response.strengths.clone(),
+        status: "Pending".to_string(),
+        submitted_at: response.response_timestamp.clone(),
+        preceptor_id,
+        response_timestamp: Some(response.response_timestamp.clone()),
+        response_key: Some(response_key(response)),
+    }
+}
@@ -89,6 +89,16 @@ pub fn handle_tray_event(app: &AppHandle, event_id: &str) {
             }
             let _ = app.emit("menu:navigate", "/clinical-logs");
         }
+        "tray_alert_submissions" | "tray_alert_evaluations" | "tray_alert_certifications" | "tray_alert_deadlines" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            // The dashboard's pending tasks list pending evaluations, overdue deadlines and
+            // expired or expiring certifications alongside the upcoming ones
+            let route = if event_id == "tray_alert_submissions" { "/hour-approvals" } else { "/" };
+            let _ = app.emit("menu:navigate", route);
+        }
         "tray_settings" => {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
@@ -0,0 +1,875 @@
+// Configurable rules engine for student flags.
+// Rule sets are versioned in `flag_rule_sets`; saving a rule set adds a new version and
+// makes it active, so earlier versions can be reviewed or re-activated.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Value};
+use sqlx::types::Json;
+use sqlx::{Pool, Sqlite};
+use tauri::{AppHandle, Manager};
+
+use crate::db::DbState;
+use crate::models::{
+    Attendance, AttendancePolicyStatus, FlagRule, MakeupHours, FlagRuleSet, RuleMetric, RuleTier, Student, StudentCertification,
+    StudentFlag, StudentFlagRecord,
+};
+
+const OPERATORS: &[&str] = &["lt", "lte", "gt", "gte", "eq"];
+const SNAPSHOT_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const SEVERITIES: &[&str] = &["error", "warning", "info"];
+
+/// Everything the rules look at for one student.
+pub struct StudentMetrics {
+    pub student: Student,
+    pub absences: i32,
+    pub total_hours: f64, // Approved clinical hours
+    pub sim_hours: f64,
+    pub certifications: Vec<StudentCertification>,
+    pub remediation_plans: Vec<RemediationSummary>,
+    pub attendance_policy: Vec<AttendancePolicyStatus>, // One entry per attendance type with sessions
+    pub makeup_obligations: Vec<MakeupHours>, // Pending or in-progress makeup hours
+}
+
+/// A remediation plan as the rules see it.
+pub struct RemediationSummary {
+    pub plan_id: String,
+    pub topic: String,
+    pub status: String,
+    pub overdue_activities: i32, // Pending activities past their due date
+}
+
+enum Measure {
+    Number(f64),
+    Text(String),
+}
+
+fn tier(operator: &str, value: Value, severity: &str, message: &str) -> RuleTier {
+    RuleTier {
+        operator: operator.to_string(),
+        value,
+        severity: severity.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn rule(id: &str, flag_type: &str, metric: RuleMetric, description: &str, tiers: Vec<RuleTier>) -> FlagRule {
+    FlagRule {
+        id: id.to_string(),
+        flag_type: flag_type.to_string(),
+        metric,
+        enabled: true,
+        description: Some(description.to_string()),
+        tiers,
+    }
+}
+
+/// The program rules the app shipped with before rules were configurable.
+pub fn default_rules() -> Vec<FlagRule> {
+    vec![
+        rule(
+            "clinical_hours_progress",
+            "clinical_hours",
+            RuleMetric::ClinicalHoursPercent,
+            "Percentage of required clinical hours completed",
+            vec![tier("lt", json!(25), "warning", "Clinical hours behind schedule: {completed}/{required} hours completed")],
+        ),
+        rule(
+            "simulation_cap",
+            "simulation_compliance",
+            RuleMetric::SimulationPercent,
+            "Simulation hours as a percentage of approved clinical hours",
+            vec![tier("gt", json!(50), "error", "Simulation hours exceed {threshold}% of total: {value}%")],
+        ),
+        rule(
+            "gpa_minimum",
+            "gpa",
+            RuleMetric::Gpa,
+            "Cumulative GPA",
+            vec![
+                tier("lt", json!(2.5), "error", "GPA below minimum requirement: {value}"),
+                tier("lt", json!(3.0), "warning", "GPA approaching minimum requirement: {value}"),
+            ],
+        ),
+        rule(
+            "nclex_predictor",
+            "nclex_predictor",
+            RuleMetric::NclexPredictor,
+            "Most recent NCLEX predictor score",
+            vec![
+                tier("lt", json!(60), "error", "NCLEX predictor score below threshold: {value}%"),
+                tier("lt", json!(70), "warning", "NCLEX predictor score needs improvement: {value}%"),
+            ],
+        ),
+        rule(
+            "remediation_open",
+            "remediation",
+            RuleMetric::RemediationStatus,
+            "Open remediation",
+            vec![
+                tier("eq", json!("Required"), "error", "Remediation {status}: {topic}"),
+                tier("eq", json!("In Progress"), "warning", "Remediation {status}: {topic}"),
+            ],
+        ),
+        rule(
+            "absences",
+            "attendance",
+            RuleMetric::Absences,
+            "Recorded absences",
+            vec![
+                tier("gte", json!(5), "error", "Excessive absences: {value} absences recorded"),
+                tier("gte", json!(3), "warning", "Multiple absences: {value} absences recorded"),
+            ],
+        ),
+        rule(
+            "certification_expiry",
+            "certification",
+            RuleMetric::CertificationDaysUntilExpiry,
+            "Days until each certification expires",
+            vec![
+                tier("lt", json!(0), "error", "{name} has expired"),
+                tier("lte", json!(7), "error", "{name} expires in {value} days"),
+                tier("lte", json!(30), "warning", "{name} expires in {value} days"),
+            ],
+        ),
+        rule(
+            "remediation_plans",
+            "remediation",
+            RuleMetric::RemediationOverdueActivities,
+            "Overdue activities on each active remediation plan",
+            vec![
+                tier("gte", json!(1), "error", "Remediation plan '{topic}' has {value} overdue activities"),
+                tier("gte", json!(0), "warning", "Active remediation plan: {topic}"),
+            ],
+        ),
+        rule(
+            "attendance_policy",
+            "attendance",
+            RuleMetric::AttendancePolicyExcess,
+            "Absences counted by the attendance policy beyond the allowed maximum, per attendance type",
+            vec![
+                tier("gt", json!(0), "error", "{type} attendance policy violated: {absences} absences counted, {max} allowed"),
+                tier("eq", json!(0), "warning", "At the {type} absence limit: {absences} of {max} allowed"),
+            ],
+        ),
+        rule(
+            "makeup_overdue",
+            "makeup",
+            RuleMetric::MakeupHoursDaysOverdue,
+            "Days past the due date of each open makeup hours obligation",
+            vec![
+                tier("gt", json!(0), "error", "Makeup hours overdue: {remaining} of {owed} hours were due {due_date}"),
+                tier("gte", json!(-7), "warning", "Makeup hours due {due_date}: {remaining} hours remaining"),
+            ],
+        ),
+    ]
+}
+
+/// Checks a rule set before it is saved or previewed.
+pub fn validate(rules: &[FlagRule]) -> Result<(), String> {
+    let mut ids: Vec<&str> = Vec::new();
+    for rule in rules {
+        if rule.id.trim().is_empty() || rule.flag_type.trim().is_empty() {
+            return Err("Every rule needs an id and a flag type".to_string());
+        }
+        if ids.contains(&rule.id.as_str()) {
+            return Err(format!("Duplicate rule id '{}'", rule.id));
+        }
+        ids.push(&rule.id);
+
+        if rule.tiers.is_empty() {
+            return Err(format!("Rule '{}' has no thresholds", rule.id));
+        }
+        for tier in &rule.tiers {
+            if !OPERATORS.contains(&tier.operator.as_str()) {
+                return Err(format!("Rule '{}': unknown operator '{}'", rule.id, tier.operator));
+            }
+            if !SEVERITIES.contains(&tier.severity.as_str()) {
+                return Err(format!("Rule '{}': unknown severity '{}'", rule.id, tier.severity));
+            }
+            let text_metric = rule.metric == RuleMetric::RemediationStatus;
+            let valid_value = if text_metric {
+                tier.operator == "eq" && tier.value.is_string()
+            } else {
+                tier.value.is_number()
+            };
+            if !valid_value {
+                return Err(if text_metric {
+                    format!("Rule '{}' compares text, so each threshold must use 'eq' with a text value", rule.id)
+                } else {
+                    format!("Rule '{}' needs numeric threshold values", rule.id)
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn matches(tier: &RuleTier, measure: &Measure) -> bool {
+    match measure {
+        Measure::Number(actual) => {
+            let Some(threshold) = tier.value.as_f64() else { return false };
+            match tier.operator.as_str() {
+                "lt" => *actual < threshold,
+                "lte" => *actual <= threshold,
+                "gt" => *actual > threshold,
+                "gte" => *actual >= threshold,
+                "eq" => (*actual - threshold).abs() < f64::EPSILON,
+                _ => false,
+            }
+        }
+        Measure::Text(actual) => tier.operator == "eq" && tier.value.as_str() == Some(actual.as_str()),
+    }
+}
+
+fn threshold_label(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.as_f64().map(|n| n.to_string()).unwrap_or_default(),
+    }
+}
+
+fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |message, (key, value)| message.replace(&format!("{{{}}}", key), value))
+}
+
+// One measurement per student, except certifications which are measured individually
+fn measurements(
+    metric: RuleMetric,
+    metrics: &StudentMetrics,
+    today: chrono::NaiveDate,
+) -> Vec<(Measure, Vec<(&'static str, String)>, Option<String>)> {
+    let student = &metrics.student;
+    match metric {
+        RuleMetric::ClinicalHoursPercent => {
+            if student.clinical_hours_required <= 0.0 || student.clinical_hours_completed >= student.clinical_hours_required {
+                return vec![];
+            }
+            let percent = student.clinical_hours_completed / student.clinical_hours_required * 100.0;
+            vec![(
+                Measure::Number(percent),
+                vec![
+                    ("value", format!("{:.1}", percent)),
+                    ("completed", format!("{:.1}", student.clinical_hours_completed)),
+                    ("required", format!("{:.1}", student.clinical_hours_required)),
+                ],
+                None,
+            )]
+        }
+        RuleMetric::SimulationPercent => {
+            if metrics.total_hours <= 0.0 {
+                return vec![];
+            }
+            let percent = metrics.sim_hours / metrics.total_hours * 100.0;
+            vec![(Measure::Number(percent), vec![("value", format!("{:.1}", percent))], None)]
+        }
+        RuleMetric::Gpa => student
+            .gpa
+            .map(|gpa| (Measure::Number(gpa), vec![("value", format!("{:.2}", gpa))], None))
+            .into_iter()
+            .collect(),
+        RuleMetric::NclexPredictor => student
+            .nclex_predictor_score
+            .map(|score| (Measure::Number(score), vec![("value", format!("{:.1}", score))], None))
+            .into_iter()
+            .collect(),
+        // Structured plans supersede the free-text remediation columns
+        RuleMetric::RemediationStatus if !metrics.remediation_plans.is_empty() => vec![],
+        RuleMetric::RemediationStatus => student
+            .remediation_status
+            .as_ref()
+            .map(|status| {
+                (
+                    Measure::Text(status.clone()),
+                    vec![
+                        ("value", status.clone()),
+                        ("status", status.to_lowercase()),
+                        ("topic", student.remediation_topic.clone().unwrap_or_else(|| "Unknown topic".to_string())),
+                    ],
+                    None,
+                )
+            })
+            .into_iter()
+            .collect(),
+        RuleMetric::Absences => vec![(
+            Measure::Number(metrics.absences as f64),
+            vec![("value", metrics.absences.to_string())],
+            None,
+        )],
+        RuleMetric::CertificationDaysUntilExpiry => metrics
+            .certifications
+            .iter()
+            .filter_map(|cert| {
+                let expiry = chrono::NaiveDate::parse_from_str(&cert.expiry_date, "%Y-%m-%d").ok()?;
+                let days = (expiry - today).num_days();
+                Some((
+                    Measure::Number(days as f64),
+                    vec![("value", days.to_string()), ("name", cert.certification_name.clone())],
+                    Some(cert.id.clone()),
+                ))
+            })
+            .collect(),
+        RuleMetric::RemediationOverdueActivities => metrics
+            .remediation_plans
+            .iter()
+            .filter(|plan| plan.status == "active")
+            .map(|plan| {
+                (
+                    Measure::Number(plan.overdue_activities as f64),
+                    vec![("value", plan.overdue_activities.to_string()), ("topic", plan.topic.clone())],
+                    Some(plan.plan_id.clone()),
+                )
+            })
+            .collect(),
+        RuleMetric::AttendancePolicyExcess => metrics
+            .attendance_policy
+            .iter()
+            .filter_map(|status| {
+                let max = status.max_absences?;
+                let excess = status.effective_absences - max;
+                Some((
+                    Measure::Number(excess as f64),
+                    vec![
+                        ("value", excess.to_string()),
+                        ("type", capitalize(&status.attendance_type)),
+                        ("absences", status.effective_absences.to_string()),
+                        ("max", max.to_string()),
+                    ],
+                    Some(status.attendance_type.clone()),
+                ))
+            })
+            .collect(),
+        RuleMetric::MakeupHoursDaysOverdue => metrics
+            .makeup_obligations
+            .iter()
+            .filter_map(|makeup| {
+                let due_date = makeup.due_date.as_deref()?;
+                let due = chrono::NaiveDate::parse_from_str(due_date, "%Y-%m-%d").ok()?;
+                let days = (today - due).num_days();
+                Some((
+                    Measure::Number(days as f64),
+                    vec![
+                        ("value", days.to_string()),
+                        ("due_date", due_date.to_string()),
+                        ("owed", format!("{:.1}", makeup.hours_owed)),
+                        ("remaining", format!("{:.1}", (makeup.hours_owed - makeup.hours_completed).max(0.0))),
+                    ],
+                    Some(makeup.id.clone()),
+                ))
+            })
+            .collect(),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Runs every enabled rule against a student. For each measurement the first matching tier wins.
+pub fn evaluate(rules: &[FlagRule], metrics: &StudentMetrics, today: chrono::NaiveDate) -> Vec<StudentFlag> {
+    let mut flags = Vec::new();
+    for rule in rules.iter().filter(|r| r.enabled) {
+        for (measure, mut placeholders, related_id) in measurements(rule.metric, metrics, today) {
+            let Some(tier) = rule.tiers.iter().find(|t| matches(t, &measure)) else { continue };
+            placeholders.push(("threshold", threshold_label(&tier.value)));
+            flags.push(StudentFlag {
+                rule_id: rule.id.clone(),
+                flag_type: rule.flag_type.clone(),
+                message: render(&tier.message, &placeholders),
+                severity: tier.severity.clone(),
+                related_id,
+                history_id: None,
+                acknowledged_at: None,
+            });
+        }
+    }
+    flags
+}
+
+/// The simulation percentage above which a student is out of compliance, if the program caps it.
+pub fn simulation_cap(rules: &[FlagRule]) -> Option<f64> {
+    rules
+        .iter()
+        .filter(|r| r.enabled && r.metric == RuleMetric::SimulationPercent)
+        .flat_map(|r| r.tiers.iter())
+        .filter(|t| t.operator == "gt" || t.operator == "gte")
+        .filter_map(|t| t.value.as_f64())
+        .reduce(f64::min)
+}
+
+/// Flags that appear, disappear or change severity between two evaluations of the same student.
+pub fn diff_flags(
+    current: &[StudentFlag],
+    proposed: &[StudentFlag],
+) -> (Vec<StudentFlag>, Vec<StudentFlag>, Vec<StudentFlag>) {
+    let key = |f: &StudentFlag| (f.rule_id.clone(), f.related_id.clone());
+    let current_by_key: HashMap<_, &StudentFlag> = current.iter().map(|f| (key(f), f)).collect();
+    let proposed_by_key: HashMap<_, &StudentFlag> = proposed.iter().map(|f| (key(f), f)).collect();
+
+    let added = proposed
+        .iter()
+        .filter(|f| !current_by_key.contains_key(&key(f)))
+        .cloned()
+        .collect();
+    let removed = current
+        .iter()
+        .filter(|f| !proposed_by_key.contains_key(&key(f)))
+        .cloned()
+        .collect();
+    let changed = proposed
+        .iter()
+        .filter(|f| current_by_key.get(&key(f)).map(|c| c.severity != f.severity).unwrap_or(false))
+        .cloned()
+        .collect();
+    (added, removed, changed)
+}
+
+/// The active rule set, falling back to the built-in defaults if none has been saved.
+pub async fn active_rule_set(pool: &Pool<Sqlite>) -> Result<FlagRuleSet, sqlx::Error> {
+    let active = sqlx::query_as::<_, FlagRuleSet>(
+        "SELECT * FROM flag_rule_sets WHERE is_active = 1 ORDER BY version DESC LIMIT 1"
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(active.unwrap_or_else(|| FlagRuleSet {
+        version: 0,
+        rules: Json(default_rules()),
+        notes: Some("Built-in defaults".to_string()),
+        is_active: 1,
+        created_at: String::new(),
+    }))
+}
+
+/// Stores the built-in rules as version 1 the first time the app runs.
+pub async fn seed_default_rules(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let count: (i32,) = sqlx::query_as("SELECT COUNT(*) FROM flag_rule_sets")
+        .fetch_one(pool)
+        .await?;
+    if count.0 > 0 {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO flag_rule_sets (version, rules, notes, is_active, created_at) VALUES (1, ?, ?, 1, ?)"
+    )
+    .bind(Json(default_rules()))
+    .bind("Default program rules")
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Adds built-in rules for metrics that no saved rule set has ever used, as a new version.
+/// Rules a program removed on purpose are not re-added since their metric appears in an older version.
+pub async fn add_new_default_rules(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let versions = sqlx::query_as::<_, FlagRuleSet>("SELECT * FROM flag_rule_sets")
+        .fetch_all(pool)
+        .await?;
+    let known_metrics: Vec<RuleMetric> = versions
+        .iter()
+        .flat_map(|v| v.rules.iter().map(|r| r.metric))
+        .collect();
+
+    let new_rules: Vec<FlagRule> = default_rules()
+        .into_iter()
+        .filter(|r| !known_metrics.contains(&r.metric))
+        .collect();
+    if new_rules.is_empty() {
+        return Ok(());
+    }
+
+    let mut rules = active_rule_set(pool).await?.rules.0;
+    let added: Vec<String> = new_rules.iter().map(|r| r.id.clone()).collect();
+    rules.extend(new_rules);
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE flag_rule_sets SET is_active = 0")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        "INSERT INTO flag_rule_sets (version, rules, notes, is_active, created_at)
+        VALUES ((SELECT COALESCE(MAX(version), 0) + 1 FROM flag_rule_sets), ?, ?, 1, ?)"
+    )
+    .bind(Json(rules))
+    .bind(format!("Added new built-in rules: {}", added.join(", ")))
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+const REMEDIATION_SUMMARY_QUERY: &str = "
+    SELECT p.id, p.student_id, p.topic, p.status,
+        (SELECT COUNT(*) FROM remediation_activities a
+         WHERE a.plan_id = p.id AND a.status != 'completed' AND a.due_date IS NOT NULL AND a.due_date < ?)
+    FROM remediation_plans p
+    JOIN students s ON s.id = p.student_id";
+
+fn remediation_summary((plan_id, _, topic, status, overdue_activities): (String, String, String, String, i32)) -> RemediationSummary {
+    RemediationSummary { plan_id, topic, status, overdue_activities }
+}
+
+/// Loads the metrics for a single student. Cohort-wide evaluation builds these in bulk instead.
+pub async fn load_metrics(pool: &Pool<Sqlite>, student: Student) -> Result<StudentMetrics, sqlx::Error> {
+    let absences: (i32,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM attendance WHERE student_id = ? AND status = 'Absent'"
+    )
+    .bind(&student.id)
+    .fetch_one(pool)
+    .await?;
+
+    let hours: (Option<f64>, Option<f64>) = sqlx::query_as(
+        "SELECT SUM(COALESCE(hours, 8.0)),
+                SUM(CASE WHEN COALESCE(is_simulation, 0) = 1 THEN COALESCE(hours, 8.0) ELSE 0.0 END)
+        FROM clinical_logs WHERE student_id = ? AND status = 'Approved'"
+    )
+    .bind(&student.id)
+    .fetch_one(pool)
+    .await?;
+
+    let certifications = sqlx::query_as::<_, StudentCertification>(
+        "SELECT * FROM student_certifications WHERE student_id = ?"
+    )
+    .bind(&student.id)
+    .fetch_all(pool)
+    .await?;
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let remediation_plans = sqlx::query_as::<_, (String, String, String, String, i32)>(
+        &format!("{} WHERE p.student_id = ?", REMEDIATION_SUMMARY_QUERY)
+    )
+    .bind(&today)
+    .bind(&student.id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(remediation_summary)
+    .collect();
+
+    let policies = crate::attendance_policy::load_policies(pool).await?;
+    let attendance = sqlx::query_as::<_, Attendance>("SELECT * FROM attendance WHERE student_id = ?")
+        .bind(&student.id)
+        .fetch_all(pool)
+        .await?;
+
+    let makeup_obligations = sqlx::query_as::<_, MakeupHours>(
+        "SELECT * FROM makeup_hours WHERE student_id = ? AND status IN ('pending', 'in_progress')"
+    )
+    .bind(&student.id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(StudentMetrics {
+        student,
+        absences: absences.0,
+        total_hours: hours.0.unwrap_or(0.0),
+        sim_hours: hours.1.unwrap_or(0.0),
+        certifications,
+        remediation_plans,
+        attendance_policy: crate::attendance_policy::evaluate_all(&policies, &attendance),
+        makeup_obligations,
+    })
+}
+
+/// Loads metrics for every student in `cohort` (or all students) with one query per table.
+pub async fn load_cohort_metrics(pool: &Pool<Sqlite>, cohort: Option<&str>) -> Result<Vec<StudentMetrics>, sqlx::Error> {
+    let students = sqlx::query_as::<_, Student>(
+        "SELECT * FROM students WHERE (? IS NULL OR cohort = ?) ORDER BY last_name, first_name"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?;
+
+    let absences: HashMap<String, i32> = sqlx::query_as::<_, (String, i32)>(
+        "SELECT a.student_id, COUNT(*) FROM attendance a
+        JOIN students s ON s.id = a.student_id
+        WHERE a.status = 'Absent' AND (? IS NULL OR s.cohort = ?)
+        GROUP BY a.student_id"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let hours: HashMap<String, (f64, f64)> = sqlx::query_as::<_, (String, f64, f64)>(
+        "SELECT l.student_id,
+                SUM(COALESCE(l.hours, 8.0)),
+                SUM(CASE WHEN COALESCE(l.is_simulation, 0) = 1 THEN COALESCE(l.hours, 8.0) ELSE 0.0 END)
+        FROM clinical_logs l
+        JOIN students s ON s.id = l.student_id
+        WHERE l.status = 'Approved' AND (? IS NULL OR s.cohort = ?)
+        GROUP BY l.student_id"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(student_id, total, sim)| (student_id, (total, sim)))
+    .collect();
+
+    let mut certifications: HashMap<String, Vec<StudentCertification>> = HashMap::new();
+    for cert in sqlx::query_as::<_, StudentCertification>(
+        "SELECT c.* FROM student_certifications c
+        JOIN students s ON s.id = c.student_id
+        WHERE (? IS NULL OR s.cohort = ?)"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    {
+        certifications.entry(cert.student_id.clone()).or_default().push(cert);
+    }
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut remediation_plans: HashMap<String, Vec<RemediationSummary>> = HashMap::new();
+    for row in sqlx::query_as::<_, (String, String, String, String, i32)>(
+        &format!("{} WHERE (? IS NULL OR s.cohort = ?)", REMEDIATION_SUMMARY_QUERY)
+    )
+    .bind(&today)
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    {
+        remediation_plans.entry(row.1.clone()).or_default().push(remediation_summary(row));
+    }
+
+    let policies = crate::attendance_policy::load_policies(pool).await?;
+    let mut attendance: HashMap<String, Vec<Attendance>> = HashMap::new();
+    for record in sqlx::query_as::<_, Attendance>(
+        "SELECT a.* FROM attendance a
+        JOIN students s ON s.id = a.student_id
+        WHERE (? IS NULL OR s.cohort = ?)"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    {
+        attendance.entry(record.student_id.clone()).or_default().push(record);
+    }
+
+    let mut makeup_obligations: HashMap<String, Vec<MakeupHours>> = HashMap::new();
+    for makeup in sqlx::query_as::<_, MakeupHours>(
+        "SELECT m.* FROM makeup_hours m
+        JOIN students s ON s.id = m.student_id
+        WHERE m.status IN ('pending', 'in_progress') AND (? IS NULL OR s.cohort = ?)"
+    )
+    .bind(cohort)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    {
+        makeup_obligations.entry(makeup.student_id.clone()).or_default().push(makeup);
+    }
+
+    Ok(students
+        .into_iter()
+        .map(|student| {
+            let (total_hours, sim_hours) = hours.get(&student.id).copied().unwrap_or((0.0, 0.0));
+            StudentMetrics {
+                absences: absences.get(&student.id).copied().unwrap_or(0),
+                total_hours,
+                sim_hours,
+                certifications: certifications.remove(&student.id).unwrap_or_default(),
+                remediation_plans: remediation_plans.remove(&student.id).unwrap_or_default(),
+                attendance_policy: crate::attendance_policy::evaluate_all(
+                    &policies,
+                    &attendance.remove(&student.id).unwrap_or_default(),
+                ),
+                makeup_obligations: makeup_obligations.remove(&student.id).unwrap_or_default(),
+                student,
+            }
+        })
+        .collect())
+}
+
+/// Weight of a flag when ranking at-risk students.
+pub fn severity_weight(severity: &str) -> i32 {
+    match severity {
+        "error" => 3,
+        "warning" => 1,
+        _ => 0,
+    }
+}
+
+/// Fills in `history_id` and `acknowledged_at` from the student's open history records without
+/// writing anything; flags not recorded yet are left without a history id.
+pub async fn attach_history(pool: &Pool<Sqlite>, student_id: &str, flags: &mut [StudentFlag]) -> Result<(), sqlx::Error> {
+    let open = sqlx::query_as::<_, StudentFlagRecord>(
+        "SELECT * FROM student_flag_history WHERE student_id = ? AND resolved_at IS NULL"
+    )
+    .bind(student_id)
+    .fetch_all(pool)
+    .await?;
+    apply_history(&open, flags);
+    Ok(())
+}
+
+/// Open history records of every student in the cohort, keyed by student id, for attaching
+/// history to a whole cohort's flags with one query.
+pub async fn open_history_for_cohort(
+    pool: &Pool<Sqlite>,
+    cohort: &str,
+) -> Result<HashMap<String, Vec<StudentFlagRecord>>, sqlx::Error> {
+    let open = sqlx::query_as::<_, StudentFlagRecord>(
+        "SELECT * FROM student_flag_history
+        WHERE resolved_at IS NULL AND student_id IN (SELECT id FROM students WHERE cohort = ?)"
+    )
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_student: HashMap<String, Vec<StudentFlagRecord>> = HashMap::new();
+    for record in open {
+        by_student.entry(record.student_id.clone()).or_default().push(record);
+    }
+    Ok(by_student)
+}
+
+// Records opened before flags were keyed by rule fall back to matching on the flag type
+fn is_record_of(record: &StudentFlagRecord, flag: &StudentFlag) -> bool {
+    record.related_id == flag.related_id
+        && match record.rule_id {
+            Some(ref rule_id) => *rule_id == flag.rule_id,
+            None => record.flag_type == flag.flag_type,
+        }
+}
+
+/// `attach_history` against records that were already loaded.
+pub fn apply_history(open: &[StudentFlagRecord], flags: &mut [StudentFlag]) {
+    for flag in flags.iter_mut() {
+        if let Some(record) = open.iter().find(|r| is_record_of(r, flag)) {
+            flag.history_id = Some(record.id.clone());
+            flag.acknowledged_at = record.acknowledged_at.clone();
+        }
+    }
+}
+
+/// Materializes the current flags into `student_flag_history`: matching open records are
+/// refreshed, new flags are opened and open records no longer raised are resolved.
+/// Fills in `history_id` and `acknowledged_at` on the passed flags.
+pub async fn record_flags(pool: &Pool<Sqlite>, student_id: &str, flags: &mut [StudentFlag]) -> Result<(), sqlx::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = pool.begin().await?;
+
+    let open = sqlx::query_as::<_, StudentFlagRecord>(
+        "SELECT * FROM student_flag_history WHERE student_id = ? AND resolved_at IS NULL"
+    )
+    .bind(student_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut still_open: HashSet<&str> = HashSet::new();
+    for flag in flags.iter_mut() {
+        let existing = open.iter().find(|r| is_record_of(r, flag));
+
+        match existing {
+            Some(record) => {
+                sqlx::query(
+                    "UPDATE student_flag_history SET last_seen = ?, severity = ?, message = ?, rule_id = ? WHERE id = ?"
+                )
+                .bind(&now)
+                .bind(&flag.severity)
+                .bind(&flag.message)
+                .bind(&flag.rule_id)
+                .bind(&record.id)
+                .execute(&mut *tx)
+                .await?;
+
+                still_open.insert(&record.id);
+                flag.history_id = Some(record.id.clone());
+                flag.acknowledged_at = record.acknowledged_at.clone();
+            }
+            None => {
+                let id = format!("FLAG-{}", uuid::Uuid::new_v4());
+                sqlx::query(
+                    "INSERT INTO student_flag_history (id, student_id, rule_id, flag_type, related_id, severity, message, first_seen, last_seen)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                )
+                .bind(&id)
+                .bind(student_id)
+                .bind(&flag.rule_id)
+                .bind(&flag.flag_type)
+                .bind(&flag.related_id)
+                .bind(&flag.severity)
+                .bind(&flag.message)
+                .bind(&now)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+
+                flag.history_id = Some(id);
+            }
+        }
+    }
+
+    for record in open.iter().filter(|r| !still_open.contains(r.id.as_str())) {
+        sqlx::query("UPDATE student_flag_history SET resolved_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&record.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await
+}
+
+/// Re-evaluates a student against the active rule set and records the result, for writes that
+/// change what the rules see (attendance, policies). Flag queries only read the history.
+pub async fn refresh_student_flags(pool: &Pool<Sqlite>, student_id: &str) -> Result<Vec<StudentFlag>, sqlx::Error> {
+    let Some(student) = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
+        .bind(student_id)
+        .fetch_optional(pool)
+        .await?
+    else {
+        return Ok(vec![]);
+    };
+
+    let rule_set = active_rule_set(pool).await?;
+    let metrics = load_metrics(pool, student).await?;
+    let mut flags = evaluate(&rule_set.rules, &metrics, chrono::Utc::now().date_naive());
+    record_flags(pool, student_id, &mut flags).await?;
+    Ok(flags)
+}
+
+/// Records the current flags of every student (or one cohort's) under the active rule set.
+/// Returns the number of students recorded.
+pub async fn snapshot_flags(pool: &Pool<Sqlite>, cohort: Option<&str>) -> Result<i32, sqlx::Error> {
+    let rule_set = active_rule_set(pool).await?;
+    let today = chrono::Utc::now().date_naive();
+    let mut recorded = 0;
+    for metrics in load_cohort_metrics(pool, cohort).await? {
+        let mut flags = evaluate(&rule_set.rules, &metrics, today);
+        record_flags(pool, &metrics.student.id, &mut flags).await?;
+        recorded += 1;
+    }
+    Ok(recorded)
+}
+
+/// Flags also change with the calendar alone (a certification lapsing, a deadline passing), so the
+/// history is brought up to date shortly after startup and then daily.
+pub fn spawn_daily_snapshot(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let pool = app.state::<DbState>().db.clone();
+            if let Err(e) = snapshot_flags(&pool, None).await {
+                log::error!("Flag snapshot failed: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(SNAPSHOT_INTERVAL_SECS)).await;
+        }
+    });
+}
@@ -0,0 +1,866 @@
+// Spreadsheet (CSV/XLSX) import for students, grades and attendance.
+// Rows are read as text, mapped to target fields through an ImportMapping, coerced and
+// validated, then checked for duplicates against existing records before anything is written.
+
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::Datelike;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::matching;
+use crate::models::{ImportField, ImportMapping, ImportPreview, ImportRowPreview, ImportTarget, Student};
+
+pub struct SpreadsheetTable {
+    pub sheet_names: Vec<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    Name,
+    Email,
+    Phone,
+    Date,
+    Gpa,
+    Grade,
+    Hours,
+    Minutes,
+    StudentStatus,
+    AttendanceStatus,
+    AttendanceType,
+}
+
+struct FieldSpec {
+    name: &'static str,
+    kind: FieldKind,
+    required: bool,
+}
+
+const fn field(name: &'static str, kind: FieldKind, required: bool) -> FieldSpec {
+    FieldSpec { name, kind, required }
+}
+
+const STUDENT_FIELDS: &[FieldSpec] = &[
+    field("id", FieldKind::Text, false),
+    field("first_name", FieldKind::Name, true),
+    field("last_name", FieldKind::Name, true),
+    field("full_name", FieldKind::Name, false), // "Last, First" or "First Last"; used when first/last aren't mapped
+    field("cohort", FieldKind::Text, true),
+    field("status", FieldKind::StudentStatus, false),
+    field("email", FieldKind::Email, false),
+    field("phone", FieldKind::Phone, false),
+    field("dob", FieldKind::Date, false),
+    field("gpa", FieldKind::Gpa, false),
+    field("clinical_hours_completed", FieldKind::Hours, false),
+    field("clinical_hours_required", FieldKind::Hours, false),
+    field("notes", FieldKind::Text, false),
+    field("emergency_contact_name", FieldKind::Name, false),
+    field("emergency_contact_phone", FieldKind::Phone, false),
+];
+
+// Fields used by grades and attendance rows to identify the student
+const STUDENT_REFERENCE_FIELDS: &[FieldSpec] = &[
+    field("student_id", FieldKind::Text, false),
+    field("student_email", FieldKind::Email, false),
+    field("student_name", FieldKind::Name, false),
+    field("first_name", FieldKind::Name, false),
+    field("last_name", FieldKind::Name, false),
+];
+
+const GRADE_FIELDS: &[FieldSpec] = &[
+    field("course_id", FieldKind::Text, true),
+    field("course_name", FieldKind::Text, true),
+    field("grade", FieldKind::Grade, true),
+    field("semester", FieldKind::Text, true),
+];
+
+const ATTENDANCE_FIELDS: &[FieldSpec] = &[
+    field("date", FieldKind::Date, true),
+    field("status", FieldKind::AttendanceStatus, true),
+    field("attendance_type", FieldKind::AttendanceType, false),
+    field("hours_attended", FieldKind::Hours, false),
+    field("hours_required", FieldKind::Hours, false),
+    field("minutes_late", FieldKind::Minutes, false),
+    field("course_id", FieldKind::Text, false),
+    field("notes", FieldKind::Text, false),
+];
+
+fn fields_for(target: ImportTarget) -> Vec<&'static FieldSpec> {
+    match target {
+        ImportTarget::Students => STUDENT_FIELDS.iter().collect(),
+        ImportTarget::Grades => STUDENT_REFERENCE_FIELDS.iter().chain(GRADE_FIELDS.iter()).collect(),
+        ImportTarget::Attendance => STUDENT_REFERENCE_FIELDS.iter().chain(ATTENDANCE_FIELDS.iter()).collect(),
+    }
+}
+
+/// The fields a mapping can fill for `target`, in display order.
+pub fn import_fields(target: ImportTarget) -> Vec<ImportField> {
+    fields_for(target)
+        .into_iter()
+        .map(|spec| ImportField { name: spec.name.to_string(), required: spec.required })
+        .collect()
+}
+
+// ==================== READING ====================
+
+fn is_workbook(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    matches!(extension.as_deref(), Some("xlsx") | Some("xlsm") | Some("xls") | Some("ods"))
+}
+
+pub fn read_table(path: &Path, sheet_name: Option<&str>) -> Result<SpreadsheetTable, String> {
+    let (sheet_names, mut rows) = if is_workbook(path) {
+        read_workbook(path, sheet_name)?
+    } else {
+        (vec![], read_csv(path)?)
+    };
+
+    if rows.is_empty() {
+        return Err("Spreadsheet is empty".to_string());
+    }
+
+    let headers = rows
+        .remove(0)
+        .into_iter()
+        .map(|h| h.trim_start_matches('\u{feff}').trim().to_string())
+        .collect();
+
+    Ok(SpreadsheetTable { sheet_names, headers, rows })
+}
+
+fn read_csv(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("Failed to open CSV: {}", e))?;
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Failed to read CSV: {}", e))?;
+        rows.push(record.iter().map(|v| v.trim().to_string()).collect());
+    }
+    Ok(rows)
+}
+
+fn read_workbook(path: &Path, sheet_name: Option<&str>) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open workbook: {}", e))?;
+    let sheet_names = workbook.sheet_names();
+
+    let sheet = match sheet_name {
+        Some(name) => name.to_string(),
+        None => sheet_names
+            .first()
+            .cloned()
+            .ok_or_else(|| "Workbook has no sheets".to_string())?,
+    };
+
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| format!("Failed to read sheet '{}': {}", sheet, e))?;
+
+    let rows = range
+        .rows()
+        .map(|row| row.iter().map(cell_to_string).collect())
+        .collect();
+
+    Ok((sheet_names, rows))
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::Empty | Data::Error(_) => String::new(),
+        Data::String(s) => s.trim().to_string(),
+        // Whole numbers (IDs, phone numbers) shouldn't pick up a trailing ".0"
+        Data::Float(f) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", *f as i64),
+        Data::Float(f) => f.to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(dt) => excel_serial_to_date(dt.as_f64()).unwrap_or_default(),
+        Data::DateTimeIso(s) | Data::DurationIso(s) => s.clone(),
+    }
+}
+
+fn excel_serial_to_date(serial: f64) -> Option<String> {
+    let epoch = chrono::NaiveDate::from_ymd_opt(1899, 12, 30)?;
+    epoch
+        .checked_add_signed(chrono::TimeDelta::try_days(serial.floor() as i64)?)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+// ==================== COERCION ====================
+
+/// Parses the date formats registrars and Google Forms commonly produce into `YYYY-MM-DD`.
+pub fn parse_date(raw: &str, preferred_format: Option<&str>) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+
+    let formats = [
+        "%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%m-%d-%Y", "%Y/%m/%d",
+        "%d-%b-%Y", "%d %b %Y", "%b %d, %Y", "%B %d, %Y", "%b %d %Y", "%B %d %Y",
+    ];
+    for format in preferred_format.into_iter().chain(formats.iter().copied()) {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(raw, format) {
+            // "%Y" happily reads "1/15/25" as the year 25
+            if date.year() >= 1900 {
+                return Some(date.format("%Y-%m-%d").to_string());
+            }
+        }
+    }
+
+    let datetime_formats = [
+        "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y/%m/%d %H:%M:%S",
+        "%m/%d/%Y %H:%M:%S", "%m/%d/%Y %H:%M",
+    ];
+    for format in datetime_formats {
+        if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(raw, format) {
+            return Some(datetime.date().format("%Y-%m-%d").to_string());
+        }
+    }
+
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return Some(datetime.date_naive().format("%Y-%m-%d").to_string());
+    }
+
+    // Excel serial dates that were exported as plain numbers (1954-2119)
+    if let Ok(serial) = raw.parse::<f64>() {
+        if (20000.0..=80000.0).contains(&serial) {
+            return excel_serial_to_date(serial);
+        }
+    }
+
+    None
+}
+
+/// Collapses whitespace and title-cases names typed in all caps or all lowercase.
+/// Mixed-case names are left alone so "McDonald" or "DeShawn" aren't mangled.
+pub fn normalize_person_name(raw: &str) -> String {
+    let collapsed = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let has_upper = collapsed.chars().any(|c| c.is_uppercase());
+    let has_lower = collapsed.chars().any(|c| c.is_lowercase());
+    if has_upper && has_lower {
+        return collapsed;
+    }
+
+    let mut result = String::with_capacity(collapsed.len());
+    let mut capitalize = true;
+    for c in collapsed.chars() {
+        if capitalize {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        capitalize = c == ' ' || c == '-' || c == '\'';
+    }
+    result
+}
+
+/// Splits "Last, First" or "First Middle Last" into (first, last).
+pub fn split_full_name(full_name: &str) -> Option<(String, String)> {
+    if let Some((last, first)) = full_name.split_once(',') {
+        let (first, last) = (first.trim(), last.trim());
+        if !first.is_empty() && !last.is_empty() {
+            return Some((first.to_string(), last.to_string()));
+        }
+        return None;
+    }
+
+    let parts: Vec<&str> = full_name.split_whitespace().collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    let (last, first) = parts.split_last()?;
+    Some((first.join(" "), last.to_string()))
+}
+
+fn parse_number(raw: &str) -> Result<f64, String> {
+    raw.replace(',', "")
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a number", raw))
+}
+
+fn coerce(kind: FieldKind, raw: &str, date_format: Option<&str>) -> Result<String, String> {
+    match kind {
+        FieldKind::Text => Ok(raw.to_string()),
+        FieldKind::Name => Ok(normalize_person_name(raw)),
+        FieldKind::Email => {
+            let email = raw.to_lowercase();
+            let valid = email
+                .split_once('@')
+                .map(|(user, domain)| !user.is_empty() && domain.contains('.') && !domain.ends_with('.'))
+                .unwrap_or(false);
+            if valid {
+                Ok(email)
+            } else {
+                Err(format!("'{}' is not a valid email address", raw))
+            }
+        }
+        FieldKind::Phone => {
+            let digits = raw.chars().filter(|c| c.is_ascii_digit()).count();
+            if (7..=15).contains(&digits) {
+                Ok(raw.to_string())
+            } else {
+                Err(format!("'{}' is not a valid phone number", raw))
+            }
+        }
+        FieldKind::Date => parse_date(raw, date_format).ok_or_else(|| format!("'{}' is not a recognized date", raw)),
+        FieldKind::Gpa => {
+            let gpa = parse_number(raw)?;
+            if (0.0..=4.0).contains(&gpa) {
+                Ok(gpa.to_string())
+            } else {
+                Err(format!("GPA {} is outside the 0.0-4.0 scale", raw))
+            }
+        }
+        FieldKind::Grade => {
+            let grade = parse_number(raw.trim_end_matches('%'))?;
+            if (0.0..=100.0).contains(&grade) {
+                Ok(grade.to_string())
+            } else {
+                Err(format!("Grade {} is outside 0-100", raw))
+            }
+        }
+        FieldKind::Hours => {
+            let hours = parse_number(raw)?;
+            if hours >= 0.0 {
+                Ok(hours.to_string())
+            } else {
+                Err(format!("Hours cannot be negative: {}", raw))
+            }
+        }
+        FieldKind::Minutes => {
+            let minutes = parse_number(raw)?;
+            if minutes >= 0.0 {
+                Ok((minutes.round() as i64).to_string())
+            } else {
+                Err(format!("Minutes cannot be negative: {}", raw))
+            }
+        }
+        FieldKind::StudentStatus => match raw.to_lowercase().as_str() {
+            "active" => Ok("Active".to_string()),
+            "at risk" | "at-risk" | "atrisk" => Ok("At Risk".to_string()),
+            "graduated" => Ok("Graduated".to_string()),
+            _ => Err(format!("Unknown student status '{}'", raw)),
+        },
+        FieldKind::AttendanceStatus => match raw.to_lowercase().as_str() {
+            "p" | "present" => Ok("Present".to_string()),
+            "a" | "absent" => Ok("Absent".to_string()),
+            "t" | "tardy" | "late" => Ok("Tardy".to_string()),
+            "e" | "excused" => Ok("Excused".to_string()),
+            "partial" => Ok("Partial".to_string()),
+            _ => Err(format!("Unknown attendance status '{}'", raw)),
+        },
+        FieldKind::AttendanceType => match raw.to_lowercase().as_str() {
+            "classroom" | "class" | "didactic" | "theory" => Ok("classroom".to_string()),
+            "clinical" | "clinicals" => Ok("clinical".to_string()),
+            "lab" | "skills lab" | "simulation" | "sim" => Ok("lab".to_string()),
+            _ => Err(format!("Unknown attendance type '{}'", raw)),
+        },
+    }
+}
+
+// ==================== PREVIEW ====================
+
+struct StudentIndex {
+    ids: HashMap<String, String>,                              // id -> display name
+    by_email: HashMap<String, String>,                         // lowercase email -> id
+    by_name: HashMap<String, Vec<(String, Option<String>)>>,   // normalized "first last" -> [(id, dob)]
+}
+
+impl StudentIndex {
+    fn new(students: &[Student]) -> Self {
+        let mut index = StudentIndex {
+            ids: HashMap::new(),
+            by_email: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+        for s in students {
+            let name = format!("{} {}", s.first_name, s.last_name);
+            if let Some(ref email) = s.email {
+                if !email.trim().is_empty() {
+                    index.by_email.insert(email.trim().to_lowercase(), s.id.clone());
+                }
+            }
+            index
+                .by_name
+                .entry(matching::normalize_name(&name))
+                .or_default()
+                .push((s.id.clone(), s.dob.clone()));
+            index.ids.insert(s.id.clone(), name);
+        }
+        index
+    }
+
+    fn resolve(&self, values: &HashMap<String, String>) -> Result<String, String> {
+        if let Some(id) = values.get("student_id") {
+            return if self.ids.contains_key(id) {
+                Ok(id.clone())
+            } else {
+                Err(format!("No student with id '{}'", id))
+            };
+        }
+
+        if let Some(email) = values.get("student_email") {
+            return self
+                .by_email
+                .get(email)
+                .cloned()
+                .ok_or_else(|| format!("No student with email '{}'", email));
+        }
+
+        let name = match (values.get("first_name"), values.get("last_name"), values.get("student_name")) {
+            (Some(first), Some(last), _) => format!("{} {}", first, last),
+            (_, _, Some(full)) => match split_full_name(full) {
+                Some((first, last)) => format!("{} {}", first, last),
+                None => full.clone(),
+            },
+            _ => return Err("Row has no student id, email or name".to_string()),
+        };
+
+        match self.by_name.get(&matching::normalize_name(&name)).map(|m| m.as_slice()) {
+            Some([(id, _)]) => Ok(id.clone()),
+            Some(matches) if matches.len() > 1 => Err(format!(
+                "{} students are named '{}'; map an id or email column instead",
+                matches.len(),
+                name
+            )),
+            _ => Err(format!("No student named '{}'", name)),
+        }
+    }
+}
+
+fn column_indexes(headers: &[String], mapping: &ImportMapping) -> Result<HashMap<String, usize>, String> {
+    let known: Vec<&str> = fields_for(mapping.target).iter().map(|f| f.name).collect();
+    let mut indexes = HashMap::new();
+
+    for (target_field, column) in &mapping.columns {
+        if !known.contains(&target_field.as_str()) {
+            return Err(format!("'{}' is not an importable field", target_field));
+        }
+        let index = headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(column.trim()))
+            .ok_or_else(|| format!("Column '{}' not found in spreadsheet", column))?;
+        indexes.insert(target_field.clone(), index);
+    }
+
+    Ok(indexes)
+}
+
+pub async fn build_preview(
+    pool: &Pool<Sqlite>,
+    table: &SpreadsheetTable,
+    mapping: &ImportMapping,
+) -> Result<ImportPreview, String> {
+    let indexes = column_indexes(&table.headers, mapping)?;
+    let fields = fields_for(mapping.target);
+    let defaults = mapping.defaults.clone().unwrap_or_default();
+    let date_format = mapping.date_format.as_deref();
+
+    let students = sqlx::query_as::<_, Student>("SELECT * FROM students")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let student_index = StudentIndex::new(&students);
+
+    // Existing records keyed the same way rows are compared
+    let existing: HashMap<String, String> = match mapping.target {
+        ImportTarget::Students => HashMap::new(),
+        ImportTarget::Grades => sqlx::query_as::<_, (String, String, String, String)>(
+            "SELECT id, student_id, course_id, semester FROM grades"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(id, student_id, course_id, semester)| (grade_key(&student_id, &course_id, &semester), id))
+        .collect(),
+        ImportTarget::Attendance => sqlx::query_as::<_, (String, String, String, Option<String>)>(
+            "SELECT id, student_id, date, attendance_type FROM attendance"
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(id, student_id, date, attendance_type)| {
+            (attendance_key(&student_id, &date, attendance_type.as_deref().unwrap_or("classroom")), id)
+        })
+        .collect(),
+    };
+
+    let mut seen_in_file: HashMap<String, i32> = HashMap::new();
+    let mut rows = Vec::new();
+
+    for (i, raw_row) in table.rows.iter().enumerate() {
+        if raw_row.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        let row_number = i as i32 + 2;
+
+        let mut values: HashMap<String, String> = HashMap::new();
+        let mut errors: Vec<String> = Vec::new();
+        let mut warnings: Vec<String> = Vec::new();
+
+        for spec in &fields {
+            let raw = indexes
+                .get(spec.name)
+                .and_then(|&idx| raw_row.get(idx))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .or_else(|| defaults.get(spec.name).map(|v| v.trim().to_string()));
+
+            if let Some(raw) = raw.filter(|v| !v.is_empty()) {
+                match coerce(spec.kind, &raw, date_format) {
+                    Ok(value) => {
+                        values.insert(spec.name.to_string(), value);
+                    }
+                    Err(e) => errors.push(format!("{}: {}", spec.name, e)),
+                }
+            }
+        }
+
+        if mapping.target == ImportTarget::Students
+            && !(values.contains_key("first_name") && values.contains_key("last_name"))
+        {
+            if let Some(full_name) = values.remove("full_name") {
+                match split_full_name(&full_name) {
+                    Some((first, last)) => {
+                        values.entry("first_name".to_string()).or_insert(first);
+                        values.entry("last_name".to_string()).or_insert(last);
+                    }
+                    None => errors.push(format!("full_name: can't split '{}' into first and last name", full_name)),
+                }
+            }
+        }
+
+        for spec in &fields {
+            if spec.required && !values.contains_key(spec.name) {
+                errors.push(format!("Missing required field {}", spec.name));
+            }
+        }
+
+        let mut student_id = None;
+        let mut duplicate_of = None;
+        let mut file_key = None;
+
+        match mapping.target {
+            ImportTarget::Students => {
+                let name_key = match (values.get("first_name"), values.get("last_name")) {
+                    (Some(first), Some(last)) => Some(matching::normalize_name(&format!("{} {}", first, last))),
+                    _ => None,
+                };
+                let dob = values.get("dob");
+
+                duplicate_of = values
+                    .get("id")
+                    .filter(|id| student_index.ids.contains_key(*id))
+                    .cloned()
+                    .or_else(|| values.get("email").and_then(|e| student_index.by_email.get(e)).cloned())
+                    .or_else(|| {
+                        // Same name is a duplicate unless both sides have a different date of birth
+                        name_key.as_ref().and_then(|key| student_index.by_name.get(key)).and_then(|matches| {
+                            matches
+                                .iter()
+                                .find(|(_, existing_dob)| match (dob, existing_dob) {
+                                    (Some(a), Some(b)) => a == b,
+                                    _ => true,
+                                })
+                                .map(|(id, _)| id.clone())
+                        })
+                    });
+
+                file_key = values
+                    .get("email")
+                    .cloned()
+                    .or_else(|| name_key.map(|key| format!("{}|{}", key, dob.cloned().unwrap_or_default())));
+            }
+            ImportTarget::Grades | ImportTarget::Attendance => {
+                match student_index.resolve(&values) {
+                    Ok(id) => student_id = Some(id),
+                    Err(e) => errors.push(e),
+                }
+
+                if let Some(ref sid) = student_id {
+                    let key = if mapping.target == ImportTarget::Grades {
+                        match (values.get("course_id"), values.get("semester")) {
+                            (Some(course_id), Some(semester)) => Some(grade_key(sid, course_id, semester)),
+                            _ => None,
+                        }
+                    } else {
+                        let attendance_type = values
+                            .get("attendance_type")
+                            .map(|t| t.as_str())
+                            .unwrap_or("classroom");
+                        values.get("date").map(|date| attendance_key(sid, date, attendance_type))
+                    };
+
+                    if let Some(ref key) = key {
+                        duplicate_of = existing.get(key).cloned();
+                    }
+                    file_key = key;
+                }
+
+                if let (Some(attended), Some(required)) = (values.get("hours_attended"), values.get("hours_required")) {
+                    if attended.parse::<f64>().unwrap_or(0.0) > required.parse::<f64>().unwrap_or(f64::MAX) {
+                        warnings.push("Hours attended exceed hours required".to_string());
+                    }
+                }
+            }
+        }
+
+        if let Some(key) = file_key {
+            if let Some(first_row) = seen_in_file.get(&key) {
+                errors.push(format!("Duplicate of row {} in this file", first_row));
+            } else {
+                seen_in_file.insert(key, row_number);
+            }
+        }
+
+        if let Some(ref existing_id) = duplicate_of {
+            let label = match mapping.target {
+                ImportTarget::Students => student_index
+                    .ids
+                    .get(existing_id)
+                    .map(|name| format!("Matches existing student {} ({})", name, existing_id))
+                    .unwrap_or_else(|| format!("Matches existing student {}", existing_id)),
+                ImportTarget::Grades => "A grade for this course and semester already exists".to_string(),
+                ImportTarget::Attendance => "Attendance is already recorded for this date".to_string(),
+            };
+            warnings.push(label);
+        }
+
+        rows.push(ImportRowPreview {
+            row_number,
+            values,
+            student_id,
+            duplicate_of,
+            errors,
+            warnings,
+        });
+    }
+
+    let total_rows = rows.len() as i32;
+    let error_rows = rows.iter().filter(|r| !r.errors.is_empty()).count() as i32;
+    let duplicate_rows = rows
+        .iter()
+        .filter(|r| r.errors.is_empty() && r.duplicate_of.is_some())
+        .count() as i32;
+
+    Ok(ImportPreview {
+        target: mapping.target,
+        total_rows,
+        valid_rows: total_rows - error_rows,
+        error_rows,
+        duplicate_rows,
+        rows,
+    })
+}
+
+fn grade_key(student_id: &str, course_id: &str, semester: &str) -> String {
+    format!("{}|{}|{}", student_id, course_id.trim().to_lowercase(), semester.trim().to_lowercase())
+}
+
+fn attendance_key(student_id: &str, date: &str, attendance_type: &str) -> String {
+    format!("{}|{}|{}", student_id, date, attendance_type)
+}
+
+// ==================== COMMIT ====================
+
+pub struct CommitOutcome {
+    pub imported: i32,
+    pub updated: i32,
+    pub skipped: i32,
+    pub failed: i32,
+    pub errors: Vec<String>,
+}
+
+fn number(values: &HashMap<String, String>, key: &str) -> Option<f64> {
+    values.get(key).and_then(|v| v.parse::<f64>().ok())
+}
+
+/// Writes every error-free row of a preview. Rows matching an existing record are
+/// updated when `update_duplicates` is set and skipped otherwise.
+pub async fn commit_preview(
+    pool: &Pool<Sqlite>,
+    preview: &ImportPreview,
+    update_duplicates: bool,
+) -> Result<CommitOutcome, String> {
+    // All or nothing: rows that fail are reported, but a dropped connection or crash partway
+    // through leaves the database as it was
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let mut outcome = CommitOutcome {
+        imported: 0,
+        updated: 0,
+        skipped: 0,
+        failed: 0,
+        errors: vec![],
+    };
+
+    for row in &preview.rows {
+        if !row.errors.is_empty() {
+            outcome.failed += 1;
+            outcome.errors.push(format!("Row {}: {}", row.row_number, row.errors.join("; ")));
+            continue;
+        }
+        if row.duplicate_of.is_some() && !update_duplicates {
+            outcome.skipped += 1;
+            continue;
+        }
+
+        let result = match (preview.target, row.duplicate_of.as_deref()) {
+            (ImportTarget::Students, None) => insert_student(&mut *tx, &row.values).await,
+            (ImportTarget::Students, Some(id)) => update_student(&mut *tx, id, &row.values).await,
+            (ImportTarget::Grades, None) => insert_grade(&mut *tx, row).await,
+            (ImportTarget::Grades, Some(id)) => update_grade(&mut *tx, id, &row.values).await,
+            (ImportTarget::Attendance, None) => insert_attendance(&mut *tx, row).await,
+            (ImportTarget::Attendance, Some(id)) => update_attendance(&mut *tx, id, &row.values).await,
+        };
+
+        match result {
+            Ok(_) if row.duplicate_of.is_some() => outcome.updated += 1,
+            Ok(_) => outcome.imported += 1,
+            Err(e) => {
+                outcome.failed += 1;
+                outcome.errors.push(format!("Row {}: {}", row.row_number, e));
+            }
+        }
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(outcome)
+}
+
+async fn insert_student(conn: &mut SqliteConnection, values: &HashMap<String, String>) -> Result<(), sqlx::Error> {
+    let id = values
+        .get("id")
+        .cloned()
+        .unwrap_or_else(|| format!("STU-{}", uuid::Uuid::new_v4()));
+
+    sqlx::query(
+        "INSERT INTO students (
+            id, first_name, last_name, cohort, status, clinical_hours_completed, clinical_hours_required,
+            skills_completed, email, phone, dob, gpa, notes, emergency_contact_name, emergency_contact_phone
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, '[]', ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(&id)
+    .bind(values.get("first_name"))
+    .bind(values.get("last_name"))
+    .bind(values.get("cohort"))
+    .bind(values.get("status").map(|s| s.as_str()).unwrap_or("Active"))
+    .bind(number(values, "clinical_hours_completed").unwrap_or(0.0))
+    .bind(number(values, "clinical_hours_required").unwrap_or(400.0))
+    .bind(values.get("email"))
+    .bind(values.get("phone"))
+    .bind(values.get("dob"))
+    .bind(number(values, "gpa"))
+    .bind(values.get("notes"))
+    .bind(values.get("emergency_contact_name"))
+    .bind(values.get("emergency_contact_phone"))
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn update_student(conn: &mut SqliteConnection, id: &str, values: &HashMap<String, String>) -> Result<(), sqlx::Error> {
+    // Only overwrite the columns that were actually imported
+    let columns: Vec<&FieldSpec> = STUDENT_FIELDS
+        .iter()
+        .filter(|f| f.name != "id" && f.name != "full_name" && values.contains_key(f.name))
+        .collect();
+    if columns.is_empty() {
+        return Ok(());
+    }
+
+    let assignments = columns
+        .iter()
+        .map(|f| format!("{} = ?", f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!("UPDATE students SET {} WHERE id = ?", assignments);
+
+    let mut query = sqlx::query(&sql);
+    for f in &columns {
+        query = match f.kind {
+            FieldKind::Gpa | FieldKind::Hours => query.bind(number(values, f.name)),
+            _ => query.bind(values.get(f.name).cloned()),
+        };
+    }
+    query.bind(id).execute(&mut *conn).await?;
+    Ok(())
+}
+
+async fn insert_grade(conn: &mut SqliteConnection, row: &ImportRowPreview) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO grades (id, student_id, course_id, course_name, grade, semester) VALUES (?, ?, ?, ?, ?, ?)"
+    )
+    .bind(format!("GRD-{}", uuid::Uuid::new_v4()))
+    .bind(&row.student_id)
+    .bind(row.values.get("course_id"))
+    .bind(row.values.get("course_name"))
+    .bind(number(&row.values, "grade"))
+    .bind(row.values.get("semester"))
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn update_grade(conn: &mut SqliteConnection, id: &str, values: &HashMap<String, String>) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE grades SET course_name = ?, grade = ? WHERE id = ?")
+        .bind(values.get("course_name"))
+        .bind(number(values, "grade"))
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn insert_attendance(conn: &mut SqliteConnection, row: &ImportRowPreview) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO attendance (id, student_id, date, status, notes, recorded_at, attendance_type, hours_attended, hours_required, minutes_late, course_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+    )
+    .bind(format!("ATT-{}", uuid::Uuid::new_v4()))
+    .bind(&row.student_id)
+    .bind(row.values.get("date"))
+    .bind(row.values.get("status"))
+    .bind(row.values.get("notes"))
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(row.values.get("attendance_type").map(|t| t.as_str()).unwrap_or("classroom"))
+    .bind(number(&row.values, "hours_attended"))
+    .bind(number(&row.values, "hours_required").unwrap_or(8.0))
+    .bind(number(&row.values, "minutes_late").map(|m| m as i32))
+    .bind(row.values.get("course_id"))
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn update_attendance(conn: &mut SqliteConnection, id: &str, values: &HashMap<String, String>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE attendance SET
+            status = ?, notes = COALESCE(?, notes), hours_attended = COALESCE(?, hours_attended),
+            hours_required = COALESCE(?, hours_required), minutes_late = COALESCE(?, minutes_late),
+            course_id = COALESCE(?, course_id), recorded_at = ?
+        WHERE id = ?"
+    )
+    .bind(values.get("status"))
+    .bind(values.get("notes"))
+    .bind(number(values, "hours_attended"))
+    .bind(number(values, "hours_required"))
+    .bind(number(values, "minutes_late").map(|m| m as i32))
+    .bind(values.get("course_id"))
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
@@ -1,6 +1,24 @@
+mod attendance_analytics;
+mod attendance_policy;
+mod cert_status;
+mod clearance;
 mod commands;
+mod comp_ledger;
+mod course_schedule;
 mod db;
+mod evaluation_forms;
+mod flag_rules;
+mod importer;
+mod makeup_ledger;
+mod matching;
 mod models;
+mod nclex_model;
+mod regulation_catalog;
+mod regulation_links;
+mod regulations;
+mod reminders;
+mod reports;
+mod vbon_evidence;
 mod vector_store;
 mod menu;
 mod tray;
@@ -21,6 +39,7 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::new().build())
+        .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
             // Vector Store
             commands::init_vector_store,
@@ -57,6 +76,12 @@ pub fn run() {
             commands::create_lesson_plan,
             commands::update_lesson_plan,
             commands::delete_lesson_plan,
+            commands::generate_semester_schedule,
+            commands::reschedule_course_session,
+            commands::get_course_schedule,
+            commands::get_academic_holidays,
+            commands::save_academic_holiday,
+            commands::delete_academic_holiday,
             // SQL Commands - Teaching Materials
             commands::add_teaching_material,
             commands::update_teaching_material,
@@ -65,9 +90,13 @@ pub fn run() {
             // SQL Commands - Attendance
             commands::record_attendance,
             commands::bulk_record_attendance,
+            commands::get_attendance_policies,
+            commands::update_attendance_policy,
+            commands::get_student_attendance_policy_status,
             commands::get_attendance_for_date,
             commands::get_student_attendance,
             commands::get_students_with_attendance_issues,
+            commands::get_attendance_analytics,
             commands::get_student_attendance_detail,
             // SQL Commands - Makeup Hours
             commands::add_makeup_hours,
@@ -75,6 +104,7 @@ pub fn run() {
             commands::get_student_makeup_hours,
             commands::get_all_makeup_hours_summaries,
             commands::delete_makeup_hours,
+            commands::get_makeup_allocations,
             commands::auto_create_makeup_hours,
             // SQL Commands - Certifications
             commands::add_certification,
@@ -82,12 +112,20 @@ pub fn run() {
             commands::delete_certification,
             commands::get_student_certifications,
             commands::get_expiring_certifications,
+            commands::get_certification_requirements,
+            commands::save_certification_requirement,
+            commands::delete_certification_requirement,
+            commands::get_clinical_clearance,
+            commands::get_site_clearance,
             // SQL Commands - Preceptor Evaluations
             commands::add_preceptor_evaluation,
             commands::update_preceptor_evaluation,
             commands::get_pending_evaluations,
             commands::get_student_evaluations,
             commands::review_evaluation,
+            commands::get_preceptor_evaluations,
+            commands::get_preceptor_evaluation_stats,
+            commands::get_all_preceptor_evaluation_stats,
             // SQL Commands - Deadlines
             commands::add_deadline,
             commands::update_deadline,
@@ -103,6 +141,34 @@ pub fn run() {
             commands::get_student_simulation_summary,
             // SQL Commands - Student Flags
             commands::get_student_flags,
+            commands::get_cohort_flags,
+            commands::acknowledge_student_flag,
+            commands::get_student_flag_history,
+            commands::get_student_flag_timeline,
+            commands::create_remediation_plan,
+            commands::update_remediation_plan,
+            commands::close_remediation_plan,
+            commands::delete_remediation_plan,
+            commands::get_student_remediation_plans,
+            commands::get_active_remediation_plans,
+            commands::add_remediation_activity,
+            commands::update_remediation_activity,
+            commands::delete_remediation_activity,
+            commands::add_remediation_checkpoint,
+            commands::delete_remediation_checkpoint,
+            commands::record_nclex_outcome,
+            commands::get_nclex_outcomes,
+            commands::delete_nclex_outcome,
+            commands::get_nclex_model,
+            commands::train_nclex_model,
+            commands::predict_nclex_readiness,
+            commands::predict_cohort_nclex_readiness,
+            commands::get_flag_rules,
+            commands::get_flag_rule_versions,
+            commands::save_flag_rules,
+            commands::activate_flag_rule_version,
+            commands::snapshot_student_flags,
+            commands::preview_flag_rules,
             // SQL Commands - Clinical Sites
             commands::add_clinical_site,
             commands::get_all_clinical_sites,
@@ -160,24 +226,46 @@ pub fn run() {
             commands::delete_comp_hours_used,
             commands::get_comp_hours_summary,
             commands::get_comp_hours_expiration_warnings,
+            commands::get_comp_hours_ledger,
+            commands::approve_comp_hours_earned,
+            commands::get_comp_hours_policy,
+            commands::update_comp_hours_policy,
+            commands::get_comp_timesheet,
+            commands::export_comp_timesheet,
             // SQL Commands - Courses
             commands::add_course,
             commands::get_all_courses,
             commands::get_course_by_id,
             commands::update_course,
             commands::delete_course,
+            commands::clone_course,
             commands::get_lesson_plans_by_course,
             commands::get_lesson_plans_by_week,
             commands::get_lesson_plan_with_materials,
             // SQL Commands - VBON Compliance
+            commands::get_regulatory_frameworks,
+            commands::save_regulatory_framework,
+            commands::delete_regulatory_framework,
+            commands::set_current_regulatory_framework,
             commands::get_all_vbon_regulations,
             commands::add_vbon_regulation,
             commands::seed_vbon_regulations,
             commands::get_vbon_regulations_with_mappings,
+            commands::get_lesson_plan_regulations,
+            commands::set_lesson_plan_regulations,
+            commands::set_regulation_lesson_plans,
             commands::get_vbon_mapping,
             commands::upsert_vbon_mapping,
             commands::get_vbon_compliance_summary,
+            commands::get_compliance_by_framework,
+            commands::get_vbon_evidence_report,
+            commands::export_vbon_evidence_package,
             commands::delete_vbon_mapping,
+            commands::get_regulation_catalog_editions,
+            commands::import_regulation_catalog,
+            commands::diff_regulation_catalog_editions,
+            commands::apply_regulation_catalog_edition,
+            commands::delete_regulation_catalog_edition,
             // File Management Commands
             commands::save_file,
             commands::delete_file,
@@ -185,6 +273,23 @@ pub fn run() {
             commands::get_file_as_base64,
             // Bulk Import Commands
             commands::import_preceptor_evaluations,
+            commands::get_import_fields,
+            commands::inspect_spreadsheet,
+            commands::preview_spreadsheet_import,
+            commands::commit_spreadsheet_import,
+            commands::suggest_evaluation_form_mapping,
+            commands::import_evaluation_form_csv,
+            commands::get_evaluation_import_queue,
+            commands::resolve_evaluation_import,
+            commands::dismiss_evaluation_import,
+            commands::export_report,
+            // Reminders
+            commands::get_pending_reminders,
+            commands::check_reminders_now,
+            commands::snooze_reminder,
+            commands::get_reminder_settings,
+            commands::update_reminder_setting,
+            commands::refresh_tray,
         ])
         .on_menu_event(|app, event| {
             event_handlers::handle_menu_event(app, event.id().as_ref());
@@ -240,6 +345,18 @@ pub fn run() {
                 handle.manage(db::DbState { db: pool });
             });
 
+            // Keep certification statuses and renewal deadlines current while the app runs
+            cert_status::spawn_daily_sweep(handle.clone());
+
+            // Keep the student flag history current as flags change with the date
+            flag_rules::spawn_daily_snapshot(handle.clone());
+
+            // Native notifications for expiring items and upcoming deadlines
+            reminders::spawn_scheduler(handle.clone());
+
+            // Keep tray counts, tooltip and icon in step with pending work
+            tray::spawn_refresh(handle.clone());
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,185 @@
+// Fulfilment of makeup_hours obligations from approved makeup clinical logs.
+// Hours from each approved `is_makeup` log are allocated FIFO against the student's oldest open
+// obligations and recorded in `makeup_allocations`, so an obligation's completed hours can be
+// traced back to the logs that paid them off. Completed hours are only ever changed here.
+
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::models::{ClinicalLog, MakeupHours};
+
+// Tolerance when comparing hour amounts
+const HOURS_EPSILON: f64 = 0.01;
+
+fn status_for(hours_owed: f64, hours_completed: f64) -> &'static str {
+    if hours_completed + HOURS_EPSILON >= hours_owed {
+        "completed"
+    } else if hours_completed > HOURS_EPSILON {
+        "in_progress"
+    } else {
+        "pending"
+    }
+}
+
+/// Adds `delta` hours to an obligation and moves its status along with them.
+/// `completion_date` is the date the obligation was paid off, if this change completes it.
+async fn adjust_completed(
+    conn: &mut SqliteConnection,
+    makeup: &MakeupHours,
+    delta: f64,
+    completion_date: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let hours_completed = (makeup.hours_completed + delta).max(0.0);
+    let status = if makeup.status == "cancelled" {
+        "cancelled"
+    } else {
+        status_for(makeup.hours_owed, hours_completed)
+    };
+    let completion_date = match status {
+        "completed" => makeup.completion_date.clone().or(completion_date.map(str::to_string)),
+        _ => None,
+    };
+
+    sqlx::query(
+        "UPDATE makeup_hours SET hours_completed = ?, status = ?, completion_date = ?, updated_at = ? WHERE id = ?"
+    )
+    .bind(hours_completed)
+    .bind(status)
+    .bind(&completion_date)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(&makeup.id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn release(conn: &mut SqliteConnection, column: &str, id: &str) -> Result<(), sqlx::Error> {
+    let allocations: Vec<(String, String, f64)> = sqlx::query_as(&format!(
+        "SELECT id, makeup_hours_id, hours FROM makeup_allocations WHERE {} = ?",
+        column
+    ))
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (allocation_id, makeup_id, hours) in allocations {
+        let makeup = sqlx::query_as::<_, MakeupHours>("SELECT * FROM makeup_hours WHERE id = ?")
+            .bind(&makeup_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        if let Some(makeup) = makeup {
+            adjust_completed(conn, &makeup, -hours, None).await?;
+        }
+        sqlx::query("DELETE FROM makeup_allocations WHERE id = ?")
+            .bind(&allocation_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Takes back every hour a log allocated, e.g. before it is edited, unapproved or deleted.
+pub async fn release_log(conn: &mut SqliteConnection, log_id: &str) -> Result<(), sqlx::Error> {
+    release(conn, "clinical_log_id", log_id).await
+}
+
+/// Takes back every hour allocated to an obligation, e.g. when it is resized or cancelled.
+pub async fn release_obligation(conn: &mut SqliteConnection, makeup_id: &str) -> Result<(), sqlx::Error> {
+    release(conn, "makeup_hours_id", makeup_id).await
+}
+
+/// Cancels an obligation, handing its allocated hours back to their logs, or reopens a cancelled
+/// one. Call `sync_student` afterwards so the freed hours or reopened obligation are allocated.
+pub async fn set_cancelled(conn: &mut SqliteConnection, makeup_id: &str, cancelled: bool) -> Result<(), sqlx::Error> {
+    if cancelled {
+        release_obligation(conn, makeup_id).await?;
+    }
+    let Some(makeup) = sqlx::query_as::<_, MakeupHours>("SELECT * FROM makeup_hours WHERE id = ?")
+        .bind(makeup_id)
+        .fetch_optional(&mut *conn)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let status = if cancelled { "cancelled" } else { status_for(makeup.hours_owed, makeup.hours_completed) };
+    let completion_date = if status == "completed" { makeup.completion_date } else { None };
+    sqlx::query("UPDATE makeup_hours SET status = ?, completion_date = ?, updated_at = ? WHERE id = ?")
+        .bind(status)
+        .bind(&completion_date)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(makeup_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Allocates the unallocated hours of the student's approved makeup logs, oldest log first,
+/// to their open obligations, oldest obligation first.
+pub async fn allocate_student(conn: &mut SqliteConnection, student_id: &str) -> Result<(), sqlx::Error> {
+    let logs = sqlx::query_as::<_, ClinicalLog>(
+        "SELECT * FROM clinical_logs
+        WHERE student_id = ? AND status = 'Approved' AND COALESCE(is_makeup, 0) = 1
+        ORDER BY date, id"
+    )
+    .bind(student_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for log in logs {
+        let allocated: (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(hours), 0.0) FROM makeup_allocations WHERE clinical_log_id = ?"
+        )
+        .bind(&log.id)
+        .fetch_one(&mut *conn)
+        .await?;
+        let mut remaining = log.hours.unwrap_or(8.0) - allocated.0;
+
+        while remaining > HOURS_EPSILON {
+            let Some(makeup) = sqlx::query_as::<_, MakeupHours>(
+                "SELECT * FROM makeup_hours
+                WHERE student_id = ? AND status IN ('pending', 'in_progress') AND hours_completed < hours_owed
+                ORDER BY created_at, id LIMIT 1"
+            )
+            .bind(student_id)
+            .fetch_optional(&mut *conn)
+            .await?
+            else {
+                return Ok(());
+            };
+
+            let hours = remaining.min(makeup.hours_owed - makeup.hours_completed);
+            sqlx::query(
+                "INSERT INTO makeup_allocations (id, makeup_hours_id, clinical_log_id, hours, allocated_at)
+                VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(format!("MKA-{}", uuid::Uuid::new_v4()))
+            .bind(&makeup.id)
+            .bind(&log.id)
+            .bind(hours)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+            adjust_completed(conn, &makeup, hours, Some(&log.date)).await?;
+            remaining -= hours;
+        }
+    }
+    Ok(())
+}
+
+/// Re-allocates a log after it was added, edited or approved. Pass the log's student before and
+/// after the change so hours move with the log if it was reassigned.
+pub async fn sync_log(pool: &Pool<Sqlite>, log_id: &str, student_ids: &[&str]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    release_log(&mut *tx, log_id).await?;
+    for student_id in student_ids {
+        allocate_student(&mut *tx, student_id).await?;
+    }
+    tx.commit().await
+}
+
+/// Re-allocates a student's makeup logs after their obligations changed.
+pub async fn sync_student(pool: &Pool<Sqlite>, student_id: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    allocate_student(&mut *tx, student_id).await?;
+    tx.commit().await
+}
@@ -0,0 +1,98 @@
+// Fuzzy name matching used to link free-text names (site names, preceptor names, etc.)
+// to managed records.
+
+use std::collections::HashMap;
+
+// Minimum similarity score for a candidate to be considered a match
+pub const DEFAULT_MATCH_THRESHOLD: f64 = 0.75;
+
+/// Lowercases, strips punctuation and collapses whitespace.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bigrams(s: &str) -> HashMap<(char, char), i32> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut grams = HashMap::new();
+    for pair in chars.windows(2) {
+        *grams.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+    grams
+}
+
+/// Similarity between two names in the range 0.0..=1.0 (Sorensen-Dice over character bigrams).
+/// Exact normalized matches score 1.0 and one name containing the other scores at least 0.9.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize_name(a);
+    let b = normalize_name(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    let a_grams = bigrams(&a);
+    let b_grams = bigrams(&b);
+    let a_total: i32 = a_grams.values().sum();
+    let b_total: i32 = b_grams.values().sum();
+
+    let dice = if a_total + b_total == 0 {
+        0.0
+    } else {
+        let shared: i32 = a_grams
+            .iter()
+            .map(|(gram, count)| (*count).min(*b_grams.get(gram).unwrap_or(&0)))
+            .sum();
+        (2.0 * shared as f64) / (a_total + b_total) as f64
+    };
+
+    // "Riverside" vs "Riverside Regional Medical Center"
+    let contains = format!(" {} ", a).contains(&format!(" {} ", b))
+        || format!(" {} ", b).contains(&format!(" {} ", a));
+
+    if contains {
+        dice.max(0.9)
+    } else {
+        dice
+    }
+}
+
+/// Finds the best matching candidate `(id, name)` for `needle`.
+/// Returns `None` when nothing reaches `threshold` or when two candidates tie for the best score.
+pub fn best_match<'a>(needle: &str, candidates: &'a [(String, String)], threshold: f64) -> Option<&'a str> {
+    let mut best: Option<(&'a str, f64)> = None;
+    let mut tied = false;
+
+    for (id, name) in candidates {
+        let score = name_similarity(needle, name);
+        if score < threshold {
+            continue;
+        }
+        match best {
+            Some((best_id, best_score)) if (score - best_score).abs() < f64::EPSILON => {
+                // Two records with the same name (e.g. duplicate entries) point to the same match only if ids agree
+                if best_id != id.as_str() {
+                    tied = true;
+                }
+            }
+            Some((_, best_score)) if score < best_score => {}
+            _ => {
+                best = Some((id.as_str(), score));
+                tied = false;
+            }
+        }
+    }
+
+    if tied {
+        None
+    } else {
+        best.map(|(id, _)| id)
+    }
+}
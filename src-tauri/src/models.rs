@@ -54,6 +54,7 @@ pub struct ClinicalLog {
     pub hours: Option<f64>,
     pub is_simulation: Option<i32>,
     pub is_makeup: Option<i32>,
+    pub site_id: Option<String>, // Linked clinical_sites record (resolved from site_name when not set)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
@@ -68,6 +69,8 @@ pub struct CalendarEvent {
     pub proctor: Option<String>,
     pub status: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub lesson_plan_id: Option<String>, // Class session this event was generated for
 }
 
 // Course for curriculum organization
@@ -129,6 +132,146 @@ pub struct TeachingMaterial {
     pub sort_order: i32,
 }
 
+// ==================== SEMESTER SCHEDULING ====================
+
+/// A day without classes (holiday, break or closure) that generated schedules skip.
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct AcademicHoliday {
+    #[serde(default)]
+    pub id: String,
+    pub date: String,
+    pub name: String,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+/// The meeting pattern a course's lesson plans were generated with, reused when sessions are rescheduled.
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseSchedule {
+    pub course_id: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub meeting_days: String, // Comma-separated weekdays, e.g. "Mon,Wed"
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One class session of a course outline.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseOutlineEntry {
+    pub week_number: i32,
+    #[serde(default)]
+    pub chapter: Option<String>,
+    pub topic: String,
+    #[serde(default)]
+    pub topics_covered: Option<String>,
+    #[serde(default)]
+    pub assessment_method: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SemesterScheduleRequest {
+    pub course_id: String,
+    pub start_date: String,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    pub meeting_days: Vec<String>, // "Mon", "Tuesday", ...
+    pub outline: Vec<CourseOutlineEntry>,
+    #[serde(default)]
+    pub holidays: Vec<AcademicHoliday>, // Extra non-teaching days on top of the saved holiday calendar
+    #[serde(default)]
+    pub create_calendar_events: bool,
+    #[serde(default)]
+    pub replace_existing: bool, // Delete the course's lesson plans from the start date on first
+    #[serde(default)]
+    pub preview: bool, // Compute the schedule without saving anything
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledSession {
+    pub lesson_plan_id: String,
+    pub date: String,
+    pub week_number: i32,
+    pub chapter: Option<String>,
+    pub topic: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedDate {
+    pub date: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SemesterScheduleResult {
+    pub sessions: Vec<ScheduledSession>,
+    pub skipped_dates: Vec<SkippedDate>,
+    pub replaced_count: i32,
+    pub events_created: i32,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleRequest {
+    pub course_id: String,
+    pub date: String, // The cancelled class day
+    #[serde(default)]
+    pub reason: Option<String>, // e.g. "Snow day"
+    #[serde(default)]
+    pub record_closure: bool, // Save the date to the holiday calendar so later schedules skip it too
+    #[serde(default)]
+    pub preview: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMove {
+    pub lesson_plan_id: String,
+    pub topic: String,
+    pub from_date: String,
+    pub to_date: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleResult {
+    pub moves: Vec<SessionMove>,
+    pub events_updated: i32,
+    pub warnings: Vec<String>,
+}
+
+/// Copy a course with its lesson plans, materials and regulation links into a new term.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCloneRequest {
+    pub course_id: String,
+    pub semester: String,
+    pub year: i32,
+    pub start_date: String, // First day of the new term; week 1 is the week it falls in
+    #[serde(default)]
+    pub code: Option<String>, // Defaults to the source course's code
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCloneResult {
+    pub course: Course,
+    pub lesson_plan_count: i32,
+    pub material_count: i32,
+    pub regulation_link_count: i32,
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Attendance {
@@ -141,6 +284,10 @@ pub struct Attendance {
     pub attendance_type: Option<String>,
     pub hours_attended: Option<f64>,
     pub hours_required: Option<f64>,
+    #[serde(default)]
+    pub minutes_late: Option<i32>, // Tardy sessions
+    #[serde(default)]
+    pub course_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
@@ -154,12 +301,24 @@ pub struct MakeupHours {
     pub reason: Option<String>,
     pub due_date: Option<String>,
     pub completion_date: Option<String>,
-    pub status: String,
+    pub status: String, // "pending" | "in_progress" | "completed" | "cancelled"
     pub notes: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MakeupAllocation {
+    pub id: String,
+    pub makeup_hours_id: String,
+    pub clinical_log_id: String,
+    pub hours: f64,
+    pub allocated_at: String,
+    pub log_date: String, // Joined from clinical_logs
+    pub site_name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MakeupHoursSummary {
@@ -185,6 +344,42 @@ pub struct StudentCertification {
     pub notes: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificationRequirement {
+    pub id: String,
+    pub name: String, // e.g. "TB screening"
+    pub certification_type: String, // Matches student_certifications.certification_type
+    pub name_keyword: Option<String>, // Case-insensitive match on certification_name, e.g. "TB" among Immunizations
+    pub cohort: Option<String>, // None = every cohort
+    pub site_id: Option<String>, // None = every site
+    pub validity_months: Option<i32>, // Counted from issue_date; None = valid until expiry_date
+    pub is_active: i32,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequirementClearance {
+    pub requirement_id: String,
+    pub requirement_name: String,
+    pub status: String, // "met" | "missing" | "expired" | "pending"
+    pub certification_id: Option<String>,
+    pub valid_until: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClinicalClearance {
+    pub student_id: String,
+    pub student_name: String,
+    pub site_id: String,
+    pub date: String,
+    pub cleared: bool,
+    pub requirements: Vec<RequirementClearance>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct PreceptorEvaluation {
@@ -202,6 +397,42 @@ pub struct PreceptorEvaluation {
     pub strengths: Option<String>,
     pub status: String,
     pub submitted_at: String,
+    pub preceptor_id: Option<String>, // Linked preceptors record (resolved from preceptor_name when not set)
+    pub response_timestamp: Option<String>, // Google Forms response timestamp
+    #[serde(default)]
+    pub response_key: Option<String>, // Timestamp plus respondent, used to skip re-imported responses
+}
+
+// A single parsed Google Forms evaluation response
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluationFormResponse {
+    pub response_timestamp: String,
+    pub student_name: Option<String>,
+    pub student_email: Option<String>,
+    pub preceptor_name: Option<String>,
+    pub evaluation_date: String,
+    pub overall_rating: Option<i32>,
+    pub clinical_skills_rating: Option<i32>,
+    pub professionalism_rating: Option<i32>,
+    pub communication_rating: Option<i32>,
+    pub comments: Option<String>,
+    pub areas_for_improvement: Option<String>,
+    pub strengths: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluationImportQueueItem {
+    pub id: String,
+    pub response_timestamp: String,
+    pub response_key: Option<String>,
+    pub response: Json<EvaluationFormResponse>,
+    pub reason: String, // Why the response couldn't be imported automatically
+    pub status: String, // "pending" | "resolved" | "dismissed"
+    pub resolved_evaluation_id: Option<String>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
@@ -220,6 +451,35 @@ pub struct Deadline {
 
 // Combined response types for dashboard
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendancePolicy {
+    pub attendance_type: String, // "classroom" | "clinical" | "lab"
+    pub tardies_per_absence: Option<i32>, // None = tardies never convert to absences
+    pub absence_hours_threshold: Option<f64>, // Missing at least this many hours counts as an absence
+    pub max_absences: Option<i32>,
+    pub makeup_required: i32, // Missed hours create makeup_hours obligations
+    pub excused_requires_makeup: i32,
+    pub makeup_due_days: i32,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendancePolicyStatus {
+    pub attendance_type: String,
+    pub sessions: i32,
+    pub absences: i32,
+    pub excused: i32,
+    pub tardies: i32,
+    pub hours_based_absences: i32, // Partial attendance past the hours threshold
+    pub converted_tardy_absences: i32,
+    pub effective_absences: i32, // What the policy counts against max_absences
+    pub max_absences: Option<i32>,
+    pub hours_missed: f64,
+    pub over_limit: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AttendanceSummary {
@@ -228,6 +488,73 @@ pub struct AttendanceSummary {
     pub total_absences: i32,
     pub total_tardies: i32,
     pub total_present: i32,
+    pub classroom_absences: i32,
+    pub classroom_tardies: i32,
+    pub clinical_absences: i32,
+    pub clinical_tardies: i32,
+    pub hours_required: f64,
+    pub hours_missed: f64,
+    pub attendance_percent: f64, // Hours attended / hours required
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceAnalyticsFilters {
+    pub student_id: Option<String>,
+    pub cohort: Option<String>,
+    pub course_id: Option<String>,
+    pub attendance_type: Option<String>,
+    pub start_date: Option<String>, // Inclusive, YYYY-MM-DD
+    pub end_date: Option<String>,
+    pub trend_period: Option<String>, // "week" (default) | "month"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceBreakdown {
+    pub attendance_type: String, // "all" for totals across types
+    pub sessions: i32,
+    pub present: i32,
+    pub absent: i32,
+    pub tardy: i32,
+    pub excused: i32,
+    pub partial: i32,
+    pub hours_required: f64,
+    pub hours_attended: f64,
+    pub hours_missed: f64,
+    pub attendance_percent: f64,
+    pub tardy_minutes: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceTrendPoint {
+    pub period: String, // Week start date or YYYY-MM
+    pub sessions: i32,
+    pub hours_required: f64,
+    pub hours_missed: f64,
+    pub attendance_percent: f64,
+    pub absences: i32,
+    pub tardy_minutes: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAttendanceAnalytics {
+    pub student_id: String,
+    pub student_name: String,
+    pub total: AttendanceBreakdown,
+    pub by_type: Vec<AttendanceBreakdown>,
+    pub trend: Vec<AttendanceTrendPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceAnalytics {
+    pub total: AttendanceBreakdown,
+    pub by_type: Vec<AttendanceBreakdown>,
+    pub trend: Vec<AttendanceTrendPoint>,
+    pub students: Vec<StudentAttendanceAnalytics>, // Lowest attendance percentage first
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -255,6 +582,7 @@ pub struct SkillValidation {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StudentHoursBySite {
+    pub site_id: Option<String>, // None for logs that couldn't be linked to a clinical site
     pub site_name: String,
     pub direct_hours: f64,
     pub sim_hours: f64,
@@ -271,13 +599,280 @@ pub struct StudentSimulationSummary {
     pub is_compliant: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct StudentFlag {
+    #[serde(default)]
+    pub rule_id: String, // Flag rule that raised it; with related_id, identifies the flag
     pub flag_type: String,
     pub message: String,
     pub severity: String,
     pub related_id: Option<String>,
+    #[serde(default)]
+    pub history_id: Option<String>, // student_flag_history record tracking this flag
+    #[serde(default)]
+    pub acknowledged_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentFlagRecord {
+    pub id: String,
+    pub student_id: String,
+    pub flag_type: String,
+    pub related_id: Option<String>,
+    pub severity: String, // Latest severity while open
+    pub message: String,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub resolved_at: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub acknowledged_by: Option<String>,
+    pub acknowledgement_note: Option<String>,
+    pub action_plan: Option<String>,
+    pub rule_id: Option<String>, // Missing on records opened before flags were keyed by rule
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagTimelineEvent {
+    pub date: String,
+    pub event: String, // "raised" | "acknowledged" | "resolved"
+    pub history_id: String,
+    pub flag_type: String,
+    pub severity: String,
+    pub message: String,
+    pub note: Option<String>,
+    pub action_plan: Option<String>,
+    pub acknowledged_by: Option<String>,
+}
+
+// ==================== FLAG RULES ====================
+
+/// Student measure a flag rule is evaluated against.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMetric {
+    ClinicalHoursPercent, // Completed / required hours, only while hours remain
+    SimulationPercent, // Share of approved clinical hours done in simulation
+    Gpa,
+    NclexPredictor,
+    RemediationStatus, // Compared as text; ignored once a student has remediation plans
+    Absences,
+    CertificationDaysUntilExpiry, // Evaluated once per certification
+    RemediationOverdueActivities, // Evaluated once per active remediation plan
+    AttendancePolicyExcess, // Counted absences minus the allowed maximum, per attendance type
+    MakeupHoursDaysOverdue, // Days past due_date, per open makeup obligation (negative = not yet due)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTier {
+    pub operator: String, // "lt" | "lte" | "gt" | "gte" | "eq"
+    pub value: serde_json::Value, // Number, or text for "eq"
+    pub severity: String, // "error" | "warning" | "info"
+    pub message: String, // Template with {value}, {threshold} and metric-specific placeholders
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagRule {
+    pub id: String,
+    pub flag_type: String,
+    pub metric: RuleMetric,
+    pub enabled: bool,
+    pub description: Option<String>,
+    pub tiers: Vec<RuleTier>, // Checked in order; the first matching tier raises the flag
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagRuleSet {
+    pub version: i32,
+    pub rules: Json<Vec<FlagRule>>,
+    pub notes: Option<String>,
+    pub is_active: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagRulePreview {
+    pub student_id: String,
+    pub student_name: String,
+    pub added: Vec<StudentFlag>,
+    pub removed: Vec<StudentFlag>,
+    pub changed: Vec<StudentFlag>, // Same flag under a different severity (shown with the new severity)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortFlagFilters {
+    pub flag_types: Option<Vec<String>>,
+    pub severities: Option<Vec<String>>,
+    pub student_status: Option<String>, // e.g. "Active"
+    pub limit: Option<i32>, // Max at-risk students returned
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagTypeCount {
+    pub flag_type: String,
+    pub errors: i32,
+    pub warnings: i32,
+    pub info: i32,
+    pub total: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagSeverityCounts {
+    pub error: i32,
+    pub warning: i32,
+    pub info: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AtRiskStudent {
+    pub student_id: String,
+    pub student_name: String,
+    pub status: String,
+    pub risk_score: i32, // Errors weigh 3, warnings 1
+    pub error_count: i32,
+    pub warning_count: i32,
+    pub flags: Vec<StudentFlag>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortFlagReport {
+    pub cohort: String,
+    pub student_count: i32,
+    pub flagged_student_count: i32,
+    pub by_type: Vec<FlagTypeCount>,
+    pub by_severity: FlagSeverityCounts,
+    pub at_risk: Vec<AtRiskStudent>, // Highest risk first
+}
+
+// ==================== REMEDIATION PLANS ====================
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationPlan {
+    pub id: String,
+    pub student_id: String,
+    pub topic: String,
+    pub reason: Option<String>,
+    pub status: String, // "active" | "completed" | "cancelled"
+    pub start_date: String,
+    pub target_date: Option<String>,
+    pub triggering_grade_ids: Json<Vec<String>>,
+    pub triggering_evaluation_ids: Json<Vec<String>>,
+    pub outcome: Option<String>, // "successful" | "unsuccessful" | "withdrawn"
+    pub outcome_notes: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub review_deadline_id: Option<String>, // Deadline created for target_date
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationActivity {
+    pub id: String,
+    pub plan_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub due_date: Option<String>,
+    pub status: String, // "pending" | "completed"
+    pub completed_date: Option<String>,
+    pub deadline_id: Option<String>, // Deadline created for due_date
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationCheckpoint {
+    pub id: String,
+    pub plan_id: String,
+    pub checkpoint_date: String,
+    pub progress: String, // "on_track" | "at_risk" | "off_track"
+    pub score: Option<f64>,
+    pub notes: Option<String>,
+    pub recorded_by: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationPlanWithDetails {
+    #[serde(flatten)]
+    pub plan: RemediationPlan,
+    pub student_name: String,
+    pub activities: Vec<RemediationActivity>,
+    pub checkpoints: Vec<RemediationCheckpoint>,
+    pub triggering_grades: Vec<Grade>,
+    pub triggering_evaluations: Vec<PreceptorEvaluation>,
+}
+
+// ==================== NCLEX READINESS ====================
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct NclexOutcome {
+    pub id: String,
+    pub student_id: String,
+    pub exam_date: String,
+    pub attempt_number: i32,
+    pub passed: i32,
+    pub notes: Option<String>,
+    pub recorded_at: String,
+}
+
+/// Standardized logistic regression over the features in `nclex_model::FEATURES`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NclexModel {
+    pub features: Vec<String>,
+    pub means: Vec<f64>, // Also used in place of missing values
+    pub std_devs: Vec<f64>,
+    pub weights: Vec<f64>,
+    pub intercept: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct NclexModelInfo {
+    pub id: String,
+    pub trained_at: String,
+    pub sample_count: i32,
+    pub pass_count: i32,
+    pub training_accuracy: f64,
+    pub model: Json<NclexModel>,
+    pub is_active: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictionFactor {
+    pub feature: String,
+    pub label: String,
+    pub value: Option<f64>, // None when the student has no data (the cohort average was used)
+    pub contribution: f64, // Change in log-odds relative to an average student
+    pub direction: String, // "raises" | "lowers"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NclexPrediction {
+    pub student_id: String,
+    pub student_name: String,
+    pub probability: f64, // 0.0..=1.0
+    pub top_factors: Vec<PredictionFactor>,
+    pub model_id: String,
+    pub model_trained_at: String,
 }
 
 // ==================== CLINICAL TRACKING SYSTEM ====================
@@ -352,6 +947,22 @@ pub struct PreceptorVerificationAlert {
     pub alert_level: String, // "warning" (30-60 days) | "critical" (< 30 days) | "overdue" (< 0 days)
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PreceptorEvaluationStats {
+    pub preceptor_id: String,
+    pub preceptor_name: String,
+    pub site_name: Option<String>,
+    pub evaluation_count: i32,
+    pub students_evaluated: i32,
+    pub avg_overall_rating: Option<f64>,
+    pub avg_clinical_skills_rating: Option<f64>,
+    pub avg_professionalism_rating: Option<f64>,
+    pub avg_communication_rating: Option<f64>,
+    pub first_evaluation_date: Option<String>,
+    pub last_evaluation_date: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct ClinicalAssignment {
@@ -443,6 +1054,7 @@ pub struct StudentHourSubmission {
     pub reviewed_at: Option<String>,
     pub reviewed_by: Option<String>,
     pub submitted_at: String,
+    pub site_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -491,6 +1103,10 @@ pub struct CompHoursEarned {
     pub notes: Option<String>,
     pub expiration_date: Option<String>, // When these hours expire (e.g., end of fiscal year)
     pub created_at: String,
+    #[serde(default)]
+    pub approval_status: Option<String>, // "approved" | "pending"; set from the policy when hours are earned
+    #[serde(default)]
+    pub approved_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
@@ -515,6 +1131,8 @@ pub struct CompHoursSummary {
     pub expiring_soon: f64, // Hours expiring within 6 months
     pub expiring_date: Option<String>, // Nearest expiration date
     pub days_until_expiry: Option<i32>,
+    pub forfeited: f64,
+    pub unallocated_used: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -526,12 +1144,111 @@ pub struct CompHoursExpirationWarning {
     pub alert_level: String, // "green" (> 6 months) | "yellow" (3-6 months) | "red" (< 3 months)
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CompHoursAllocation {
+    pub id: String,
+    pub used_id: String,
+    pub earned_id: String,
+    pub hours: f64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompHoursLot {
+    #[serde(flatten)]
+    pub earned: CompHoursEarned,
+    pub consumed: f64,
+    pub forfeited: f64, // Unused hours past the expiration date
+    pub remaining: f64,
+    pub status: String, // "available" | "consumed" | "forfeited"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompHoursLedger {
+    pub lots: Vec<CompHoursLot>, // Oldest first
+    pub allocations: Vec<CompHoursAllocation>,
+    pub available: f64,
+    pub forfeited: f64,
+    pub unallocated_used: f64, // Hours used with no unexpired earned hours to draw from
+    pub forfeiture_schedule: Vec<CompHoursExpirationWarning>, // Remaining hours by upcoming expiration date
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CompHoursPolicy {
+    pub accrual_cap: Option<f64>, // Most hours that can be banked (available plus pending approval)
+    pub expiration_months: Option<i32>, // Earned hours expire this many months after the date earned
+    pub requires_approval: bool,
+    pub warning_days_red: i32,
+    pub warning_days_yellow: i32,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompTimesheetEntry {
+    pub date: String,
+    pub kind: String, // "Earned" | "Used" | "Forfeited"
+    pub description: String,
+    pub hours: f64,
+    pub expiration_date: Option<String>,
+    pub status: String, // "approved" | "pending"
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompTimesheet {
+    pub start_date: String,
+    pub end_date: String,
+    pub opening_balance: f64,
+    pub earned: f64, // Approved hours only
+    pub used: f64,
+    pub forfeited: f64,
+    pub closing_balance: f64,
+    pub pending_approval: f64,
+    pub entries: Vec<CompTimesheetEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompTimesheetRequest {
+    pub start_date: String,
+    pub end_date: String,
+    pub format: String, // "csv" | "pdf"
+    pub output_path: String,
+    pub employee_name: String,
+    pub branding: ReportBranding,
+}
+
 // ==================== VBON COMPLIANCE MAPPING ====================
 
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulatoryFramework {
+    pub id: String, // e.g., "vbon", "ncbon", "acen"
+    pub code: String, // Short label, e.g., "VBON"
+    pub name: String,
+    pub framework_type: String, // "state_board" | "accreditor"
+    pub jurisdiction: Option<String>, // Two-letter state for state boards
+    pub description: Option<String>,
+    pub is_current: bool,
+    pub created_at: String,
+}
+
+fn default_framework_id() -> String {
+    crate::regulations::DEFAULT_FRAMEWORK_ID.to_string()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct VBONRegulation {
     pub id: String,
+    #[serde(default = "default_framework_id")]
+    pub framework_id: String,
     pub code: String, // e.g., "B.1", "B.2", "B.6"
     pub section: String, // e.g., "18VAC90-27-100"
     pub category: String, // e.g., "Curriculum Content", "Clinical Requirements"
@@ -539,6 +1256,8 @@ pub struct VBONRegulation {
     pub description: String, // Full description of the requirement
     pub sort_order: i32,
     pub is_active: i32,
+    #[serde(default)]
+    pub edition_id: Option<String>, // Catalog edition the wording comes from; None for the built-in catalog
     pub created_at: String,
     pub updated_at: String,
 }
@@ -557,6 +1276,12 @@ pub struct VBONMapping {
     pub coverage_status: String, // "not_covered" | "partial" | "covered"
     pub last_reviewed_date: Option<String>,
     pub reviewed_by: Option<String>,
+    #[serde(default)]
+    pub reviewed_edition_id: Option<String>, // Catalog edition the regulation was at when last reviewed
+    #[serde(default)]
+    pub needs_review: bool, // Set when a new edition changes or removes the regulation
+    #[serde(default)]
+    pub review_reason: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -567,6 +1292,8 @@ pub struct VBONRegulationWithMapping {
     #[serde(flatten)]
     pub regulation: VBONRegulation,
     pub mapping: Option<VBONMapping>,
+    pub lesson_plans: Vec<EvidenceLessonPlan>, // Linked lesson plans, oldest first
+    pub taught_dates: Vec<String>, // Dates of the linked plans already taught
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -582,11 +1309,277 @@ pub struct VBONCategoryStats {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VBONComplianceSummary {
+    pub framework_id: String,
     pub total_regulations: i32,
     pub covered_count: i32,
     pub partial_count: i32,
     pub not_covered_count: i32,
     pub percentage_complete: f64,
     pub last_audit_date: Option<String>,
+    pub needs_review_count: i32, // Mappings flagged by a catalog change
     pub regulations_by_category: Vec<VBONCategoryStats>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameworkComplianceSummary {
+    pub framework: RegulatoryFramework,
+    pub summary: VBONComplianceSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationCatalogEdition {
+    pub id: String,
+    pub framework_id: String,
+    pub edition: String, // Label, e.g., "2024" or "Rev. 3"
+    pub effective_date: Option<String>,
+    pub source_file: Option<String>,
+    pub notes: Option<String>,
+    pub regulation_count: i32,
+    pub is_current: bool, // Applied to the live catalog
+    pub imported_at: String,
+}
+
+/// One regulation as worded in a catalog edition.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationSnapshot {
+    #[serde(default)]
+    pub id: String, // Generated from the framework and code when an imported file leaves it out
+    pub code: String,
+    #[serde(default)]
+    pub section: String,
+    #[serde(default)]
+    pub category: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationChange {
+    pub before: RegulationSnapshot,
+    pub after: RegulationSnapshot,
+    pub changed_fields: Vec<String>, // "code", "section", "category", "title", "description"
+    pub has_mapping: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationCatalogDiff {
+    pub framework_id: String,
+    pub from_edition_id: Option<String>, // None compares against the live catalog
+    pub to_edition_id: String,
+    pub added: Vec<RegulationSnapshot>,
+    pub removed: Vec<RegulationSnapshot>,
+    pub changed: Vec<RegulationChange>,
+    pub unchanged_count: i32,
+    pub affected_mapping_count: i32, // Mappings of changed or removed regulations
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationCatalogImportRequest {
+    pub framework_id: Option<String>, // Defaults to the current framework
+    pub file_path: String, // JSON or CSV (spreadsheets work too); just the file name when `content` is given
+    pub content: Option<String>, // Text of a file picked in the UI; otherwise `file_path` is read
+    pub edition: Option<String>, // Required unless the JSON file names its edition
+    pub effective_date: Option<String>,
+    pub notes: Option<String>,
+    pub apply: bool, // Otherwise the edition is stored for review and applied later
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationCatalogImportResult {
+    pub edition: RegulationCatalogEdition,
+    pub diff: RegulationCatalogDiff,
+    pub mappings_flagged: i32, // Zero unless the edition was applied
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceLessonPlan {
+    pub id: String,
+    pub date: String,
+    pub course_name: String,
+    pub week_number: Option<i32>,
+    pub chapter: Option<String>,
+    pub topic: String,
+    pub assessment_method: Option<String>,
+    pub taught: bool, // Dated on or before the report date
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceMaterial {
+    pub title: String,
+    pub url: Option<String>,
+    pub material_type: String, // Teaching material type, or "mapping" for links entered on the mapping
+    pub lesson_plan_id: Option<String>,
+    pub packaged_file: Option<String>, // Path inside the exported package when the file was copied
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegulationEvidence {
+    #[serde(flatten)]
+    pub regulation: VBONRegulation,
+    pub coverage_status: String,
+    pub syllabus_references: Vec<String>, // Mapping reference plus syllabi of the linked courses
+    pub lesson_plans: Vec<EvidenceLessonPlan>,
+    pub materials: Vec<EvidenceMaterial>,
+    pub assessment_methods: Vec<String>,
+    pub clinical_experience: Option<String>,
+    pub last_reviewed_date: Option<String>,
+    pub days_since_review: Option<i64>,
+    pub is_stale: bool, // Never reviewed, or reviewed longer ago than the review interval
+    pub gaps: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VBONEvidenceReport {
+    pub framework_id: String,
+    pub framework_code: String,
+    pub framework_name: String,
+    pub generated_on: String,
+    pub review_interval_days: i64,
+    pub regulations: Vec<RegulationEvidence>,
+    pub gap_count: i32, // Regulations with at least one gap
+    pub stale_count: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VBONEvidenceExportRequest {
+    pub framework_id: Option<String>, // Defaults to the current framework
+    pub output_path: String, // Folder to create, or the .zip file when `zip` is set
+    pub review_interval_days: Option<i64>,
+    pub zip: bool,
+    pub branding: ReportBranding,
+}
+
+// ==================== SPREADSHEET IMPORT ====================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportTarget {
+    Students,
+    Grades,
+    Attendance,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportMapping {
+    pub target: ImportTarget,
+    pub columns: std::collections::HashMap<String, String>, // target field (e.g. "first_name") -> spreadsheet column header
+    pub defaults: Option<std::collections::HashMap<String, String>>, // Constant values for unmapped fields (e.g. cohort, semester)
+    pub sheet_name: Option<String>, // XLSX only; defaults to the first sheet
+    pub date_format: Option<String>, // chrono format tried first when parsing dates, e.g. "%d/%m/%Y"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportField {
+    pub name: String, // Key used in ImportMapping.columns, e.g. "first_name"
+    pub required: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadsheetInfo {
+    pub sheet_names: Vec<String>,
+    pub headers: Vec<String>,
+    pub row_count: i32,
+    pub sample_rows: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowPreview {
+    pub row_number: i32, // Spreadsheet row number (header is row 1)
+    pub values: std::collections::HashMap<String, String>, // Coerced values keyed by target field
+    pub student_id: Option<String>, // Matched student for grades/attendance
+    pub duplicate_of: Option<String>, // Existing record this row would duplicate
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub target: ImportTarget,
+    pub total_rows: i32,
+    pub valid_rows: i32,
+    pub error_rows: i32,
+    pub duplicate_rows: i32,
+    pub rows: Vec<ImportRowPreview>,
+}
+
+// ==================== REPORT EXPORT ====================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    StudentTranscript,
+    SkillsChecklist,
+    AttendanceRecord,
+    VbonCompliance,
+    SiteRoster,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportBranding {
+    pub program_name: String,
+    pub institution_name: Option<String>,
+    pub prepared_by: Option<String>,
+    pub footer_text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportRequest {
+    pub kind: ReportKind,
+    pub student_id: Option<String>, // Required for the student reports
+    #[serde(default)]
+    pub framework_id: Option<String>, // Compliance report framework; defaults to the current one
+    pub output_path: String,
+    pub branding: ReportBranding,
+    pub signature_lines: Option<Vec<String>>, // Defaults depend on the report kind
+}
+
+// ==================== REMINDERS ====================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminder {
+    pub key: String, // Stable per item and date; used for deduplication and snoozing
+    pub category: String,
+    pub title: String,
+    pub body: String,
+    pub route: String, // App route opened from the notification
+}
+
+/// What was just notified, so the app can offer to open it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderNotice {
+    pub title: String,
+    pub body: String,
+    pub route: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderSetting {
+    pub category: String,
+    pub label: String,
+    pub enabled: bool,
+}
@@ -0,0 +1,204 @@
+// Local NCLEX readiness model.
+// A logistic regression is trained on first-attempt outcomes of graduated students and
+// explains each prediction by how far each feature moves the log-odds from an average student.
+
+use std::collections::HashMap;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{NclexModel, PredictionFactor};
+
+/// `(key, label)` for each model input, in model order.
+pub const FEATURES: &[(&str, &str)] = &[
+    ("gpa", "GPA"),
+    ("avg_course_grade", "Average course grade"),
+    ("predictor_score", "NCLEX predictor score"),
+    ("clinical_rating", "Preceptor evaluation rating"),
+    ("absences", "Absences"),
+    ("tardies", "Tardies"),
+    ("remediation", "Remediation history"),
+];
+
+pub const MIN_TRAINING_SAMPLES: usize = 10;
+const ITERATIONS: usize = 5000;
+const LEARNING_RATE: f64 = 0.1;
+// L2 penalty keeps weights stable with the small cohorts a single program has
+const L2_PENALTY: f64 = 0.01;
+const TOP_FACTORS: usize = 3;
+
+pub type FeatureValues = Vec<Option<f64>>;
+
+/// Feature values per student, loaded with one aggregate query per source table.
+pub async fn load_features(pool: &Pool<Sqlite>) -> Result<HashMap<String, FeatureValues>, sqlx::Error> {
+    let students: Vec<(String, Option<f64>, Option<f64>, Option<String>)> = sqlx::query_as(
+        "SELECT id, gpa, nclex_predictor_score, remediation_status FROM students"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let grades: HashMap<String, f64> = sqlx::query_as::<_, (String, f64)>(
+        "SELECT student_id, AVG(grade) FROM grades GROUP BY student_id"
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let ratings: HashMap<String, f64> = sqlx::query_as::<_, (String, f64)>(
+        "SELECT student_id, AVG(overall_rating) FROM preceptor_evaluations
+        WHERE overall_rating IS NOT NULL GROUP BY student_id"
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let attendance: HashMap<String, (i32, i32)> = sqlx::query_as::<_, (String, i32, i32)>(
+        "SELECT student_id,
+                SUM(CASE WHEN status = 'Absent' THEN 1 ELSE 0 END),
+                SUM(CASE WHEN status = 'Tardy' THEN 1 ELSE 0 END)
+        FROM attendance GROUP BY student_id"
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(id, absences, tardies)| (id, (absences, tardies)))
+    .collect();
+
+    let with_plans: Vec<String> = sqlx::query_as::<_, (String,)>("SELECT DISTINCT student_id FROM remediation_plans")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id,)| id)
+        .collect();
+
+    Ok(students
+        .into_iter()
+        .map(|(id, gpa, predictor, remediation_status)| {
+            let (absences, tardies) = attendance.get(&id).copied().unwrap_or((0, 0));
+            let had_remediation = with_plans.contains(&id)
+                || remediation_status
+                    .map(|s| !s.trim().is_empty() && !s.eq_ignore_ascii_case("none"))
+                    .unwrap_or(false);
+            let values = vec![
+                gpa,
+                grades.get(&id).copied(),
+                predictor,
+                ratings.get(&id).copied(),
+                Some(absences as f64),
+                Some(tardies as f64),
+                Some(if had_remediation { 1.0 } else { 0.0 }),
+            ];
+            (id, values)
+        })
+        .collect())
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn standardize(model: &NclexModel, values: &FeatureValues) -> Vec<f64> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v.map(|v| (v - model.means[i]) / model.std_devs[i]).unwrap_or(0.0))
+        .collect()
+}
+
+/// Fits the model by gradient descent. Returns the model and its accuracy on the training set.
+pub fn train(samples: &[(FeatureValues, bool)]) -> Result<(NclexModel, f64), String> {
+    let passes = samples.iter().filter(|(_, passed)| *passed).count();
+    if samples.len() < MIN_TRAINING_SAMPLES {
+        return Err(format!(
+            "At least {} recorded outcomes are needed to train (have {})",
+            MIN_TRAINING_SAMPLES,
+            samples.len()
+        ));
+    }
+    if passes == 0 || passes == samples.len() {
+        return Err("Outcomes must include both passes and failures to train".to_string());
+    }
+
+    let feature_count = FEATURES.len();
+    let mut means = vec![0.0; feature_count];
+    let mut std_devs = vec![1.0; feature_count];
+    for i in 0..feature_count {
+        let present: Vec<f64> = samples.iter().filter_map(|(values, _)| values[i]).collect();
+        if present.is_empty() {
+            continue;
+        }
+        let mean = present.iter().sum::<f64>() / present.len() as f64;
+        let variance = present.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / present.len() as f64;
+        means[i] = mean;
+        std_devs[i] = if variance.sqrt() > 1e-9 { variance.sqrt() } else { 1.0 };
+    }
+
+    let base_rate = passes as f64 / samples.len() as f64;
+    let mut model = NclexModel {
+        features: FEATURES.iter().map(|(key, _)| key.to_string()).collect(),
+        means,
+        std_devs,
+        weights: vec![0.0; feature_count],
+        intercept: (base_rate / (1.0 - base_rate)).ln(),
+    };
+
+    let rows: Vec<(Vec<f64>, f64)> = samples
+        .iter()
+        .map(|(values, passed)| (standardize(&model, values), if *passed { 1.0 } else { 0.0 }))
+        .collect();
+    let n = rows.len() as f64;
+
+    for _ in 0..ITERATIONS {
+        let mut weight_gradient = vec![0.0; feature_count];
+        let mut intercept_gradient = 0.0;
+        for (x, y) in &rows {
+            let z = model.intercept + x.iter().zip(&model.weights).map(|(x, w)| x * w).sum::<f64>();
+            let error = sigmoid(z) - y;
+            intercept_gradient += error;
+            for (g, x) in weight_gradient.iter_mut().zip(x) {
+                *g += error * x;
+            }
+        }
+        model.intercept -= LEARNING_RATE * intercept_gradient / n;
+        for (w, g) in model.weights.iter_mut().zip(&weight_gradient) {
+            *w -= LEARNING_RATE * (g / n + L2_PENALTY * *w);
+        }
+    }
+
+    let correct = samples
+        .iter()
+        .filter(|(values, passed)| (predict(&model, values).0 >= 0.5) == *passed)
+        .count();
+    Ok((model, correct as f64 / n))
+}
+
+/// Pass probability and the factors that moved it most, largest effect first.
+pub fn predict(model: &NclexModel, values: &FeatureValues) -> (f64, Vec<PredictionFactor>) {
+    let x = standardize(model, values);
+    let contributions: Vec<f64> = x.iter().zip(&model.weights).map(|(x, w)| x * w).collect();
+    let probability = sigmoid(model.intercept + contributions.iter().sum::<f64>());
+
+    let mut factors: Vec<PredictionFactor> = contributions
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.abs() > 1e-6)
+        .map(|(i, contribution)| PredictionFactor {
+            feature: model.features[i].clone(),
+            label: FEATURES.get(i).map(|(_, label)| label.to_string()).unwrap_or_default(),
+            value: values[i],
+            contribution: *contribution,
+            direction: if *contribution > 0.0 { "raises" } else { "lowers" }.to_string(),
+        })
+        .collect();
+    factors.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()));
+    factors.truncate(TOP_FACTORS);
+
+    (probability, factors)
+}
+
+/// Whether a stored model was trained on the current feature list.
+pub fn is_compatible(model: &NclexModel) -> bool {
+    model.features.len() == FEATURES.len()
+        && model.features.iter().zip(FEATURES).all(|(stored, (key, _))| stored == key)
+}
@@ -0,0 +1,498 @@
+// Versioned regulation catalogs.
+// A framework's catalog is imported from a JSON or CSV file as a named edition and kept as a snapshot, so
+// any two editions (or an edition and the live catalog) can be compared. Applying an edition rewrites the
+// live catalog in `vbon_regulations` and flags the mappings of changed or removed regulations for re-review.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde::Deserialize;
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::importer::SpreadsheetTable;
+use crate::models::{
+    RegulationCatalogDiff, RegulationCatalogEdition, RegulationCatalogImportRequest, RegulationCatalogImportResult,
+    RegulationChange, RegulationSnapshot, RegulatoryFramework,
+};
+
+const COMPARED_FIELDS: [&str; 5] = ["code", "section", "category", "title", "description"];
+
+fn field<'a>(regulation: &'a RegulationSnapshot, name: &str) -> &'a str {
+    match name {
+        "code" => &regulation.code,
+        "section" => &regulation.section,
+        "category" => &regulation.category,
+        "title" => &regulation.title,
+        _ => &regulation.description,
+    }
+}
+
+// ==================== PARSING ====================
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogFile {
+    edition: Option<String>,
+    effective_date: Option<String>,
+    regulations: Vec<RegulationSnapshot>,
+}
+
+/// JSON is either a bare array of regulations or an object naming the edition.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CatalogJson {
+    File(CatalogFile),
+    List(Vec<RegulationSnapshot>),
+}
+
+pub struct ParsedCatalog {
+    pub edition: Option<String>,
+    pub effective_date: Option<String>,
+    pub regulations: Vec<RegulationSnapshot>,
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| extensions.iter().any(|x| e.eq_ignore_ascii_case(x)))
+        .unwrap_or(false)
+}
+
+pub fn parse_file(path: &Path) -> Result<ParsedCatalog, String> {
+    if has_extension(path, &["xlsx", "xlsm", "xls", "ods"]) {
+        let table = crate::importer::read_table(path, None)?;
+        return Ok(ParsedCatalog { edition: None, effective_date: None, regulations: parse_table(&table)? });
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_text(path, &text)
+}
+
+/// JSON when the file is named `.json`; anything else is read as CSV.
+pub fn parse_text(path: &Path, text: &str) -> Result<ParsedCatalog, String> {
+    let text = text.trim_start_matches('\u{feff}');
+    if has_extension(path, &["json"]) {
+        let parsed = serde_json::from_str::<CatalogJson>(text).map_err(|e| format!("Invalid catalog JSON: {}", e))?;
+        return Ok(match parsed {
+            CatalogJson::File(file) => ParsedCatalog {
+                edition: file.edition,
+                effective_date: file.effective_date,
+                regulations: file.regulations,
+            },
+            CatalogJson::List(regulations) => ParsedCatalog { edition: None, effective_date: None, regulations },
+        });
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Failed to read CSV: {}", e))?;
+        rows.push(record.iter().map(|v| v.trim().to_string()).collect::<Vec<String>>());
+    }
+    if rows.is_empty() {
+        return Err("The file is empty".to_string());
+    }
+    let headers = rows.remove(0);
+    let table = SpreadsheetTable { sheet_names: Vec::new(), headers, rows };
+    Ok(ParsedCatalog { edition: None, effective_date: None, regulations: parse_table(&table)? })
+}
+
+fn header_key(header: &str) -> String {
+    header.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+/// Rows of a spreadsheet with at least `code` and `title` columns.
+fn parse_table(table: &SpreadsheetTable) -> Result<Vec<RegulationSnapshot>, String> {
+    let column = |names: &[&str]| table.headers.iter().position(|h| names.contains(&header_key(h).as_str()));
+    let code_col = column(&["code", "regulationcode"]).ok_or("The catalog needs a 'code' column")?;
+    let title_col = column(&["title", "name"]).ok_or("The catalog needs a 'title' column")?;
+    let id_col = column(&["id", "regulationid"]);
+    let section_col = column(&["section"]);
+    let category_col = column(&["category"]);
+    let description_col = column(&["description", "requirement", "text"]);
+    let sort_col = column(&["sortorder", "order"]);
+
+    let cell = |row: &[String], col: Option<usize>| col.and_then(|c| row.get(c)).cloned().unwrap_or_default();
+    Ok(table
+        .rows
+        .iter()
+        .filter(|row| row.iter().any(|v| !v.trim().is_empty()))
+        .map(|row| RegulationSnapshot {
+            id: cell(row, id_col),
+            code: cell(row, Some(code_col)),
+            section: cell(row, section_col),
+            category: cell(row, category_col),
+            title: cell(row, Some(title_col)),
+            description: cell(row, description_col),
+            sort_order: cell(row, sort_col).trim().parse().unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Trims fields, fills in missing ids (`VBON-B1` for code `B.1`) and sort order, and rejects duplicates.
+fn normalize(framework: &RegulatoryFramework, regulations: &mut [RegulationSnapshot]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for (index, regulation) in regulations.iter_mut().enumerate() {
+        for value in [
+            &mut regulation.id,
+            &mut regulation.code,
+            &mut regulation.section,
+            &mut regulation.category,
+            &mut regulation.title,
+            &mut regulation.description,
+        ] {
+            *value = value.trim().to_string();
+        }
+        if regulation.code.is_empty() || regulation.title.is_empty() {
+            return Err(format!("Regulation {} is missing a code or title", index + 1));
+        }
+        if regulation.id.is_empty() {
+            let code: String = regulation.code.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+            regulation.id = format!("{}-{}", framework.code.to_uppercase(), code.to_uppercase());
+        }
+        if regulation.category.is_empty() {
+            regulation.category = "General".to_string();
+        }
+        if regulation.sort_order == 0 {
+            regulation.sort_order = index as i32 + 1;
+        }
+        if !seen.insert(regulation.id.clone()) {
+            return Err(format!("Regulation '{}' appears more than once", regulation.id));
+        }
+    }
+    Ok(())
+}
+
+/// Regulation ids are global, so an id already used by another framework would be moved to this one
+/// (taking its mappings along) when the edition is applied. Such catalogs are rejected instead.
+async fn reject_foreign_ids<'a>(
+    conn: &mut SqliteConnection,
+    framework_id: &str,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> Result<(), String> {
+    let others: HashMap<String, String> = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, framework_id FROM vbon_regulations WHERE framework_id != ?"
+    )
+    .bind(framework_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .collect();
+
+    let conflicts: Vec<String> = ids
+        .into_iter()
+        .filter_map(|id| others.get(id).map(|other| format!("{} (in {})", id, other)))
+        .collect();
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "These regulation ids belong to another framework; give them ids of their own: {}",
+            conflicts.join(", ")
+        ))
+    }
+}
+
+// ==================== DIFF ====================
+
+pub fn diff(
+    framework_id: &str,
+    from_edition_id: Option<String>,
+    to_edition_id: String,
+    before: &[RegulationSnapshot],
+    after: &[RegulationSnapshot],
+    mapped: &HashSet<String>,
+) -> RegulationCatalogDiff {
+    let before_by_id: HashMap<&str, &RegulationSnapshot> = before.iter().map(|r| (r.id.as_str(), r)).collect();
+    let after_ids: HashSet<&str> = after.iter().map(|r| r.id.as_str()).collect();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged_count = 0;
+    for regulation in after {
+        match before_by_id.get(regulation.id.as_str()) {
+            None => added.push(regulation.clone()),
+            Some(previous) => {
+                let changed_fields: Vec<String> = COMPARED_FIELDS
+                    .iter()
+                    .filter(|name| field(previous, name) != field(regulation, name))
+                    .map(|name| name.to_string())
+                    .collect();
+                if changed_fields.is_empty() {
+                    unchanged_count += 1;
+                } else {
+                    changed.push(RegulationChange {
+                        before: (*previous).clone(),
+                        after: regulation.clone(),
+                        changed_fields,
+                        has_mapping: mapped.contains(&regulation.id),
+                    });
+                }
+            }
+        }
+    }
+    let removed: Vec<RegulationSnapshot> =
+        before.iter().filter(|r| !after_ids.contains(r.id.as_str())).cloned().collect();
+
+    let affected_mapping_count = changed.iter().filter(|c| c.has_mapping).count()
+        + removed.iter().filter(|r| mapped.contains(&r.id)).count();
+
+    RegulationCatalogDiff {
+        framework_id: framework_id.to_string(),
+        from_edition_id,
+        to_edition_id,
+        added,
+        removed,
+        changed,
+        unchanged_count,
+        affected_mapping_count: affected_mapping_count as i32,
+    }
+}
+
+// ==================== STORAGE ====================
+
+/// Active regulations of the live catalog.
+pub async fn live_catalog(pool: &Pool<Sqlite>, framework_id: &str) -> Result<Vec<RegulationSnapshot>, String> {
+    sqlx::query_as::<_, RegulationSnapshot>(
+        "SELECT id, code, section, category, title, description, sort_order
+        FROM vbon_regulations WHERE framework_id = ? AND is_active = 1 ORDER BY sort_order"
+    )
+    .bind(framework_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn edition_entries(pool: &Pool<Sqlite>, edition_id: &str) -> Result<Vec<RegulationSnapshot>, String> {
+    sqlx::query_as::<_, RegulationSnapshot>(
+        "SELECT id, code, section, category, title, description, sort_order
+        FROM regulation_edition_entries WHERE edition_id = ? ORDER BY sort_order"
+    )
+    .bind(edition_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn load_edition(pool: &Pool<Sqlite>, edition_id: &str) -> Result<RegulationCatalogEdition, String> {
+    sqlx::query_as::<_, RegulationCatalogEdition>("SELECT * FROM regulation_catalog_editions WHERE id = ?")
+        .bind(edition_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Catalog edition not found".to_string())
+}
+
+pub async fn current_edition(pool: &Pool<Sqlite>, framework_id: &str) -> Result<Option<RegulationCatalogEdition>, String> {
+    sqlx::query_as::<_, RegulationCatalogEdition>(
+        "SELECT * FROM regulation_catalog_editions WHERE framework_id = ? AND is_current = 1"
+    )
+    .bind(framework_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+async fn mapped_regulation_ids(pool: &Pool<Sqlite>, framework_id: &str) -> Result<HashSet<String>, String> {
+    let ids: Vec<(String,)> = sqlx::query_as(
+        "SELECT m.regulation_id FROM vbon_mappings m
+        JOIN vbon_regulations r ON r.id = m.regulation_id
+        WHERE r.framework_id = ?"
+    )
+    .bind(framework_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
+/// Compares two editions, or an edition against the live catalog when `from_edition_id` is None.
+pub async fn diff_editions(
+    pool: &Pool<Sqlite>,
+    from_edition_id: Option<&str>,
+    to_edition_id: &str,
+) -> Result<RegulationCatalogDiff, String> {
+    let to = load_edition(pool, to_edition_id).await?;
+    let before = match from_edition_id {
+        Some(from_id) => {
+            let from = load_edition(pool, from_id).await?;
+            if from.framework_id != to.framework_id {
+                return Err("Editions belong to different frameworks".to_string());
+            }
+            edition_entries(pool, from_id).await?
+        }
+        None => live_catalog(pool, &to.framework_id).await?,
+    };
+    let after = edition_entries(pool, to_edition_id).await?;
+    let mapped = mapped_regulation_ids(pool, &to.framework_id).await?;
+    Ok(diff(
+        &to.framework_id,
+        from_edition_id.map(String::from),
+        to.id.clone(),
+        &before,
+        &after,
+        &mapped,
+    ))
+}
+
+/// Stores the file as a new edition, diffs it against the live catalog and, when asked, applies it.
+pub async fn import(
+    pool: &Pool<Sqlite>,
+    framework: &RegulatoryFramework,
+    request: &RegulationCatalogImportRequest,
+) -> Result<RegulationCatalogImportResult, String> {
+    let path = Path::new(&request.file_path);
+    let parsed = match &request.content {
+        Some(content) => parse_text(path, content)?,
+        None => parse_file(path)?,
+    };
+    let mut regulations = parsed.regulations;
+    if regulations.is_empty() {
+        return Err("The file has no regulations".to_string());
+    }
+    normalize(framework, &mut regulations)?;
+
+    let edition_label = request
+        .edition
+        .clone()
+        .or(parsed.edition)
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .ok_or("Name the edition being imported")?;
+    let (existing,): (i32,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM regulation_catalog_editions WHERE framework_id = ? AND edition = ?"
+    )
+    .bind(&framework.id)
+    .bind(&edition_label)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if existing > 0 {
+        return Err(format!("Edition '{}' of {} was already imported", edition_label, framework.code));
+    }
+
+    let edition_id = format!("EDN-{}", uuid::Uuid::new_v4());
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    reject_foreign_ids(&mut *tx, &framework.id, regulations.iter().map(|r| r.id.as_str())).await?;
+    sqlx::query(
+        "INSERT INTO regulation_catalog_editions (
+            id, framework_id, edition, effective_date, source_file, notes, regulation_count, is_current, imported_at
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, 0, ?)"
+    )
+    .bind(&edition_id)
+    .bind(&framework.id)
+    .bind(&edition_label)
+    .bind(request.effective_date.clone().or(parsed.effective_date))
+    .bind(path.file_name().map(|n| n.to_string_lossy().to_string()))
+    .bind(&request.notes)
+    .bind(regulations.len() as i32)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    for regulation in &regulations {
+        sqlx::query(
+            "INSERT INTO regulation_edition_entries (edition_id, id, code, section, category, title, description, sort_order)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&edition_id)
+        .bind(&regulation.id)
+        .bind(&regulation.code)
+        .bind(&regulation.section)
+        .bind(&regulation.category)
+        .bind(&regulation.title)
+        .bind(&regulation.description)
+        .bind(regulation.sort_order)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    // The diff is taken before applying so it describes what applying changes
+    let diff = diff_editions(pool, None, &edition_id).await?;
+    let mappings_flagged = if request.apply { apply(pool, &edition_id).await? } else { 0 };
+
+    Ok(RegulationCatalogImportResult {
+        edition: load_edition(pool, &edition_id).await?,
+        diff,
+        mappings_flagged,
+    })
+}
+
+/// Makes the edition live: adds and rewords regulations, deactivates removed ones (their mappings are kept
+/// as history) and flags the mappings of changed or removed regulations. Returns the number of mappings flagged.
+pub async fn apply(pool: &Pool<Sqlite>, edition_id: &str) -> Result<i32, String> {
+    let edition = load_edition(pool, edition_id).await?;
+    let diff = diff_editions(pool, None, edition_id).await?;
+    let entries = edition_entries(pool, edition_id).await?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    // Checked again here: another framework may have taken an id since the edition was imported
+    reject_foreign_ids(&mut *tx, &edition.framework_id, entries.iter().map(|e| e.id.as_str())).await?;
+    for entry in &entries {
+        sqlx::query(
+            "INSERT INTO vbon_regulations (
+                id, framework_id, code, section, category, title, description, sort_order, is_active, edition_id, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                code = excluded.code, section = excluded.section,
+                category = excluded.category, title = excluded.title, description = excluded.description,
+                sort_order = excluded.sort_order, is_active = 1, edition_id = excluded.edition_id,
+                updated_at = excluded.updated_at"
+        )
+        .bind(&entry.id)
+        .bind(&edition.framework_id)
+        .bind(&entry.code)
+        .bind(&entry.section)
+        .bind(&entry.category)
+        .bind(&entry.title)
+        .bind(&entry.description)
+        .bind(entry.sort_order)
+        .bind(&edition.id)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    for removed in &diff.removed {
+        sqlx::query("UPDATE vbon_regulations SET is_active = 0, updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&removed.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let reasons = diff
+        .changed
+        .iter()
+        .map(|c| (c.after.id.clone(), format!("Edition {} changed the {}", edition.edition, c.changed_fields.join(", "))))
+        .chain(diff.removed.iter().map(|r| (r.id.clone(), format!("Removed in edition {}", edition.edition))));
+    let mut flagged = 0;
+    for (regulation_id, reason) in reasons {
+        let result = sqlx::query("UPDATE vbon_mappings SET needs_review = 1, review_reason = ?, updated_at = ? WHERE regulation_id = ?")
+            .bind(&reason)
+            .bind(&now)
+            .bind(&regulation_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        flagged += result.rows_affected() as i32;
+    }
+
+    sqlx::query("UPDATE regulation_catalog_editions SET is_current = (id = ?) WHERE framework_id = ?")
+        .bind(&edition.id)
+        .bind(&edition.framework_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(flagged)
+}
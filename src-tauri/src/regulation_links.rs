@@ -0,0 +1,285 @@
+// Links between lesson plans and the regulations they teach.
+// `lesson_plan_regulations` is the source of truth. The older `lesson_plans.vbon_tags` (JSON array) and
+// `vbon_mappings.lesson_plan_ids` (comma-separated, as the mapping form writes it) columns are rewritten
+// from it whenever links change, so either side can be edited and the two always agree.
+
+use std::collections::{BTreeSet, HashMap};
+
+use sqlx::{Pool, Sqlite, SqliteConnection};
+
+use crate::models::EvidenceLessonPlan;
+
+/// Id lists are stored as a JSON array or, when typed by hand, separated by commas or whitespace.
+pub fn parse_id_list(raw: Option<&str>) -> Vec<String> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Vec::new();
+    };
+    if let Ok(ids) = serde_json::from_str::<Vec<String>>(raw) {
+        return ids.into_iter().map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).collect();
+    }
+    raw.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|id| id.trim_matches(|c: char| c == '"' || c == '[' || c == ']').to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Regulation ids for free-text tags, each of which is a regulation id or code.
+pub async fn resolve_tags(conn: &mut SqliteConnection, tags: &[String]) -> Result<Vec<String>, sqlx::Error> {
+    let regulations: Vec<(String, String)> = sqlx::query_as("SELECT id, code FROM vbon_regulations")
+        .fetch_all(&mut *conn)
+        .await?;
+    let mut ids = BTreeSet::new();
+    for tag in tags {
+        if regulations.iter().any(|(id, _)| id == tag) {
+            ids.insert(tag.clone());
+        } else {
+            ids.extend(
+                regulations
+                    .iter()
+                    .filter(|(_, code)| code.eq_ignore_ascii_case(tag))
+                    .map(|(id, _)| id.clone()),
+            );
+        }
+    }
+    Ok(ids.into_iter().collect())
+}
+
+async fn regulations_of(conn: &mut SqliteConnection, lesson_plan_id: &str) -> Result<Vec<String>, sqlx::Error> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT regulation_id FROM lesson_plan_regulations WHERE lesson_plan_id = ? ORDER BY regulation_id"
+    )
+    .bind(lesson_plan_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+async fn lesson_plans_of(conn: &mut SqliteConnection, regulation_id: &str) -> Result<Vec<String>, sqlx::Error> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT l.lesson_plan_id FROM lesson_plan_regulations l
+        JOIN lesson_plans p ON p.id = l.lesson_plan_id
+        WHERE l.regulation_id = ? ORDER BY p.date, p.id"
+    )
+    .bind(regulation_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+async fn sync_lesson_plan(conn: &mut SqliteConnection, lesson_plan_id: &str) -> Result<(), sqlx::Error> {
+    let ids = regulations_of(conn, lesson_plan_id).await?;
+    let tags = if ids.is_empty() { None } else { serde_json::to_string(&ids).ok() };
+    sqlx::query("UPDATE lesson_plans SET vbon_tags = ? WHERE id = ?")
+        .bind(tags)
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+async fn sync_mapping(conn: &mut SqliteConnection, regulation_id: &str) -> Result<(), sqlx::Error> {
+    let ids = lesson_plans_of(conn, regulation_id).await?;
+    let joined = if ids.is_empty() { None } else { Some(ids.join(",")) };
+    sqlx::query("UPDATE vbon_mappings SET lesson_plan_ids = ? WHERE regulation_id = ?")
+        .bind(joined)
+        .bind(regulation_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Replaces the regulations a lesson plan teaches. Unknown regulation ids are ignored.
+pub async fn set_for_lesson_plan(
+    conn: &mut SqliteConnection,
+    lesson_plan_id: &str,
+    regulation_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut touched: BTreeSet<String> = regulations_of(conn, lesson_plan_id).await?.into_iter().collect();
+    sqlx::query("DELETE FROM lesson_plan_regulations WHERE lesson_plan_id = ?")
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    for regulation_id in regulation_ids {
+        sqlx::query(
+            "INSERT OR IGNORE INTO lesson_plan_regulations (lesson_plan_id, regulation_id, created_at)
+            SELECT ?, id, ? FROM vbon_regulations WHERE id = ?"
+        )
+        .bind(lesson_plan_id)
+        .bind(&now)
+        .bind(regulation_id)
+        .execute(&mut *conn)
+        .await?;
+        touched.insert(regulation_id.clone());
+    }
+
+    sync_lesson_plan(conn, lesson_plan_id).await?;
+    for regulation_id in &touched {
+        sync_mapping(conn, regulation_id).await?;
+    }
+    Ok(())
+}
+
+/// Replaces the lesson plans that teach a regulation. Unknown lesson plan ids are ignored.
+pub async fn set_for_regulation(
+    conn: &mut SqliteConnection,
+    regulation_id: &str,
+    lesson_plan_ids: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut touched: BTreeSet<String> = lesson_plans_of(conn, regulation_id).await?.into_iter().collect();
+    sqlx::query("DELETE FROM lesson_plan_regulations WHERE regulation_id = ?")
+        .bind(regulation_id)
+        .execute(&mut *conn)
+        .await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    for lesson_plan_id in lesson_plan_ids {
+        sqlx::query(
+            "INSERT OR IGNORE INTO lesson_plan_regulations (lesson_plan_id, regulation_id, created_at)
+            SELECT id, ?, ? FROM lesson_plans WHERE id = ?"
+        )
+        .bind(regulation_id)
+        .bind(&now)
+        .bind(lesson_plan_id)
+        .execute(&mut *conn)
+        .await?;
+        touched.insert(lesson_plan_id.clone());
+    }
+
+    sync_mapping(conn, regulation_id).await?;
+    for lesson_plan_id in &touched {
+        sync_lesson_plan(conn, lesson_plan_id).await?;
+    }
+    Ok(())
+}
+
+/// Links from a saved lesson plan's tags. Without tags the links are left alone and the tags column
+/// (which the save may have overwritten) is rewritten from them.
+pub async fn save_lesson_plan_tags(pool: &Pool<Sqlite>, lesson_plan_id: &str, tags: Option<&str>) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    match tags {
+        Some(tags) => {
+            let regulation_ids = resolve_tags(&mut *tx, &parse_id_list(Some(tags))).await?;
+            set_for_lesson_plan(&mut *tx, lesson_plan_id, &regulation_ids).await?;
+        }
+        None => sync_lesson_plan(&mut *tx, lesson_plan_id).await?,
+    }
+    tx.commit().await
+}
+
+/// Links from a saved mapping's lesson plan list, which the mapping form always sends in full.
+pub async fn save_mapping_lesson_plans(
+    pool: &Pool<Sqlite>,
+    regulation_id: &str,
+    lesson_plan_ids: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    set_for_regulation(&mut *tx, regulation_id, &parse_id_list(lesson_plan_ids)).await?;
+    tx.commit().await
+}
+
+/// Drops a lesson plan's links before the plan is deleted.
+pub async fn remove_lesson_plan(conn: &mut SqliteConnection, lesson_plan_id: &str) -> Result<(), sqlx::Error> {
+    set_for_lesson_plan(conn, lesson_plan_id, &[]).await
+}
+
+/// Drops the links of regulations that are being deleted.
+pub async fn remove_regulations(conn: &mut SqliteConnection, regulation_ids: &[String]) -> Result<(), sqlx::Error> {
+    for regulation_id in regulation_ids {
+        set_for_regulation(conn, regulation_id, &[]).await?;
+    }
+    Ok(())
+}
+
+/// Builds the join table from the legacy columns the first time it is empty.
+pub async fn backfill(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let (existing,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM lesson_plan_regulations")
+        .fetch_one(pool)
+        .await?;
+    if existing > 0 {
+        return Ok(());
+    }
+
+    let tagged: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT id, vbon_tags FROM lesson_plans WHERE COALESCE(vbon_tags, '') != ''")
+            .fetch_all(pool)
+            .await?;
+    let mapped: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT regulation_id, lesson_plan_ids FROM vbon_mappings WHERE COALESCE(lesson_plan_ids, '') != ''")
+            .fetch_all(pool)
+            .await?;
+    if tagged.is_empty() && mapped.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+    let mut links: BTreeSet<(String, String)> = BTreeSet::new();
+    for (lesson_plan_id, tags) in &tagged {
+        for regulation_id in resolve_tags(&mut *tx, &parse_id_list(tags.as_deref())).await? {
+            links.insert((lesson_plan_id.clone(), regulation_id));
+        }
+    }
+    for (regulation_id, lesson_plan_ids) in &mapped {
+        for lesson_plan_id in parse_id_list(lesson_plan_ids.as_deref()) {
+            links.insert((lesson_plan_id, regulation_id.clone()));
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for (lesson_plan_id, regulation_id) in &links {
+        sqlx::query(
+            "INSERT OR IGNORE INTO lesson_plan_regulations (lesson_plan_id, regulation_id, created_at)
+            SELECT p.id, r.id, ? FROM lesson_plans p, vbon_regulations r WHERE p.id = ? AND r.id = ?"
+        )
+        .bind(&now)
+        .bind(lesson_plan_id)
+        .bind(regulation_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Both sides now carry the union of what either recorded; tags that matched nothing are left as typed
+    let lesson_plan_ids: BTreeSet<&String> = links.iter().map(|(id, _)| id).collect();
+    let regulation_ids: BTreeSet<&String> = links.iter().map(|(_, id)| id).collect();
+    for lesson_plan_id in lesson_plan_ids {
+        sync_lesson_plan(&mut *tx, lesson_plan_id).await?;
+    }
+    for regulation_id in regulation_ids {
+        sync_mapping(&mut *tx, regulation_id).await?;
+    }
+    tx.commit().await
+}
+
+/// Linked lesson plans of every regulation in the framework, oldest first, with whether each was taught by `today`.
+pub async fn lesson_plans_by_regulation(
+    pool: &Pool<Sqlite>,
+    framework_id: &str,
+    today: &str,
+) -> Result<HashMap<String, Vec<EvidenceLessonPlan>>, sqlx::Error> {
+    let rows: Vec<(String, String, String, String, Option<i32>, Option<String>, String, Option<String>)> = sqlx::query_as(
+        "SELECT l.regulation_id, p.id, p.date, p.course_name, p.week_number, p.chapter, p.topic, p.assessment_method
+        FROM lesson_plan_regulations l
+        JOIN lesson_plans p ON p.id = l.lesson_plan_id
+        JOIN vbon_regulations r ON r.id = l.regulation_id
+        WHERE r.framework_id = ?
+        ORDER BY p.date, p.id"
+    )
+    .bind(framework_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_regulation: HashMap<String, Vec<EvidenceLessonPlan>> = HashMap::new();
+    for (regulation_id, id, date, course_name, week_number, chapter, topic, assessment_method) in rows {
+        let taught = date.get(..10).unwrap_or(&date) <= today;
+        by_regulation.entry(regulation_id).or_default().push(EvidenceLessonPlan {
+            id,
+            date,
+            course_name,
+            week_number,
+            chapter,
+            topic,
+            assessment_method,
+            taught,
+        });
+    }
+    Ok(by_regulation)
+}
@@ -0,0 +1,194 @@
+// Regulatory frameworks.
+// Each framework (a state board of nursing or a national accreditor) has its own regulation catalog in
+// `vbon_regulations` (rows carry a `framework_id`) and its own mappings through those regulations.
+// One framework is current; commands that are not given a framework use it.
+
+use std::collections::HashMap;
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{
+    RegulatoryFramework, VBONCategoryStats, VBONComplianceSummary, VBONMapping, VBONRegulation,
+    VBONRegulationWithMapping,
+};
+
+pub const DEFAULT_FRAMEWORK_ID: &str = "vbon";
+
+/// `(id, code, name, framework_type, jurisdiction)` available out of the box. Only VBON ships a catalog;
+/// the others start empty until their regulations are added or imported.
+const DEFAULT_FRAMEWORKS: &[(&str, &str, &str, &str, Option<&str>)] = &[
+    ("vbon", "VBON", "Virginia Board of Nursing", "state_board", Some("VA")),
+    ("ncbon", "NCBON", "North Carolina Board of Nursing", "state_board", Some("NC")),
+    ("acen", "ACEN", "Accreditation Commission for Education in Nursing", "accreditor", None),
+    ("ccne", "CCNE", "Commission on Collegiate Nursing Education", "accreditor", None),
+];
+
+pub async fn seed_frameworks(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    for (id, code, name, framework_type, jurisdiction) in DEFAULT_FRAMEWORKS {
+        sqlx::query(
+            "INSERT OR IGNORE INTO regulatory_frameworks (id, code, name, framework_type, jurisdiction, description, is_current, created_at)
+            VALUES (?, ?, ?, ?, ?, NULL, 0, ?)"
+        )
+        .bind(id)
+        .bind(code)
+        .bind(name)
+        .bind(framework_type)
+        .bind(jurisdiction)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+    }
+
+    let (current,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM regulatory_frameworks WHERE is_current = 1")
+        .fetch_one(pool)
+        .await?;
+    if current == 0 {
+        sqlx::query("UPDATE regulatory_frameworks SET is_current = 1 WHERE id = ?")
+            .bind(DEFAULT_FRAMEWORK_ID)
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn current(pool: &Pool<Sqlite>) -> Result<RegulatoryFramework, String> {
+    sqlx::query_as::<_, RegulatoryFramework>(
+        "SELECT * FROM regulatory_frameworks ORDER BY is_current DESC, id = ? DESC LIMIT 1"
+    )
+    .bind(DEFAULT_FRAMEWORK_ID)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "No regulatory frameworks are set up".to_string())
+}
+
+/// The framework with `id`, or the current one when no id is given.
+pub async fn resolve(pool: &Pool<Sqlite>, id: Option<&str>) -> Result<RegulatoryFramework, String> {
+    match id.filter(|id| !id.is_empty()) {
+        Some(id) => sqlx::query_as::<_, RegulatoryFramework>("SELECT * FROM regulatory_frameworks WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Unknown regulatory framework '{}'", id)),
+        None => current(pool).await,
+    }
+}
+
+/// Two-letter license state implied by the current framework, when it is a state board.
+pub async fn default_license_state(pool: &Pool<Sqlite>) -> Option<String> {
+    current(pool).await.ok().and_then(|f| f.jurisdiction)
+}
+
+pub async fn regulations_with_mappings(
+    pool: &Pool<Sqlite>,
+    framework_id: &str,
+) -> Result<Vec<VBONRegulationWithMapping>, sqlx::Error> {
+    let regulations = sqlx::query_as::<_, VBONRegulation>(
+        "SELECT * FROM vbon_regulations WHERE is_active = 1 AND framework_id = ? ORDER BY sort_order"
+    )
+    .bind(framework_id)
+    .fetch_all(pool)
+    .await?;
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut plans_by_regulation =
+        crate::regulation_links::lesson_plans_by_regulation(pool, framework_id, &today).await?;
+
+    let mut results = Vec::new();
+    for reg in regulations {
+        let mapping = sqlx::query_as::<_, VBONMapping>(
+            "SELECT * FROM vbon_mappings WHERE regulation_id = ?"
+        )
+        .bind(&reg.id)
+        .fetch_optional(pool)
+        .await?;
+
+        let lesson_plans = plans_by_regulation.remove(&reg.id).unwrap_or_default();
+        let taught_dates = lesson_plans.iter().filter(|p| p.taught).map(|p| p.date.clone()).collect();
+        results.push(VBONRegulationWithMapping {
+            regulation: reg,
+            mapping,
+            lesson_plans,
+            taught_dates,
+        });
+    }
+    Ok(results)
+}
+
+/// Coverage of one framework's active regulations by its mappings.
+pub async fn compliance_summary(pool: &Pool<Sqlite>, framework_id: &str) -> Result<VBONComplianceSummary, sqlx::Error> {
+    let regulations = regulations_with_mappings(pool, framework_id).await?;
+
+    // Calculate stats
+    let total_regulations = regulations.len() as i32;
+    let mut covered_count = 0;
+    let mut partial_count = 0;
+    let mut not_covered_count = 0;
+    let mut needs_review_count = 0;
+
+    // Group by category
+    let mut category_map: HashMap<String, VBONCategoryStats> = HashMap::new();
+
+    for entry in &regulations {
+        let status = entry.mapping.as_ref()
+            .map(|m| m.coverage_status.as_str())
+            .unwrap_or("not_covered");
+        if entry.mapping.as_ref().map(|m| m.needs_review).unwrap_or(false) {
+            needs_review_count += 1;
+        }
+
+        match status {
+            "covered" => covered_count += 1,
+            "partial" => partial_count += 1,
+            _ => not_covered_count += 1,
+        }
+
+        let category = &entry.regulation.category;
+        let category_stat = category_map
+            .entry(category.clone())
+            .or_insert_with(|| VBONCategoryStats {
+                category: category.clone(),
+                total: 0,
+                covered: 0,
+                partial: 0,
+                not_covered: 0,
+            });
+
+        category_stat.total += 1;
+        match status {
+            "covered" => category_stat.covered += 1,
+            "partial" => category_stat.partial += 1,
+            _ => category_stat.not_covered += 1,
+        }
+    }
+
+    // Calculate percentage (count covered as 100%, partial as 50%)
+    let percentage_complete = if total_regulations > 0 {
+        ((covered_count as f64 * 100.0) + (partial_count as f64 * 50.0)) / (total_regulations as f64)
+    } else {
+        0.0
+    };
+
+    // Last audit date is the most recent review of this framework's mappings
+    let last_audit_date = regulations
+        .iter()
+        .filter_map(|r| r.mapping.as_ref().and_then(|m| m.last_reviewed_date.clone()))
+        .max();
+
+    let mut regulations_by_category: Vec<VBONCategoryStats> = category_map.into_values().collect();
+    regulations_by_category.sort_by(|a, b| a.category.cmp(&b.category));
+
+    Ok(VBONComplianceSummary {
+        framework_id: framework_id.to_string(),
+        total_regulations,
+        covered_count,
+        partial_count,
+        not_covered_count,
+        percentage_complete,
+        last_audit_date,
+        needs_review_count,
+        regulations_by_category,
+    })
+}
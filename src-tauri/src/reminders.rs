@@ -0,0 +1,271 @@
+// Reminder scheduler.
+// Periodically collects every alert source (student certifications, deadlines, instructor credentials,
+// preceptor license verifications), skips items already notified or snoozed via `reminder_log`, and shows
+// an OS notification for the rest. The notification carries its route in `extra` for platforms that report
+// taps; desktop notifications have no click callback, so the frontend is also sent `NOTIFIED_EVENT` and
+// offers an in-app "Open" toast instead.
+
+use std::time::Duration;
+
+use sqlx::{Pool, Sqlite};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::db::DbState;
+use crate::models::{Deadline, Reminder, ReminderNotice, ReminderSetting, StudentCertification};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Emitted with a `ReminderNotice` whenever a notification is shown.
+pub const NOTIFIED_EVENT: &str = "reminders:notified";
+const CERTIFICATION_DAYS_AHEAD: i64 = 30;
+const DEADLINE_DAYS_AHEAD: i64 = 7;
+const PRECEPTOR_DAYS_AHEAD: i32 = 30;
+/// Expired certifications and overdue deadlines are reminded about for this long after their date.
+const PAST_DUE_DAYS_BACK: i64 = 90;
+
+fn date_offset(today: chrono::NaiveDate, days: i64) -> String {
+    (today + chrono::Duration::days(days)).format("%Y-%m-%d").to_string()
+}
+
+// Student certifications expiring soon or already expired, skipping ones a later certification
+// of the same name has replaced, with the student's name
+async fn student_certifications_due(
+    pool: &Pool<Sqlite>,
+    today: chrono::NaiveDate,
+) -> Result<Vec<(StudentCertification, String)>, sqlx::Error> {
+    let certs = sqlx::query_as::<_, StudentCertification>(
+        "SELECT c.* FROM student_certifications c
+        WHERE c.expiry_date >= ? AND c.expiry_date <= ?
+          AND NOT EXISTS (
+              SELECT 1 FROM student_certifications n
+              WHERE n.student_id = c.student_id AND n.certification_type = c.certification_type
+                AND lower(n.certification_name) = lower(c.certification_name)
+                AND n.expiry_date > c.expiry_date AND n.id != c.id
+          )
+        ORDER BY c.expiry_date"
+    )
+    .bind(date_offset(today, -PAST_DUE_DAYS_BACK))
+    .bind(date_offset(today, CERTIFICATION_DAYS_AHEAD))
+    .fetch_all(pool)
+    .await?;
+
+    let mut due = Vec::new();
+    for cert in certs {
+        let name: Option<(String,)> = sqlx::query_as("SELECT first_name || ' ' || last_name FROM students WHERE id = ?")
+            .bind(&cert.student_id)
+            .fetch_optional(pool)
+            .await?;
+        if let Some((name,)) = name {
+            due.push((cert, name));
+        }
+    }
+    Ok(due)
+}
+
+// Open deadlines due soon or already overdue
+async fn deadlines_due(pool: &Pool<Sqlite>, today: chrono::NaiveDate) -> Result<Vec<Deadline>, sqlx::Error> {
+    sqlx::query_as::<_, Deadline>(
+        "SELECT * FROM deadlines
+        WHERE due_date >= ? AND due_date <= ? AND status != 'Completed'
+        ORDER BY due_date, priority DESC"
+    )
+    .bind(date_offset(today, -PAST_DUE_DAYS_BACK))
+    .bind(date_offset(today, DEADLINE_DAYS_AHEAD))
+    .fetch_all(pool)
+    .await
+}
+
+/// `(category, label)` for each alert source; every category can be turned off on its own.
+pub const CATEGORIES: &[(&str, &str)] = &[
+    ("student_certifications", "Student certifications"),
+    ("deadlines", "Deadlines"),
+    ("instructor_certifications", "Instructor credentials"),
+    ("preceptor_verifications", "Preceptor license verifications"),
+];
+
+/// Current reminders from every enabled source. Keys include the date the reminder is about,
+/// so a renewed certification or moved deadline is reminded about again.
+pub async fn collect(app: &AppHandle) -> Result<Vec<Reminder>, String> {
+    let state: State<'_, DbState> = app.state();
+    let disabled = disabled_categories(&state.db).await.map_err(|e| e.to_string())?;
+    let enabled = |category: &str| !disabled.iter().any(|c| c == category);
+    let today = chrono::Local::now().date_naive();
+    let mut reminders = Vec::new();
+
+    if enabled("student_certifications") {
+        for (cert, student_name) in student_certifications_due(&state.db, today).await.map_err(|e| e.to_string())? {
+            let days_until_expiry = chrono::NaiveDate::parse_from_str(&cert.expiry_date, "%Y-%m-%d")
+                .map(|expiry| (expiry - today).num_days())
+                .unwrap_or(0);
+            reminders.push(Reminder {
+                key: format!("student_cert:{}:{}", cert.id, cert.expiry_date),
+                category: "student_certifications".to_string(),
+                title: if days_until_expiry < 0 {
+                    format!("{} expired", cert.certification_name)
+                } else {
+                    format!("{} expires in {} days", cert.certification_name, days_until_expiry)
+                },
+                body: format!("{} — expires {}", student_name, cert.expiry_date),
+                route: format!("/students/view?id={}", cert.student_id),
+            });
+        }
+    }
+
+    if enabled("deadlines") {
+        for deadline in deadlines_due(&state.db, today).await.map_err(|e| e.to_string())? {
+            let overdue = deadline.due_date < today.format("%Y-%m-%d").to_string();
+            reminders.push(Reminder {
+                key: format!("deadline:{}:{}", deadline.id, deadline.due_date),
+                category: "deadlines".to_string(),
+                title: if overdue {
+                    format!("Overdue since {}: {}", deadline.due_date, deadline.title)
+                } else {
+                    format!("Due {}: {}", deadline.due_date, deadline.title)
+                },
+                body: deadline.description.clone().unwrap_or_else(|| format!("{} priority", deadline.priority)),
+                route: match &deadline.related_student_id {
+                    Some(student_id) => format!("/students/view?id={}", student_id),
+                    None => "/".to_string(),
+                },
+            });
+        }
+    }
+
+    if enabled("instructor_certifications") {
+        for alert in crate::commands::get_instructor_certification_alerts(state.clone()).await? {
+            let cert = &alert.certification;
+            reminders.push(Reminder {
+                key: format!("instructor_cert:{}:{}", cert.id, cert.expiry_date),
+                category: "instructor_certifications".to_string(),
+                title: if alert.days_until_expiry < 0 {
+                    format!("Your {} has expired", cert.certification_name)
+                } else {
+                    format!("Your {} expires in {} days", cert.certification_name, alert.days_until_expiry)
+                },
+                body: format!("Expires {}", cert.expiry_date),
+                route: "/profile".to_string(),
+            });
+        }
+    }
+
+    if enabled("preceptor_verifications") {
+        for alert in crate::commands::get_preceptors_needing_verification(state.clone(), PRECEPTOR_DAYS_AHEAD).await? {
+            let due = alert.next_verification_due.clone().unwrap_or_default();
+            reminders.push(Reminder {
+                key: format!("preceptor:{}:{}", alert.preceptor_id, due),
+                category: "preceptor_verifications".to_string(),
+                title: format!("Verify license: {}", alert.preceptor_name),
+                body: if alert.days_until_due < 0 {
+                    format!("Verification overdue since {}", due)
+                } else {
+                    format!("Verification due {}", due)
+                },
+                route: "/preceptors".to_string(),
+            });
+        }
+    }
+
+    Ok(reminders)
+}
+
+async fn disabled_categories(pool: &Pool<Sqlite>) -> Result<Vec<String>, sqlx::Error> {
+    let rows: Vec<(String,)> = sqlx::query_as("SELECT category FROM reminder_settings WHERE enabled = 0")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(|(category,)| category).collect())
+}
+
+pub async fn load_settings(pool: &Pool<Sqlite>) -> Result<Vec<ReminderSetting>, sqlx::Error> {
+    let disabled = disabled_categories(pool).await?;
+    Ok(CATEGORIES
+        .iter()
+        .map(|(category, label)| ReminderSetting {
+            category: category.to_string(),
+            label: label.to_string(),
+            enabled: !disabled.iter().any(|c| c == category),
+        })
+        .collect())
+}
+
+/// Reminders not yet notified (or whose snooze ran out).
+pub async fn filter_due(pool: &Pool<Sqlite>, reminders: Vec<Reminder>) -> Result<Vec<Reminder>, sqlx::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut due = Vec::new();
+    for reminder in reminders {
+        let log: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT notified_at, snoozed_until FROM reminder_log WHERE key = ?"
+        )
+        .bind(&reminder.key)
+        .fetch_optional(pool)
+        .await?;
+        let is_due = match log {
+            None => true,
+            Some((Some(_), _)) => false,
+            Some((None, Some(until))) => until <= now,
+            Some((None, None)) => true,
+        };
+        if is_due {
+            due.push(reminder);
+        }
+    }
+    Ok(due)
+}
+
+async fn mark_notified(pool: &Pool<Sqlite>, reminder: &Reminder) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO reminder_log (key, category, notified_at, snoozed_until) VALUES (?, ?, ?, NULL)
+        ON CONFLICT(key) DO UPDATE SET notified_at = excluded.notified_at, snoozed_until = NULL"
+    )
+    .bind(&reminder.key)
+    .bind(&reminder.category)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Fires a notification for every due reminder. Several due at once are summarized in one notification.
+pub async fn run_once(app: &AppHandle) -> Result<usize, String> {
+    let pool = app.state::<DbState>().db.clone();
+    let reminders = collect(app).await?;
+    let due = filter_due(&pool, reminders).await.map_err(|e| e.to_string())?;
+    if due.is_empty() {
+        return Ok(0);
+    }
+
+    let (title, body, route) = match due.as_slice() {
+        [only] => (only.title.clone(), only.body.clone(), only.route.clone()),
+        many => (
+            format!("{} new reminders", many.len()),
+            many.iter().take(3).map(|r| r.title.clone()).collect::<Vec<_>>().join("\n"),
+            // Several items: land on the dashboard, which lists them all
+            "/".to_string(),
+        ),
+    };
+
+    app.notification()
+        .builder()
+        .title(&title)
+        .body(&body)
+        .extra("route", &route)
+        .show()
+        .map_err(|e| e.to_string())?;
+
+    let _ = app.emit(NOTIFIED_EVENT, ReminderNotice { title, body, route });
+    for reminder in &due {
+        mark_notified(&pool, reminder).await.map_err(|e| e.to_string())?;
+    }
+    Ok(due.len())
+}
+
+/// Checks for reminders shortly after startup and then every `CHECK_INTERVAL`.
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = run_once(&app).await {
+                log::error!("Reminder check failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,702 @@
+// PDF report generation for accreditation packets and student files, plus the comp time timesheet
+// (which HR also takes as CSV).
+// Pages are US Letter and use the PDF built-in Helvetica faces, so no font files ship with the app.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point,
+};
+
+use crate::models::{
+    Attendance, ClinicalLog, ClinicalSite, CompTimesheet, ReportBranding, SkillValidation, Student, StudentHoursBySite,
+    StudentSimulationSummary, VBONComplianceSummary, VBONEvidenceReport, VBONRegulationWithMapping,
+};
+
+const PAGE_WIDTH: f32 = 215.9;
+const PAGE_HEIGHT: f32 = 279.4;
+const MARGIN: f32 = 18.0;
+const FOOTER_SPACE: f32 = 14.0;
+const BODY_SIZE: f32 = 9.0;
+const PT_TO_MM: f32 = 0.3528;
+// Average Helvetica glyph width as a fraction of the font size, used to fit text to columns
+const AVG_CHAR_WIDTH: f32 = 0.5;
+
+/// Lays out report content top to bottom, starting new pages (with the branded header) as needed.
+pub struct ReportWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    branding: ReportBranding,
+    title: String,
+    generated_on: String,
+    page: i32,
+    y: f32,
+}
+
+// Built-in fonts only cover WinAnsi, so typographic punctuation is flattened to ASCII
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' => '\'',
+            '\u{201C}' | '\u{201D}' => '"',
+            '\u{2013}' | '\u{2014}' => '-',
+            '\n' | '\r' | '\t' => ' ',
+            c if c.is_ascii() => c,
+            _ => '?',
+        })
+        .collect()
+}
+
+fn max_chars(width: f32, size: f32) -> usize {
+    (width / (size * AVG_CHAR_WIDTH * PT_TO_MM)).floor().max(1.0) as usize
+}
+
+fn fit(text: &str, width: f32, size: f32) -> String {
+    let text = sanitize(text);
+    let max = max_chars(width, size);
+    if text.chars().count() <= max {
+        text
+    } else {
+        let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{}...", kept.trim_end())
+    }
+}
+
+fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let max = max_chars(width, size);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in sanitize(text).split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > max {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn line_height(size: f32) -> f32 {
+    size * PT_TO_MM * 1.45
+}
+
+fn hours(value: f64) -> String {
+    format!("{:.1}", value)
+}
+
+impl ReportWriter {
+    pub fn new(title: &str, branding: &ReportBranding) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Page 1");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| e.to_string())?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| e.to_string())?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        let mut writer = ReportWriter {
+            doc,
+            layer,
+            regular,
+            bold,
+            branding: branding.clone(),
+            title: title.to_string(),
+            generated_on: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            page: 1,
+            y: 0.0,
+        };
+        writer.draw_page_frame();
+        Ok(writer)
+    }
+
+    fn text_at(&self, text: &str, size: f32, x: f32, y: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer.use_text(sanitize(text), size, Mm(x), Mm(y), font);
+    }
+
+    fn rule(&self, x1: f32, x2: f32, y: f32, thickness: f32) {
+        self.layer.set_outline_thickness(thickness);
+        self.layer.add_line(Line {
+            points: vec![(Point::new(Mm(x1), Mm(y)), false), (Point::new(Mm(x2), Mm(y)), false)],
+            is_closed: false,
+        });
+    }
+
+    // Branded header and page footer, repeated on every page
+    fn draw_page_frame(&mut self) {
+        let mut y = PAGE_HEIGHT - MARGIN;
+        self.text_at(&self.branding.program_name, 14.0, MARGIN, y, true);
+        if let Some(ref institution) = self.branding.institution_name {
+            y -= line_height(14.0);
+            self.text_at(institution, 10.0, MARGIN, y, false);
+        }
+        y -= line_height(10.0) + 2.0;
+        self.text_at(&self.title, 12.0, MARGIN, y, true);
+        y -= 3.0;
+        self.rule(MARGIN, PAGE_WIDTH - MARGIN, y, 1.0);
+
+        let footer = match self.branding.footer_text {
+            Some(ref text) => format!("{}  |  Generated {}  |  Page {}", text, self.generated_on, self.page),
+            None => format!("Generated {}  |  Page {}", self.generated_on, self.page),
+        };
+        self.rule(MARGIN, PAGE_WIDTH - MARGIN, MARGIN + 4.0, 0.5);
+        self.text_at(&footer, 7.5, MARGIN, MARGIN, false);
+        if let Some(ref prepared_by) = self.branding.prepared_by {
+            let label = format!("Prepared by {}", prepared_by);
+            let width = label.len() as f32 * 7.5 * AVG_CHAR_WIDTH * PT_TO_MM;
+            self.text_at(&label, 7.5, PAGE_WIDTH - MARGIN - width, MARGIN, false);
+        }
+
+        self.y = y - 7.0;
+    }
+
+    fn new_page(&mut self) {
+        self.page += 1;
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), format!("Page {}", self.page));
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.draw_page_frame();
+    }
+
+    /// Starts a new page when less than `height` mm is left above the footer.
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height < MARGIN + FOOTER_SPACE {
+            self.new_page();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn heading(&mut self, text: &str) {
+        self.ensure_space(line_height(11.0) + line_height(BODY_SIZE) * 3.0);
+        self.y -= 2.0;
+        self.text_at(text, 11.0, MARGIN, self.y, true);
+        self.y -= line_height(11.0) + 1.0;
+    }
+
+    pub fn paragraph(&mut self, text: &str) {
+        for line in wrap(text, PAGE_WIDTH - 2.0 * MARGIN, BODY_SIZE) {
+            self.ensure_space(line_height(BODY_SIZE));
+            self.text_at(&line, BODY_SIZE, MARGIN, self.y, false);
+            self.y -= line_height(BODY_SIZE);
+        }
+        self.y -= 2.0;
+    }
+
+    /// Label/value pairs in two aligned columns.
+    pub fn key_values(&mut self, pairs: &[(&str, String)]) {
+        let label_width = 48.0;
+        let value_width = PAGE_WIDTH - 2.0 * MARGIN - label_width;
+        for (label, value) in pairs {
+            self.ensure_space(line_height(BODY_SIZE));
+            self.text_at(label, BODY_SIZE, MARGIN, self.y, true);
+            self.text_at(&fit(value, value_width, BODY_SIZE), BODY_SIZE, MARGIN + label_width, self.y, false);
+            self.y -= line_height(BODY_SIZE);
+        }
+        self.y -= 3.0;
+    }
+
+    /// A table with `(header, width_mm)` columns. The header row repeats after page breaks.
+    pub fn table(&mut self, columns: &[(&str, f32)], rows: &[Vec<String>]) {
+        let row_height = line_height(BODY_SIZE);
+        let right = MARGIN + columns.iter().map(|(_, w)| w).sum::<f32>();
+
+        let draw_header = |writer: &mut ReportWriter| {
+            let mut x = MARGIN;
+            for (header, width) in columns {
+                writer.text_at(&fit(header, width - 1.5, BODY_SIZE), BODY_SIZE, x, writer.y, true);
+                x += width;
+            }
+            writer.rule(MARGIN, right, writer.y - 1.5, 0.5);
+            writer.y -= row_height + 1.0;
+        };
+
+        self.ensure_space(row_height * 3.0);
+        draw_header(self);
+
+        if rows.is_empty() {
+            self.text_at("No records.", BODY_SIZE, MARGIN, self.y, false);
+            self.y -= row_height;
+        }
+
+        for row in rows {
+            if self.ensure_space(row_height) {
+                draw_header(self);
+            }
+            let mut x = MARGIN;
+            for ((_, width), cell) in columns.iter().zip(row) {
+                self.text_at(&fit(cell, width - 1.5, BODY_SIZE), BODY_SIZE, x, self.y, false);
+                x += width;
+            }
+            self.y -= row_height;
+        }
+        self.y -= 4.0;
+    }
+
+    /// Signature and date lines for each signer, kept together on one page.
+    pub fn signature_block(&mut self, signers: &[String]) {
+        if signers.is_empty() {
+            return;
+        }
+        let block_height = 16.0;
+        self.ensure_space(block_height * signers.len() as f32 + 6.0);
+        self.y -= 6.0;
+
+        let signature_end = MARGIN + 110.0;
+        let date_start = signature_end + 12.0;
+        for signer in signers {
+            self.y -= 8.0;
+            self.rule(MARGIN, signature_end, self.y, 0.5);
+            self.rule(date_start, PAGE_WIDTH - MARGIN, self.y, 0.5);
+            self.text_at(signer, 8.0, MARGIN, self.y - 3.5, false);
+            self.text_at("Date", 8.0, date_start, self.y - 3.5, false);
+            self.y -= block_height - 8.0;
+        }
+    }
+
+    pub fn save(self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+        self.doc
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| format!("Could not write PDF: {}", e))
+    }
+
+    /// The finished PDF in memory, for packaging alongside other files.
+    pub fn to_bytes(self) -> Result<Vec<u8>, String> {
+        let mut buffer = BufWriter::new(Vec::new());
+        self.doc.save(&mut buffer).map_err(|e| format!("Could not write PDF: {}", e))?;
+        buffer.into_inner().map_err(|e| format!("Could not write PDF: {}", e))
+    }
+}
+
+fn student_details(writer: &mut ReportWriter, student: &Student) {
+    writer.key_values(&[
+        ("Student", format!("{} {}", student.first_name, student.last_name)),
+        ("Student ID", student.id.clone()),
+        ("Cohort", student.cohort.clone()),
+        ("Status", student.status.clone()),
+    ]);
+}
+
+pub fn student_transcript(
+    writer: &mut ReportWriter,
+    student: &Student,
+    hours_by_site: &[StudentHoursBySite],
+    logs: &[ClinicalLog],
+    simulation: &StudentSimulationSummary,
+) {
+    student_details(writer, student);
+
+    writer.heading("Clinical Hours by Site");
+    let mut sites: Vec<&StudentHoursBySite> = hours_by_site.iter().collect();
+    sites.sort_by(|a, b| a.site_name.cmp(&b.site_name));
+    let mut rows: Vec<Vec<String>> = sites
+        .iter()
+        .map(|s| vec![s.site_name.clone(), hours(s.direct_hours), hours(s.sim_hours), hours(s.total_hours)])
+        .collect();
+    rows.push(vec![
+        "Total".to_string(),
+        hours(simulation.direct_hours),
+        hours(simulation.sim_hours),
+        hours(simulation.total_hours),
+    ]);
+    writer.table(&[("Site", 100.0), ("Direct", 26.0), ("Simulation", 26.0), ("Total", 26.0)], &rows);
+
+    writer.paragraph(&format!(
+        "Hours recorded on the student file: {} of {} required. Simulation accounts for {:.1}% of approved hours ({}).",
+        hours(student.clinical_hours_completed),
+        hours(student.clinical_hours_required),
+        simulation.sim_percentage,
+        if simulation.is_compliant { "within the program limit" } else { "exceeds the program limit" },
+    ));
+
+    writer.heading("Approved Clinical Log");
+    let mut logs: Vec<&ClinicalLog> = logs.iter().filter(|l| l.status == "Approved").collect();
+    logs.sort_by(|a, b| a.date.cmp(&b.date));
+    let rows: Vec<Vec<String>> = logs
+        .iter()
+        .map(|l| {
+            let kind = match (l.is_simulation.unwrap_or(0) == 1, l.is_makeup.unwrap_or(0) == 1) {
+                (true, _) => "Simulation",
+                (false, true) => "Makeup",
+                _ => "Direct",
+            };
+            vec![
+                l.date.clone(),
+                l.site_name.clone(),
+                kind.to_string(),
+                hours(l.hours.unwrap_or(8.0)),
+                l.patient_diagnosis.clone(),
+            ]
+        })
+        .collect();
+    writer.table(
+        &[("Date", 24.0), ("Site", 52.0), ("Type", 22.0), ("Hours", 16.0), ("Focus / Diagnosis", 66.0)],
+        &rows,
+    );
+}
+
+pub fn skills_checklist(writer: &mut ReportWriter, student: &Student, validations: &[SkillValidation]) {
+    student_details(writer, student);
+
+    // Most recent validation per skill; skills marked complete without a validation still appear
+    let mut skills: BTreeMap<String, Option<&SkillValidation>> = BTreeMap::new();
+    for skill in student.skills_completed.iter() {
+        skills.entry(skill.clone()).or_insert(None);
+    }
+    for validation in validations {
+        let entry = skills.entry(validation.skill_id.clone()).or_insert(None);
+        if entry.map(|v| v.validated_date < validation.validated_date).unwrap_or(true) {
+            *entry = Some(validation);
+        }
+    }
+
+    writer.heading("Skills Validation Checklist");
+    let rows: Vec<Vec<String>> = skills
+        .iter()
+        .map(|(skill, validation)| match validation {
+            Some(v) => vec![
+                "[X]".to_string(),
+                skill.clone(),
+                v.proficiency.clone(),
+                v.validated_date.clone(),
+                v.validated_by.clone().unwrap_or_default(),
+                v.validated_location.clone().unwrap_or_default(),
+            ],
+            None => vec![
+                "[ ]".to_string(),
+                skill.clone(),
+                "Not validated".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        })
+        .collect();
+    writer.table(
+        &[("", 9.0), ("Skill", 58.0), ("Proficiency", 28.0), ("Date", 22.0), ("Validated By", 34.0), ("Location", 29.0)],
+        &rows,
+    );
+
+    let validated = skills.values().filter(|v| v.is_some()).count();
+    writer.paragraph(&format!("{} of {} skills validated.", validated, skills.len()));
+}
+
+pub fn attendance_record(writer: &mut ReportWriter, student: &Student, records: &[Attendance]) {
+    student_details(writer, student);
+
+    let mut counts: HashMap<&str, i32> = HashMap::new();
+    let mut hours_missed = 0.0;
+    for record in records {
+        *counts.entry(record.status.as_str()).or_insert(0) += 1;
+        if let (Some(required), attended) = (record.hours_required, record.hours_attended.unwrap_or(0.0)) {
+            hours_missed += (required - attended).max(0.0);
+        }
+    }
+    let count = |status: &str| counts.get(status).copied().unwrap_or(0).to_string();
+
+    writer.heading("Summary");
+    writer.key_values(&[
+        ("Sessions recorded", records.len().to_string()),
+        ("Present", count("Present")),
+        ("Tardy", count("Tardy")),
+        ("Absent", count("Absent")),
+        ("Excused", count("Excused")),
+        ("Hours missed", hours(hours_missed)),
+    ]);
+
+    writer.heading("Attendance Detail");
+    let mut records: Vec<&Attendance> = records.iter().collect();
+    records.sort_by(|a, b| a.date.cmp(&b.date));
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|r| {
+            let hours_cell = match (r.hours_attended, r.hours_required) {
+                (Some(attended), Some(required)) => format!("{} / {}", hours(attended), hours(required)),
+                (Some(attended), None) => hours(attended),
+                _ => String::new(),
+            };
+            vec![
+                r.date.clone(),
+                r.attendance_type.clone().unwrap_or_else(|| "classroom".to_string()),
+                r.status.clone(),
+                hours_cell,
+                r.notes.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    writer.table(&[("Date", 24.0), ("Type", 24.0), ("Status", 22.0), ("Hours", 24.0), ("Notes", 86.0)], &rows);
+}
+
+pub fn vbon_compliance(
+    writer: &mut ReportWriter,
+    summary: &VBONComplianceSummary,
+    regulations: &[VBONRegulationWithMapping],
+) {
+    writer.key_values(&[
+        ("Regulations", summary.total_regulations.to_string()),
+        ("Covered", summary.covered_count.to_string()),
+        ("Partially covered", summary.partial_count.to_string()),
+        ("Not covered", summary.not_covered_count.to_string()),
+        ("Compliance", format!("{:.1}%", summary.percentage_complete)),
+        ("Last reviewed", summary.last_audit_date.clone().unwrap_or_else(|| "Never".to_string())),
+    ]);
+
+    writer.heading("Coverage by Category");
+    let rows: Vec<Vec<String>> = summary
+        .regulations_by_category
+        .iter()
+        .map(|c| {
+            vec![
+                c.category.clone(),
+                c.total.to_string(),
+                c.covered.to_string(),
+                c.partial.to_string(),
+                c.not_covered.to_string(),
+            ]
+        })
+        .collect();
+    writer.table(
+        &[("Category", 88.0), ("Total", 22.0), ("Covered", 22.0), ("Partial", 22.0), ("Not Covered", 26.0)],
+        &rows,
+    );
+
+    writer.heading("Regulation Detail");
+    let rows: Vec<Vec<String>> = regulations
+        .iter()
+        .filter(|r| r.regulation.is_active == 1)
+        .map(|r| {
+            let mapping = r.mapping.as_ref();
+            vec![
+                r.regulation.code.clone(),
+                r.regulation.title.clone(),
+                mapping
+                    .map(|m| m.coverage_status.replace('_', " "))
+                    .unwrap_or_else(|| "not covered".to_string()),
+                mapping.and_then(|m| m.syllabus_reference.clone()).unwrap_or_default(),
+                r.taught_dates.last().map(|d| d.get(..10).unwrap_or(d).to_string()).unwrap_or_default(),
+                mapping.and_then(|m| m.last_reviewed_date.clone()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+    writer.table(
+        &[
+            ("Code", 16.0),
+            ("Requirement", 62.0),
+            ("Status", 22.0),
+            ("Syllabus", 30.0),
+            ("Last Taught", 24.0),
+            ("Reviewed", 26.0),
+        ],
+        &rows,
+    );
+}
+
+pub fn vbon_evidence(writer: &mut ReportWriter, report: &VBONEvidenceReport) {
+    writer.key_values(&[
+        ("Framework", report.framework_name.clone()),
+        ("Regulations", report.regulations.len().to_string()),
+        ("With gaps", report.gap_count.to_string()),
+        ("Stale reviews", report.stale_count.to_string()),
+        ("Review interval", format!("{} days", report.review_interval_days)),
+        ("As of", report.generated_on.clone()),
+    ]);
+
+    writer.heading("Gap Report");
+    let rows: Vec<Vec<String>> = report
+        .regulations
+        .iter()
+        .filter(|e| !e.gaps.is_empty())
+        .map(|e| vec![e.regulation.code.clone(), e.regulation.title.clone(), e.gaps.join("; ")])
+        .collect();
+    writer.table(&[("Code", 16.0), ("Requirement", 64.0), ("Gaps", 100.0)], &rows);
+
+    for evidence in &report.regulations {
+        writer.heading(&format!("{} - {}", evidence.regulation.code, evidence.regulation.title));
+        writer.paragraph(&evidence.regulation.description);
+        let none = || "None recorded".to_string();
+        let join_or_none = |items: &[String]| if items.is_empty() { none() } else { items.join("; ") };
+        writer.key_values(&[
+            ("Section", evidence.regulation.section.clone()),
+            ("Coverage", evidence.coverage_status.replace('_', " ")),
+            ("Syllabus", join_or_none(&evidence.syllabus_references)),
+            ("Assessment", join_or_none(&evidence.assessment_methods)),
+            ("Clinical experience", evidence.clinical_experience.clone().unwrap_or_else(none)),
+            (
+                "Last reviewed",
+                match (&evidence.last_reviewed_date, evidence.is_stale) {
+                    (Some(date), true) => format!("{} (review overdue)", date),
+                    (Some(date), false) => date.clone(),
+                    (None, _) => "Never".to_string(),
+                },
+            ),
+        ]);
+
+        let rows: Vec<Vec<String>> = evidence
+            .lesson_plans
+            .iter()
+            .map(|p| {
+                vec![
+                    if p.taught { p.date.clone() } else { format!("{} (planned)", p.date) },
+                    p.course_name.clone(),
+                    p.week_number.map(|w| w.to_string()).unwrap_or_default(),
+                    p.topic.clone(),
+                    p.assessment_method.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        writer.table(&[("Date", 30.0), ("Course", 34.0), ("Week", 12.0), ("Topic", 62.0), ("Assessment", 42.0)], &rows);
+
+        if !evidence.materials.is_empty() {
+            let rows: Vec<Vec<String>> = evidence
+                .materials
+                .iter()
+                .map(|m| {
+                    vec![
+                        m.title.clone(),
+                        m.material_type.clone(),
+                        m.packaged_file.clone().or_else(|| m.url.clone()).unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            writer.table(&[("Material", 64.0), ("Type", 24.0), ("File / Link", 92.0)], &rows);
+        }
+    }
+}
+
+/// `students_by_site` holds the names of students with upcoming assignments at each site.
+pub fn site_roster(
+    writer: &mut ReportWriter,
+    sites: &[ClinicalSite],
+    students_by_site: &HashMap<String, Vec<String>>,
+) {
+    writer.heading("Sites");
+    let rows: Vec<Vec<String>> = sites
+        .iter()
+        .map(|s| {
+            vec![
+                match s.unit_name {
+                    Some(ref unit) => format!("{} - {}", s.name, unit),
+                    None => s.name.clone(),
+                },
+                s.site_type.clone(),
+                s.contact_name.clone().unwrap_or_default(),
+                s.contact_phone.clone().unwrap_or_default(),
+                s.contract_expiration_date.clone().unwrap_or_default(),
+                students_by_site.get(&s.id).map(|names| names.len()).unwrap_or(0).to_string(),
+            ]
+        })
+        .collect();
+    writer.table(
+        &[("Site / Unit", 58.0), ("Type", 22.0), ("Contact", 34.0), ("Phone", 26.0), ("Contract Ends", 24.0), ("Students", 16.0)],
+        &rows,
+    );
+
+    for site in sites {
+        let Some(names) = students_by_site.get(&site.id) else { continue };
+        writer.heading(&format!("{} - Assigned Students", site.name));
+        writer.key_values(&[
+            ("Address", site.address.clone().unwrap_or_default()),
+            ("Accrediting body", site.accrediting_body.clone().unwrap_or_default()),
+            ("Max students per day", site.max_students_per_day.map(|m| m.to_string()).unwrap_or_default()),
+        ]);
+        let rows: Vec<Vec<String>> = names.iter().map(|n| vec![n.clone()]).collect();
+        writer.table(&[("Student", 100.0)], &rows);
+    }
+}
+
+pub fn comp_timesheet(writer: &mut ReportWriter, employee_name: &str, timesheet: &CompTimesheet) {
+    writer.key_values(&[
+        ("Employee", employee_name.to_string()),
+        ("Pay period", format!("{} to {}", timesheet.start_date, timesheet.end_date)),
+        ("Opening balance", hours(timesheet.opening_balance)),
+        ("Hours earned", hours(timesheet.earned)),
+        ("Hours used", hours(timesheet.used)),
+        ("Hours forfeited", hours(timesheet.forfeited)),
+        ("Closing balance", hours(timesheet.closing_balance)),
+    ]);
+    if timesheet.pending_approval > 0.0 {
+        writer.paragraph(&format!(
+            "{} earned hours in this period are awaiting approval and are not included in the balances.",
+            hours(timesheet.pending_approval)
+        ));
+    }
+
+    writer.heading("Activity");
+    let rows: Vec<Vec<String>> = timesheet
+        .entries
+        .iter()
+        .map(|e| {
+            let signed = if e.kind == "Earned" { hours(e.hours) } else { format!("-{}", hours(e.hours)) };
+            vec![
+                e.date.clone(),
+                e.kind.clone(),
+                e.description.clone(),
+                signed,
+                e.expiration_date.clone().unwrap_or_default(),
+                e.status.clone(),
+                e.notes.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    writer.table(
+        &[("Date", 22.0), ("Type", 20.0), ("Description", 46.0), ("Hours", 16.0), ("Expires", 22.0), ("Status", 20.0), ("Notes", 34.0)],
+        &rows,
+    );
+}
+
+/// The same timesheet as CSV: a short header block, the activity rows, then the period totals.
+pub fn comp_timesheet_csv(path: &Path, employee_name: &str, timesheet: &CompTimesheet) -> Result<(), String> {
+    let mut csv = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+    let write = |csv: &mut csv::Writer<File>, record: &[String]| csv.write_record(record).map_err(|e| e.to_string());
+
+    write(&mut csv, &["Employee".to_string(), employee_name.to_string()])?;
+    write(&mut csv, &["Pay period start".to_string(), timesheet.start_date.clone()])?;
+    write(&mut csv, &["Pay period end".to_string(), timesheet.end_date.clone()])?;
+    write(&mut csv, &[])?;
+
+    write(
+        &mut csv,
+        &["Date", "Type", "Description", "Hours", "Expires", "Status", "Notes"].map(String::from),
+    )?;
+    for e in &timesheet.entries {
+        let signed = if e.kind == "Earned" { e.hours } else { -e.hours };
+        write(
+            &mut csv,
+            &[
+                e.date.clone(),
+                e.kind.clone(),
+                e.description.clone(),
+                format!("{:.2}", signed),
+                e.expiration_date.clone().unwrap_or_default(),
+                e.status.clone(),
+                e.notes.clone().unwrap_or_default(),
+            ],
+        )?;
+    }
+    write(&mut csv, &[])?;
+
+    for (label, value) in [
+        ("Opening balance", timesheet.opening_balance),
+        ("Hours earned", timesheet.earned),
+        ("Hours used", timesheet.used),
+        ("Hours forfeited", timesheet.forfeited),
+        ("Closing balance", timesheet.closing_balance),
+        ("Pending approval", timesheet.pending_approval),
+    ] {
+        write(&mut csv, &[label.to_string(), format!("{:.2}", value)])?;
+    }
+    csv.flush().map_err(|e| format!("Could not write CSV: {}", e))
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use sqlx::{Pool, Sqlite};
 use tauri::{
     image::Image,
     menu::{Menu, MenuItem, PredefinedMenuItem},
@@ -5,24 +8,135 @@ use tauri::{
     AppHandle, Manager, Runtime,
 };
 
-pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
-    // Load tray icon
-    let icon_bytes = include_bytes!("../icons/32x32.png");
-    let icon = Image::from_bytes(icon_bytes)?;
+use crate::db::DbState;
+
+const TRAY_ID: &str = "main";
+const TOOLTIP: &str = "NursEd Admin";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Counts shown in the tray menu. Anything non-zero marks the tray as needing attention.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TrayAlerts {
+    pub pending_submissions: i64,
+    pub pending_evaluations: i64,
+    pub expiring_certifications: i64,
+    pub overdue_deadlines: i64,
+}
+
+impl TrayAlerts {
+    fn total(&self) -> i64 {
+        self.pending_submissions + self.pending_evaluations + self.expiring_certifications + self.overdue_deadlines
+    }
+}
+
+fn plural(count: i64, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+pub async fn load_alerts(pool: &Pool<Sqlite>) -> Result<TrayAlerts, sqlx::Error> {
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let (pending_submissions,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM student_hour_submissions WHERE status = 'pending'")
+            .fetch_one(pool)
+            .await?;
+    let (pending_evaluations,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM preceptor_evaluations WHERE status = 'Pending'")
+            .fetch_one(pool)
+            .await?;
+    // Statuses are kept current by the daily certification sweep; certifications that a later
+    // one of the same name has replaced stay Expired but need no action
+    let (expiring_certifications,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM student_certifications c
+         WHERE c.status IN ('Expiring Soon', 'Expired')
+           AND NOT EXISTS (
+               SELECT 1 FROM student_certifications n
+               WHERE n.student_id = c.student_id
+                 AND n.certification_type = c.certification_type
+                 AND lower(n.certification_name) = lower(c.certification_name)
+                 AND n.expiry_date > c.expiry_date
+                 AND n.id != c.id
+           )"
+    )
+    .fetch_one(pool)
+    .await?;
+    let (overdue_deadlines,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM deadlines WHERE due_date < ? AND status != 'Completed'")
+            .bind(&today)
+            .fetch_one(pool)
+            .await?;
+    Ok(TrayAlerts {
+        pending_submissions,
+        pending_evaluations,
+        expiring_certifications,
+        overdue_deadlines,
+    })
+}
+
+fn base_icon() -> Result<Image<'static>, tauri::Error> {
+    Image::from_bytes(include_bytes!("../icons/32x32.png"))
+}
+
+/// The app icon with a red dot in the top-right corner.
+fn attention_icon() -> Result<Image<'static>, tauri::Error> {
+    let icon = base_icon()?;
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+    let radius = width.min(height) as f64 * 0.22;
+    let (cx, cy) = (width as f64 - radius - 1.0, radius + 1.0);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f64 + 0.5 - cx, y as f64 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[220, 38, 38, 255]);
+            }
+        }
+    }
+    Ok(Image::new_owned(rgba, width, height))
+}
 
-    // Create tray menu
+fn build_menu<R: Runtime>(app: &AppHandle<R>, alerts: &TrayAlerts) -> Result<Menu<R>, tauri::Error> {
     let show_hide = MenuItem::with_id(app, "tray_show_hide", "Show/Hide Window", true, None::<&str>)?;
+
+    // Alert items navigate to the view that lists them; disabled while there is nothing to see
+    let alert_item = |id: &str, count: i64, label: String| MenuItem::with_id(app, id, label, count > 0, None::<&str>);
+    let submissions = alert_item(
+        "tray_alert_submissions",
+        alerts.pending_submissions,
+        plural(alerts.pending_submissions, "pending hour submission", "pending hour submissions"),
+    )?;
+    let evaluations = alert_item(
+        "tray_alert_evaluations",
+        alerts.pending_evaluations,
+        plural(alerts.pending_evaluations, "pending evaluation", "pending evaluations"),
+    )?;
+    let certifications = alert_item(
+        "tray_alert_certifications",
+        alerts.expiring_certifications,
+        plural(alerts.expiring_certifications, "expiring certification", "expiring certifications"),
+    )?;
+    let deadlines = alert_item(
+        "tray_alert_deadlines",
+        alerts.overdue_deadlines,
+        plural(alerts.overdue_deadlines, "overdue deadline", "overdue deadlines"),
+    )?;
+
     let dashboard = MenuItem::with_id(app, "tray_dashboard", "Dashboard", true, None::<&str>)?;
     let new_student = MenuItem::with_id(app, "tray_new_student", "New Student", true, None::<&str>)?;
     let clinical_logs = MenuItem::with_id(app, "tray_clinical_logs", "Clinical Logs", true, None::<&str>)?;
     let settings = MenuItem::with_id(app, "tray_settings", "Settings", true, None::<&str>)?;
     let quit = MenuItem::with_id(app, "tray_quit", "Quit", true, None::<&str>)?;
 
-    let tray_menu = Menu::with_items(
+    Menu::with_items(
         app,
         &[
             &show_hide,
             &PredefinedMenuItem::separator(app)?,
+            &submissions,
+            &evaluations,
+            &certifications,
+            &deadlines,
+            &PredefinedMenuItem::separator(app)?,
             &dashboard,
             &new_student,
             &clinical_logs,
@@ -31,13 +145,38 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
             &PredefinedMenuItem::separator(app)?,
             &quit,
         ],
-    )?;
+    )
+}
+
+fn tooltip(alerts: &TrayAlerts) -> String {
+    if alerts.total() == 0 {
+        return TOOLTIP.to_string();
+    }
+    let mut parts = Vec::new();
+    if alerts.pending_submissions > 0 {
+        parts.push(plural(alerts.pending_submissions, "submission", "submissions"));
+    }
+    if alerts.pending_evaluations > 0 {
+        parts.push(plural(alerts.pending_evaluations, "evaluation", "evaluations"));
+    }
+    if alerts.expiring_certifications > 0 {
+        parts.push(plural(alerts.expiring_certifications, "certification", "certifications"));
+    }
+    if alerts.overdue_deadlines > 0 {
+        parts.push(plural(alerts.overdue_deadlines, "overdue deadline", "overdue deadlines"));
+    }
+    format!("{} — {}", TOOLTIP, parts.join(", "))
+}
+
+pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+    // Counts are filled in by the first refresh once the database is ready
+    let tray_menu = build_menu(app, &TrayAlerts::default())?;
 
     // Build the tray icon
-    let _tray = TrayIconBuilder::new()
-        .icon(icon)
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(base_icon()?)
         .menu(&tray_menu)
-        .tooltip("NursEd Admin")
+        .tooltip(TOOLTIP)
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
                 button: MouseButton::Left,
@@ -60,3 +199,43 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
 
     Ok(())
 }
+
+/// Rebuilds the tray menu with current counts and swaps the tooltip and icon to match.
+pub fn apply_alerts<R: Runtime>(app: &AppHandle<R>, alerts: &TrayAlerts) -> Result<(), tauri::Error> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    tray.set_menu(Some(build_menu(app, alerts)?))?;
+    tray.set_tooltip(Some(tooltip(alerts)))?;
+    tray.set_icon(Some(if alerts.total() > 0 { attention_icon()? } else { base_icon()? }))?;
+    Ok(())
+}
+
+pub async fn refresh<R: Runtime>(app: &AppHandle<R>) -> Result<TrayAlerts, String> {
+    let pool = app.state::<DbState>().db.clone();
+    let alerts = load_alerts(&pool).await.map_err(|e| e.to_string())?;
+    apply_alerts(app, &alerts).map_err(|e| e.to_string())?;
+    Ok(alerts)
+}
+
+/// Refreshes the tray now and then every `REFRESH_INTERVAL`, rebuilding only when a count changed.
+pub fn spawn_refresh<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut shown: Option<TrayAlerts> = None;
+        loop {
+            let pool = app.state::<DbState>().db.clone();
+            match load_alerts(&pool).await {
+                Ok(alerts) if shown != Some(alerts) => {
+                    if let Err(e) = apply_alerts(&app, &alerts) {
+                        log::error!("Tray refresh failed: {}", e);
+                    } else {
+                        shown = Some(alerts);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("Tray refresh failed: {}", e),
+            }
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,395 @@
+// VBON evidence package for Board site visits (and the equivalent for any other regulatory framework).
+// Gathers, per active regulation of the framework, everything the mapping points at (syllabus reference, lesson plans and
+// when they were taught, materials, assessment and clinical experience), flags gaps and stale reviews,
+// and writes it out as a folder or zip with a PDF report, a CSV index and copies of referenced local files.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use sqlx::{Pool, Sqlite};
+
+use crate::models::{
+    Course, EvidenceLessonPlan, EvidenceMaterial, LessonPlan, RegulationEvidence, RegulatoryFramework, ReportBranding,
+    TeachingMaterial, VBONEvidenceReport, VBONMapping, VBONRegulation,
+};
+use crate::reports::{self, ReportWriter};
+
+pub const DEFAULT_REVIEW_INTERVAL_DAYS: i64 = 365;
+
+fn looks_like_link(text: &str) -> bool {
+    text.starts_with("http://") || text.starts_with("https://") || text.starts_with("file://") || Path::new(text).is_absolute()
+}
+
+/// Mapping material links: a JSON array of `{ title, url }`, or free text with one reference per line.
+fn parse_material_links(raw: Option<&str>) -> Vec<EvidenceMaterial> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Vec::new();
+    };
+    let material = |title: String, url: Option<String>| EvidenceMaterial {
+        title,
+        url,
+        material_type: "mapping".to_string(),
+        lesson_plan_id: None,
+        packaged_file: None,
+    };
+    if let Ok(links) = serde_json::from_str::<Vec<serde_json::Value>>(raw) {
+        return links
+            .iter()
+            .filter_map(|link| {
+                let url = link.get("url").and_then(|u| u.as_str()).map(str::to_string);
+                let title = link
+                    .get("title")
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string)
+                    .or_else(|| url.clone())?;
+                Some(material(title, url))
+            })
+            .collect();
+    }
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| material(line.to_string(), looks_like_link(line).then(|| line.to_string())))
+        .collect()
+}
+
+fn push_unique(list: &mut Vec<String>, value: Option<&str>) {
+    if let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) {
+        if !list.iter().any(|existing| existing.eq_ignore_ascii_case(value)) {
+            list.push(value.to_string());
+        }
+    }
+}
+
+fn find_gaps(evidence: &RegulationEvidence) -> Vec<String> {
+    let mut gaps = Vec::new();
+    match evidence.coverage_status.as_str() {
+        "covered" => {}
+        "partial" => gaps.push("Only partially covered".to_string()),
+        _ => gaps.push("Not covered".to_string()),
+    }
+    if evidence.syllabus_references.is_empty() {
+        gaps.push("No syllabus reference".to_string());
+    }
+    if evidence.lesson_plans.is_empty() {
+        gaps.push("No lesson plans linked".to_string());
+    } else if !evidence.lesson_plans.iter().any(|p| p.taught) {
+        gaps.push("Linked lesson plans not taught yet".to_string());
+    }
+    if evidence.assessment_methods.is_empty() {
+        gaps.push("No assessment method".to_string());
+    }
+    if evidence.regulation.category.to_lowercase().contains("clinical") && evidence.clinical_experience.is_none() {
+        gaps.push("No clinical experience documented".to_string());
+    }
+    if evidence.is_stale {
+        gaps.push(match (&evidence.last_reviewed_date, evidence.days_since_review) {
+            (Some(date), Some(days)) => format!("Last reviewed {} ({} days ago)", date, days),
+            _ => "Never reviewed".to_string(),
+        });
+    }
+    gaps
+}
+
+/// Evidence for every active regulation of `framework` as of `today`.
+pub async fn build(
+    pool: &Pool<Sqlite>,
+    framework: &RegulatoryFramework,
+    review_interval_days: i64,
+    today: chrono::NaiveDate,
+) -> Result<VBONEvidenceReport, sqlx::Error> {
+    let regulations = sqlx::query_as::<_, VBONRegulation>(
+        "SELECT * FROM vbon_regulations WHERE is_active = 1 AND framework_id = ? ORDER BY sort_order"
+    )
+    .bind(&framework.id)
+    .fetch_all(pool)
+    .await?;
+    let mappings: HashMap<String, VBONMapping> = sqlx::query_as::<_, VBONMapping>("SELECT * FROM vbon_mappings")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|m| (m.regulation_id.clone(), m))
+        .collect();
+    let plans = sqlx::query_as::<_, LessonPlan>("SELECT * FROM lesson_plans ORDER BY date")
+        .fetch_all(pool)
+        .await?;
+    let mut links: HashMap<String, HashSet<String>> = HashMap::new();
+    for (regulation_id, lesson_plan_id) in
+        sqlx::query_as::<_, (String, String)>("SELECT regulation_id, lesson_plan_id FROM lesson_plan_regulations")
+            .fetch_all(pool)
+            .await?
+    {
+        links.entry(regulation_id).or_default().insert(lesson_plan_id);
+    }
+    let mut materials_by_plan: HashMap<String, Vec<TeachingMaterial>> = HashMap::new();
+    for material in sqlx::query_as::<_, TeachingMaterial>("SELECT * FROM teaching_materials ORDER BY sort_order")
+        .fetch_all(pool)
+        .await?
+    {
+        materials_by_plan.entry(material.lesson_plan_id.clone()).or_default().push(material);
+    }
+    let courses: HashMap<String, Course> = sqlx::query_as::<_, Course>("SELECT * FROM courses")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|c| (c.id.clone(), c))
+        .collect();
+
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let mut evidence_list = Vec::new();
+    for regulation in regulations {
+        let mapping = mappings.get(&regulation.id);
+
+        let linked_plans: Vec<&LessonPlan> = match links.get(&regulation.id) {
+            Some(linked) => plans.iter().filter(|plan| linked.contains(&plan.id)).collect(),
+            None => Vec::new(),
+        };
+
+        let mut syllabus_references = Vec::new();
+        push_unique(&mut syllabus_references, mapping.and_then(|m| m.syllabus_reference.as_deref()));
+        let mut assessment_methods = Vec::new();
+        push_unique(&mut assessment_methods, mapping.and_then(|m| m.assessment_method.as_deref()));
+        let mut materials = parse_material_links(mapping.and_then(|m| m.material_links.as_deref()));
+
+        let mut lesson_plans = Vec::new();
+        for plan in &linked_plans {
+            if let Some(course) = plan.course_id.as_ref().and_then(|id| courses.get(id)) {
+                push_unique(&mut syllabus_references, course.syllabus_url.as_deref());
+            }
+            push_unique(&mut assessment_methods, plan.assessment_method.as_deref());
+            for material in materials_by_plan.get(&plan.id).into_iter().flatten() {
+                materials.push(EvidenceMaterial {
+                    title: material.title.clone(),
+                    url: Some(material.url.clone()),
+                    material_type: material.material_type.clone(),
+                    lesson_plan_id: Some(plan.id.clone()),
+                    packaged_file: None,
+                });
+            }
+            lesson_plans.push(EvidenceLessonPlan {
+                id: plan.id.clone(),
+                date: plan.date.clone(),
+                course_name: plan.course_name.clone(),
+                week_number: plan.week_number,
+                chapter: plan.chapter.clone(),
+                topic: plan.topic.clone(),
+                assessment_method: plan.assessment_method.clone(),
+                taught: plan.date.get(..10).unwrap_or(&plan.date) <= today_str.as_str(),
+            });
+        }
+
+        let last_reviewed_date = mapping.and_then(|m| m.last_reviewed_date.clone());
+        let days_since_review = last_reviewed_date
+            .as_deref()
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d.get(..10).unwrap_or(d), "%Y-%m-%d").ok())
+            .map(|d| (today - d).num_days());
+
+        let mut evidence = RegulationEvidence {
+            coverage_status: mapping
+                .map(|m| m.coverage_status.clone())
+                .unwrap_or_else(|| "not_covered".to_string()),
+            syllabus_references,
+            lesson_plans,
+            materials,
+            assessment_methods,
+            clinical_experience: mapping
+                .and_then(|m| m.clinical_experience.clone())
+                .filter(|c| !c.trim().is_empty()),
+            is_stale: days_since_review.map(|d| d > review_interval_days).unwrap_or(true),
+            last_reviewed_date,
+            days_since_review,
+            gaps: Vec::new(),
+            regulation,
+        };
+        evidence.gaps = find_gaps(&evidence);
+        // A newer catalog edition changed or removed the regulation since the mapping was reviewed
+        if let Some(mapping) = mapping.filter(|m| m.needs_review) {
+            let reason = mapping.review_reason.as_deref().unwrap_or("regulation changed");
+            evidence.gaps.push(format!("Needs re-review: {}", reason));
+        }
+        evidence_list.push(evidence);
+    }
+
+    Ok(VBONEvidenceReport {
+        framework_id: framework.id.clone(),
+        framework_code: framework.code.clone(),
+        framework_name: framework.name.clone(),
+        generated_on: today_str,
+        review_interval_days,
+        gap_count: evidence_list.iter().filter(|e| !e.gaps.is_empty()).count() as i32,
+        stale_count: evidence_list.iter().filter(|e| e.is_stale).count() as i32,
+        regulations: evidence_list,
+    })
+}
+
+/// Where package files go: a folder on disk or entries in a zip archive.
+enum PackageSink {
+    Folder(PathBuf),
+    Zip(zip::ZipWriter<std::fs::File>),
+}
+
+impl PackageSink {
+    fn add(&mut self, relative_path: &str, bytes: &[u8]) -> Result<(), String> {
+        match self {
+            PackageSink::Folder(root) => {
+                let path = root.join(relative_path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                std::fs::write(&path, bytes).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+            }
+            PackageSink::Zip(archive) => {
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated);
+                archive.start_file(relative_path, options).map_err(|e| e.to_string())?;
+                archive.write_all(bytes).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        if let PackageSink::Zip(archive) = self {
+            archive.finish().map_err(|e| format!("Could not write zip: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+// Folder names inside the package, e.g. "B.1" stays readable but "18VAC90/27" does not nest.
+// Text that leaves nothing usable (empty, or only dots like "..") falls back to `fallback`.
+fn safe_name(text: &str, fallback: &str) -> String {
+    let sanitize = |text: &str| -> String {
+        let name: String = text
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+            .collect();
+        name.trim_matches('.').to_string()
+    };
+    [sanitize(text), sanitize(fallback)]
+        .into_iter()
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "unnamed".to_string())
+}
+
+// `name`, or `2-name`, `3-name`, ... when it is already taken (compared case-insensitively,
+// since the package may be unpacked on a case-insensitive filesystem)
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 1;
+    while !used.insert(candidate.to_ascii_lowercase()) {
+        n += 1;
+        candidate = format!("{}-{}", n, name);
+    }
+    candidate
+}
+
+fn local_file(url: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(url.trim().strip_prefix("file://").unwrap_or(url.trim()));
+    (path.is_absolute() && path.is_file()).then_some(path)
+}
+
+fn csv_bytes(rows: &[Vec<String>]) -> Result<Vec<u8>, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.write_record(row).map_err(|e| e.to_string())?;
+    }
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// Writes the package and returns the path of the folder or zip created.
+pub fn export(mut report: VBONEvidenceReport, output_path: &str, as_zip: bool, branding: &ReportBranding) -> Result<String, String> {
+    let mut path = PathBuf::from(output_path);
+    if as_zip && path.extension().map(|e| !e.eq_ignore_ascii_case("zip")).unwrap_or(true) {
+        path.set_extension("zip");
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if !parent.is_dir() {
+            return Err(format!("Folder does not exist: {}", parent.display()));
+        }
+    }
+
+    let mut sink = if as_zip {
+        let file = std::fs::File::create(&path).map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+        PackageSink::Zip(zip::ZipWriter::new(file))
+    } else {
+        if path.exists() && std::fs::read_dir(&path).map(|mut d| d.next().is_some()).unwrap_or(true) {
+            return Err(format!("{} already exists and is not empty", path.display()));
+        }
+        std::fs::create_dir_all(&path).map_err(|e| e.to_string())?;
+        PackageSink::Folder(path.clone())
+    };
+
+    // Referenced files first, so the report and index can point at their packaged copies.
+    // Codes that sanitize alike (e.g. "A/1" and "A:1") get distinct folders.
+    let mut used_folders: HashSet<String> = HashSet::new();
+    for evidence in report.regulations.iter_mut() {
+        let folder = unique_name(safe_name(&evidence.regulation.code, &evidence.regulation.id), &mut used_folders);
+        // materials.csv is written alongside the copied files
+        let mut used_names: HashSet<String> = HashSet::from(["materials.csv".to_string()]);
+        for material in evidence.materials.iter_mut() {
+            let Some(source) = material.url.as_deref().and_then(local_file) else { continue };
+            let file_name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let name = unique_name(safe_name(&file_name, "file"), &mut used_names);
+            let bytes = std::fs::read(&source).map_err(|e| format!("Could not read {}: {}", source.display(), e))?;
+            let relative = format!("{}/{}", folder, name);
+            sink.add(&relative, &bytes)?;
+            material.packaged_file = Some(relative);
+        }
+
+        let mut links = vec![vec![
+            "Title".to_string(),
+            "Type".to_string(),
+            "Link".to_string(),
+            "Lesson plan".to_string(),
+            "Packaged file".to_string(),
+        ]];
+        for material in &evidence.materials {
+            links.push(vec![
+                material.title.clone(),
+                material.material_type.clone(),
+                material.url.clone().unwrap_or_default(),
+                material.lesson_plan_id.clone().unwrap_or_default(),
+                material.packaged_file.clone().unwrap_or_default(),
+            ]);
+        }
+        sink.add(&format!("{}/materials.csv", folder), &csv_bytes(&links)?)?;
+    }
+
+    let mut index = vec![[
+        "Code", "Section", "Category", "Title", "Coverage", "Syllabus", "Lesson plans taught", "Assessment",
+        "Clinical experience", "Last reviewed", "Stale", "Gaps",
+    ]
+    .map(String::from)
+    .to_vec()];
+    for e in &report.regulations {
+        index.push(vec![
+            e.regulation.code.clone(),
+            e.regulation.section.clone(),
+            e.regulation.category.clone(),
+            e.regulation.title.clone(),
+            e.coverage_status.clone(),
+            e.syllabus_references.join("; "),
+            e.lesson_plans
+                .iter()
+                .filter(|p| p.taught)
+                .map(|p| format!("{} {} ({})", p.date, p.topic, p.course_name))
+                .collect::<Vec<_>>()
+                .join("; "),
+            e.assessment_methods.join("; "),
+            e.clinical_experience.clone().unwrap_or_default(),
+            e.last_reviewed_date.clone().unwrap_or_default(),
+            if e.is_stale { "yes" } else { "no" }.to_string(),
+            e.gaps.join("; "),
+        ]);
+    }
+    sink.add("evidence-index.csv", &csv_bytes(&index)?)?;
+
+    let mut writer = ReportWriter::new(&format!("{} Evidence Package", report.framework_code), branding)?;
+    reports::vbon_evidence(&mut writer, &report);
+    writer.signature_block(&["Program Director".to_string()]);
+    sink.add("evidence-report.pdf", &writer.to_bytes()?)?;
+
+    sink.finish()?;
+    Ok(path.to_string_lossy().to_string())
+}
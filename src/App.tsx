@@ -25,21 +25,27 @@ import ClinicalSchedule from './pages/ClinicalSchedule';
 import DailyTracking from './pages/DailyTracking';
 import HourApprovals from './pages/HourApprovals';
 import VRTracking from './pages/VRTracking';
-import { useMenuEvents, setAboutDialogTrigger } from './hooks/useMenuEvents';
+import { useMenuEvents, setAboutDialogTrigger, setExportReportsModalTrigger, setImportDataModalTrigger } from './hooks/useMenuEvents';
 import { clsx } from 'clsx';
 import { useState, useEffect } from 'react';
 import { AboutDialog } from './components/AboutDialog';
+import ReportExportDialog from './components/ReportExportDialog';
+import SpreadsheetImportDialog from './components/SpreadsheetImportDialog';
 
 function AppContent() {
   const { isCollapsed } = useSidebar();
   const [showAboutDialog, setShowAboutDialog] = useState(false);
+  const [showReportExport, setShowReportExport] = useState(false);
+  const [showSpreadsheetImport, setShowSpreadsheetImport] = useState(false);
   
   // Listen for native menu events from Tauri
   useMenuEvents();
 
-  // Register about dialog trigger
+  // Register about dialog, report export and spreadsheet import triggers
   useEffect(() => {
     setAboutDialogTrigger(() => setShowAboutDialog(true));
+    setExportReportsModalTrigger(() => setShowReportExport(true));
+    setImportDataModalTrigger(() => setShowSpreadsheetImport(true));
   }, []);
 
   return (
@@ -80,6 +86,8 @@ function AppContent() {
         </div>
       </main>
       <AboutDialog isOpen={showAboutDialog} onClose={() => setShowAboutDialog(false)} />
+      <ReportExportDialog isOpen={showReportExport} onClose={() => setShowReportExport(false)} />
+      <SpreadsheetImportDialog isOpen={showSpreadsheetImport} onClose={() => setShowSpreadsheetImport(false)} />
     </div>
   );
 }
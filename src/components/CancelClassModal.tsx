@@ -0,0 +1,116 @@
+import { useState, useEffect } from 'react';
+import { CalendarX, Loader2, AlertTriangle, ArrowRight } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import { Modal } from '@/components/Modal';
+import { FormField, Input } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { rescheduleCourseSession } from '@/lib/db';
+import type { LessonPlan, RescheduleResult } from '@/types';
+
+interface CancelClassModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  plan: LessonPlan | null;
+  onRescheduled: () => void;
+}
+
+// Cancel a class day (e.g., a snow day) and push it and every later session of the course to the next meeting day
+export default function CancelClassModal({ isOpen, onClose, plan, onRescheduled }: CancelClassModalProps) {
+  const toast = useToast();
+  const [reason, setReason] = useState('Snow day');
+  const [recordClosure, setRecordClosure] = useState(true);
+  const [preview, setPreview] = useState<RescheduleResult | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const request = (previewOnly: boolean) => ({
+    courseId: plan?.courseId ?? '',
+    date: plan?.date ?? '',
+    reason: reason.trim() || undefined,
+    recordClosure,
+    preview: previewOnly,
+  });
+
+  useEffect(() => {
+    if (!isOpen || !plan?.courseId) return;
+    setPreview(null);
+    rescheduleCourseSession(request(true))
+      .then(setPreview)
+      .catch(error => {
+        console.error('Failed to preview reschedule:', error);
+        toast.error('Error', typeof error === 'string' ? error : 'Failed to preview the new schedule');
+      });
+  }, [isOpen, plan?.id]);
+
+  const handleConfirm = async () => {
+    setBusy(true);
+    try {
+      const result = await rescheduleCourseSession(request(false));
+      toast.success(
+        'Class Cancelled',
+        `${result.moves.length} session${result.moves.length === 1 ? '' : 's'} moved${result.eventsUpdated > 0 ? `, ${result.eventsUpdated} calendar events updated` : ''}.`
+      );
+      onRescheduled();
+      onClose();
+    } catch (error) {
+      console.error('Failed to reschedule:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to reschedule the course');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title={plan ? `Cancel ${plan.courseName} on ${format(parseISO(plan.date), 'MMM d')}` : 'Cancel Class'}
+      size="lg"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={onClose} className="btn btn-outline">Keep Class</button>
+          <button onClick={handleConfirm} disabled={busy || !preview} className="btn btn-primary flex items-center gap-2">
+            {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <CalendarX className="w-4 h-4" />}
+            Cancel and Shift Sessions
+          </button>
+        </div>
+      }
+    >
+      {plan && !plan.courseId ? (
+        <p className="text-sm text-gray-600">This lesson plan is not assigned to a course, so there is no schedule to shift.</p>
+      ) : (
+        <div className="space-y-4">
+          <FormField label="Reason">
+            <Input value={reason} onChange={(e) => setReason(e.target.value)} />
+          </FormField>
+          <label className="flex items-center gap-2 text-sm text-gray-700">
+            <input type="checkbox" checked={recordClosure} onChange={(e) => setRecordClosure(e.target.checked)} />
+            Add this day to the holiday calendar (school closed)
+          </label>
+
+          {preview && (
+            <div className="space-y-2">
+              {preview.warnings.map(warning => (
+                <p key={warning} className="text-sm text-amber-700 flex items-center gap-2">
+                  <AlertTriangle className="w-4 h-4" />
+                  {warning}
+                </p>
+              ))}
+              <ul className="max-h-64 overflow-y-auto divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+                {preview.moves.map(move => (
+                  <li key={move.lessonPlanId} className="flex items-center justify-between px-3 py-1.5">
+                    <span className="text-gray-900 truncate mr-3">{move.topic}</span>
+                    <span className="flex items-center gap-2 text-gray-600 whitespace-nowrap">
+                      {format(parseISO(move.fromDate), 'EEE, MMM d')}
+                      <ArrowRight className="w-3 h-3" />
+                      <span className="font-medium text-teal-700">{format(parseISO(move.toDate), 'EEE, MMM d')}</span>
+                    </span>
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+        </div>
+      )}
+    </Modal>
+  );
+}
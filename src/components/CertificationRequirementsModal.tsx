@@ -0,0 +1,216 @@
+import { useState, useEffect } from 'react';
+import { Plus, Trash2, Edit2, Loader2 } from 'lucide-react';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Textarea, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { getCertificationRequirements, saveCertificationRequirement, deleteCertificationRequirement } from '@/lib/db';
+import type { CertificationRequirement, ClinicalSite } from '@/types';
+
+interface CertificationRequirementsModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  sites: ClinicalSite[];
+  onChanged?: () => void;
+}
+
+export const CERTIFICATION_TYPES = ['BLS', 'CPR', 'Immunization', 'Background Check', 'Drug Screen', 'Other'];
+
+const emptyRequirement: CertificationRequirement = {
+  id: '',
+  name: '',
+  certificationType: 'BLS',
+  isActive: 1,
+  createdAt: '',
+};
+
+// Edit the catalog of certifications students need before they can be scheduled for clinical
+export default function CertificationRequirementsModal({ isOpen, onClose, sites, onChanged }: CertificationRequirementsModalProps) {
+  const toast = useToast();
+  const [requirements, setRequirements] = useState<CertificationRequirement[]>([]);
+  const [editing, setEditing] = useState<CertificationRequirement | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  const loadRequirements = async () => {
+    try {
+      setRequirements(await getCertificationRequirements());
+    } catch (error) {
+      console.error('Failed to load requirements:', error);
+    }
+  };
+
+  useEffect(() => {
+    if (isOpen) {
+      setEditing(null);
+      loadRequirements();
+    }
+  }, [isOpen]);
+
+  const handleSave = async (requirement: CertificationRequirement) => {
+    setSaving(true);
+    try {
+      await saveCertificationRequirement(requirement);
+      setEditing(null);
+      loadRequirements();
+      onChanged?.();
+    } catch (error) {
+      console.error('Failed to save requirement:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to save the requirement');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (requirement: CertificationRequirement) => {
+    if (!confirm(`Delete the "${requirement.name}" requirement?`)) return;
+    try {
+      await deleteCertificationRequirement(requirement.id);
+      loadRequirements();
+      onChanged?.();
+    } catch (error) {
+      console.error('Failed to delete requirement:', error);
+      toast.error('Error', 'Failed to delete the requirement');
+    }
+  };
+
+  const siteName = (siteId?: string) => sites.find(s => s.id === siteId)?.name ?? 'Unknown site';
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title="Clinical Certification Requirements"
+      size="xl"
+      footer={
+        <div className="flex justify-end gap-3">
+          {editing ? (
+            <>
+              <button onClick={() => setEditing(null)} className="btn btn-outline">Back</button>
+              <button
+                onClick={() => handleSave(editing)}
+                disabled={saving || !editing.name.trim()}
+                className="btn btn-primary flex items-center gap-2"
+              >
+                {saving && <Loader2 className="w-4 h-4 animate-spin" />}
+                Save Requirement
+              </button>
+            </>
+          ) : (
+            <>
+              <button onClick={onClose} className="btn btn-outline">Close</button>
+              <button onClick={() => setEditing({ ...emptyRequirement })} className="btn btn-primary flex items-center gap-2">
+                <Plus className="w-4 h-4" />
+                Add Requirement
+              </button>
+            </>
+          )}
+        </div>
+      }
+    >
+      {editing ? (
+        <div className="space-y-4">
+          <div className="grid grid-cols-2 gap-4">
+            <FormField label="Requirement" required>
+              <Input
+                value={editing.name}
+                placeholder="e.g., TB screening"
+                onChange={(e) => setEditing({ ...editing, name: e.target.value })}
+              />
+            </FormField>
+            <FormField label="Certification type" required>
+              <Select
+                value={editing.certificationType}
+                onChange={(e) => setEditing({ ...editing, certificationType: e.target.value })}
+              >
+                {CERTIFICATION_TYPES.map(type => <option key={type} value={type}>{type}</option>)}
+              </Select>
+            </FormField>
+          </div>
+          <div className="grid grid-cols-2 gap-4">
+            <FormField label="Name contains" hint="Narrows the type, e.g. TB among immunizations">
+              <Input
+                value={editing.nameKeyword ?? ''}
+                onChange={(e) => setEditing({ ...editing, nameKeyword: e.target.value || undefined })}
+              />
+            </FormField>
+            <FormField label="Valid for (months)" hint="From the issue date; leave blank to use the expiry date">
+              <Input
+                type="number"
+                min="1"
+                value={editing.validityMonths ?? ''}
+                onChange={(e) => setEditing({ ...editing, validityMonths: e.target.value ? parseInt(e.target.value, 10) : undefined })}
+              />
+            </FormField>
+          </div>
+          <div className="grid grid-cols-2 gap-4">
+            <FormField label="Cohort" hint="Blank applies to every cohort">
+              <Input
+                value={editing.cohort ?? ''}
+                onChange={(e) => setEditing({ ...editing, cohort: e.target.value || undefined })}
+              />
+            </FormField>
+            <FormField label="Site">
+              <Select
+                value={editing.siteId ?? ''}
+                onChange={(e) => setEditing({ ...editing, siteId: e.target.value || undefined })}
+              >
+                <option value="">Every site</option>
+                {sites.map(site => <option key={site.id} value={site.id}>{site.name}</option>)}
+              </Select>
+            </FormField>
+          </div>
+          <FormField label="Notes">
+            <Textarea
+              rows={2}
+              value={editing.notes ?? ''}
+              onChange={(e) => setEditing({ ...editing, notes: e.target.value || undefined })}
+            />
+          </FormField>
+          <label className="flex items-center gap-2 text-sm text-gray-700">
+            <input
+              type="checkbox"
+              checked={editing.isActive === 1}
+              onChange={(e) => setEditing({ ...editing, isActive: e.target.checked ? 1 : 0 })}
+            />
+            Enforce when scheduling clinical assignments
+          </label>
+        </div>
+      ) : requirements.length === 0 ? (
+        <p className="text-sm text-gray-500">No requirements yet. Students can be scheduled without any certifications.</p>
+      ) : (
+        <ul className="divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+          {requirements.map(requirement => (
+            <li key={requirement.id} className="flex items-center justify-between px-3 py-2">
+              <div>
+                <span className="font-semibold text-gray-900">{requirement.name}</span>
+                <span className={`ml-2 px-2 py-0.5 text-xs rounded-full ${requirement.isActive ? 'bg-green-100 text-green-700' : 'bg-gray-100 text-gray-500'}`}>
+                  {requirement.isActive ? 'Enforced' : 'Off'}
+                </span>
+                <div className="text-gray-500">
+                  {requirement.certificationType}
+                  {requirement.nameKeyword ? ` containing "${requirement.nameKeyword}"` : ''}
+                  {requirement.validityMonths ? ` · valid ${requirement.validityMonths} months` : ''}
+                  {requirement.cohort ? ` · ${requirement.cohort}` : ''}
+                  {requirement.siteId ? ` · ${siteName(requirement.siteId)}` : ''}
+                </div>
+              </div>
+              <div className="flex items-center gap-2">
+                <button
+                  onClick={() => handleSave({ ...requirement, isActive: requirement.isActive ? 0 : 1 })}
+                  className="btn btn-outline btn-sm"
+                >
+                  {requirement.isActive ? 'Turn Off' : 'Enforce'}
+                </button>
+                <button onClick={() => setEditing(requirement)} className="p-1.5 text-gray-400 hover:text-blue-600" aria-label="Edit requirement">
+                  <Edit2 className="w-4 h-4" />
+                </button>
+                <button onClick={() => handleDelete(requirement)} className="p-1.5 text-gray-400 hover:text-red-600" aria-label="Delete requirement">
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </div>
+            </li>
+          ))}
+        </ul>
+      )}
+    </Modal>
+  );
+}
@@ -0,0 +1,109 @@
+import { useState, useEffect } from 'react';
+import { ShieldCheck, ShieldAlert, Loader2 } from 'lucide-react';
+import { format } from 'date-fns';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Select } from '@/components/FormField';
+import { getSiteClearance } from '@/lib/db';
+import type { ClinicalClearance, ClinicalSite } from '@/types';
+
+interface ClinicalClearanceModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  sites: ClinicalSite[];
+  onManageCertifications: (studentId: string, studentName: string) => void;
+  refreshKey?: number; // Bumped when certifications change
+}
+
+// One student's clearance: cleared, or the requirements still blocking them
+export function ClearanceRow({ clearance, onManage }: { clearance: ClinicalClearance; onManage: () => void }) {
+  const blockers = clearance.requirements.filter(r => r.status !== 'met');
+  return (
+    <li className="flex items-center justify-between px-3 py-2 text-sm">
+      <div className="flex items-start gap-2">
+        {clearance.cleared
+          ? <ShieldCheck className="w-4 h-4 text-green-600 mt-0.5" />
+          : <ShieldAlert className="w-4 h-4 text-red-600 mt-0.5" />}
+        <div>
+          <span className="font-medium text-gray-900">{clearance.studentName}</span>
+          {blockers.length > 0 && (
+            <div className="text-xs text-red-700">
+              {blockers.map(b => `${b.requirementName} (${b.status})`).join(', ')}
+            </div>
+          )}
+        </div>
+      </div>
+      <button onClick={onManage} className="btn btn-outline btn-sm">Certifications</button>
+    </li>
+  );
+}
+
+// Which students are cleared for clinical at a site on a date
+export default function ClinicalClearanceModal({ isOpen, onClose, sites, onManageCertifications, refreshKey }: ClinicalClearanceModalProps) {
+  const [siteId, setSiteId] = useState('');
+  const [date, setDate] = useState(format(new Date(), 'yyyy-MM-dd'));
+  const [clearances, setClearances] = useState<ClinicalClearance[]>([]);
+  const [loading, setLoading] = useState(false);
+
+  useEffect(() => {
+    if (isOpen && !siteId && sites.length > 0) setSiteId(sites[0].id);
+  }, [isOpen, sites]);
+
+  useEffect(() => {
+    if (!isOpen || !siteId || !date) return;
+    setLoading(true);
+    getSiteClearance(siteId, date)
+      .then(setClearances)
+      .catch(error => {
+        console.error('Failed to load clearance:', error);
+        setClearances([]);
+      })
+      .finally(() => setLoading(false));
+  }, [isOpen, siteId, date, refreshKey]);
+
+  const uncleared = clearances.filter(c => !c.cleared).length;
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title="Clinical Clearance"
+      size="lg"
+      footer={
+        <div className="flex justify-end">
+          <button onClick={onClose} className="btn btn-outline">Close</button>
+        </div>
+      }
+    >
+      <div className="space-y-4">
+        <div className="grid grid-cols-2 gap-4">
+          <FormField label="Site">
+            <Select value={siteId} onChange={(e) => setSiteId(e.target.value)}>
+              {sites.map(site => <option key={site.id} value={site.id}>{site.name}</option>)}
+            </Select>
+          </FormField>
+          <FormField label="Date">
+            <Input type="date" value={date} onChange={(e) => setDate(e.target.value)} />
+          </FormField>
+        </div>
+        {loading ? (
+          <div className="flex justify-center py-6"><Loader2 className="w-6 h-6 animate-spin text-indigo-600" /></div>
+        ) : (
+          <>
+            <p className="text-sm text-gray-600">
+              {clearances.length - uncleared} of {clearances.length} students cleared
+            </p>
+            <ul className="max-h-96 overflow-y-auto divide-y divide-gray-100 border border-gray-100 rounded-lg">
+              {clearances.map(clearance => (
+                <ClearanceRow
+                  key={clearance.studentId}
+                  clearance={clearance}
+                  onManage={() => onManageCertifications(clearance.studentId, clearance.studentName)}
+                />
+              ))}
+            </ul>
+          </>
+        )}
+      </div>
+    </Modal>
+  );
+}
@@ -0,0 +1,185 @@
+import { useState, useEffect } from 'react';
+import { downloadDir, join } from '@tauri-apps/api/path';
+import { Settings2, Download, Save } from 'lucide-react';
+import { getCompHoursPolicy, updateCompHoursPolicy, exportCompTimesheet } from '@/lib/db';
+import { useInstructor } from '@/components/InstructorProvider';
+import { useToast } from '@/components/Toast';
+import type { CompHoursPolicy } from '@/types';
+
+const inputClass =
+  'w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-indigo-600';
+
+// Employer comp hours policy and pay period timesheet export
+export default function CompHoursPolicyPanel({ onPolicyChange }: { onPolicyChange?: (policy: CompHoursPolicy) => void }) {
+  const { profile } = useInstructor();
+  const toast = useToast();
+  const [policy, setPolicy] = useState<CompHoursPolicy | null>(null);
+  const [saving, setSaving] = useState(false);
+  const [period, setPeriod] = useState(() => {
+    const now = new Date();
+    const start = new Date(now.getFullYear(), now.getMonth(), 1);
+    const end = new Date(now.getFullYear(), now.getMonth() + 1, 0);
+    const iso = (d: Date) => `${d.getFullYear()}-${String(d.getMonth() + 1).padStart(2, '0')}-${String(d.getDate()).padStart(2, '0')}`;
+    return { startDate: iso(start), endDate: iso(end) };
+  });
+  const [exporting, setExporting] = useState<'csv' | 'pdf' | null>(null);
+
+  useEffect(() => {
+    getCompHoursPolicy()
+      .then(setPolicy)
+      .catch((error) => console.error('Failed to load comp hours policy:', error));
+  }, []);
+
+  const handleSave = async () => {
+    if (!policy) return;
+    setSaving(true);
+    try {
+      await updateCompHoursPolicy(policy);
+      onPolicyChange?.(policy);
+      toast.success('Policy Saved', 'New comp hours will follow this policy.');
+    } catch (error) {
+      console.error('Failed to save comp hours policy:', error);
+      toast.error('Failed', typeof error === 'string' ? error : 'Failed to save policy.');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleExport = async (format: 'csv' | 'pdf') => {
+    setExporting(format);
+    try {
+      const employeeName = `${profile.firstName || ''} ${profile.lastName || ''}`.trim() || 'Instructor';
+      const outputPath = await join(
+        await downloadDir(),
+        `comp-timesheet-${period.startDate}-to-${period.endDate}.${format}`
+      );
+      const written = await exportCompTimesheet({
+        ...period,
+        format,
+        outputPath,
+        employeeName,
+        branding: {
+          programName: profile.institution || 'Nursing Program',
+          preparedBy: employeeName,
+        },
+      });
+      toast.success('Timesheet Exported', written);
+    } catch (error) {
+      console.error('Failed to export timesheet:', error);
+      toast.error('Export Failed', typeof error === 'string' ? error : 'Failed to export timesheet.');
+    } finally {
+      setExporting(null);
+    }
+  };
+
+  if (!policy) return null;
+
+  const numberOrUndefined = (value: string) => (value === '' ? undefined : Number(value));
+
+  return (
+    <div className="mt-6 grid grid-cols-1 lg:grid-cols-2 gap-6">
+      <div className="card p-6">
+        <h3 className="text-lg font-bold text-gray-800 flex items-center gap-2 mb-4">
+          <Settings2 className="w-5 h-5 text-indigo-600" />
+          Comp Hours Policy
+        </h3>
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Accrual cap (hours)</label>
+            <input
+              type="number"
+              min="0.5"
+              step="0.5"
+              value={policy.accrualCap ?? ''}
+              placeholder="No cap"
+              onChange={(e) => setPolicy({ ...policy, accrualCap: numberOrUndefined(e.target.value) })}
+              className={inputClass}
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Expires after (months)</label>
+            <input
+              type="number"
+              min="1"
+              step="1"
+              value={policy.expirationMonths ?? ''}
+              placeholder="Set per entry"
+              onChange={(e) => setPolicy({ ...policy, expirationMonths: numberOrUndefined(e.target.value) })}
+              className={inputClass}
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Red warning (days)</label>
+            <input
+              type="number"
+              min="0"
+              value={policy.warningDaysRed}
+              onChange={(e) => setPolicy({ ...policy, warningDaysRed: Number(e.target.value) || 0 })}
+              className={inputClass}
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Yellow warning (days)</label>
+            <input
+              type="number"
+              min="0"
+              value={policy.warningDaysYellow}
+              onChange={(e) => setPolicy({ ...policy, warningDaysYellow: Number(e.target.value) || 0 })}
+              className={inputClass}
+            />
+          </div>
+        </div>
+        <label className="flex items-center gap-2 mt-4 text-sm text-gray-700">
+          <input
+            type="checkbox"
+            checked={policy.requiresApproval}
+            onChange={(e) => setPolicy({ ...policy, requiresApproval: e.target.checked })}
+          />
+          Earned hours require approval before they count
+        </label>
+        <div className="flex justify-end mt-4">
+          <button onClick={handleSave} disabled={saving} className="btn btn-primary flex items-center gap-2">
+            <Save className="w-4 h-4" />
+            {saving ? 'Saving...' : 'Save Policy'}
+          </button>
+        </div>
+      </div>
+
+      <div className="card p-6">
+        <h3 className="text-lg font-bold text-gray-800 flex items-center gap-2 mb-4">
+          <Download className="w-5 h-5 text-indigo-600" />
+          Timesheet Export
+        </h3>
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Pay period start</label>
+            <input
+              type="date"
+              value={period.startDate}
+              onChange={(e) => setPeriod({ ...period, startDate: e.target.value })}
+              className={inputClass}
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Pay period end</label>
+            <input
+              type="date"
+              value={period.endDate}
+              onChange={(e) => setPeriod({ ...period, endDate: e.target.value })}
+              className={inputClass}
+            />
+          </div>
+        </div>
+        <p className="text-xs text-gray-500 mt-2">Saved to your Downloads folder.</p>
+        <div className="flex justify-end gap-2 mt-4">
+          <button onClick={() => handleExport('csv')} disabled={!!exporting} className="btn btn-outline">
+            {exporting === 'csv' ? 'Exporting...' : 'Export CSV'}
+          </button>
+          <button onClick={() => handleExport('pdf')} disabled={!!exporting} className="btn btn-primary">
+            {exporting === 'pdf' ? 'Exporting...' : 'Export PDF'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
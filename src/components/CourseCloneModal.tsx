@@ -0,0 +1,118 @@
+import { useState, useEffect } from 'react';
+import { Copy, Loader2 } from 'lucide-react';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { cloneCourse } from '@/lib/db';
+import type { Course } from '@/types';
+
+interface CourseCloneModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  course: Course | null;
+  onCloned: () => void;
+}
+
+const SEMESTER_ORDER = ['Spring', 'Summer', 'Fall'];
+
+// The term after the course's own, as a starting suggestion
+function nextTerm(course: Course | null): { semester: string; year: number } {
+  const year = course?.year ?? new Date().getFullYear();
+  const index = SEMESTER_ORDER.indexOf(course?.semester ?? '');
+  if (index < 0) return { semester: 'Fall', year };
+  return index === SEMESTER_ORDER.length - 1
+    ? { semester: SEMESTER_ORDER[0], year: year + 1 }
+    : { semester: SEMESTER_ORDER[index + 1], year };
+}
+
+// Copy a course with its lesson plans, materials and regulation links forward to a new term
+export default function CourseCloneModal({ isOpen, onClose, course, onCloned }: CourseCloneModalProps) {
+  const toast = useToast();
+  const [form, setForm] = useState({ semester: 'Fall', year: new Date().getFullYear(), startDate: '', code: '', name: '' });
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    if (isOpen && course) {
+      setForm({ ...nextTerm(course), startDate: '', code: course.code, name: course.name });
+    }
+  }, [isOpen, course?.id]);
+
+  const handleClone = async () => {
+    if (!course) return;
+    setBusy(true);
+    try {
+      const result = await cloneCourse({
+        courseId: course.id,
+        semester: form.semester,
+        year: form.year,
+        startDate: form.startDate,
+        code: form.code || undefined,
+        name: form.name || undefined,
+      });
+      toast.success(
+        'Course Copied',
+        `${result.course.code} ${form.semester} ${form.year}: ${result.lessonPlanCount} lesson plans, ${result.materialCount} materials, ${result.regulationLinkCount} regulation links.`
+      );
+      result.warnings.forEach(warning => toast.warning('Check the New Term', warning));
+      onCloned();
+      onClose();
+    } catch (error) {
+      console.error('Failed to copy course:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to copy the course');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title={course ? `Copy ${course.code} to a New Term` : 'Copy Course'}
+      size="md"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={onClose} className="btn btn-outline">Cancel</button>
+          <button onClick={handleClone} disabled={busy || !form.startDate || !form.semester} className="btn btn-primary flex items-center gap-2">
+            {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <Copy className="w-4 h-4" />}
+            Copy Course
+          </button>
+        </div>
+      }
+    >
+      <div className="space-y-4">
+        <p className="text-sm text-gray-600">
+          Lesson plans keep their week number and weekday, counted from the first day of the new term.
+          Last term's "notes for next time" carry over as last-taught notes.
+        </p>
+        <div className="grid grid-cols-2 gap-4">
+          <FormField label="Semester" required>
+            <Select value={form.semester} onChange={(e) => setForm({ ...form, semester: e.target.value })}>
+              {SEMESTER_ORDER.map(semester => (
+                <option key={semester} value={semester}>{semester}</option>
+              ))}
+            </Select>
+          </FormField>
+          <FormField label="Year" required>
+            <Input
+              type="number"
+              value={form.year}
+              onChange={(e) => setForm({ ...form, year: parseInt(e.target.value, 10) || form.year })}
+            />
+          </FormField>
+        </div>
+        <FormField label="First day of classes" required>
+          <Input type="date" value={form.startDate} onChange={(e) => setForm({ ...form, startDate: e.target.value })} />
+        </FormField>
+        <div className="grid grid-cols-2 gap-4">
+          <FormField label="Course Code">
+            <Input value={form.code} onChange={(e) => setForm({ ...form, code: e.target.value })} />
+          </FormField>
+          <FormField label="Course Name">
+            <Input value={form.name} onChange={(e) => setForm({ ...form, name: e.target.value })} />
+          </FormField>
+        </div>
+      </div>
+    </Modal>
+  );
+}
@@ -1,8 +1,8 @@
-import { useState, useCallback } from 'react';
+import { useState } from 'react';
 import { Upload, AlertCircle, CheckCircle, X, FileSpreadsheet, ArrowRight } from 'lucide-react';
 import { clsx } from 'clsx';
-import { PreceptorEvaluation, Student } from '@/types';
-import { importPreceptorEvaluations, loadStudents } from '@/lib/db';
+import { open } from '@tauri-apps/plugin-dialog';
+import { ImportResult, inspectSpreadsheet, suggestEvaluationFormMapping, importEvaluationFormCsv } from '@/lib/db';
 
 interface EvaluationCSVImportProps {
   isOpen: boolean;
@@ -10,253 +10,89 @@ interface EvaluationCSVImportProps {
   onImportComplete: () => void;
 }
 
-interface CSVRow {
-  [key: string]: string;
-}
-
-interface MappedEvaluation {
-  original: CSVRow;
-  mapped: PreceptorEvaluation | null;
-  studentMatch: Student | null;
-  error?: string;
-}
-
-// Common Google Forms column names that might map to our fields
-const COLUMN_MAPPINGS: Record<string, string[]> = {
-  studentName: ['Student Name', 'Student', 'Name', 'student_name', 'student'],
-  preceptorName: ['Preceptor Name', 'Preceptor', 'Evaluator', 'preceptor_name', 'preceptor', 'Your Name'],
-  evaluationDate: ['Date', 'Evaluation Date', 'evaluation_date', 'Timestamp', 'date'],
-  overallRating: ['Overall Rating', 'Overall', 'overall_rating', 'Overall Performance', 'overall'],
-  clinicalSkillsRating: ['Clinical Skills', 'Clinical Skills Rating', 'clinical_skills_rating', 'Clinical Competency'],
-  professionalismRating: ['Professionalism', 'Professionalism Rating', 'professionalism_rating', 'Professional Behavior'],
-  communicationRating: ['Communication', 'Communication Rating', 'communication_rating', 'Communication Skills'],
-  comments: ['Comments', 'Additional Comments', 'comments', 'Notes', 'General Comments'],
-  areasForImprovement: ['Areas for Improvement', 'Improvement Areas', 'areas_for_improvement', 'Needs Improvement'],
-  strengths: ['Strengths', 'strengths', 'Strong Points', 'Positive Observations'],
-};
-
-function parseCSV(text: string): CSVRow[] {
-  const lines = text.split('\n').filter(line => line.trim());
-  if (lines.length < 2) return [];
-
-  // Parse header
-  const headers = parseCSVLine(lines[0]);
-
-  // Parse rows
-  const rows: CSVRow[] = [];
-  for (let i = 1; i < lines.length; i++) {
-    const values = parseCSVLine(lines[i]);
-    if (values.length === headers.length) {
-      const row: CSVRow = {};
-      headers.forEach((header, index) => {
-        row[header] = values[index];
-      });
-      rows.push(row);
-    }
-  }
-  return rows;
-}
-
-function parseCSVLine(line: string): string[] {
-  const result: string[] = [];
-  let current = '';
-  let inQuotes = false;
-
-  for (let i = 0; i < line.length; i++) {
-    const char = line[i];
-    if (char === '"') {
-      inQuotes = !inQuotes;
-    } else if (char === ',' && !inQuotes) {
-      result.push(current.trim());
-      current = '';
-    } else {
-      current += char;
-    }
-  }
-  result.push(current.trim());
-  return result;
-}
-
-function findMatchingColumn(headers: string[], fieldMappings: string[]): string | null {
-  for (const header of headers) {
-    const normalizedHeader = header.toLowerCase().trim();
-    for (const mapping of fieldMappings) {
-      if (normalizedHeader === mapping.toLowerCase()) {
-        return header;
-      }
-    }
-  }
-  return null;
-}
-
-function parseRating(value: string | undefined): number | undefined {
-  if (!value) return undefined;
-  // Handle various rating formats: "5", "5/5", "Excellent (5)", etc.
-  const match = value.match(/(\d+)/);
-  if (match) {
-    const num = parseInt(match[1], 10);
-    // Normalize to 1-5 scale if needed
-    if (num >= 1 && num <= 5) return num;
-    if (num >= 1 && num <= 10) return Math.round(num / 2);
-  }
-  return undefined;
-}
-
-function fuzzyMatchStudent(name: string, students: Student[]): Student | null {
-  const normalizedName = name.toLowerCase().trim();
-
-  // Try exact match first
-  for (const student of students) {
-    const fullName = `${student.firstName} ${student.lastName}`.toLowerCase();
-    const reverseName = `${student.lastName}, ${student.firstName}`.toLowerCase();
-    const reverseNameAlt = `${student.lastName} ${student.firstName}`.toLowerCase();
-
-    if (fullName === normalizedName || reverseName === normalizedName || reverseNameAlt === normalizedName) {
-      return student;
-    }
-  }
-
-  // Try partial match
-  for (const student of students) {
-    const fullName = `${student.firstName} ${student.lastName}`.toLowerCase();
-    if (fullName.includes(normalizedName) || normalizedName.includes(fullName)) {
-      return student;
-    }
-    // Check last name only
-    if (student.lastName.toLowerCase() === normalizedName) {
-      return student;
-    }
-  }
-
-  return null;
-}
+// Keys match the backend's evaluation form fields
+const FORM_FIELDS: Array<{ field: string; label: string }> = [
+  { field: 'response_timestamp', label: 'Timestamp' },
+  { field: 'student_name', label: 'Student Name' },
+  { field: 'student_email', label: 'Student Email' },
+  { field: 'preceptor_name', label: 'Preceptor Name' },
+  { field: 'evaluation_date', label: 'Evaluation Date' },
+  { field: 'overall_rating', label: 'Overall Rating' },
+  { field: 'clinical_skills_rating', label: 'Clinical Skills Rating' },
+  { field: 'professionalism_rating', label: 'Professionalism Rating' },
+  { field: 'communication_rating', label: 'Communication Rating' },
+  { field: 'comments', label: 'Comments' },
+  { field: 'areas_for_improvement', label: 'Areas for Improvement' },
+  { field: 'strengths', label: 'Strengths' },
+];
 
 export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: EvaluationCSVImportProps) {
-  const [step, setStep] = useState<'upload' | 'mapping' | 'preview' | 'importing' | 'complete'>('upload');
-  const [csvData, setCsvData] = useState<CSVRow[]>([]);
+  const [step, setStep] = useState<'upload' | 'mapping' | 'importing' | 'complete'>('upload');
+  const [filePath, setFilePath] = useState('');
+  const [rowCount, setRowCount] = useState(0);
   const [headers, setHeaders] = useState<string[]>([]);
   const [columnMappings, setColumnMappings] = useState<Record<string, string>>({});
-  const [mappedEvaluations, setMappedEvaluations] = useState<MappedEvaluation[]>([]);
-  const [students, setStudents] = useState<Student[]>([]);
-  const [importResult, setImportResult] = useState<{ imported: number; failed: number; errors: string[] } | null>(null);
+  const [importResult, setImportResult] = useState<ImportResult | null>(null);
   const [error, setError] = useState<string | null>(null);
 
-  const handleFileUpload = useCallback(async (file: File) => {
+  const handleChooseFile = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: 'Form responses', extensions: ['csv', 'xlsx', 'xls', 'ods'] }],
+    });
+    if (typeof selected !== 'string') return;
+
     try {
       setError(null);
-      const text = await file.text();
-      const rows = parseCSV(text);
+      const [info, suggested] = await Promise.all([
+        inspectSpreadsheet(selected),
+        suggestEvaluationFormMapping(selected),
+      ]);
 
-      if (rows.length === 0) {
-        setError('No data found in CSV file');
+      if (info.rowCount === 0) {
+        setError('No responses found in this file');
         return;
       }
 
-      const csvHeaders = Object.keys(rows[0]);
-      setHeaders(csvHeaders);
-      setCsvData(rows);
-
-      // Auto-detect column mappings
-      const detectedMappings: Record<string, string> = {};
-      for (const [field, possibleNames] of Object.entries(COLUMN_MAPPINGS)) {
-        const match = findMatchingColumn(csvHeaders, possibleNames);
-        if (match) {
-          detectedMappings[field] = match;
-        }
-      }
-      setColumnMappings(detectedMappings);
-
-      // Load students for matching
-      const loadedStudents = await loadStudents();
-      setStudents(loadedStudents);
-
+      setFilePath(selected);
+      setRowCount(info.rowCount);
+      setHeaders(info.headers);
+      setColumnMappings(suggested);
       setStep('mapping');
     } catch (err) {
-      setError(`Failed to parse CSV: ${err}`);
+      setError(`Failed to read file: ${err}`);
     }
-  }, []);
-
-  const handleDrop = useCallback((e: React.DragEvent) => {
-    e.preventDefault();
-    const file = e.dataTransfer.files[0];
-    if (file && (file.type === 'text/csv' || file.name.endsWith('.csv'))) {
-      handleFileUpload(file);
-    } else {
-      setError('Please upload a CSV file');
-    }
-  }, [handleFileUpload]);
-
-  const handleMappingChange = (field: string, column: string) => {
-    setColumnMappings(prev => ({
-      ...prev,
-      [field]: column
-    }));
   };
 
-  const processMapping = () => {
-    const mapped: MappedEvaluation[] = csvData.map((row) => {
-      const studentName = row[columnMappings.studentName] || '';
-      const studentMatch = fuzzyMatchStudent(studentName, students);
-
-      if (!studentMatch) {
-        return {
-          original: row,
-          mapped: null,
-          studentMatch: null,
-          error: `Could not match student: "${studentName}"`
-        };
-      }
-
-      const evaluation: PreceptorEvaluation = {
-        id: crypto.randomUUID(),
-        studentId: studentMatch.id,
-        preceptorName: row[columnMappings.preceptorName] || 'Unknown',
-        evaluationDate: row[columnMappings.evaluationDate] || new Date().toISOString().split('T')[0],
-        overallRating: parseRating(row[columnMappings.overallRating]),
-        clinicalSkillsRating: parseRating(row[columnMappings.clinicalSkillsRating]),
-        professionalismRating: parseRating(row[columnMappings.professionalismRating]),
-        communicationRating: parseRating(row[columnMappings.communicationRating]),
-        comments: row[columnMappings.comments],
-        areasForImprovement: row[columnMappings.areasForImprovement],
-        strengths: row[columnMappings.strengths],
-        status: 'Pending',
-        submittedAt: new Date().toISOString()
-      };
-
-      return {
-        original: row,
-        mapped: evaluation,
-        studentMatch
-      };
+  const handleMappingChange = (field: string, column: string) => {
+    setColumnMappings(prev => {
+      const next = { ...prev };
+      if (column) next[field] = column;
+      else delete next[field];
+      return next;
     });
-
-    setMappedEvaluations(mapped);
-    setStep('preview');
   };
 
+  // Responses are matched to students on the backend; anything it can't match is queued for review
   const handleImport = async () => {
     setStep('importing');
 
-    const validEvaluations = mappedEvaluations
-      .filter(m => m.mapped !== null)
-      .map(m => m.mapped!);
-
     try {
-      const result = await importPreceptorEvaluations(validEvaluations);
+      const result = await importEvaluationFormCsv(filePath, columnMappings);
       setImportResult(result);
       setStep('complete');
     } catch (err) {
       setError(`Import failed: ${err}`);
-      setStep('preview');
+      setStep('mapping');
     }
   };
 
   const handleClose = () => {
     setStep('upload');
-    setCsvData([]);
+    setFilePath('');
+    setRowCount(0);
     setHeaders([]);
     setColumnMappings({});
-    setMappedEvaluations([]);
     setImportResult(null);
     setError(null);
     onClose();
@@ -267,6 +103,9 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
     onImportComplete();
   };
 
+  const canImport = !!columnMappings.response_timestamp
+    && (!!columnMappings.student_name || !!columnMappings.student_email);
+
   if (!isOpen) return null;
 
   return (
@@ -280,7 +119,7 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
             </div>
             <div>
               <h2 className="text-xl font-bold text-gray-900">Import Preceptor Evaluations</h2>
-              <p className="text-sm text-gray-500">Import evaluations from Google Sheets CSV export</p>
+              <p className="text-sm text-gray-500">Import evaluations from a Google or Microsoft Forms export</p>
             </div>
           </div>
           <button onClick={handleClose} className="p-2 hover:bg-gray-100 rounded-lg transition-colors">
@@ -290,9 +129,9 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
 
         {/* Progress Steps */}
         <div className="flex items-center justify-center gap-2 py-4 px-6 bg-gray-50 border-b border-gray-200">
-          {['Upload', 'Map Columns', 'Preview', 'Import'].map((label, index) => {
-            const stepIndex = ['upload', 'mapping', 'preview', 'importing'].indexOf(step);
-            const isActive = index === stepIndex || (step === 'complete' && index === 3);
+          {['Upload', 'Map Columns', 'Import'].map((label, index) => {
+            const stepIndex = ['upload', 'mapping', 'importing'].indexOf(step);
+            const isActive = index === stepIndex || (step === 'complete' && index === 2);
             const isComplete = index < stepIndex || step === 'complete';
 
             return (
@@ -309,7 +148,7 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
                   'text-sm font-medium',
                   isActive || isComplete ? 'text-gray-900' : 'text-gray-400'
                 )}>{label}</span>
-                {index < 3 && <ArrowRight className="w-4 h-4 text-gray-300 mx-2" />}
+                {index < 2 && <ArrowRight className="w-4 h-4 text-gray-300 mx-2" />}
               </div>
             );
           })}
@@ -327,27 +166,18 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
           {/* Step 1: Upload */}
           {step === 'upload' && (
             <div
-              onDrop={handleDrop}
-              onDragOver={(e) => e.preventDefault()}
               className="border-2 border-dashed border-gray-300 rounded-xl p-12 text-center hover:border-indigo-400 hover:bg-indigo-50/50 transition-all cursor-pointer"
-              onClick={() => document.getElementById('csv-file-input')?.click()}
+              onClick={handleChooseFile}
             >
-              <input
-                id="csv-file-input"
-                type="file"
-                accept=".csv"
-                onChange={(e) => e.target.files?.[0] && handleFileUpload(e.target.files[0])}
-                className="hidden"
-              />
               <Upload className="w-16 h-16 mx-auto mb-4 text-gray-400" />
               <p className="text-lg font-semibold text-gray-700 mb-2">
-                Drop your CSV file here
+                Choose a form responses file
               </p>
               <p className="text-sm text-gray-500 mb-4">
-                or click to browse
+                CSV, XLSX, XLS or ODS
               </p>
               <p className="text-xs text-gray-400">
-                Export your Google Sheets form responses as CSV first
+                Responses already imported or queued are skipped, so the same export can be imported again
               </p>
             </div>
           )}
@@ -357,16 +187,16 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
             <div className="space-y-6">
               <div className="bg-blue-50 border border-blue-200 rounded-lg p-4">
                 <p className="text-sm text-blue-700">
-                  Found {csvData.length} rows in your CSV. Map the columns below to import fields.
+                  Found {rowCount} responses. Map the columns below to import fields. Students are matched by email, then by name.
                 </p>
               </div>
 
               <div className="grid grid-cols-2 gap-4">
-                {Object.entries(COLUMN_MAPPINGS).map(([field, _]) => (
+                {FORM_FIELDS.map(({ field, label }) => (
                   <div key={field} className="space-y-1">
-                    <label className="block text-sm font-medium text-gray-700 capitalize">
-                      {field.replace(/([A-Z])/g, ' $1').trim()}
-                      {field === 'studentName' && <span className="text-red-500">*</span>}
+                    <label className="block text-sm font-medium text-gray-700">
+                      {label}
+                      {field === 'response_timestamp' && <span className="text-red-500">*</span>}
                     </label>
                     <select
                       value={columnMappings[field] || ''}
@@ -389,104 +219,19 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
                 >
                   Back
                 </button>
-                <button
-                  onClick={processMapping}
-                  disabled={!columnMappings.studentName}
-                  className="px-6 py-2 bg-indigo-600 text-white rounded-lg hover:bg-indigo-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
-                >
-                  Continue
-                </button>
-              </div>
-            </div>
-          )}
-
-          {/* Step 3: Preview */}
-          {step === 'preview' && (
-            <div className="space-y-4">
-              <div className="flex items-center gap-4 p-4 bg-gray-50 rounded-lg">
-                <div className="flex items-center gap-2">
-                  <div className="w-3 h-3 rounded-full bg-green-500"></div>
-                  <span className="text-sm text-gray-600">
-                    {mappedEvaluations.filter(m => m.mapped).length} ready to import
-                  </span>
-                </div>
-                <div className="flex items-center gap-2">
-                  <div className="w-3 h-3 rounded-full bg-red-500"></div>
-                  <span className="text-sm text-gray-600">
-                    {mappedEvaluations.filter(m => !m.mapped).length} with errors
-                  </span>
-                </div>
-              </div>
-
-              <div className="border border-gray-200 rounded-lg overflow-hidden">
-                <table className="w-full text-sm">
-                  <thead className="bg-gray-50">
-                    <tr>
-                      <th className="px-4 py-3 text-left font-medium text-gray-700">Status</th>
-                      <th className="px-4 py-3 text-left font-medium text-gray-700">Student</th>
-                      <th className="px-4 py-3 text-left font-medium text-gray-700">Preceptor</th>
-                      <th className="px-4 py-3 text-left font-medium text-gray-700">Date</th>
-                      <th className="px-4 py-3 text-left font-medium text-gray-700">Rating</th>
-                    </tr>
-                  </thead>
-                  <tbody className="divide-y divide-gray-200">
-                    {mappedEvaluations.slice(0, 10).map((item, index) => (
-                      <tr key={index} className={item.error ? 'bg-red-50' : ''}>
-                        <td className="px-4 py-3">
-                          {item.mapped ? (
-                            <CheckCircle className="w-5 h-5 text-green-500" />
-                          ) : (
-                            <AlertCircle className="w-5 h-5 text-red-500" />
-                          )}
-                        </td>
-                        <td className="px-4 py-3">
-                          {item.studentMatch ? (
-                            <span className="text-gray-900">
-                              {item.studentMatch.firstName} {item.studentMatch.lastName}
-                            </span>
-                          ) : (
-                            <span className="text-red-600">{item.error}</span>
-                          )}
-                        </td>
-                        <td className="px-4 py-3 text-gray-600">
-                          {item.mapped?.preceptorName || '-'}
-                        </td>
-                        <td className="px-4 py-3 text-gray-600">
-                          {item.mapped?.evaluationDate || '-'}
-                        </td>
-                        <td className="px-4 py-3 text-gray-600">
-                          {item.mapped?.overallRating ? `${item.mapped.overallRating}/5` : '-'}
-                        </td>
-                      </tr>
-                    ))}
-                  </tbody>
-                </table>
-                {mappedEvaluations.length > 10 && (
-                  <div className="px-4 py-3 bg-gray-50 text-sm text-gray-500 text-center">
-                    ... and {mappedEvaluations.length - 10} more rows
-                  </div>
-                )}
-              </div>
-
-              <div className="flex justify-end gap-3 pt-4">
-                <button
-                  onClick={() => setStep('mapping')}
-                  className="px-4 py-2 text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
-                >
-                  Back
-                </button>
                 <button
                   onClick={handleImport}
-                  disabled={mappedEvaluations.filter(m => m.mapped).length === 0}
+                  disabled={!canImport}
+                  title={canImport ? undefined : 'Map the timestamp and a student name or email column'}
                   className="px-6 py-2 bg-indigo-600 text-white rounded-lg hover:bg-indigo-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
                 >
-                  Import {mappedEvaluations.filter(m => m.mapped).length} Evaluations
+                  Import {rowCount} Responses
                 </button>
               </div>
             </div>
           )}
 
-          {/* Step 4: Importing */}
+          {/* Step 3: Importing */}
           {step === 'importing' && (
             <div className="text-center py-12">
               <div className="animate-spin w-12 h-12 border-4 border-indigo-200 border-t-indigo-600 rounded-full mx-auto mb-4"></div>
@@ -495,7 +240,7 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
             </div>
           )}
 
-          {/* Step 5: Complete */}
+          {/* Step 4: Complete */}
           {step === 'complete' && importResult && (
             <div className="text-center py-8">
               <div className="w-16 h-16 bg-green-100 rounded-full flex items-center justify-center mx-auto mb-4">
@@ -503,10 +248,28 @@ export function EvaluationCSVImport({ isOpen, onClose, onImportComplete }: Evalu
               </div>
               <h3 className="text-xl font-bold text-gray-900 mb-2">Import Complete!</h3>
               <p className="text-gray-600 mb-6">
-                Successfully imported {importResult.imported} evaluations
+                Imported {importResult.imported} evaluations
+                {!!importResult.skipped && `, skipped ${importResult.skipped} already seen`}
                 {importResult.failed > 0 && ` (${importResult.failed} failed)`}
               </p>
 
+              {!!importResult.queued && (
+                <div className="text-left bg-amber-50 border border-amber-200 rounded-lg p-4 mb-4">
+                  <p className="text-sm text-amber-800">
+                    {importResult.queued} response{importResult.queued !== 1 ? 's' : ''} couldn't be matched to a student
+                    and {importResult.queued !== 1 ? 'are' : 'is'} waiting under Form Responses to Review.
+                  </p>
+                </div>
+              )}
+
+              {!!importResult.unmatchedPreceptors?.length && (
+                <div className="text-left bg-blue-50 border border-blue-200 rounded-lg p-4 mb-4">
+                  <p className="text-sm text-blue-800">
+                    No preceptor record found for: {importResult.unmatchedPreceptors.join(', ')}
+                  </p>
+                </div>
+              )}
+
               {importResult.errors.length > 0 && (
                 <div className="text-left bg-red-50 border border-red-200 rounded-lg p-4 mb-6 max-h-40 overflow-y-auto">
                   <p className="text-sm font-medium text-red-800 mb-2">Errors:</p>
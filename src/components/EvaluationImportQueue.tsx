@@ -0,0 +1,142 @@
+import { useState, useEffect } from 'react';
+import { format, parseISO } from 'date-fns';
+import { ClipboardList, Check, X, Loader2 } from 'lucide-react';
+import { useToast } from '@/components/Toast';
+import { Select, Input } from '@/components/FormField';
+import { getEvaluationImportQueue, resolveEvaluationImport, dismissEvaluationImport, loadStudents } from '@/lib/db';
+import type { EvaluationImportQueueItem, Student } from '@/types';
+
+interface EvaluationImportQueueProps {
+  refreshKey?: number; // Bump to reload after an import
+  onResolved?: () => void;
+}
+
+// Form responses the importer couldn't match to a student, waiting for a manual match
+export function EvaluationImportQueue({ refreshKey, onResolved }: EvaluationImportQueueProps) {
+  const toast = useToast();
+  const [items, setItems] = useState<EvaluationImportQueueItem[]>([]);
+  const [students, setStudents] = useState<Student[]>([]);
+  const [selectedStudents, setSelectedStudents] = useState<Record<string, string>>({});
+  const [preceptorNames, setPreceptorNames] = useState<Record<string, string>>({});
+  const [busyId, setBusyId] = useState<string | null>(null);
+
+  useEffect(() => {
+    Promise.all([getEvaluationImportQueue('pending'), loadStudents()])
+      .then(([queue, loadedStudents]) => {
+        setItems(queue);
+        setStudents(loadedStudents);
+      })
+      .catch(error => console.error('Failed to load evaluation import queue:', error));
+  }, [refreshKey]);
+
+  const removeItem = (id: string) => setItems(prev => prev.filter(item => item.id !== id));
+
+  const handleResolve = async (item: EvaluationImportQueueItem) => {
+    const studentId = selectedStudents[item.id];
+    if (!studentId) return;
+
+    setBusyId(item.id);
+    try {
+      await resolveEvaluationImport(item.id, studentId, preceptorNames[item.id]?.trim() || undefined);
+      removeItem(item.id);
+      toast.success('Evaluation Imported', 'The response was added to the student\'s evaluations');
+      onResolved?.();
+    } catch (error) {
+      console.error('Failed to resolve queued evaluation:', error);
+      toast.error('Could Not Import', typeof error === 'string' ? error : 'Failed to import the response');
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const handleDismiss = async (item: EvaluationImportQueueItem) => {
+    setBusyId(item.id);
+    try {
+      await dismissEvaluationImport(item.id);
+      removeItem(item.id);
+    } catch (error) {
+      console.error('Failed to dismiss queued evaluation:', error);
+      toast.error('Could Not Dismiss', typeof error === 'string' ? error : 'Failed to dismiss the response');
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  if (items.length === 0) return null;
+
+  return (
+    <div className="bg-linear-to-r from-amber-50 to-yellow-50 border-2 border-amber-200 rounded-2xl p-5">
+      <div className="flex items-center gap-3 mb-4">
+        <div className="p-2 bg-amber-100 rounded-xl">
+          <ClipboardList className="w-5 h-5 text-amber-600" />
+        </div>
+        <div>
+          <h2 className="font-black text-gray-900">
+            {items.length} Form Response{items.length !== 1 ? 's' : ''} to Review
+          </h2>
+          <p className="text-sm text-gray-600">Choose the student each evaluation belongs to, or dismiss it</p>
+        </div>
+      </div>
+      <div className="space-y-3">
+        {items.map((item) => {
+          const { response } = item;
+          const busy = busyId === item.id;
+          return (
+            <div key={item.id} className="p-3 bg-white rounded-xl border border-amber-100 grid grid-cols-1 lg:grid-cols-3 gap-3 items-center">
+              <div>
+                <div className="font-bold text-gray-900">
+                  {response.studentName || response.studentEmail || 'Unnamed student'}
+                  {response.studentName && response.studentEmail && (
+                    <span className="ml-2 text-xs font-medium text-gray-500">{response.studentEmail}</span>
+                  )}
+                </div>
+                <div className="text-xs text-gray-500">
+                  {response.preceptorName || 'No preceptor'} · {format(parseISO(response.evaluationDate), 'MMM d, yyyy')}
+                  {!!response.overallRating && ` · ${response.overallRating}/5`}
+                </div>
+                <div className="text-xs font-bold text-amber-700 mt-1">{item.reason}</div>
+              </div>
+              <div className="flex gap-2">
+                <Select
+                  value={selectedStudents[item.id] ?? ''}
+                  onChange={(e) => setSelectedStudents(prev => ({ ...prev, [item.id]: e.target.value }))}
+                >
+                  <option value="">Select student...</option>
+                  {students.map(student => (
+                    <option key={student.id} value={student.id}>{student.lastName}, {student.firstName}</option>
+                  ))}
+                </Select>
+                {!response.preceptorName && (
+                  <Input
+                    value={preceptorNames[item.id] ?? ''}
+                    onChange={(e) => setPreceptorNames(prev => ({ ...prev, [item.id]: e.target.value }))}
+                    placeholder="Preceptor name"
+                  />
+                )}
+              </div>
+              <div className="flex justify-end gap-2">
+                <button
+                  onClick={() => handleResolve(item)}
+                  disabled={busy || !selectedStudents[item.id] || (!response.preceptorName && !preceptorNames[item.id]?.trim())}
+                  className="btn btn-primary btn-sm flex items-center gap-1"
+                >
+                  {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <Check className="w-4 h-4" />}
+                  Import
+                </button>
+                <button
+                  onClick={() => handleDismiss(item)}
+                  disabled={busy}
+                  className="btn btn-outline btn-sm flex items-center gap-1"
+                  title="Dismiss; re-imports will keep skipping this response"
+                >
+                  <X className="w-4 h-4" />
+                  Dismiss
+                </button>
+              </div>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
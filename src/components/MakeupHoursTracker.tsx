@@ -4,16 +4,15 @@ import {
   Clock,
   CheckCircle,
   AlertTriangle,
-  Plus,
+  FileText,
   Trash2,
   Loader2,
   ChevronDown,
   ChevronUp,
   Calendar as CalendarIcon
 } from 'lucide-react';
-import { getAllMakeupHoursSummaries, updateMakeupHours, deleteMakeupHours } from '@/lib/db';
-import { MakeupHoursSummary, MakeupHours } from '@/types';
-import { Modal } from './Modal';
+import { getAllMakeupHoursSummaries, getMakeupAllocations, deleteMakeupHours } from '@/lib/db';
+import { MakeupHoursSummary, MakeupAllocation } from '@/types';
 import { useToast } from './Toast';
 import { clsx } from 'clsx';
 
@@ -25,9 +24,9 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
   const [summaries, setSummaries] = useState<MakeupHoursSummary[]>([]);
   const [loading, setLoading] = useState(true);
   const [expandedStudent, setExpandedStudent] = useState<string | null>(null);
-  const [editingRecord, setEditingRecord] = useState<MakeupHours | null>(null);
-  const [hoursToAdd, setHoursToAdd] = useState<number>(0);
-  const [saving, setSaving] = useState(false);
+  // Completed hours come from approved makeup clinical logs; this shows which logs paid each record
+  const [openAllocations, setOpenAllocations] = useState<string | null>(null);
+  const [allocations, setAllocations] = useState<Record<string, MakeupAllocation[]>>({});
   const toast = useToast();
 
   useEffect(() => {
@@ -39,6 +38,7 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
     try {
       const data = await getAllMakeupHoursSummaries();
       setSummaries(data);
+      setAllocations({});
     } catch (error) {
       console.error('Failed to load makeup hours:', error);
       toast.error('Failed to Load', 'Could not load makeup hours data');
@@ -47,36 +47,18 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
     }
   };
 
-  const handleUpdateHours = async () => {
-    if (!editingRecord) return;
-
-    setSaving(true);
+  const handleToggleAllocations = async (recordId: string) => {
+    if (openAllocations === recordId) {
+      setOpenAllocations(null);
+      return;
+    }
+    setOpenAllocations(recordId);
     try {
-      const newCompleted = editingRecord.hoursCompleted + hoursToAdd;
-      const isComplete = newCompleted >= editingRecord.hoursOwed;
-
-      await updateMakeupHours({
-        ...editingRecord,
-        hoursCompleted: Math.min(newCompleted, editingRecord.hoursOwed),
-        status: isComplete ? 'completed' : 'in_progress',
-        completionDate: isComplete ? new Date().toISOString() : undefined,
-        updatedAt: new Date().toISOString()
-      });
-
-      toast.success(
-        'Hours Updated',
-        `Added ${hoursToAdd} hours. ${isComplete ? 'Makeup complete!' : `${editingRecord.hoursOwed - newCompleted} hours remaining.`}`
-      );
-
-      setEditingRecord(null);
-      setHoursToAdd(0);
-      loadData();
-      onRefresh?.();
+      const data = await getMakeupAllocations(recordId);
+      setAllocations(prev => ({ ...prev, [recordId]: data }));
     } catch (error) {
-      console.error('Failed to update hours:', error);
-      toast.error('Update Failed', 'Could not update makeup hours');
-    } finally {
-      setSaving(false);
+      console.error('Failed to load makeup allocations:', error);
+      toast.error('Failed to Load', 'Could not load the logs credited to this record');
     }
   };
 
@@ -150,7 +132,7 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
             Make-Up Hours Tracker
           </h2>
           <p className="text-sm text-gray-500 mt-1">
-            {summaries.length} student{summaries.length !== 1 ? 's' : ''} with makeup hours
+            {summaries.length} student{summaries.length !== 1 ? 's' : ''} with makeup hours · hours are credited from approved makeup clinical logs
           </p>
         </div>
 
@@ -268,19 +250,18 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
                               </div>
                             </div>
 
-                            {record.status !== 'completed' && (
-                              <div className="flex items-center gap-2">
-                                <button
-                                  onClick={(e) => {
-                                    e.stopPropagation();
-                                    setEditingRecord(record);
-                                    setHoursToAdd(0);
-                                  }}
-                                  className="p-2 text-blue-600 hover:bg-blue-50 rounded-lg transition-colors"
-                                  title="Log hours"
-                                >
-                                  <Plus className="w-4 h-4" />
-                                </button>
+                            <div className="flex items-center gap-2">
+                              <button
+                                onClick={(e) => {
+                                  e.stopPropagation();
+                                  handleToggleAllocations(record.id);
+                                }}
+                                className="p-2 text-blue-600 hover:bg-blue-50 rounded-lg transition-colors"
+                                title="Credited logs"
+                              >
+                                <FileText className="w-4 h-4" />
+                              </button>
+                              {record.status !== 'completed' && (
                                 <button
                                   onClick={(e) => {
                                     e.stopPropagation();
@@ -291,9 +272,30 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
                                 >
                                   <Trash2 className="w-4 h-4" />
                                 </button>
-                              </div>
-                            )}
+                              )}
+                            </div>
                           </div>
+
+                          {openAllocations === record.id && (
+                            <div className="mt-3 pt-3 border-t border-gray-200">
+                              {!allocations[record.id] ? (
+                                <Loader2 className="w-4 h-4 text-gray-400 animate-spin" />
+                              ) : allocations[record.id].length === 0 ? (
+                                <p className="text-xs text-gray-500">No approved makeup logs have been credited yet.</p>
+                              ) : (
+                                <ul className="space-y-1 text-xs text-gray-600">
+                                  {allocations[record.id].map(allocation => (
+                                    <li key={allocation.id} className="flex items-center justify-between">
+                                      <span>
+                                        {format(parseISO(allocation.logDate), 'MMM d, yyyy')} · {allocation.siteName}
+                                      </span>
+                                      <span className="font-bold text-gray-900">{allocation.hours} hrs</span>
+                                    </li>
+                                  ))}
+                                </ul>
+                              )}
+                            </div>
+                          )}
                         </div>
                       ))}
                     </div>
@@ -304,102 +306,6 @@ export default function MakeupHoursTracker({ onRefresh }: MakeupHoursTrackerProp
           })}
         </div>
       </div>
-
-      {/* Edit Hours Modal */}
-      <Modal
-        isOpen={!!editingRecord}
-        onClose={() => {
-          setEditingRecord(null);
-          setHoursToAdd(0);
-        }}
-        title="Log Make-Up Hours"
-        size="sm"
-        footer={
-          <div className="flex justify-end gap-3">
-            <button
-              onClick={() => {
-                setEditingRecord(null);
-                setHoursToAdd(0);
-              }}
-              className="px-4 py-2 text-sm font-semibold text-gray-500 hover:text-gray-700"
-            >
-              Cancel
-            </button>
-            <button
-              onClick={handleUpdateHours}
-              disabled={saving || hoursToAdd <= 0}
-              className="btn btn-primary px-6 disabled:opacity-50 flex items-center gap-2"
-            >
-              {saving ? (
-                <>
-                  <Loader2 className="w-4 h-4 animate-spin" />
-                  Saving...
-                </>
-              ) : (
-                <>
-                  <CheckCircle className="w-4 h-4" />
-                  Log Hours
-                </>
-              )}
-            </button>
-          </div>
-        }
-      >
-        {editingRecord && (
-          <div className="space-y-4">
-            <div>
-              <label className="text-sm font-bold text-gray-700 block mb-2">Current Progress</label>
-              <div className="flex items-center gap-3">
-                <div className="flex-1 bg-gray-100 rounded-full h-3">
-                  <div
-                    className="h-3 bg-orange-500 rounded-full transition-all"
-                    style={{ width: `${(editingRecord.hoursCompleted / editingRecord.hoursOwed) * 100}%` }}
-                  />
-                </div>
-                <span className="text-sm font-bold text-gray-700">
-                  {editingRecord.hoursCompleted} / {editingRecord.hoursOwed} hrs
-                </span>
-              </div>
-              <p className="text-xs text-gray-500 mt-1">
-                {editingRecord.hoursOwed - editingRecord.hoursCompleted} hours remaining
-              </p>
-            </div>
-
-            <div>
-              <label className="text-sm font-bold text-gray-700 block mb-2">Hours to Add</label>
-              <input
-                type="number"
-                min="0.5"
-                max={editingRecord.hoursOwed - editingRecord.hoursCompleted}
-                step="0.5"
-                value={hoursToAdd}
-                onChange={(e) => setHoursToAdd(parseFloat(e.target.value) || 0)}
-                className="w-full px-4 py-3 border-2 border-gray-200 rounded-xl text-lg font-bold focus:outline-none focus:border-indigo-500"
-              />
-              <p className="text-xs text-gray-500 mt-1">
-                Max: {editingRecord.hoursOwed - editingRecord.hoursCompleted} hours
-              </p>
-            </div>
-
-            {hoursToAdd > 0 && (
-              <div className="p-3 bg-blue-50 rounded-lg">
-                <p className="text-sm text-blue-700">
-                  After logging <span className="font-bold">{hoursToAdd}</span> hours:
-                </p>
-                <p className="text-sm text-blue-700">
-                  {editingRecord.hoursCompleted + hoursToAdd >= editingRecord.hoursOwed ? (
-                    <span className="font-bold text-green-600">Makeup requirement complete!</span>
-                  ) : (
-                    <span>
-                      <span className="font-bold">{editingRecord.hoursOwed - editingRecord.hoursCompleted - hoursToAdd}</span> hours will remain
-                    </span>
-                  )}
-                </p>
-              </div>
-            )}
-          </div>
-        )}
-      </Modal>
     </>
   );
 }
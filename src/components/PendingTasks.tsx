@@ -6,7 +6,7 @@ import {
   ChevronRight, CheckCircle, Loader2, Clock
 } from 'lucide-react';
 import {
-  getPendingEvaluations, getUpcomingDeadlines, getExpiringCertifications, completeDeadline
+  getPendingEvaluations, getUpcomingDeadlines, getExpiringCertifications, completeDeadline, refreshTray
 } from '@/lib/db';
 import { PreceptorEvaluation, Deadline, CertificationAlert } from '@/types';
 import { useToast } from './Toast';
@@ -35,8 +35,8 @@ export default function PendingTasks({ pendingLogsCount, onRefresh }: PendingTas
     try {
       const [evals, dls, certs] = await Promise.all([
         getPendingEvaluations(),
-        getUpcomingDeadlines(14),
-        getExpiringCertifications(30)
+        getUpcomingDeadlines(14, true),
+        getExpiringCertifications(30, true)
       ]);
       setEvaluations(evals);
       setDeadlines(dls);
@@ -55,6 +55,7 @@ export default function PendingTasks({ pendingLogsCount, onRefresh }: PendingTas
       showSuccess('Completed', 'Deadline marked as complete');
       await loadData();
       onRefresh?.();
+      refreshTray().catch(console.error);
     } catch (error) {
       console.error('Failed to complete deadline:', error);
       showError('Failed', 'Could not complete deadline');
@@ -174,7 +175,7 @@ export default function PendingTasks({ pendingLogsCount, onRefresh }: PendingTas
                 </div>
                 <div>
                   <div className="font-bold text-slate-900">Upcoming Deadlines</div>
-                  <div className="text-sm text-slate-600 font-medium">Next 14 days</div>
+                  <div className="text-sm text-slate-600 font-medium">Overdue and next 14 days</div>
                 </div>
               </div>
               <div className="space-y-2">
@@ -227,7 +228,7 @@ export default function PendingTasks({ pendingLogsCount, onRefresh }: PendingTas
                 </div>
                 <div>
                   <div className="font-bold text-slate-900">Expiring Certifications</div>
-                  <div className="text-sm text-slate-600 font-medium">{certAlerts.length} in next 30 days</div>
+                  <div className="text-sm text-slate-600 font-medium">{certAlerts.length} expired or in next 30 days</div>
                 </div>
               </div>
               <div className="space-y-2">
@@ -239,7 +240,9 @@ export default function PendingTasks({ pendingLogsCount, onRefresh }: PendingTas
                     <div className="min-w-0 flex-1">
                       <div className="font-semibold text-slate-900 text-sm truncate">{alert.studentName}</div>
                       <div className="text-xs text-slate-600 truncate font-medium">
-                        {alert.certification.certificationName} - expires in {alert.daysUntilExpiry} days
+                        {alert.certification.certificationName} - {alert.daysUntilExpiry < 0
+                          ? `expired ${-alert.daysUntilExpiry} days ago`
+                          : `expires in ${alert.daysUntilExpiry} days`}
                       </div>
                     </div>
                     <AlertTriangle className={`w-5 h-5 shrink-0 ml-3 ${alert.daysUntilExpiry <= 7 ? 'text-rose-600' : 'text-amber-600'}`} />
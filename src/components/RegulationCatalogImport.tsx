@@ -0,0 +1,265 @@
+import { useState, useEffect } from 'react';
+import { Upload, GitCompare, Check, Trash2, Loader2, AlertTriangle } from 'lucide-react';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Textarea } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import {
+  getRegulationCatalogEditions, importRegulationCatalog, diffRegulationCatalogEditions,
+  applyRegulationCatalogEdition, deleteRegulationCatalogEdition
+} from '@/lib/db';
+import type { RegulatoryFramework, RegulationCatalogEdition, RegulationCatalogDiff } from '@/types';
+
+interface RegulationCatalogImportProps {
+  isOpen: boolean;
+  onClose: () => void;
+  framework: RegulatoryFramework | null;
+  onApplied: () => void;
+}
+
+// Diff of an edition against the live catalog
+function CatalogDiffView({ diff }: { diff: RegulationCatalogDiff }) {
+  return (
+    <div className="space-y-3">
+      <div className="grid grid-cols-4 gap-2 text-center">
+        <div className="bg-green-50 rounded-lg p-2">
+          <div className="text-lg font-bold text-green-700">{diff.added.length}</div>
+          <div className="text-xs text-gray-600">Added</div>
+        </div>
+        <div className="bg-amber-50 rounded-lg p-2">
+          <div className="text-lg font-bold text-amber-700">{diff.changed.length}</div>
+          <div className="text-xs text-gray-600">Changed</div>
+        </div>
+        <div className="bg-red-50 rounded-lg p-2">
+          <div className="text-lg font-bold text-red-700">{diff.removed.length}</div>
+          <div className="text-xs text-gray-600">Removed</div>
+        </div>
+        <div className="bg-gray-50 rounded-lg p-2">
+          <div className="text-lg font-bold text-gray-700">{diff.unchangedCount}</div>
+          <div className="text-xs text-gray-600">Unchanged</div>
+        </div>
+      </div>
+      {diff.affectedMappingCount > 0 && (
+        <p className="text-sm text-amber-700 flex items-center gap-2">
+          <AlertTriangle className="w-4 h-4" />
+          {diff.affectedMappingCount} mapping{diff.affectedMappingCount === 1 ? '' : 's'} will be marked for re-review.
+        </p>
+      )}
+      <ul className="max-h-64 overflow-y-auto text-sm divide-y divide-gray-100 border border-gray-100 rounded-lg">
+        {diff.added.map(r => (
+          <li key={`added-${r.id}`} className="px-3 py-2">
+            <span className="font-semibold text-green-700">+ {r.code}</span> {r.title}
+          </li>
+        ))}
+        {diff.changed.map(c => (
+          <li key={`changed-${c.after.id}`} className="px-3 py-2">
+            <span className="font-semibold text-amber-700">~ {c.after.code}</span> {c.after.title}
+            <span className="text-xs text-gray-500 ml-2">({c.changedFields.join(', ')})</span>
+            {c.changedFields.includes('description') && (
+              <div className="mt-1 text-xs">
+                <div className="text-red-600 line-through">{c.before.description}</div>
+                <div className="text-green-700">{c.after.description}</div>
+              </div>
+            )}
+          </li>
+        ))}
+        {diff.removed.map(r => (
+          <li key={`removed-${r.id}`} className="px-3 py-2">
+            <span className="font-semibold text-red-700">− {r.code}</span> {r.title}
+          </li>
+        ))}
+        {diff.added.length + diff.changed.length + diff.removed.length === 0 && (
+          <li className="px-3 py-2 text-gray-500">No differences from the live catalog.</li>
+        )}
+      </ul>
+    </div>
+  );
+}
+
+// Import a regulation catalog edition from JSON or CSV, review its diff and apply it
+export default function RegulationCatalogImport({ isOpen, onClose, framework, onApplied }: RegulationCatalogImportProps) {
+  const toast = useToast();
+  const [editions, setEditions] = useState<RegulationCatalogEdition[]>([]);
+  const [file, setFile] = useState<File | null>(null);
+  const [form, setForm] = useState({ edition: '', effectiveDate: '', notes: '' });
+  const [preview, setPreview] = useState<{ edition: RegulationCatalogEdition; diff: RegulationCatalogDiff } | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const loadEditions = async () => {
+    if (!framework) return;
+    try {
+      setEditions(await getRegulationCatalogEditions(framework.id));
+    } catch (error) {
+      console.error('Failed to load catalog editions:', error);
+    }
+  };
+
+  useEffect(() => {
+    if (isOpen) {
+      setPreview(null);
+      setFile(null);
+      setForm({ edition: '', effectiveDate: '', notes: '' });
+      loadEditions();
+    }
+  }, [isOpen, framework?.id]);
+
+  const handleImport = async () => {
+    if (!framework || !file) return;
+    setBusy(true);
+    try {
+      const result = await importRegulationCatalog({
+        frameworkId: framework.id,
+        filePath: file.name,
+        content: await file.text(),
+        edition: form.edition || undefined,
+        effectiveDate: form.effectiveDate || undefined,
+        notes: form.notes || undefined,
+        apply: false,
+      });
+      setPreview({ edition: result.edition, diff: result.diff });
+      loadEditions();
+    } catch (error) {
+      console.error('Failed to import catalog:', error);
+      toast.error('Import Failed', typeof error === 'string' ? error : 'Failed to import the catalog');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleCompare = async (edition: RegulationCatalogEdition) => {
+    try {
+      setPreview({ edition, diff: await diffRegulationCatalogEditions(edition.id) });
+    } catch (error) {
+      console.error('Failed to compare editions:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to compare editions');
+    }
+  };
+
+  const handleApply = async (edition: RegulationCatalogEdition) => {
+    setBusy(true);
+    try {
+      const flagged = await applyRegulationCatalogEdition(edition.id);
+      toast.success(
+        'Edition Applied',
+        flagged > 0
+          ? `${edition.edition} is now live. ${flagged} mapping${flagged === 1 ? '' : 's'} marked for re-review.`
+          : `${edition.edition} is now live.`
+      );
+      setPreview(null);
+      loadEditions();
+      onApplied();
+    } catch (error) {
+      console.error('Failed to apply edition:', error);
+      toast.error('Apply Failed', typeof error === 'string' ? error : 'Failed to apply the edition');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleDelete = async (edition: RegulationCatalogEdition) => {
+    try {
+      await deleteRegulationCatalogEdition(edition.id);
+      if (preview?.edition.id === edition.id) setPreview(null);
+      loadEditions();
+    } catch (error) {
+      console.error('Failed to delete edition:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to delete the edition');
+    }
+  };
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title={`${framework?.code ?? ''} Regulation Catalog Editions`}
+      size="xl"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={onClose} className="btn btn-outline">Close</button>
+          {preview && !preview.edition.isCurrent && (
+            <button onClick={() => handleApply(preview.edition)} disabled={busy} className="btn btn-primary flex items-center gap-2">
+              {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <Check className="w-4 h-4" />}
+              Apply {preview.edition.edition}
+            </button>
+          )}
+        </div>
+      }
+    >
+      <div className="space-y-6">
+        {editions.length > 0 && (
+          <div>
+            <h4 className="text-sm font-semibold text-gray-700 mb-2">Imported editions</h4>
+            <ul className="divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+              {editions.map(edition => (
+                <li key={edition.id} className="flex items-center justify-between px-3 py-2">
+                  <div>
+                    <span className="font-semibold text-gray-900">{edition.edition}</span>
+                    {edition.isCurrent && (
+                      <span className="ml-2 px-2 py-0.5 text-xs rounded-full bg-teal-100 text-teal-700">Live</span>
+                    )}
+                    <span className="text-gray-500 ml-2">
+                      {edition.regulationCount} regulations
+                      {edition.effectiveDate ? ` · effective ${edition.effectiveDate}` : ''}
+                      {edition.sourceFile ? ` · ${edition.sourceFile}` : ''}
+                    </span>
+                  </div>
+                  {!edition.isCurrent && (
+                    <div className="flex items-center gap-2">
+                      <button onClick={() => handleCompare(edition)} className="btn btn-outline btn-sm flex items-center gap-1">
+                        <GitCompare className="w-4 h-4" />
+                        Compare
+                      </button>
+                      <button onClick={() => handleDelete(edition)} className="p-1.5 text-gray-400 hover:text-red-600" aria-label="Delete edition">
+                        <Trash2 className="w-4 h-4" />
+                      </button>
+                    </div>
+                  )}
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+
+        {preview ? (
+          <div>
+            <h4 className="text-sm font-semibold text-gray-700 mb-2">
+              {preview.edition.edition} compared with the live catalog
+            </h4>
+            <CatalogDiffView diff={preview.diff} />
+          </div>
+        ) : (
+          <div className="space-y-4">
+            <FormField label="Catalog file" hint="JSON (a list of regulations, or { edition, regulations }) or CSV with code and title columns" required>
+              <input
+                type="file"
+                accept=".json,.csv"
+                onChange={(e) => setFile(e.target.files?.[0] ?? null)}
+                className="block w-full text-sm text-gray-700"
+              />
+            </FormField>
+            <div className="grid grid-cols-2 gap-4">
+              <FormField label="Edition" hint="e.g., 2025; optional when the JSON names it">
+                <Input value={form.edition} onChange={(e) => setForm({ ...form, edition: e.target.value })} />
+              </FormField>
+              <FormField label="Effective date">
+                <Input
+                  type="date"
+                  value={form.effectiveDate}
+                  onChange={(e) => setForm({ ...form, effectiveDate: e.target.value })}
+                />
+              </FormField>
+            </div>
+            <FormField label="Notes">
+              <Textarea rows={2} value={form.notes} onChange={(e) => setForm({ ...form, notes: e.target.value })} />
+            </FormField>
+            <div className="flex justify-end">
+              <button onClick={handleImport} disabled={!file || busy} className="btn btn-primary flex items-center gap-2">
+                {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <Upload className="w-4 h-4" />}
+                Import and Compare
+              </button>
+            </div>
+          </div>
+        )}
+      </div>
+    </Modal>
+  );
+}
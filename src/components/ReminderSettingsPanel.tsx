@@ -0,0 +1,136 @@
+import { useState, useEffect } from 'react';
+import { Link } from 'react-router-dom';
+import { Bell, BellOff, RefreshCw } from 'lucide-react';
+import { getPendingReminders, checkRemindersNow, snoozeReminder, getReminderSettings, updateReminderSetting } from '@/lib/db';
+import { useToast } from '@/components/Toast';
+import type { Reminder, ReminderSetting } from '@/types';
+
+const SNOOZE_OPTIONS = [
+  { label: '1 hour', minutes: 60 },
+  { label: '1 day', minutes: 24 * 60 },
+  { label: '1 week', minutes: 7 * 24 * 60 },
+];
+
+// Which alert sources send desktop notifications, and the current reminders with snooze
+export default function ReminderSettingsPanel() {
+  const toast = useToast();
+  const [settings, setSettings] = useState<ReminderSetting[]>([]);
+  const [reminders, setReminders] = useState<Reminder[]>([]);
+  const [checking, setChecking] = useState(false);
+
+  const loadReminders = async () => {
+    try {
+      const [loadedSettings, loadedReminders] = await Promise.all([getReminderSettings(), getPendingReminders()]);
+      setSettings(loadedSettings);
+      setReminders(loadedReminders);
+    } catch (error) {
+      console.error('Failed to load reminders:', error);
+    }
+  };
+
+  useEffect(() => {
+    loadReminders();
+  }, []);
+
+  const handleToggle = async (setting: ReminderSetting) => {
+    try {
+      await updateReminderSetting(setting.category, !setting.enabled);
+      loadReminders();
+    } catch (error) {
+      console.error('Failed to update reminder setting:', error);
+      toast.error('Error', 'Failed to update the reminder setting');
+    }
+  };
+
+  const handleSnooze = async (reminder: Reminder, minutes: number) => {
+    try {
+      await snoozeReminder(reminder, minutes);
+      const option = SNOOZE_OPTIONS.find(o => o.minutes === minutes);
+      toast.success('Reminder Snoozed', `${reminder.title} — for ${option?.label ?? `${minutes} minutes`}`);
+    } catch (error) {
+      console.error('Failed to snooze reminder:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to snooze the reminder');
+    }
+  };
+
+  const handleCheckNow = async () => {
+    setChecking(true);
+    try {
+      const count = await checkRemindersNow();
+      toast.info('Reminders Checked', count > 0 ? `${count} new reminder${count === 1 ? '' : 's'} sent` : 'Nothing new to remind you about');
+      loadReminders();
+    } catch (error) {
+      console.error('Failed to check reminders:', error);
+      toast.error('Error', 'Failed to check reminders');
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  const labelFor = (category: string) => settings.find(s => s.category === category)?.label ?? category;
+
+  return (
+    <div className="card p-6">
+      <div className="flex items-center justify-between mb-4">
+        <h2 className="text-lg font-bold text-gray-800 flex items-center gap-2">
+          <Bell className="w-5 h-5 text-indigo-600" />
+          Reminders
+        </h2>
+        <button onClick={handleCheckNow} disabled={checking} className="btn btn-outline btn-sm flex items-center gap-2">
+          <RefreshCw className={`w-4 h-4 ${checking ? 'animate-spin' : ''}`} />
+          Check Now
+        </button>
+      </div>
+
+      <div className="space-y-5">
+        <div>
+          <p className="text-sm text-gray-600 mb-3">
+            Desktop notifications are sent once per item; turn off any source you don't want to hear about.
+          </p>
+          <div className="grid grid-cols-2 gap-3">
+            {settings.map(setting => (
+              <label key={setting.category} className="flex items-center gap-2 text-sm text-gray-700">
+                <input type="checkbox" checked={setting.enabled} onChange={() => handleToggle(setting)} />
+                {setting.label}
+              </label>
+            ))}
+          </div>
+        </div>
+
+        <div className="border-t border-gray-100 pt-4">
+          <h3 className="text-sm font-semibold text-gray-700 mb-2">Current reminders</h3>
+          {reminders.length === 0 ? (
+            <p className="text-sm text-gray-500">Nothing needs your attention right now.</p>
+          ) : (
+            <ul className="max-h-80 overflow-y-auto divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+              {reminders.map(reminder => (
+                <li key={reminder.key} className="flex items-center justify-between gap-3 px-3 py-2">
+                  <div className="min-w-0">
+                    <Link to={reminder.route} className="font-semibold text-gray-900 hover:text-indigo-600">
+                      {reminder.title}
+                    </Link>
+                    <div className="text-gray-500 truncate">
+                      {labelFor(reminder.category)} · {reminder.body}
+                    </div>
+                  </div>
+                  <div className="flex items-center gap-1 shrink-0">
+                    <BellOff className="w-4 h-4 text-gray-400" />
+                    {SNOOZE_OPTIONS.map(option => (
+                      <button
+                        key={option.minutes}
+                        onClick={() => handleSnooze(reminder, option.minutes)}
+                        className="btn btn-outline btn-sm"
+                      >
+                        {option.label}
+                      </button>
+                    ))}
+                  </div>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
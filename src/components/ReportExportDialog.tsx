@@ -0,0 +1,113 @@
+import { useState, useEffect } from 'react';
+import { FileDown, Loader2 } from 'lucide-react';
+import { save } from '@tauri-apps/plugin-dialog';
+import { Modal } from '@/components/Modal';
+import { FormField, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { useInstructor } from '@/components/InstructorProvider';
+import { loadStudents, exportReport } from '@/lib/db';
+import type { Student, ReportKind } from '@/types';
+
+interface ReportExportDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const REPORTS: Array<{ kind: ReportKind; label: string; perStudent: boolean }> = [
+  { kind: 'student_transcript', label: 'Clinical hours transcript', perStudent: true },
+  { kind: 'skills_checklist', label: 'Skills validation checklist', perStudent: true },
+  { kind: 'attendance_record', label: 'Attendance record', perStudent: true },
+  { kind: 'vbon_compliance', label: 'Compliance summary', perStudent: false },
+  { kind: 'site_roster', label: 'Clinical site roster', perStudent: false },
+];
+
+// File > Export Reports: pick a report, choose where to save it, and write the PDF there
+export default function ReportExportDialog({ isOpen, onClose }: ReportExportDialogProps) {
+  const toast = useToast();
+  const { profile } = useInstructor();
+  const [students, setStudents] = useState<Student[]>([]);
+  const [kind, setKind] = useState<ReportKind>('student_transcript');
+  const [studentId, setStudentId] = useState('');
+  const [exporting, setExporting] = useState(false);
+
+  const report = REPORTS.find(r => r.kind === kind)!;
+
+  useEffect(() => {
+    if (!isOpen) return;
+    loadStudents()
+      .then(data => {
+        setStudents(data);
+        setStudentId(id => id || data[0]?.id || '');
+      })
+      .catch(error => console.error('Failed to load students:', error));
+  }, [isOpen]);
+
+  const handleExport = async () => {
+    const student = students.find(s => s.id === studentId);
+    const baseName = report.perStudent && student
+      ? `${kind.replace(/_/g, '-')}-${student.lastName}-${student.firstName}`
+      : kind.replace(/_/g, '-');
+    const outputPath = await save({
+      defaultPath: `${baseName}.pdf`,
+      filters: [{ name: 'PDF', extensions: ['pdf'] }],
+    });
+    if (!outputPath) return;
+
+    setExporting(true);
+    try {
+      const written = await exportReport({
+        kind,
+        studentId: report.perStudent ? studentId : undefined,
+        outputPath,
+        branding: {
+          programName: profile.institution || 'Nursing Program',
+          preparedBy: `${profile.firstName || ''} ${profile.lastName || ''}`.trim() || undefined,
+        },
+      });
+      toast.success('Report Exported', written);
+      onClose();
+    } catch (error) {
+      console.error('Failed to export report:', error);
+      toast.error('Export Failed', typeof error === 'string' ? error : 'Failed to export the report');
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title="Export Report"
+      size="md"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={onClose} className="btn btn-outline">Cancel</button>
+          <button
+            onClick={handleExport}
+            disabled={exporting || (report.perStudent && !studentId)}
+            className="btn btn-primary flex items-center gap-2"
+          >
+            {exporting ? <Loader2 className="w-4 h-4 animate-spin" /> : <FileDown className="w-4 h-4" />}
+            Save PDF...
+          </button>
+        </div>
+      }
+    >
+      <div className="space-y-4">
+        <FormField label="Report">
+          <Select value={kind} onChange={(e) => setKind(e.target.value as ReportKind)}>
+            {REPORTS.map(r => <option key={r.kind} value={r.kind}>{r.label}</option>)}
+          </Select>
+        </FormField>
+        {report.perStudent && (
+          <FormField label="Student" required>
+            <Select value={studentId} onChange={(e) => setStudentId(e.target.value)}>
+              {students.map(s => <option key={s.id} value={s.id}>{s.lastName}, {s.firstName}</option>)}
+            </Select>
+          </FormField>
+        )}
+      </div>
+    </Modal>
+  );
+}
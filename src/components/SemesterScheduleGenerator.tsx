@@ -0,0 +1,334 @@
+import { useState, useEffect } from 'react';
+import { CalendarPlus, Eye, Loader2, Plus, Trash2, AlertTriangle } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import { clsx } from 'clsx';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Textarea, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import {
+  generateSemesterSchedule, getCourseSchedule, getAcademicHolidays, saveAcademicHoliday, deleteAcademicHoliday
+} from '@/lib/db';
+import type { Course, AcademicHoliday, CourseOutlineEntry, SemesterScheduleResult } from '@/types';
+
+interface SemesterScheduleGeneratorProps {
+  isOpen: boolean;
+  onClose: () => void;
+  courses: Course[];
+  onGenerated: () => void;
+}
+
+const WEEKDAYS = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'];
+
+// One session per line: week, chapter, topic[, assessment]. Tabs (pasted from a spreadsheet), pipes or commas separate fields.
+function parseOutline(text: string): CourseOutlineEntry[] {
+  const entries: CourseOutlineEntry[] = [];
+  text.split('\n').map(line => line.trim()).filter(Boolean).forEach((line, index) => {
+    const separator = line.includes('\t') ? '\t' : line.includes('|') ? '|' : ',';
+    const fields = line.split(separator).map(f => f.trim());
+    const weekNumber = parseInt(fields[0], 10);
+    if (isNaN(weekNumber)) {
+      if (index === 0) return; // Header row
+      throw new Error(`Line ${index + 1} does not start with a week number`);
+    }
+    // With commas the topic may contain commas itself, so it takes the rest of the line
+    const [chapter, topic, assessmentMethod] = separator === ','
+      ? [fields[1], fields.slice(2).join(', '), undefined]
+      : [fields[1], fields[2], fields[3]];
+    if (!topic) throw new Error(`Line ${index + 1} has no topic`);
+    entries.push({ weekNumber, chapter: chapter || undefined, topic, assessmentMethod: assessmentMethod || undefined });
+  });
+  return entries;
+}
+
+// Generate a semester of lesson plans from a course outline, meeting days and the holiday calendar
+export default function SemesterScheduleGenerator({ isOpen, onClose, courses, onGenerated }: SemesterScheduleGeneratorProps) {
+  const toast = useToast();
+  const [form, setForm] = useState({
+    courseId: '',
+    startDate: '',
+    endDate: '',
+    meetingDays: ['Mon', 'Wed'] as string[],
+    outline: '',
+    createCalendarEvents: true,
+    replaceExisting: false,
+  });
+  const [holidays, setHolidays] = useState<AcademicHoliday[]>([]);
+  const [newHoliday, setNewHoliday] = useState({ date: '', name: '' });
+  const [preview, setPreview] = useState<SemesterScheduleResult | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const loadHolidays = async () => {
+    try {
+      setHolidays(await getAcademicHolidays());
+    } catch (error) {
+      console.error('Failed to load holidays:', error);
+    }
+  };
+
+  useEffect(() => {
+    if (isOpen) {
+      setPreview(null);
+      loadHolidays();
+    }
+  }, [isOpen]);
+
+  // Reuse the meeting pattern a course was last generated with
+  const handleCourseChange = async (courseId: string) => {
+    setForm(f => ({ ...f, courseId }));
+    setPreview(null);
+    if (!courseId) return;
+    try {
+      const schedule = await getCourseSchedule(courseId);
+      if (schedule) {
+        setForm(f => ({
+          ...f,
+          startDate: schedule.startDate,
+          endDate: schedule.endDate || '',
+          meetingDays: schedule.meetingDays.split(',').filter(Boolean),
+        }));
+      }
+    } catch (error) {
+      console.error('Failed to load course schedule:', error);
+    }
+  };
+
+  const toggleDay = (day: string) => {
+    setPreview(null);
+    setForm(f => ({
+      ...f,
+      meetingDays: f.meetingDays.includes(day) ? f.meetingDays.filter(d => d !== day) : [...f.meetingDays, day],
+    }));
+  };
+
+  const handleAddHoliday = async () => {
+    if (!newHoliday.date || !newHoliday.name.trim()) return;
+    try {
+      await saveAcademicHoliday(newHoliday);
+      setNewHoliday({ date: '', name: '' });
+      setPreview(null);
+      loadHolidays();
+    } catch (error) {
+      console.error('Failed to save holiday:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to save the holiday');
+    }
+  };
+
+  const handleDeleteHoliday = async (id: string) => {
+    try {
+      await deleteAcademicHoliday(id);
+      setPreview(null);
+      loadHolidays();
+    } catch (error) {
+      console.error('Failed to delete holiday:', error);
+    }
+  };
+
+  const run = async (previewOnly: boolean) => {
+    let outline: CourseOutlineEntry[];
+    try {
+      outline = parseOutline(form.outline);
+    } catch (error) {
+      toast.error('Outline', error instanceof Error ? error.message : 'Could not read the outline');
+      return;
+    }
+    setBusy(true);
+    try {
+      const result = await generateSemesterSchedule({
+        courseId: form.courseId,
+        startDate: form.startDate,
+        endDate: form.endDate || undefined,
+        meetingDays: form.meetingDays,
+        outline,
+        createCalendarEvents: form.createCalendarEvents,
+        replaceExisting: form.replaceExisting,
+        preview: previewOnly,
+      });
+      if (previewOnly) {
+        setPreview(result);
+      } else {
+        toast.success(
+          'Semester Generated',
+          `${result.sessions.length} lesson plans created${result.eventsCreated > 0 ? ` with ${result.eventsCreated} calendar events` : ''}.`
+        );
+        onGenerated();
+        onClose();
+      }
+    } catch (error) {
+      console.error('Failed to generate schedule:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to generate the schedule');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const canRun = !!form.courseId && !!form.startDate && form.meetingDays.length > 0 && form.outline.trim().length > 0;
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title="Generate Semester Lesson Plans"
+      size="xl"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={onClose} className="btn btn-outline">Cancel</button>
+          <button onClick={() => run(true)} disabled={!canRun || busy} className="btn btn-outline flex items-center gap-2">
+            <Eye className="w-4 h-4" />
+            Preview
+          </button>
+          <button onClick={() => run(false)} disabled={!canRun || busy || !preview} className="btn btn-primary flex items-center gap-2">
+            {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <CalendarPlus className="w-4 h-4" />}
+            Generate {preview ? `${preview.sessions.length} Sessions` : ''}
+          </button>
+        </div>
+      }
+    >
+      <div className="space-y-5">
+        <div className="grid grid-cols-3 gap-4">
+          <FormField label="Course" required>
+            <Select value={form.courseId} onChange={(e) => handleCourseChange(e.target.value)}>
+              <option value="">Select a course...</option>
+              {courses.map(course => (
+                <option key={course.id} value={course.id}>{course.code} - {course.name}</option>
+              ))}
+            </Select>
+          </FormField>
+          <FormField label="First day of classes" required>
+            <Input
+              type="date"
+              value={form.startDate}
+              onChange={(e) => { setForm({ ...form, startDate: e.target.value }); setPreview(null); }}
+            />
+          </FormField>
+          <FormField label="Last day of classes">
+            <Input
+              type="date"
+              value={form.endDate}
+              onChange={(e) => { setForm({ ...form, endDate: e.target.value }); setPreview(null); }}
+            />
+          </FormField>
+        </div>
+
+        <FormField label="Meeting days" required>
+          <div className="flex gap-2">
+            {WEEKDAYS.map(day => (
+              <button
+                key={day}
+                type="button"
+                onClick={() => toggleDay(day)}
+                className={clsx(
+                  'px-3 py-1.5 rounded-lg text-sm font-medium border-2',
+                  form.meetingDays.includes(day)
+                    ? 'bg-teal-600 border-teal-600 text-white'
+                    : 'bg-white border-gray-200 text-gray-600 hover:border-teal-300'
+                )}
+              >
+                {day}
+              </button>
+            ))}
+          </div>
+        </FormField>
+
+        <FormField label="Course outline" hint="One session per line: week, chapter, topic. Paste straight from a spreadsheet, or separate with | to add an assessment column." required>
+          <Textarea
+            rows={8}
+            className="font-mono text-sm"
+            placeholder={'1, 1, Introduction to Nursing\n1, 2, Legal and Ethical Issues\n2, 5, Vital Signs'}
+            value={form.outline}
+            onChange={(e) => { setForm({ ...form, outline: e.target.value }); setPreview(null); }}
+          />
+        </FormField>
+
+        <div>
+          <h4 className="text-sm font-semibold text-gray-700 mb-2">Holiday calendar</h4>
+          {holidays.length > 0 && (
+            <ul className="max-h-32 overflow-y-auto divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm mb-2">
+              {holidays.map(holiday => (
+                <li key={holiday.id} className="flex items-center justify-between px-3 py-1.5">
+                  <span>
+                    <span className="font-medium text-gray-900">{format(parseISO(holiday.date), 'EEE, MMM d, yyyy')}</span>
+                    <span className="text-gray-500 ml-2">{holiday.name}</span>
+                  </span>
+                  <button onClick={() => handleDeleteHoliday(holiday.id)} className="p-1 text-gray-400 hover:text-red-600" aria-label="Delete holiday">
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="flex gap-2">
+            <Input type="date" value={newHoliday.date} onChange={(e) => setNewHoliday({ ...newHoliday, date: e.target.value })} />
+            <Input placeholder="e.g., Spring Break" value={newHoliday.name} onChange={(e) => setNewHoliday({ ...newHoliday, name: e.target.value })} />
+            <button onClick={handleAddHoliday} disabled={!newHoliday.date || !newHoliday.name.trim()} className="btn btn-outline flex items-center gap-1">
+              <Plus className="w-4 h-4" />
+              Add
+            </button>
+          </div>
+        </div>
+
+        <div className="flex flex-col gap-2 text-sm text-gray-700">
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={form.createCalendarEvents}
+              onChange={(e) => setForm({ ...form, createCalendarEvents: e.target.checked })}
+            />
+            Add each session to the calendar
+          </label>
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={form.replaceExisting}
+              onChange={(e) => { setForm({ ...form, replaceExisting: e.target.checked }); setPreview(null); }}
+            />
+            Replace this course's lesson plans from the first day on
+          </label>
+        </div>
+
+        {preview && (
+          <div className="space-y-3">
+            {preview.warnings.map(warning => (
+              <p key={warning} className="text-sm text-amber-700 flex items-center gap-2">
+                <AlertTriangle className="w-4 h-4" />
+                {warning}
+              </p>
+            ))}
+            {form.replaceExisting && preview.replacedCount > 0 && (
+              <p className="text-sm text-red-700 flex items-center gap-2">
+                <AlertTriangle className="w-4 h-4" />
+                {preview.replacedCount} existing lesson plan{preview.replacedCount === 1 ? '' : 's'} will be deleted.
+              </p>
+            )}
+            {preview.skippedDates.length > 0 && (
+              <p className="text-sm text-gray-600">
+                No class on {preview.skippedDates.map(d => `${format(parseISO(d.date), 'MMM d')} (${d.reason})`).join(', ')}
+              </p>
+            )}
+            <div className="max-h-64 overflow-y-auto border border-gray-100 rounded-lg">
+              <table className="w-full text-sm">
+                <thead className="bg-gray-50 sticky top-0">
+                  <tr className="text-left text-xs font-semibold text-gray-600 uppercase">
+                    <th className="px-3 py-2">Week</th>
+                    <th className="px-3 py-2">Date</th>
+                    <th className="px-3 py-2">Chapter</th>
+                    <th className="px-3 py-2">Topic</th>
+                  </tr>
+                </thead>
+                <tbody className="divide-y divide-gray-100">
+                  {preview.sessions.map(session => (
+                    <tr key={session.lessonPlanId}>
+                      <td className="px-3 py-1.5 text-gray-600">{session.weekNumber}</td>
+                      <td className="px-3 py-1.5 text-gray-900">{format(parseISO(session.date), 'EEE, MMM d')}</td>
+                      <td className="px-3 py-1.5 text-gray-600">{session.chapter || '—'}</td>
+                      <td className="px-3 py-1.5 text-gray-900">{session.topic}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          </div>
+        )}
+      </div>
+    </Modal>
+  );
+}
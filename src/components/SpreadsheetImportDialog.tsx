@@ -0,0 +1,292 @@
+import { useState, useEffect } from 'react';
+import { FileSpreadsheet, Loader2, Upload, AlertTriangle, CheckCircle } from 'lucide-react';
+import { open } from '@tauri-apps/plugin-dialog';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { getImportFields, inspectSpreadsheet, previewSpreadsheetImport, commitSpreadsheetImport } from '@/lib/db';
+import type { ImportTarget, ImportField, ImportMapping, SpreadsheetInfo, ImportPreview } from '@/types';
+
+interface SpreadsheetImportDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const TARGETS: Array<{ target: ImportTarget; label: string }> = [
+  { target: 'students', label: 'Students' },
+  { target: 'grades', label: 'Grades' },
+  { target: 'attendance', label: 'Attendance' },
+];
+
+const fieldLabel = (name: string) => name.replace(/_/g, ' ').replace(/^\w/, c => c.toUpperCase());
+const normalizeHeader = (header: string) => header.trim().toLowerCase().replace(/[\s-]+/g, '_');
+
+// File > Import Data: map spreadsheet columns, review every row's errors, then import the valid rows
+export default function SpreadsheetImportDialog({ isOpen, onClose }: SpreadsheetImportDialogProps) {
+  const toast = useToast();
+  const [target, setTarget] = useState<ImportTarget>('students');
+  const [fields, setFields] = useState<ImportField[]>([]);
+  const [filePath, setFilePath] = useState('');
+  const [info, setInfo] = useState<SpreadsheetInfo | null>(null);
+  const [sheetName, setSheetName] = useState<string | undefined>();
+  const [columns, setColumns] = useState<Record<string, string>>({});
+  const [defaults, setDefaults] = useState<Record<string, string>>({});
+  const [dateFormat, setDateFormat] = useState('');
+  const [preview, setPreview] = useState<ImportPreview | null>(null);
+  const [updateDuplicates, setUpdateDuplicates] = useState(false);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    getImportFields(target)
+      .then(setFields)
+      .catch(error => console.error('Failed to load import fields:', error));
+  }, [isOpen, target]);
+
+  // Map every field whose name matches a column header
+  useEffect(() => {
+    if (!info) return;
+    const mapped: Record<string, string> = {};
+    for (const field of fields) {
+      const header = info.headers.find(h => normalizeHeader(h) === field.name);
+      if (header) mapped[field.name] = header;
+    }
+    setColumns(mapped);
+    setPreview(null);
+  }, [info, fields]);
+
+  const reset = () => {
+    setFilePath('');
+    setInfo(null);
+    setSheetName(undefined);
+    setColumns({});
+    setDefaults({});
+    setPreview(null);
+  };
+
+  const handleClose = () => {
+    reset();
+    onClose();
+  };
+
+  const loadSheet = async (path: string, sheet?: string) => {
+    setBusy(true);
+    try {
+      const loaded = await inspectSpreadsheet(path, sheet);
+      setFilePath(path);
+      setSheetName(sheet ?? loaded.sheetNames[0]);
+      setInfo(loaded);
+    } catch (error) {
+      console.error('Failed to read spreadsheet:', error);
+      toast.error('Could Not Read File', typeof error === 'string' ? error : 'Failed to read the spreadsheet');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleChooseFile = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: 'Spreadsheet', extensions: ['csv', 'xlsx', 'xls', 'ods'] }],
+    });
+    if (typeof selected === 'string') {
+      await loadSheet(selected);
+    }
+  };
+
+  const mapping = (): ImportMapping => ({
+    target,
+    columns,
+    defaults: Object.fromEntries(Object.entries(defaults).filter(([field, value]) => value.trim() && !columns[field])),
+    sheetName: info && info.sheetNames.length > 0 ? sheetName : undefined,
+    dateFormat: dateFormat.trim() || undefined,
+  });
+
+  const handlePreview = async () => {
+    setBusy(true);
+    try {
+      setPreview(await previewSpreadsheetImport(filePath, mapping()));
+    } catch (error) {
+      console.error('Failed to preview import:', error);
+      toast.error('Preview Failed', typeof error === 'string' ? error : 'Failed to preview the import');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleCommit = async () => {
+    setBusy(true);
+    try {
+      const result = await commitSpreadsheetImport(filePath, mapping(), updateDuplicates);
+      const parts = [`${result.imported} imported`];
+      if (result.updated) parts.push(`${result.updated} updated`);
+      if (result.skipped) parts.push(`${result.skipped} skipped`);
+      if (result.failed) parts.push(`${result.failed} failed`);
+      if (result.failed) {
+        toast.warning('Import Finished With Errors', `${parts.join(', ')} — ${result.errors.slice(0, 3).join('; ')}`);
+      } else {
+        toast.success('Import Complete', parts.join(', '));
+      }
+      handleClose();
+    } catch (error) {
+      console.error('Failed to import spreadsheet:', error);
+      toast.error('Import Failed', typeof error === 'string' ? error : 'Failed to import the spreadsheet');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const importableRows = preview
+    ? preview.validRows - (updateDuplicates ? 0 : preview.duplicateRows)
+    : 0;
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={handleClose}
+      title="Import Spreadsheet"
+      size="xl"
+      footer={
+        <div className="flex justify-end gap-3">
+          <button onClick={handleClose} className="btn btn-outline">Cancel</button>
+          {preview ? (
+            <button
+              onClick={handleCommit}
+              disabled={busy || importableRows <= 0}
+              className="btn btn-primary flex items-center gap-2"
+            >
+              {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <Upload className="w-4 h-4" />}
+              Import {importableRows} Row{importableRows === 1 ? '' : 's'}
+            </button>
+          ) : (
+            <button
+              onClick={handlePreview}
+              disabled={busy || !info}
+              className="btn btn-primary flex items-center gap-2"
+            >
+              {busy ? <Loader2 className="w-4 h-4 animate-spin" /> : <FileSpreadsheet className="w-4 h-4" />}
+              Preview
+            </button>
+          )}
+        </div>
+      }
+    >
+      <div className="space-y-5">
+        <div className="grid grid-cols-2 gap-4">
+          <FormField label="Import into">
+            <Select
+              value={target}
+              onChange={(e) => {
+                setTarget(e.target.value as ImportTarget);
+                setDefaults({});
+                setPreview(null);
+              }}
+            >
+              {TARGETS.map(t => <option key={t.target} value={t.target}>{t.label}</option>)}
+            </Select>
+          </FormField>
+          <FormField label="File" hint={filePath || 'CSV, XLSX, XLS or ODS'}>
+            <button onClick={handleChooseFile} disabled={busy} className="btn btn-outline w-full flex items-center justify-center gap-2">
+              <FileSpreadsheet className="w-4 h-4" />
+              {info ? `${info.rowCount} rows — choose another...` : 'Choose file...'}
+            </button>
+          </FormField>
+        </div>
+
+        {info && !preview && (
+          <>
+            <div className="grid grid-cols-2 gap-4">
+              {info.sheetNames.length > 1 && (
+                <FormField label="Sheet">
+                  <Select value={sheetName} onChange={(e) => loadSheet(filePath, e.target.value)}>
+                    {info.sheetNames.map(name => <option key={name} value={name}>{name}</option>)}
+                  </Select>
+                </FormField>
+              )}
+              <FormField label="Date format" hint='Tried first when reading dates, e.g. "%d/%m/%Y"'>
+                <Input value={dateFormat} onChange={(e) => setDateFormat(e.target.value)} placeholder="Detect automatically" />
+              </FormField>
+            </div>
+
+            <div>
+              <h3 className="text-sm font-semibold text-gray-700 mb-2">Columns</h3>
+              <div className="border border-gray-100 rounded-lg divide-y divide-gray-100 max-h-80 overflow-y-auto">
+                {fields.map(field => (
+                  <div key={field.name} className="grid grid-cols-2 gap-3 items-center px-3 py-2 text-sm">
+                    <span className="text-gray-700">
+                      {fieldLabel(field.name)}
+                      {field.required && <span className="text-red-500 ml-1">*</span>}
+                    </span>
+                    <div className="flex gap-2">
+                      <Select
+                        value={columns[field.name] ?? ''}
+                        onChange={(e) => setColumns(prev => {
+                          const next = { ...prev };
+                          if (e.target.value) next[field.name] = e.target.value;
+                          else delete next[field.name];
+                          return next;
+                        })}
+                      >
+                        <option value="">Not mapped</option>
+                        {info.headers.map(header => <option key={header} value={header}>{header}</option>)}
+                      </Select>
+                      {!columns[field.name] && (
+                        <Input
+                          value={defaults[field.name] ?? ''}
+                          onChange={(e) => setDefaults(prev => ({ ...prev, [field.name]: e.target.value }))}
+                          placeholder="Same value for every row"
+                        />
+                      )}
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </div>
+          </>
+        )}
+
+        {preview && (
+          <>
+            <div className="grid grid-cols-4 gap-3 text-center text-sm">
+              <div className="p-3 bg-gray-50 rounded-lg"><div className="text-lg font-bold">{preview.totalRows}</div>Rows</div>
+              <div className="p-3 bg-green-50 rounded-lg text-green-700"><div className="text-lg font-bold">{preview.validRows}</div>Valid</div>
+              <div className="p-3 bg-red-50 rounded-lg text-red-700"><div className="text-lg font-bold">{preview.errorRows}</div>With errors</div>
+              <div className="p-3 bg-amber-50 rounded-lg text-amber-700"><div className="text-lg font-bold">{preview.duplicateRows}</div>Duplicates</div>
+            </div>
+
+            {preview.duplicateRows > 0 && (
+              <label className="flex items-center gap-2 text-sm text-gray-700">
+                <input type="checkbox" checked={updateDuplicates} onChange={(e) => setUpdateDuplicates(e.target.checked)} />
+                Update the existing records for duplicate rows instead of skipping them
+              </label>
+            )}
+
+            <ul className="max-h-80 overflow-y-auto divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+              {preview.rows
+                .filter(row => row.errors.length > 0 || row.warnings.length > 0 || row.duplicateOf)
+                .map(row => (
+                  <li key={row.rowNumber} className="px-3 py-2">
+                    <div className="flex items-center gap-2 font-semibold text-gray-900">
+                      {row.errors.length > 0
+                        ? <AlertTriangle className="w-4 h-4 text-red-600" />
+                        : <CheckCircle className="w-4 h-4 text-amber-600" />}
+                      Row {row.rowNumber}
+                      {row.duplicateOf && <span className="text-xs font-medium text-amber-700">duplicate of {row.duplicateOf}</span>}
+                    </div>
+                    {row.errors.map((error, idx) => <div key={`e${idx}`} className="text-red-700">{error}</div>)}
+                    {row.warnings.map((warning, idx) => <div key={`w${idx}`} className="text-amber-700">{warning}</div>)}
+                  </li>
+                ))}
+            </ul>
+            {preview.errorRows > 0 && (
+              <p className="text-xs text-gray-500">Rows with errors are left out of the import.</p>
+            )}
+            <button onClick={() => setPreview(null)} disabled={busy} className="btn btn-outline btn-sm">
+              Back to columns
+            </button>
+          </>
+        )}
+      </div>
+    </Modal>
+  );
+}
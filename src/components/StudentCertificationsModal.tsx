@@ -0,0 +1,171 @@
+import { useState, useEffect } from 'react';
+import { Plus, Trash2, Loader2 } from 'lucide-react';
+import { Modal } from '@/components/Modal';
+import { FormField, Input, Select } from '@/components/FormField';
+import { useToast } from '@/components/Toast';
+import { CERTIFICATION_TYPES } from '@/components/CertificationRequirementsModal';
+import { getStudentCertifications, addCertification, deleteCertification } from '@/lib/db';
+import type { StudentCertification } from '@/types';
+
+interface StudentCertificationsModalProps {
+  isOpen: boolean;
+  onClose: () => void;
+  studentId: string | null;
+  studentName?: string;
+  onChanged?: () => void;
+}
+
+const emptyForm = {
+  certificationType: 'BLS' as StudentCertification['certificationType'],
+  certificationName: '',
+  issueDate: '',
+  expiryDate: '',
+  pending: false,
+};
+
+// A student's certifications, with a quick form to record a new one or a renewal
+export default function StudentCertificationsModal({ isOpen, onClose, studentId, studentName, onChanged }: StudentCertificationsModalProps) {
+  const toast = useToast();
+  const [certifications, setCertifications] = useState<StudentCertification[]>([]);
+  const [form, setForm] = useState(emptyForm);
+  const [saving, setSaving] = useState(false);
+
+  const loadCertifications = async () => {
+    if (!studentId) return;
+    try {
+      setCertifications(await getStudentCertifications(studentId));
+    } catch (error) {
+      console.error('Failed to load certifications:', error);
+    }
+  };
+
+  useEffect(() => {
+    if (isOpen) {
+      setForm(emptyForm);
+      loadCertifications();
+    }
+  }, [isOpen, studentId]);
+
+  const handleAdd = async () => {
+    if (!studentId || !form.certificationName.trim()) return;
+    setSaving(true);
+    try {
+      await addCertification({
+        id: `CERT-${Date.now()}`,
+        studentId,
+        certificationType: form.certificationType,
+        certificationName: form.certificationName.trim(),
+        issueDate: form.issueDate || undefined,
+        // Blank for certifications that don't expire (e.g., a background check)
+        expiryDate: form.expiryDate,
+        // The backend derives Active / Expiring Soon / Expired from the expiry date
+        status: form.pending ? 'Pending' : 'Active',
+      });
+      setForm(emptyForm);
+      loadCertifications();
+      onChanged?.();
+    } catch (error) {
+      console.error('Failed to add certification:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to add the certification');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (cert: StudentCertification) => {
+    if (!confirm(`Delete ${cert.certificationName}?`)) return;
+    try {
+      await deleteCertification(cert.id);
+      loadCertifications();
+      onChanged?.();
+    } catch (error) {
+      console.error('Failed to delete certification:', error);
+      toast.error('Error', 'Failed to delete the certification');
+    }
+  };
+
+  const statusClass = (status: StudentCertification['status']) => ({
+    'Active': 'bg-green-100 text-green-700',
+    'Expiring Soon': 'bg-amber-100 text-amber-700',
+    'Expired': 'bg-red-100 text-red-700',
+    'Pending': 'bg-gray-100 text-gray-600',
+  }[status]);
+
+  return (
+    <Modal
+      isOpen={isOpen}
+      onClose={onClose}
+      title={`Certifications${studentName ? ` - ${studentName}` : ''}`}
+      size="lg"
+      footer={
+        <div className="flex justify-end">
+          <button onClick={onClose} className="btn btn-outline">Close</button>
+        </div>
+      }
+    >
+      <div className="space-y-5">
+        {certifications.length === 0 ? (
+          <p className="text-sm text-gray-500">No certifications recorded.</p>
+        ) : (
+          <ul className="divide-y divide-gray-100 border border-gray-100 rounded-lg text-sm">
+            {certifications.map(cert => (
+              <li key={cert.id} className="flex items-center justify-between px-3 py-2">
+                <div>
+                  <span className="font-semibold text-gray-900">{cert.certificationName}</span>
+                  <span className={`ml-2 px-2 py-0.5 text-xs rounded-full ${statusClass(cert.status)}`}>{cert.status}</span>
+                  <div className="text-gray-500">
+                    {cert.certificationType}
+                    {cert.issueDate ? ` · issued ${cert.issueDate}` : ''}
+                    {cert.expiryDate ? ` · expires ${cert.expiryDate}` : ' · no expiry'}
+                  </div>
+                </div>
+                <button onClick={() => handleDelete(cert)} className="p-1.5 text-gray-400 hover:text-red-600" aria-label="Delete certification">
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+
+        <div className="border-t border-gray-100 pt-4 space-y-4">
+          <h4 className="text-sm font-semibold text-gray-700">Add certification</h4>
+          <div className="grid grid-cols-2 gap-4">
+            <FormField label="Type" required>
+              <Select
+                value={form.certificationType}
+                onChange={(e) => setForm({ ...form, certificationType: e.target.value as StudentCertification['certificationType'] })}
+              >
+                {CERTIFICATION_TYPES.map(type => <option key={type} value={type}>{type}</option>)}
+              </Select>
+            </FormField>
+            <FormField label="Name" required>
+              <Input
+                value={form.certificationName}
+                placeholder="e.g., TB skin test"
+                onChange={(e) => setForm({ ...form, certificationName: e.target.value })}
+              />
+            </FormField>
+          </div>
+          <div className="grid grid-cols-2 gap-4">
+            <FormField label="Issued">
+              <Input type="date" value={form.issueDate} onChange={(e) => setForm({ ...form, issueDate: e.target.value })} />
+            </FormField>
+            <FormField label="Expires" hint="Leave blank if it doesn't expire">
+              <Input type="date" value={form.expiryDate} onChange={(e) => setForm({ ...form, expiryDate: e.target.value })} />
+            </FormField>
+          </div>
+          <div className="flex items-center justify-between">
+            <label className="flex items-center gap-2 text-sm text-gray-700">
+              <input type="checkbox" checked={form.pending} onChange={(e) => setForm({ ...form, pending: e.target.checked })} />
+              Pending verification
+            </label>
+            <button onClick={handleAdd} disabled={saving || !form.certificationName.trim()} className="btn btn-primary flex items-center gap-2">
+              {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Plus className="w-4 h-4" />}
+              Add
+            </button>
+          </div>
+        </div>
+      </div>
+    </Modal>
+  );
+}
@@ -12,7 +12,7 @@ import {
   Stethoscope,
   Timer
 } from 'lucide-react';
-import { loadStudents, bulkRecordAttendance, getAttendanceForDate, autoCreateMakeupHours } from '@/lib/db';
+import { loadStudents, bulkRecordAttendance, getAttendanceForDate } from '@/lib/db';
 import { Student, Attendance } from '@/types';
 import { Modal } from './Modal';
 import { useToast } from './Toast';
@@ -169,31 +169,7 @@ export default function TakeAttendanceModal({ isOpen, onClose, onComplete }: Tak
 
       await bulkRecordAttendance(records);
 
-      // Auto-create makeup hours for clinical absences and partial attendance
-      if (attendanceType === 'clinical') {
-        for (const record of Array.from(attendanceRecords.values())) {
-          if (record.status === 'Absent') {
-            // Full day missed - create makeup hours for full required hours
-            await autoCreateMakeupHours(
-              `ATT-${record.student.id}-${today}-${attendanceType}`,
-              record.student.id,
-              record.hoursRequired,
-              record.notes || 'Clinical absence'
-            );
-          } else if (record.status === 'Partial' && record.hoursAttended !== null) {
-            // Partial day - create makeup hours for missed hours
-            const hoursMissed = record.hoursRequired - record.hoursAttended;
-            if (hoursMissed > 0) {
-              await autoCreateMakeupHours(
-                `ATT-${record.student.id}-${today}-${attendanceType}`,
-                record.student.id,
-                hoursMissed,
-                record.notes || `Partial attendance - ${record.hoursAttended}/${record.hoursRequired} hours`
-              );
-            }
-          }
-        }
-      }
+      // Makeup hours for missed time are created by the attendance policy on the backend
 
       const presentCount = records.filter(r => r.status === 'Present').length;
       const absentCount = records.filter(r => r.status === 'Absent').length;
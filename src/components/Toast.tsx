@@ -10,6 +10,7 @@ export interface Toast {
   title: string;
   message?: string;
   duration?: number;
+  action?: { label: string; onClick: () => void };
 }
 
 interface ToastContextType {
@@ -157,6 +158,17 @@ function ToastItem({ toast, onRemove }: { toast: Toast; onRemove: (id: string) =
         {toast.message && (
           <div className={clsx('text-sm mt-1', style.message)}>{toast.message}</div>
         )}
+        {toast.action && (
+          <button
+            onClick={() => {
+              toast.action!.onClick();
+              onRemove(toast.id);
+            }}
+            className={clsx('text-sm font-semibold mt-2 hover:underline', style.title)}
+          >
+            {toast.action.label}
+          </button>
+        )}
       </div>
       <button
         onClick={() => onRemove(toast.id)}
@@ -1,7 +1,10 @@
 import { useEffect } from 'react';
-import { listen } from '@tauri-apps/api/event';
+import { listen, emit } from '@tauri-apps/api/event';
+import { onAction } from '@tauri-apps/plugin-notification';
 import { useNavigate } from 'react-router-dom';
 import { useSidebar } from '@/components/SidebarProvider';
+import { useToast } from '@/components/Toast';
+import type { ReminderNotice } from '@/types';
 
 // Global state for modals that need to be triggered from menu
 let newStudentModalTrigger: (() => void) | null = null;
@@ -28,6 +31,7 @@ export function setAboutDialogTrigger(trigger: () => void) {
 export function useMenuEvents() {
   const navigate = useNavigate();
   const { toggleSidebar } = useSidebar();
+  const { showToast } = useToast();
 
   useEffect(() => {
     // Listen for navigation events
@@ -48,24 +52,18 @@ export function useMenuEvents() {
 
     // Listen for import data event
     const unlistenImportData = listen('menu:import-data', () => {
-      navigate('/students');
-      // Trigger import modal after navigation
-      setTimeout(() => {
-        if (importDataModalTrigger) {
-          importDataModalTrigger();
-        }
-      }, 100);
+      // The spreadsheet import dialog is mounted app-wide and imports students, grades or attendance
+      if (importDataModalTrigger) {
+        importDataModalTrigger();
+      }
     });
 
     // Listen for export reports event
     const unlistenExportReports = listen('menu:export-reports', () => {
-      navigate('/analytics');
-      // Trigger export dialog after navigation
-      setTimeout(() => {
-        if (exportReportsModalTrigger) {
-          exportReportsModalTrigger();
-        }
-      }, 100);
+      // The report export dialog is mounted app-wide and saves to a user-chosen path
+      if (exportReportsModalTrigger) {
+        exportReportsModalTrigger();
+      }
     });
 
     // Listen for settings event
@@ -100,6 +98,23 @@ export function useMenuEvents() {
       }
     });
 
+    // Reminder notifications: follow a tapped notification where the platform reports it,
+    // and otherwise offer an in-app toast that opens the same route
+    const unlistenReminders = listen<ReminderNotice>('reminders:notified', (event) => {
+      const { title, body, route } = event.payload;
+      showToast({
+        type: 'info',
+        title,
+        message: body,
+        duration: 0,
+        action: { label: 'Open', onClick: () => emit('menu:navigate', route) },
+      });
+    });
+    const notificationAction = onAction((notification) => {
+      const route = notification.extra?.route;
+      if (typeof route === 'string') navigate(route);
+    }).catch(() => null); // Desktop notifications have no click callback
+
     // Cleanup listeners on unmount
     return () => {
       unlistenNavigate.then(fn => fn());
@@ -110,6 +125,8 @@ export function useMenuEvents() {
       unlistenToggleSidebar.then(fn => fn());
       unlistenDocumentation.then(fn => fn());
       unlistenAbout.then(fn => fn());
+      unlistenReminders.then(fn => fn());
+      notificationAction.then(listener => listener?.unregister());
     };
-  }, [navigate, toggleSidebar]);
+  }, [navigate, toggleSidebar, showToast]);
 }
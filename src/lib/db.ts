@@ -3,16 +3,24 @@ import {
   Student, ClinicalLog, Grade, CalendarEvent,
   Course, LessonPlan, LessonPlanWithMaterials, TeachingMaterial,
   Attendance, AttendanceSummary, StudentCertification, CertificationAlert,
-  PreceptorEvaluation, Deadline, SkillValidation, StudentHoursSummary, StudentFlags,
-  MakeupHours, MakeupHoursSummary,
+  CertificationRequirement, ClinicalClearance,
+  PreceptorEvaluation, EvaluationImportQueueItem, Deadline, SkillValidation, StudentHoursSummary, StudentFlags,
+  CohortFlagFilters, CohortFlagReport,
+  MakeupHours, MakeupHoursSummary, MakeupAllocation,
   Preceptor, PreceptorWithDetails, PreceptorVerificationAlert,
   ClinicalSite, ClinicalSiteUsageStats,
   ClinicalAssignment, ClinicalAssignmentWithDetails,
   VrScenario, StudentVrCompletion, StudentVrSummary,
   StudentHourSubmission, StudentHourSubmissionWithStudent,
   InstructorCertification, InstructorCertificationAlert,
-  CompHoursEarned, CompHoursUsed, CompHoursSummary, CompHoursExpirationWarning,
-  VBONRegulation, VBONMapping, VBONRegulationWithMapping, VBONComplianceSummary
+  CompHoursEarned, CompHoursUsed, CompHoursSummary, CompHoursExpirationWarning, CompHoursLedger,
+  CompHoursPolicy, CompTimesheet, ReportBranding, ReportRequest,
+  VBONRegulation, VBONMapping, VBONRegulationWithMapping, VBONComplianceSummary, VBONEvidenceReport,
+  RegulatoryFramework, FrameworkComplianceSummary, RegulationCatalogEdition, RegulationCatalogDiff,
+  RegulationCatalogImportRequest, RegulationCatalogImportResult,
+  AcademicHoliday, CourseSchedule, SemesterScheduleRequest, SemesterScheduleResult, RescheduleRequest, RescheduleResult,
+  CourseCloneRequest, CourseCloneResult, Reminder, ReminderSetting,
+  ImportTarget, ImportField, ImportMapping, SpreadsheetInfo, ImportPreview
 } from '@/types';
 import { seedStudents, seedLogs, seedEvents } from './data';
 
@@ -182,6 +190,31 @@ export const deleteLessonPlan = async (id: string) => {
     await invoke('delete_lesson_plan', { id });
 };
 
+// Semester Scheduling
+export const generateSemesterSchedule = async (request: SemesterScheduleRequest): Promise<SemesterScheduleResult> => {
+    return await invoke<SemesterScheduleResult>('generate_semester_schedule', { request });
+};
+
+export const rescheduleCourseSession = async (request: RescheduleRequest): Promise<RescheduleResult> => {
+    return await invoke<RescheduleResult>('reschedule_course_session', { request });
+};
+
+export const getCourseSchedule = async (courseId: string): Promise<CourseSchedule | null> => {
+    return await invoke<CourseSchedule | null>('get_course_schedule', { courseId });
+};
+
+export const getAcademicHolidays = async (): Promise<AcademicHoliday[]> => {
+    return await invoke<AcademicHoliday[]>('get_academic_holidays');
+};
+
+export const saveAcademicHoliday = async (holiday: Partial<AcademicHoliday> & { date: string; name: string }) => {
+    await invoke('save_academic_holiday', { holiday: { id: '', createdAt: '', ...holiday } });
+};
+
+export const deleteAcademicHoliday = async (id: string) => {
+    await invoke('delete_academic_holiday', { id });
+};
+
 // ==================== TEACHING MATERIALS ====================
 
 export const addTeachingMaterial = async (material: TeachingMaterial) => {
@@ -248,6 +281,10 @@ export const deleteMakeupHours = async (id: string) => {
     await invoke('delete_makeup_hours', { id });
 };
 
+export const getMakeupAllocations = async (makeupHoursId: string): Promise<MakeupAllocation[]> => {
+    return await invoke<MakeupAllocation[]>('get_makeup_allocations', { makeupHoursId });
+};
+
 export const autoCreateMakeupHours = async (
     attendanceId: string,
     studentId: string,
@@ -280,8 +317,28 @@ export const getStudentCertifications = async (studentId: string): Promise<Stude
     return await invoke<StudentCertification[]>('get_student_certifications', { studentId });
 };
 
-export const getExpiringCertifications = async (daysAhead: number = 30): Promise<CertificationAlert[]> => {
-    return await invoke<CertificationAlert[]>('get_expiring_certifications', { daysAhead });
+export const getExpiringCertifications = async (daysAhead: number = 30, includeExpired: boolean = false): Promise<CertificationAlert[]> => {
+    return await invoke<CertificationAlert[]>('get_expiring_certifications', { daysAhead, includeExpired });
+};
+
+export const getCertificationRequirements = async (): Promise<CertificationRequirement[]> => {
+    return await invoke<CertificationRequirement[]>('get_certification_requirements');
+};
+
+export const saveCertificationRequirement = async (requirement: CertificationRequirement): Promise<CertificationRequirement> => {
+    return await invoke<CertificationRequirement>('save_certification_requirement', { requirement });
+};
+
+export const deleteCertificationRequirement = async (id: string) => {
+    await invoke('delete_certification_requirement', { id });
+};
+
+export const getClinicalClearance = async (studentId: string, siteId: string, date: string): Promise<ClinicalClearance> => {
+    return await invoke<ClinicalClearance>('get_clinical_clearance', { studentId, siteId, date });
+};
+
+export const getSiteClearance = async (siteId: string, date: string, cohort?: string): Promise<ClinicalClearance[]> => {
+    return await invoke<ClinicalClearance[]>('get_site_clearance', { siteId, date, cohort });
 };
 
 // ==================== PRECEPTOR EVALUATIONS ====================
@@ -320,8 +377,8 @@ export const deleteDeadline = async (id: string) => {
     await invoke('delete_deadline', { id });
 };
 
-export const getUpcomingDeadlines = async (daysAhead: number = 14): Promise<Deadline[]> => {
-    return await invoke<Deadline[]>('get_upcoming_deadlines', { daysAhead });
+export const getUpcomingDeadlines = async (daysAhead: number = 14, includeOverdue: boolean = false): Promise<Deadline[]> => {
+    return await invoke<Deadline[]>('get_upcoming_deadlines', { daysAhead, includeOverdue });
 };
 
 export const getAllDeadlines = async (): Promise<Deadline[]> => {
@@ -358,6 +415,15 @@ export const getStudentFlags = async (studentId: string): Promise<StudentFlags>
     return await invoke<StudentFlags>('get_student_flags', { studentId });
 };
 
+export const getCohortFlags = async (cohort: string, filters?: CohortFlagFilters): Promise<CohortFlagReport> => {
+    return await invoke<CohortFlagReport>('get_cohort_flags', { cohort, filters });
+};
+
+// Records current flags into the flag history (all students, or one cohort); returns the number of students
+export const snapshotStudentFlags = async (cohort?: string): Promise<number> => {
+    return await invoke<number>('snapshot_student_flags', { cohort });
+};
+
 // ==================== PRECEPTOR MANAGEMENT ====================
 
 export const addPreceptor = async (preceptor: Preceptor) => {
@@ -428,12 +494,12 @@ export const updateSiteLastUsed = async (siteId: string) => {
 
 // ==================== CLINICAL ASSIGNMENTS ====================
 
-export const createClinicalAssignment = async (assignment: ClinicalAssignment) => {
-    await invoke('create_clinical_assignment', { assignment });
+export const createClinicalAssignment = async (assignment: ClinicalAssignment, allowUncleared?: boolean) => {
+    await invoke('create_clinical_assignment', { assignment, allowUncleared });
 };
 
-export const bulkCreateAssignments = async (assignments: ClinicalAssignment[]) => {
-    await invoke('bulk_create_assignments', { assignments });
+export const bulkCreateAssignments = async (assignments: ClinicalAssignment[], allowUncleared?: boolean) => {
+    await invoke('bulk_create_assignments', { assignments, allowUncleared });
 };
 
 export const getAssignmentsForDate = async (date: string): Promise<ClinicalAssignmentWithDetails[]> => {
@@ -448,8 +514,8 @@ export const getStudentAssignments = async (studentId: string): Promise<Clinical
     return await invoke<ClinicalAssignmentWithDetails[]>('get_student_assignments', { studentId });
 };
 
-export const updateAssignment = async (assignment: ClinicalAssignment) => {
-    await invoke('update_assignment', { assignment });
+export const updateAssignment = async (assignment: ClinicalAssignment, allowUncleared?: boolean) => {
+    await invoke('update_assignment', { assignment, allowUncleared });
 };
 
 export const cancelAssignment = async (id: string, reason?: string) => {
@@ -514,6 +580,35 @@ export const rejectSubmission = async (id: string, feedback: string) => {
     await invoke('reject_submission', { id, feedback });
 };
 
+// Recount the tray's pending items after resolving one
+export const refreshTray = async () => {
+    await invoke('refresh_tray');
+};
+
+// ==================== REMINDERS ====================
+
+// Every current reminder from the enabled sources, including ones already notified
+export const getPendingReminders = async (): Promise<Reminder[]> => {
+    return await invoke<Reminder[]>('get_pending_reminders');
+};
+
+// Notifies anything due now; returns how many reminders were notified
+export const checkRemindersNow = async (): Promise<number> => {
+    return await invoke<number>('check_reminders_now');
+};
+
+export const snoozeReminder = async (reminder: Reminder, minutes: number) => {
+    await invoke('snooze_reminder', { key: reminder.key, category: reminder.category, minutes });
+};
+
+export const getReminderSettings = async (): Promise<ReminderSetting[]> => {
+    return await invoke<ReminderSetting[]>('get_reminder_settings');
+};
+
+export const updateReminderSetting = async (category: string, enabled: boolean) => {
+    await invoke('update_reminder_setting', { category, enabled });
+};
+
 // ==================== INSTRUCTOR CERTIFICATIONS ====================
 
 export const addInstructorCertification = async (cert: InstructorCertification) => {
@@ -538,8 +633,9 @@ export const getInstructorCertificationAlerts = async (): Promise<InstructorCert
 
 // ==================== COMP HOURS ====================
 
-export const addCompHoursEarned = async (entry: CompHoursEarned) => {
-    await invoke('add_comp_hours_earned', { entry });
+// Returns the entry as stored, after the comp hours policy is applied
+export const addCompHoursEarned = async (entry: CompHoursEarned): Promise<CompHoursEarned> => {
+    return await invoke<CompHoursEarned>('add_comp_hours_earned', { entry });
 };
 
 export const getAllCompHoursEarned = async (): Promise<CompHoursEarned[]> => {
@@ -570,6 +666,42 @@ export const getCompHoursExpirationWarnings = async (): Promise<CompHoursExpirat
     return await invoke<CompHoursExpirationWarning[]>('get_comp_hours_expiration_warnings');
 };
 
+export const getCompHoursLedger = async (): Promise<CompHoursLedger> => {
+    return await invoke<CompHoursLedger>('get_comp_hours_ledger');
+};
+
+export const approveCompHoursEarned = async (id: string) => {
+    await invoke('approve_comp_hours_earned', { id });
+};
+
+export const getCompHoursPolicy = async (): Promise<CompHoursPolicy> => {
+    return await invoke<CompHoursPolicy>('get_comp_hours_policy');
+};
+
+export const updateCompHoursPolicy = async (policy: CompHoursPolicy) => {
+    await invoke('update_comp_hours_policy', { policy });
+};
+
+export const getCompTimesheet = async (startDate: string, endDate: string): Promise<CompTimesheet> => {
+    return await invoke<CompTimesheet>('get_comp_timesheet', { startDate, endDate });
+};
+
+export const exportCompTimesheet = async (request: {
+    startDate: string;
+    endDate: string;
+    format: 'csv' | 'pdf';
+    outputPath: string;
+    employeeName: string;
+    branding: ReportBranding;
+}): Promise<string> => {
+    return await invoke<string>('export_comp_timesheet', { request });
+};
+
+// Writes a PDF report to `outputPath` and returns the path written
+export const exportReport = async (request: ReportRequest): Promise<string> => {
+    return await invoke<string>('export_report', { request });
+};
+
 // ==================== COURSES ====================
 
 export const addCourse = async (course: Course) => {
@@ -592,6 +724,10 @@ export const deleteCourse = async (id: string) => {
     await invoke('delete_course', { id });
 };
 
+export const cloneCourse = async (request: CourseCloneRequest): Promise<CourseCloneResult> => {
+    return await invoke<CourseCloneResult>('clone_course', { request });
+};
+
 export const getLessonPlansByCourse = async (courseId: string): Promise<LessonPlan[]> => {
     return await invoke<LessonPlan[]>('get_lesson_plans_by_course', { courseId });
 };
@@ -606,8 +742,30 @@ export const getLessonPlanWithMaterials = async (id: string): Promise<LessonPlan
 
 // ==================== VBON COMPLIANCE MAPPING ====================
 
-export const getAllVBONRegulations = async (): Promise<VBONRegulation[]> => {
-    return await invoke<VBONRegulation[]>('get_all_vbon_regulations');
+// Regulation commands take an optional framework id; without one they use the current framework
+
+export const getRegulatoryFrameworks = async (): Promise<RegulatoryFramework[]> => {
+    return await invoke<RegulatoryFramework[]>('get_regulatory_frameworks');
+};
+
+export const saveRegulatoryFramework = async (framework: RegulatoryFramework) => {
+    await invoke('save_regulatory_framework', { framework });
+};
+
+export const deleteRegulatoryFramework = async (id: string) => {
+    await invoke('delete_regulatory_framework', { id });
+};
+
+export const setCurrentRegulatoryFramework = async (id: string): Promise<RegulatoryFramework> => {
+    return await invoke<RegulatoryFramework>('set_current_regulatory_framework', { id });
+};
+
+export const getComplianceByFramework = async (): Promise<FrameworkComplianceSummary[]> => {
+    return await invoke<FrameworkComplianceSummary[]>('get_compliance_by_framework');
+};
+
+export const getAllVBONRegulations = async (frameworkId?: string): Promise<VBONRegulation[]> => {
+    return await invoke<VBONRegulation[]>('get_all_vbon_regulations', { frameworkId });
 };
 
 export const addVBONRegulation = async (regulation: VBONRegulation) => {
@@ -618,8 +776,20 @@ export const seedVBONRegulations = async (regulations: VBONRegulation[]) => {
     await invoke('seed_vbon_regulations', { regulations });
 };
 
-export const getVBONRegulationsWithMappings = async (): Promise<VBONRegulationWithMapping[]> => {
-    return await invoke<VBONRegulationWithMapping[]>('get_vbon_regulations_with_mappings');
+export const getVBONRegulationsWithMappings = async (frameworkId?: string): Promise<VBONRegulationWithMapping[]> => {
+    return await invoke<VBONRegulationWithMapping[]>('get_vbon_regulations_with_mappings', { frameworkId });
+};
+
+export const getLessonPlanRegulations = async (lessonPlanId: string): Promise<VBONRegulation[]> => {
+    return await invoke<VBONRegulation[]>('get_lesson_plan_regulations', { lessonPlanId });
+};
+
+export const setLessonPlanRegulations = async (lessonPlanId: string, regulationIds: string[]) => {
+    return await invoke('set_lesson_plan_regulations', { lessonPlanId, regulationIds });
+};
+
+export const setRegulationLessonPlans = async (regulationId: string, lessonPlanIds: string[]) => {
+    return await invoke('set_regulation_lesson_plans', { regulationId, lessonPlanIds });
 };
 
 export const getVBONMapping = async (regulationId: string): Promise<VBONMapping | null> => {
@@ -630,14 +800,60 @@ export const upsertVBONMapping = async (mapping: VBONMapping) => {
     await invoke('upsert_vbon_mapping', { mapping });
 };
 
-export const getVBONComplianceSummary = async (): Promise<VBONComplianceSummary> => {
-    return await invoke<VBONComplianceSummary>('get_vbon_compliance_summary');
+export const getVBONComplianceSummary = async (frameworkId?: string): Promise<VBONComplianceSummary> => {
+    return await invoke<VBONComplianceSummary>('get_vbon_compliance_summary', { frameworkId });
 };
 
 export const deleteVBONMapping = async (regulationId: string) => {
     await invoke('delete_vbon_mapping', { regulationId });
 };
 
+export const getVBONEvidenceReport = async (
+    frameworkId?: string,
+    reviewIntervalDays?: number
+): Promise<VBONEvidenceReport> => {
+    return await invoke<VBONEvidenceReport>('get_vbon_evidence_report', { frameworkId, reviewIntervalDays });
+};
+
+// Writes the evidence package as a folder, or a .zip when `zip` is set; returns the path written
+export const exportVBONEvidencePackage = async (request: {
+    frameworkId?: string;
+    outputPath: string;
+    reviewIntervalDays?: number;
+    zip: boolean;
+    branding: ReportBranding;
+}): Promise<string> => {
+    return await invoke<string>('export_vbon_evidence_package', { request });
+};
+
+export const getRegulationCatalogEditions = async (frameworkId?: string): Promise<RegulationCatalogEdition[]> => {
+    return await invoke<RegulationCatalogEdition[]>('get_regulation_catalog_editions', { frameworkId });
+};
+
+// Stores the file as a new edition and diffs it against the live catalog; applies it when `apply` is set
+export const importRegulationCatalog = async (
+    request: RegulationCatalogImportRequest
+): Promise<RegulationCatalogImportResult> => {
+    return await invoke<RegulationCatalogImportResult>('import_regulation_catalog', { request });
+};
+
+// Without `fromEditionId` the edition is compared against the live catalog
+export const diffRegulationCatalogEditions = async (
+    toEditionId: string,
+    fromEditionId?: string
+): Promise<RegulationCatalogDiff> => {
+    return await invoke<RegulationCatalogDiff>('diff_regulation_catalog_editions', { fromEditionId, toEditionId });
+};
+
+// Returns the number of mappings flagged for re-review
+export const applyRegulationCatalogEdition = async (editionId: string): Promise<number> => {
+    return await invoke<number>('apply_regulation_catalog_edition', { editionId });
+};
+
+export const deleteRegulationCatalogEdition = async (editionId: string) => {
+    await invoke('delete_regulation_catalog_edition', { editionId });
+};
+
 // ==================== FILE MANAGEMENT ====================
 
 export interface FileUploadResult {
@@ -717,6 +933,10 @@ export interface ImportResult {
     imported: number;
     failed: number;
     errors: string[];
+    unmatchedPreceptors?: string[];
+    updated?: number;
+    skipped?: number;
+    queued?: number; // Evaluation form responses waiting in the resolution queue
 }
 
 /**
@@ -729,3 +949,60 @@ export const importPreceptorEvaluations = async (
 ): Promise<ImportResult> => {
     return await invoke<ImportResult>('import_preceptor_evaluations', { evaluations });
 };
+
+// ==================== EVALUATION FORM IMPORT ====================
+
+// Maps form fields (student_name, preceptor_name, ...) to the export's column headers
+export const suggestEvaluationFormMapping = async (filePath: string): Promise<Record<string, string>> => {
+    return await invoke<Record<string, string>>('suggest_evaluation_form_mapping', { filePath });
+};
+
+// Imports a forms export; responses that can't be matched to a student are queued for review
+export const importEvaluationFormCsv = async (
+    filePath: string,
+    mapping: Record<string, string>
+): Promise<ImportResult> => {
+    return await invoke<ImportResult>('import_evaluation_form_csv', { filePath, mapping });
+};
+
+export const getEvaluationImportQueue = async (
+    status?: EvaluationImportQueueItem['status']
+): Promise<EvaluationImportQueueItem[]> => {
+    return await invoke<EvaluationImportQueueItem[]>('get_evaluation_import_queue', { status });
+};
+
+export const resolveEvaluationImport = async (
+    queueId: string,
+    studentId: string,
+    preceptorName?: string
+): Promise<PreceptorEvaluation> => {
+    return await invoke<PreceptorEvaluation>('resolve_evaluation_import', { queueId, studentId, preceptorName });
+};
+
+export const dismissEvaluationImport = async (queueId: string): Promise<void> => {
+    await invoke('dismiss_evaluation_import', { queueId });
+};
+
+// ==================== SPREADSHEET IMPORT ====================
+
+export const getImportFields = async (target: ImportTarget): Promise<ImportField[]> => {
+    return await invoke<ImportField[]>('get_import_fields', { target });
+};
+
+export const inspectSpreadsheet = async (filePath: string, sheetName?: string): Promise<SpreadsheetInfo> => {
+    return await invoke<SpreadsheetInfo>('inspect_spreadsheet', { filePath, sheetName });
+};
+
+// Validates every row and checks for duplicates without writing anything
+export const previewSpreadsheetImport = async (filePath: string, mapping: ImportMapping): Promise<ImportPreview> => {
+    return await invoke<ImportPreview>('preview_spreadsheet_import', { filePath, mapping });
+};
+
+// Re-validates and imports the rows without errors; duplicates are updated or skipped
+export const commitSpreadsheetImport = async (
+    filePath: string,
+    mapping: ImportMapping,
+    updateDuplicates: boolean
+): Promise<ImportResult> => {
+    return await invoke<ImportResult>('commit_spreadsheet_import', { filePath, mapping, updateDuplicates });
+};
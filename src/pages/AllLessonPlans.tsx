@@ -1,13 +1,25 @@
 import { useState, useEffect } from 'react';
 import { FileText, Plus, Search, Edit2, Trash2, Calendar, BookOpen } from 'lucide-react';
-import { getAllLessonPlans, getAllCourses, createLessonPlan, updateLessonPlan, deleteLessonPlan, addTeachingMaterial, getMaterialsForLesson, deleteTeachingMaterial } from '@/lib/db';
-import type { Course, LessonPlan, TeachingMaterial } from '@/types';
+import { getAllLessonPlans, getAllCourses, createLessonPlan, updateLessonPlan, deleteLessonPlan, addTeachingMaterial, getMaterialsForLesson, deleteTeachingMaterial, getAllVBONRegulations } from '@/lib/db';
+import type { Course, LessonPlan, TeachingMaterial, VBONRegulation } from '@/types';
 import { format, parseISO } from 'date-fns';
 import { useToast } from '@/components/Toast';
 import { Modal } from '@/components/Modal';
 import { FormField, Input, Textarea, Select } from '@/components/FormField';
 import { ConfirmDialog } from '@/components/ConfirmDialog';
 
+// Tags are saved as a JSON array of regulation ids; older plans may hold a comma-separated list
+function parseRegulationTags(vbonTags?: string): string[] {
+  if (!vbonTags) return [];
+  try {
+    const parsed = JSON.parse(vbonTags);
+    if (Array.isArray(parsed)) return parsed.map(String);
+  } catch {
+    // Not JSON
+  }
+  return vbonTags.split(/[\s,]+/).filter(Boolean);
+}
+
 export default function AllLessonPlans() {
   const [lessonPlans, setLessonPlans] = useState<LessonPlan[]>([]);
   const [courses, setCourses] = useState<Course[]>([]);
@@ -19,6 +31,7 @@ export default function AllLessonPlans() {
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [selectedPlan, setSelectedPlan] = useState<LessonPlan | null>(null);
   const [materials, setMaterials] = useState<TeachingMaterial[]>([]);
+  const [regulations, setRegulations] = useState<VBONRegulation[]>([]);
   const toast = useToast();
 
   const emptyForm: LessonPlan = {
@@ -48,12 +61,14 @@ export default function AllLessonPlans() {
   const loadData = async () => {
     try {
       setLoading(true);
-      const [plansData, coursesData] = await Promise.all([
+      const [plansData, coursesData, regulationsData] = await Promise.all([
         getAllLessonPlans(),
-        getAllCourses()
+        getAllCourses(),
+        getAllVBONRegulations()
       ]);
       setLessonPlans(plansData);
       setCourses(coursesData);
+      setRegulations(regulationsData);
     } catch (error) {
       console.error('Failed to load data:', error);
       toast.error('Error', 'Failed to load lesson plans');
@@ -454,6 +469,32 @@ export default function AllLessonPlans() {
             />
           </FormField>
 
+          {regulations.length > 0 && (
+            <FormField label="Regulations Taught" hint="Kept in sync with the compliance mappings">
+              <div className="max-h-40 overflow-y-auto border-2 border-gray-200 rounded-xl p-2 space-y-1">
+                {regulations.map(reg => {
+                  const tags = parseRegulationTags(form.vbonTags);
+                  return (
+                    <label key={reg.id} className="flex items-start gap-2 text-sm text-gray-700 cursor-pointer">
+                      <input
+                        type="checkbox"
+                        className="mt-1"
+                        checked={tags.includes(reg.id)}
+                        onChange={e => {
+                          const next = e.target.checked ? [...tags, reg.id] : tags.filter(id => id !== reg.id);
+                          setForm({ ...form, vbonTags: JSON.stringify(next) });
+                        }}
+                      />
+                      <span>
+                        <span className="font-mono font-semibold text-teal-700">{reg.code}</span> {reg.title}
+                      </span>
+                    </label>
+                  );
+                })}
+              </div>
+            </FormField>
+          )}
+
           <FormField label="Notes">
             <Textarea
               value={form.notes || ''}
@@ -15,7 +15,6 @@ import {
 import { useToast } from '@/components/Toast';
 import { Skeleton, SkeletonCard } from '@/components/Skeleton';
 import { ExportDialog, ExportFormat } from '@/components/ExportDialog';
-import { setExportReportsModalTrigger } from '@/hooks/useMenuEvents';
 
 export default function AnalyticsPage() {
   const [students, setStudents] = useState<Student[]>([]);
@@ -46,11 +45,6 @@ export default function AnalyticsPage() {
     fetchData();
   }, []);
 
-  // Register menu event trigger
-  useEffect(() => {
-    setExportReportsModalTrigger(() => setShowExportDialog(true));
-  }, []);
-
   // Calculate analytics
   const totalStudents = students.length;
   const activeStudents = students.filter(s => s.status === 'Active').length;
@@ -1,10 +1,13 @@
 import { useState, useEffect, useMemo } from 'react';
 import { format, startOfWeek, endOfWeek, addDays, addWeeks, subWeeks, isSameDay } from 'date-fns';
-import { Calendar, ChevronLeft, ChevronRight, Plus, Clock, X, Filter } from 'lucide-react';
-import { loadStudents, getAllClinicalSites, getAllPreceptors, getAssignmentsForWeek, createClinicalAssignment, bulkCreateAssignments, cancelAssignment } from '@/lib/db';
-import { Student, ClinicalSite, Preceptor, ClinicalAssignment, ClinicalAssignmentWithDetails } from '@/types';
+import { Calendar, ChevronLeft, ChevronRight, Plus, Clock, X, Filter, ShieldCheck, ListChecks } from 'lucide-react';
+import { loadStudents, getAllClinicalSites, getAllPreceptors, getAssignmentsForWeek, createClinicalAssignment, bulkCreateAssignments, cancelAssignment, getClinicalClearance } from '@/lib/db';
+import { Student, ClinicalSite, Preceptor, ClinicalAssignment, ClinicalAssignmentWithDetails, ClinicalClearance } from '@/types';
 import { useToast } from '@/components/Toast';
 import { Modal, FormField, Input, Textarea } from '@/components';
+import CertificationRequirementsModal from '@/components/CertificationRequirementsModal';
+import ClinicalClearanceModal, { ClearanceRow } from '@/components/ClinicalClearanceModal';
+import StudentCertificationsModal from '@/components/StudentCertificationsModal';
 
 export default function ClinicalSchedulePage() {
   const toast = useToast();
@@ -32,6 +35,14 @@ export default function ClinicalSchedulePage() {
   });
   const [saving, setSaving] = useState(false);
 
+  // Clearance
+  const [clearances, setClearances] = useState<ClinicalClearance[]>([]);
+  const [overrideClearance, setOverrideClearance] = useState(false);
+  const [showRequirements, setShowRequirements] = useState(false);
+  const [showClearance, setShowClearance] = useState(false);
+  const [certStudent, setCertStudent] = useState<{ id: string; name: string } | null>(null);
+  const [clearanceVersion, setClearanceVersion] = useState(0);
+
   // Filters
   const [filterSite, setFilterSite] = useState<string>('all');
   const [filterStudent, setFilterStudent] = useState<string>('all');
@@ -51,6 +62,21 @@ export default function ClinicalSchedulePage() {
     loadAssignments();
   }, [currentWeekStart]);
 
+  // Clearance of the selected students for the chosen site and day
+  useEffect(() => {
+    if (!showAssignmentModal || !selectedDate || !assignmentForm.siteId || assignmentForm.studentIds.length === 0) {
+      setClearances([]);
+      return;
+    }
+    const dateStr = format(selectedDate, 'yyyy-MM-dd');
+    Promise.all(assignmentForm.studentIds.map(id => getClinicalClearance(id, assignmentForm.siteId, dateStr)))
+      .then(setClearances)
+      .catch(e => {
+        console.error('Failed to check clearance:', e);
+        setClearances([]);
+      });
+  }, [showAssignmentModal, selectedDate, assignmentForm.siteId, assignmentForm.studentIds, clearanceVersion]);
+
   const loadData = async () => {
     try {
       const [studentsData, sitesData, preceptorsData] = await Promise.all([
@@ -96,6 +122,7 @@ export default function ClinicalSchedulePage() {
       objectives: '',
       notes: ''
     });
+    setOverrideClearance(false);
     setShowAssignmentModal(true);
   };
 
@@ -125,7 +152,7 @@ export default function ClinicalSchedulePage() {
           status: 'scheduled',
           createdAt: new Date().toISOString()
         };
-        await createClinicalAssignment(assignment);
+        await createClinicalAssignment(assignment, overrideClearance);
       } else {
         // Bulk create
         const assignmentsList: ClinicalAssignment[] = assignmentForm.studentIds.map((studentId, idx) => ({
@@ -142,7 +169,7 @@ export default function ClinicalSchedulePage() {
           status: 'scheduled',
           createdAt: new Date().toISOString()
         }));
-        await bulkCreateAssignments(assignmentsList);
+        await bulkCreateAssignments(assignmentsList, overrideClearance);
       }
 
       toast.success('Assignment Created', `${assignmentForm.studentIds.length} student(s) scheduled for ${format(selectedDate, 'MMM d')}`);
@@ -150,7 +177,8 @@ export default function ClinicalSchedulePage() {
       loadAssignments();
     } catch (e) {
       console.error('Failed to create assignment:', e);
-      toast.error('Failed to create assignment', 'Please try again.');
+      // Clearance failures name the unmet certification requirements
+      toast.error('Failed to create assignment', typeof e === 'string' ? e : 'Please try again.');
     } finally {
       setSaving(false);
     }
@@ -219,13 +247,23 @@ export default function ClinicalSchedulePage() {
               </p>
             </div>
           </div>
-          <button
-            onClick={() => handleDayClick(new Date())}
-            className="btn btn-primary flex items-center gap-2"
-          >
-            <Plus className="w-5 h-5" />
-            Schedule Assignment
-          </button>
+          <div className="flex items-center gap-3">
+            <button onClick={() => setShowRequirements(true)} className="btn btn-outline flex items-center gap-2">
+              <ListChecks className="w-5 h-5" />
+              Requirements
+            </button>
+            <button onClick={() => setShowClearance(true)} className="btn btn-outline flex items-center gap-2">
+              <ShieldCheck className="w-5 h-5" />
+              Clearance
+            </button>
+            <button
+              onClick={() => handleDayClick(new Date())}
+              className="btn btn-primary flex items-center gap-2"
+            >
+              <Plus className="w-5 h-5" />
+              Schedule Assignment
+            </button>
+          </div>
         </div>
 
         {/* Week Navigation */}
@@ -411,6 +449,30 @@ export default function ClinicalSchedulePage() {
             </select>
           </FormField>
 
+          {clearances.length > 0 && (
+            <FormField label="Clearance">
+              <ul className="divide-y divide-gray-100 border border-gray-100 rounded-lg">
+                {clearances.map(clearance => (
+                  <ClearanceRow
+                    key={clearance.studentId}
+                    clearance={clearance}
+                    onManage={() => setCertStudent({ id: clearance.studentId, name: clearance.studentName })}
+                  />
+                ))}
+              </ul>
+              {clearances.some(c => !c.cleared) && (
+                <label className="flex items-center gap-2 mt-2 text-sm text-red-700">
+                  <input
+                    type="checkbox"
+                    checked={overrideClearance}
+                    onChange={(e) => setOverrideClearance(e.target.checked)}
+                  />
+                  Schedule anyway (override clearance for this assignment)
+                </label>
+              )}
+            </FormField>
+          )}
+
           <FormField label="Preceptor (Optional)">
             <select
               value={assignmentForm.preceptorId}
@@ -470,6 +532,29 @@ export default function ClinicalSchedulePage() {
           </FormField>
         </div>
       </Modal>
+
+      <CertificationRequirementsModal
+        isOpen={showRequirements}
+        onClose={() => setShowRequirements(false)}
+        sites={sites}
+        onChanged={() => setClearanceVersion(v => v + 1)}
+      />
+
+      <ClinicalClearanceModal
+        isOpen={showClearance}
+        onClose={() => setShowClearance(false)}
+        sites={sites}
+        onManageCertifications={(id, name) => setCertStudent({ id, name })}
+        refreshKey={clearanceVersion}
+      />
+
+      <StudentCertificationsModal
+        isOpen={certStudent !== null}
+        onClose={() => setCertStudent(null)}
+        studentId={certStudent?.id ?? null}
+        studentName={certStudent?.name}
+        onChanged={() => setClearanceVersion(v => v + 1)}
+      />
     </div>
   );
 }
@@ -1,16 +1,20 @@
 import { useState, useEffect } from 'react';
 import { seedModules, seedStandards, seedVBONRegulations as vbonSeedData } from '@/lib/data';
-import { Link as LinkIcon, Edit2, BookOpen, Plus, Trash2, ExternalLink, GraduationCap, FileText, Shield, Check, AlertTriangle, X, Download, ChevronDown, ChevronUp, Sparkles, Loader2 } from 'lucide-react';
+import { Link as LinkIcon, Edit2, BookOpen, Plus, Trash2, ExternalLink, GraduationCap, FileText, Shield, Check, AlertTriangle, X, Download, ChevronDown, ChevronUp, Sparkles, Loader2, Upload, Copy } from 'lucide-react';
 import { useToast } from '@/components/Toast';
+import { useInstructor } from '@/components/InstructorProvider';
 import { Modal } from '@/components/Modal';
 import { FormField, Input, Textarea, Select } from '@/components/FormField';
 import { ConfirmDialog } from '@/components/ConfirmDialog';
+import RegulationCatalogImport from '@/components/RegulationCatalogImport';
+import CourseCloneModal from '@/components/CourseCloneModal';
 import {
   getAllCourses, addCourse, updateCourse, deleteCourse,
   getVBONRegulationsWithMappings, seedVBONRegulations, upsertVBONMapping, getVBONComplianceSummary,
-  getAllLessonPlans
+  getAllLessonPlans, exportVBONEvidencePackage, getRegulatoryFrameworks, setCurrentRegulatoryFramework
 } from '@/lib/db';
-import type { Course, VBONRegulationWithMapping, VBONMapping, VBONComplianceSummary, LessonPlan } from '@/types';
+import { downloadDir, join } from '@tauri-apps/api/path';
+import type { Course, VBONRegulationWithMapping, VBONMapping, VBONComplianceSummary, LessonPlan, RegulatoryFramework } from '@/types';
 import { Tabs, TabsList, TabsTrigger, TabsContent } from '@/components/Tabs';
 import { Progress } from '@/components/Progress';
 import jsPDF from 'jspdf';
@@ -22,10 +26,13 @@ export default function CurriculumPage() {
   const [courses, setCourses] = useState<Course[]>([]);
   const [loading, setLoading] = useState(true);
 
-  // VBON State
+  // VBON State (regulations and summary of the selected framework)
+  const [frameworks, setFrameworks] = useState<RegulatoryFramework[]>([]);
+  const [framework, setFramework] = useState<RegulatoryFramework | null>(null);
   const [vbonRegulations, setVbonRegulations] = useState<VBONRegulationWithMapping[]>([]);
   const [vbonSummary, setVbonSummary] = useState<VBONComplianceSummary | null>(null);
   const [vbonLoading, setVbonLoading] = useState(false);
+  const [showCatalogModal, setShowCatalogModal] = useState(false);
   const [expandedCategories, setExpandedCategories] = useState<Set<string>>(new Set(['Curriculum Content']));
   const [selectedRegulation, setSelectedRegulation] = useState<VBONRegulationWithMapping | null>(null);
   const [showMappingModal, setShowMappingModal] = useState(false);
@@ -56,6 +63,7 @@ export default function CurriculumPage() {
   const [isEditingCourse, setIsEditingCourse] = useState(false);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [selectedCourse, setSelectedCourse] = useState<Course | null>(null);
+  const [courseToClone, setCourseToClone] = useState<Course | null>(null);
   const emptyCourseForm: Course = {
     id: '',
     code: '',
@@ -73,6 +81,7 @@ export default function CurriculumPage() {
   const [courseForm, setCourseForm] = useState<Course>(emptyCourseForm);
 
   const toast = useToast();
+  const frameworkCode = framework?.code ?? 'VBON';
 
   useEffect(() => {
     loadCourses();
@@ -96,15 +105,23 @@ export default function CurriculumPage() {
     try {
       setVbonLoading(true);
 
-      // Seed VBON regulations if not present
-      await seedVBONRegulations(vbonSeedData);
+      // The current framework is listed first
+      const allFrameworks = await getRegulatoryFrameworks();
+      const current = allFrameworks.find(f => f.isCurrent) ?? allFrameworks[0] ?? null;
+      setFrameworks(allFrameworks);
+      setFramework(current);
+
+      // Seed VBON regulations if not present; other frameworks' catalogs are added by the user
+      if (current?.id === 'vbon') {
+        await seedVBONRegulations(vbonSeedData);
+      }
 
       // Load regulations with mappings
-      const regs = await getVBONRegulationsWithMappings();
+      const regs = await getVBONRegulationsWithMappings(current?.id);
       setVbonRegulations(regs);
 
       // Load summary
-      const summary = await getVBONComplianceSummary();
+      const summary = await getVBONComplianceSummary(current?.id);
       setVbonSummary(summary);
 
       // Load lesson plans for mapping
@@ -212,6 +229,16 @@ export default function CurriculumPage() {
   };
 
   // VBON handlers
+  const handleSwitchFramework = async (frameworkId: string) => {
+    try {
+      await setCurrentRegulatoryFramework(frameworkId);
+      await loadVBONData();
+    } catch (error) {
+      console.error('Failed to switch framework:', error);
+      toast.error('Error', typeof error === 'string' ? error : 'Failed to switch regulatory framework');
+    }
+  };
+
   const toggleCategory = (category: string) => {
     const newExpanded = new Set(expandedCategories);
     if (newExpanded.has(category)) {
@@ -296,9 +323,9 @@ export default function CurriculumPage() {
         ? lessonPlans.map(lp => `- ID:${lp.id} | ${lp.courseName} Week ${lp.weekNumber || '?'}: "${lp.topic}"${lp.chapter ? ` (${lp.chapter})` : ''}`).join('\n')
         : 'No lesson plans available';
 
-      const prompt = `You are a nursing curriculum specialist. Analyze this VBON regulation requirement and suggest how it might be covered in the curriculum.
+      const prompt = `You are a nursing curriculum specialist. Analyze this ${frameworkCode} regulation requirement and suggest how it might be covered in the curriculum.
 
-VBON REGULATION:
+${frameworkCode} REGULATION:
 Code: ${selectedRegulation.code}
 Title: ${selectedRegulation.title}
 Description: ${selectedRegulation.description}
@@ -397,6 +424,30 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
   }, {} as Record<string, VBONRegulationWithMapping[]>);
 
   // Export VBON Audit Report
+  const { profile } = useInstructor();
+  const [exportingEvidence, setExportingEvidence] = useState(false);
+
+  // Zip of the per-regulation evidence report, index and referenced files, saved to Downloads
+  const exportEvidencePackage = async () => {
+    setExportingEvidence(true);
+    try {
+      const date = new Date().toISOString().split('T')[0];
+      const outputPath = await join(await downloadDir(), `${framework?.id ?? 'vbon'}-evidence-${date}.zip`);
+      const written = await exportVBONEvidencePackage({
+        frameworkId: framework?.id,
+        outputPath,
+        zip: true,
+        branding: { programName: profile.institution || 'Nursing Program' },
+      });
+      toast.success('Evidence Package Exported', written);
+    } catch (error) {
+      console.error('Failed to export evidence package:', error);
+      toast.error('Export Failed', typeof error === 'string' ? error : 'Failed to export evidence package');
+    } finally {
+      setExportingEvidence(false);
+    }
+  };
+
   const exportVBONAuditReport = () => {
     const doc = new jsPDF();
     const pageWidth = doc.internal.pageSize.getWidth();
@@ -405,7 +456,7 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
     // Header
     doc.setFontSize(20);
     doc.setFont('helvetica', 'bold');
-    doc.text('VBON Curriculum Compliance Report', pageWidth / 2, yPos, { align: 'center' });
+    doc.text(`${frameworkCode} Curriculum Compliance Report`, pageWidth / 2, yPos, { align: 'center' });
     yPos += 10;
 
     doc.setFontSize(12);
@@ -484,8 +535,8 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
       yPos += 5;
     });
 
-    doc.save('vbon-compliance-report.pdf');
-    toast.success('Exported', 'VBON Audit Report has been downloaded');
+    doc.save(`${framework?.id ?? 'vbon'}-compliance-report.pdf`);
+    toast.success('Exported', `${frameworkCode} Audit Report has been downloaded`);
   };
 
   return (
@@ -499,7 +550,7 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
             <h1 className="text-4xl font-black bg-linear-to-r from-gray-900 via-teal-900 to-cyan-900 bg-clip-text text-transparent mb-1">
               Curriculum Management
             </h1>
-            <p className="text-gray-600 text-lg font-medium">Manage courses, modules, and VBON compliance</p>
+            <p className="text-gray-600 text-lg font-medium">Manage courses, modules, and regulatory compliance</p>
           </div>
         </div>
       </header>
@@ -517,7 +568,7 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
             </TabsTrigger>
             <TabsTrigger value="vbon">
               <Shield className="w-4 h-4 mr-2" />
-              VBON Compliance
+              {frameworkCode} Compliance
             </TabsTrigger>
           </TabsList>
         </div>
@@ -560,6 +611,13 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
                       )}
                     </div>
                     <div className="flex gap-1">
+                      <button
+                        onClick={() => setCourseToClone(course)}
+                        className="p-2 text-gray-400 hover:text-teal-600 rounded-lg hover:bg-teal-50"
+                        title="Copy to a new term"
+                      >
+                        <Copy className="w-4 h-4" />
+                      </button>
                       <button
                         onClick={() => handleEditCourse(course)}
                         className="p-2 text-gray-400 hover:text-blue-600 rounded-lg hover:bg-blue-50"
@@ -686,19 +744,50 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
                     <div>
                       <h2 className="text-xl font-bold text-gray-900 flex items-center gap-2">
                         <Shield className="w-5 h-5 text-teal-600" />
-                        VBON Compliance Dashboard
+                        {frameworkCode} Compliance Dashboard
                       </h2>
                       <p className="text-sm text-gray-500 mt-1">
-                        Virginia Board of Nursing (18VAC90-27) Requirements
+                        {framework?.id === 'vbon'
+                          ? 'Virginia Board of Nursing (18VAC90-27) Requirements'
+                          : `${framework?.name ?? ''} Requirements`}
                       </p>
                     </div>
-                    <button
-                      onClick={exportVBONAuditReport}
-                      className="btn btn-primary flex items-center gap-2"
-                    >
-                      <Download className="w-4 h-4" />
-                      Export Audit Report
-                    </button>
+                    <div className="flex items-center gap-2">
+                      {frameworks.length > 1 && (
+                        <select
+                          value={framework?.id ?? ''}
+                          onChange={(e) => handleSwitchFramework(e.target.value)}
+                          className="px-3 py-2 border border-gray-300 rounded-md text-sm focus:outline-none focus:ring-2 focus:ring-teal-600"
+                          aria-label="Regulatory framework"
+                        >
+                          {frameworks.map(f => (
+                            <option key={f.id} value={f.id}>{f.code} — {f.name}</option>
+                          ))}
+                        </select>
+                      )}
+                      <button
+                        onClick={() => setShowCatalogModal(true)}
+                        className="btn btn-outline flex items-center gap-2"
+                      >
+                        <Upload className="w-4 h-4" />
+                        Catalog Editions
+                      </button>
+                      <button
+                        onClick={exportEvidencePackage}
+                        disabled={exportingEvidence}
+                        className="btn btn-outline flex items-center gap-2"
+                      >
+                        {exportingEvidence ? <Loader2 className="w-4 h-4 animate-spin" /> : <FileText className="w-4 h-4" />}
+                        Evidence Package
+                      </button>
+                      <button
+                        onClick={exportVBONAuditReport}
+                        className="btn btn-primary flex items-center gap-2"
+                      >
+                        <Download className="w-4 h-4" />
+                        Export Audit Report
+                      </button>
+                    </div>
                   </div>
 
                   {/* Progress Overview */}
@@ -748,6 +837,13 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
                     </div>
                   </div>
 
+                  {vbonSummary.needsReviewCount > 0 && (
+                    <p className="mb-6 -mt-2 text-sm text-red-700 flex items-center gap-2">
+                      <AlertTriangle className="w-4 h-4" />
+                      {vbonSummary.needsReviewCount} mapping{vbonSummary.needsReviewCount === 1 ? '' : 's'} need re-review after a catalog update
+                    </p>
+                  )}
+
                   {/* Category Breakdown */}
                   <div className="border-t pt-4">
                     <h3 className="text-sm font-semibold text-gray-700 mb-3">Compliance by Category</h3>
@@ -828,6 +924,12 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
 
                                       {reg.mapping && (
                                         <div className="mt-2 flex flex-wrap gap-2 text-xs">
+                                          {reg.mapping.needsReview && (
+                                            <span className="bg-red-50 text-red-700 px-2 py-0.5 rounded flex items-center gap-1">
+                                              <AlertTriangle className="w-3 h-3" />
+                                              Re-review: {reg.mapping.reviewReason || 'regulation changed'}
+                                            </span>
+                                          )}
                                           {reg.mapping.syllabusReference && (
                                             <span className="bg-blue-50 text-blue-700 px-2 py-0.5 rounded">
                                               Syllabus: {reg.mapping.syllabusReference}
@@ -845,6 +947,19 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
                                           )}
                                         </div>
                                       )}
+
+                                      {reg.lessonPlans?.length > 0 && (
+                                        <div className="mt-2 flex flex-wrap gap-2 text-xs">
+                                          <span className="bg-teal-50 text-teal-700 px-2 py-0.5 rounded">
+                                            {reg.lessonPlans.length} lesson plan{reg.lessonPlans.length === 1 ? '' : 's'}
+                                          </span>
+                                          {reg.taughtDates.length > 0 && (
+                                            <span className="bg-green-50 text-green-700 px-2 py-0.5 rounded">
+                                              Last taught: {reg.taughtDates[reg.taughtDates.length - 1]}
+                                            </span>
+                                          )}
+                                        </div>
+                                      )}
                                     </div>
                                   </div>
                                   <div className="flex items-center gap-2">
@@ -959,6 +1074,13 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
         </form>
       </Modal>
 
+      <CourseCloneModal
+        isOpen={courseToClone !== null}
+        onClose={() => setCourseToClone(null)}
+        course={courseToClone}
+        onCloned={loadCourses}
+      />
+
       {/* Course Modal */}
       <Modal
         isOpen={showCourseModal}
@@ -1058,6 +1180,14 @@ Only output valid JSON. If no lesson plans match, set matchingLessonPlanIds to e
         </form>
       </Modal>
 
+      {/* Regulation Catalog Editions Modal */}
+      <RegulationCatalogImport
+        isOpen={showCatalogModal}
+        onClose={() => setShowCatalogModal(false)}
+        framework={framework}
+        onApplied={loadVBONData}
+      />
+
       {/* VBON Mapping Modal */}
       <Modal
         isOpen={showMappingModal}
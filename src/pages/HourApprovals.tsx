@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { format } from 'date-fns';
 import { CheckCircle2, XCircle, Clock, FileText, ChevronDown, ChevronUp, MessageSquare, Sparkles, Loader2 } from 'lucide-react';
-import { getPendingSubmissions, approveSubmission, rejectSubmission } from '@/lib/db';
+import { getPendingSubmissions, approveSubmission, rejectSubmission, refreshTray } from '@/lib/db';
 import { StudentHourSubmissionWithStudent } from '@/types';
 import { useToast } from '@/components/Toast';
 import { Modal, FormField, Textarea } from '@/components';
@@ -165,6 +165,7 @@ Only output the JSON, no other text.`;
       }
       setShowFeedbackModal(false);
       loadSubmissions();
+      refreshTray().catch(console.error);
     } catch (e) {
       console.error('Failed to process submission:', e);
       toast.error('Failed to process', 'Please try again.');
@@ -30,7 +30,8 @@ import {
   addPreceptor,
   updatePreceptor,
   deletePreceptor,
-  verifyPreceptorLicense
+  verifyPreceptorLicense,
+  getRegulatoryFrameworks
 } from '@/lib/db';
 import { Preceptor, PreceptorWithDetails, PreceptorVerificationAlert, ClinicalSite } from '@/types';
 import { useToast } from '@/components/Toast';
@@ -39,6 +40,7 @@ import { Modal } from '@/components/Modal';
 import { FormField, Input, Textarea } from '@/components/FormField';
 import { ConfirmDialog } from '@/components/ConfirmDialog';
 import { EvaluationCSVImport } from '@/components/EvaluationCSVImport';
+import { EvaluationImportQueue } from '@/components/EvaluationImportQueue';
 import { clsx } from 'clsx';
 
 const VA_BON_URL = 'https://dhp.virginiainteractive.org/Lookup/Index';
@@ -67,7 +69,7 @@ const emptyFormData: PreceptorFormData = {
   phone: '',
   siteId: '',
   licenseNumber: '',
-  licenseState: 'VA',
+  licenseState: '',
   licenseExpirationDate: '',
   specialties: '',
   notes: ''
@@ -89,6 +91,9 @@ export default function PreceptorsPage() {
   const [verifyingId, setVerifyingId] = useState<string | null>(null);
   const [expandedPreceptor, setExpandedPreceptor] = useState<string | null>(null);
   const [showImportModal, setShowImportModal] = useState(false);
+  const [importQueueKey, setImportQueueKey] = useState(0);
+  // License state of the current regulatory framework's board
+  const [defaultLicenseState, setDefaultLicenseState] = useState('VA');
   const toast = useToast();
 
   const loadData = async () => {
@@ -102,6 +107,10 @@ export default function PreceptorsPage() {
       setPreceptors(preceptorData);
       setVerificationAlerts(alertsData);
       setSites(sitesData);
+
+      const frameworks = await getRegulatoryFrameworks();
+      const jurisdiction = frameworks.find(f => f.isCurrent)?.jurisdiction;
+      if (jurisdiction) setDefaultLicenseState(jurisdiction);
     } catch (error) {
       console.error('Failed to load preceptors:', error);
       toast.error('Failed to Load', 'Could not load preceptor data');
@@ -131,7 +140,7 @@ export default function PreceptorsPage() {
   }, [preceptors, searchTerm, siteFilter, statusFilter]);
 
   const handleOpenAddModal = () => {
-    setFormData(emptyFormData);
+    setFormData({ ...emptyFormData, licenseState: defaultLicenseState });
     setEditingPreceptor(null);
     setShowAddModal(true);
   };
@@ -146,7 +155,7 @@ export default function PreceptorsPage() {
       phone: preceptor.phone || '',
       siteId: preceptor.siteId || '',
       licenseNumber: preceptor.licenseNumber || '',
-      licenseState: preceptor.licenseState || 'VA',
+      licenseState: preceptor.licenseState || defaultLicenseState,
       licenseExpirationDate: preceptor.licenseExpirationDate || '',
       specialties: preceptor.specialties || '',
       notes: preceptor.notes || ''
@@ -178,7 +187,7 @@ export default function PreceptorsPage() {
         phone: formData.phone || undefined,
         siteId: formData.siteId || undefined,
         licenseNumber: formData.licenseNumber || undefined,
-        licenseState: formData.licenseState || 'VA',
+        licenseState: formData.licenseState || defaultLicenseState,
         licenseExpirationDate: formData.licenseExpirationDate || undefined,
         specialties: formData.specialties || undefined,
         notes: formData.notes || undefined,
@@ -395,6 +404,9 @@ export default function PreceptorsPage() {
         </div>
       )}
 
+      {/* Form responses the evaluation import couldn't match */}
+      <EvaluationImportQueue refreshKey={importQueueKey} onResolved={loadData} />
+
       {/* Stats */}
       <div className="grid grid-cols-2 md:grid-cols-5 gap-4">
         <div className="bg-white rounded-2xl p-5 shadow-md border border-gray-100 flex items-center gap-4">
@@ -628,7 +640,7 @@ export default function PreceptorsPage() {
                               </div>
                               <div className="text-sm">
                                 <span className="text-gray-500">State:</span>{' '}
-                                <span className="font-medium text-gray-900">{preceptor.licenseState || 'VA'}</span>
+                                <span className="font-medium text-gray-900">{preceptor.licenseState || defaultLicenseState}</span>
                               </div>
                               {preceptor.licenseExpirationDate && (
                                 <div className="text-sm">
@@ -888,6 +900,7 @@ export default function PreceptorsPage() {
         onClose={() => setShowImportModal(false)}
         onImportComplete={() => {
           toast.success('Evaluations imported successfully');
+          setImportQueueKey(key => key + 1);
           loadData();
         }}
       />
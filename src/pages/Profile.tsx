@@ -9,6 +9,7 @@ import { useToast } from '@/components/Toast';
 import { Modal } from '@/components/Modal';
 import { Tabs, TabsList, TabsTrigger, TabsContent } from '@/components/Tabs';
 import { CertificationDocUpload } from '@/components/TauriFileUpload';
+import CompHoursPolicyPanel from '@/components/CompHoursPolicyPanel';
 import {
   getAllInstructorCertifications,
   addInstructorCertification,
@@ -17,12 +18,14 @@ import {
   getInstructorCertificationAlerts,
   getAllCompHoursEarned,
   addCompHoursEarned,
+  approveCompHoursEarned,
   deleteCompHoursEarned,
   getAllCompHoursUsed,
   addCompHoursUsed,
   deleteCompHoursUsed,
   getCompHoursSummary,
-  getCompHoursExpirationWarnings
+  getCompHoursExpirationWarnings,
+  getCompHoursPolicy
 } from '@/lib/db';
 import type {
   InstructorCertification,
@@ -30,7 +33,8 @@ import type {
   CompHoursEarned,
   CompHoursUsed,
   CompHoursSummary,
-  CompHoursExpirationWarning
+  CompHoursExpirationWarning,
+  CompHoursPolicy
 } from '@/types';
 
 // Activity type options for earning comp hours
@@ -91,6 +95,7 @@ export default function ProfilePage() {
   const [compUsed, setCompUsed] = useState<CompHoursUsed[]>([]);
   const [compSummary, setCompSummary] = useState<CompHoursSummary | null>(null);
   const [expirationWarnings, setExpirationWarnings] = useState<CompHoursExpirationWarning[]>([]);
+  const [compPolicy, setCompPolicy] = useState<CompHoursPolicy | null>(null);
   const [showEarnModal, setShowEarnModal] = useState(false);
   const [showUseModal, setShowUseModal] = useState(false);
 
@@ -125,16 +130,18 @@ export default function ProfilePage() {
 
   const loadCompHours = async () => {
     try {
-      const [earned, used, summary, warnings] = await Promise.all([
+      const [earned, used, summary, warnings, policy] = await Promise.all([
         getAllCompHoursEarned(),
         getAllCompHoursUsed(),
         getCompHoursSummary(),
-        getCompHoursExpirationWarnings()
+        getCompHoursExpirationWarnings(),
+        getCompHoursPolicy()
       ]);
       setCompEarned(earned);
       setCompUsed(used);
       setCompSummary(summary);
       setExpirationWarnings(warnings);
+      setCompPolicy(policy);
     } catch (error) {
       console.error('Failed to load comp hours:', error);
     }
@@ -196,13 +203,17 @@ export default function ProfilePage() {
   // Comp hours handlers
   const handleAddEarned = async (entry: CompHoursEarned) => {
     try {
-      await addCompHoursEarned(entry);
-      toast.success('Hours Logged', `${entry.hours} comp hours added to your balance.`);
+      const saved = await addCompHoursEarned(entry);
+      if (saved.approvalStatus === 'pending') {
+        toast.success('Hours Logged', `${saved.hours} comp hours submitted for approval.`);
+      } else {
+        toast.success('Hours Logged', `${saved.hours} comp hours added to your balance.`);
+      }
       setShowEarnModal(false);
       loadCompHours();
     } catch (error) {
       console.error('Failed to add comp hours:', error);
-      toast.error('Failed', 'Failed to log comp hours.');
+      toast.error('Failed', typeof error === 'string' ? error : 'Failed to log comp hours.');
     }
   };
 
@@ -218,6 +229,16 @@ export default function ProfilePage() {
     }
   };
 
+  const handleApproveEarned = async (id: string) => {
+    try {
+      await approveCompHoursEarned(id);
+      loadCompHours();
+    } catch (error) {
+      console.error('Failed to approve:', error);
+      toast.error('Failed', 'Failed to approve comp hours.');
+    }
+  };
+
   const handleDeleteEarned = async (id: string) => {
     if (!confirm('Are you sure you want to delete this entry?')) return;
     try {
@@ -581,6 +602,11 @@ export default function ProfilePage() {
               <p className="text-indigo-200 text-sm mt-1">
                 {compSummary?.earnedThisYear.toFixed(1) || '0'} earned / {compSummary?.usedThisYear.toFixed(1) || '0'} used this year
               </p>
+              {!!compSummary?.forfeited && (
+                <p className="text-indigo-200 text-xs mt-1">
+                  {compSummary.forfeited.toFixed(1)} hours forfeited after expiring unused
+                </p>
+              )}
             </div>
             <Clock className="w-16 h-16 text-indigo-200" />
           </div>
@@ -599,7 +625,7 @@ export default function ProfilePage() {
                 className={`p-3 rounded-lg border ${getAlertColor(warning.alertLevel)}`}
               >
                 <div className="flex items-center justify-between">
-                  <span className="font-medium">{warning.hours} hours</span>
+                  <span className="font-medium">{warning.hours.toFixed(1)} hours</span>
                   <span className="text-sm">
                     Expires on {new Date(warning.expirationDate).toLocaleDateString()} ({warning.daysUntilExpiry} days)
                   </span>
@@ -640,6 +666,15 @@ export default function ProfilePage() {
                       </p>
                     </div>
                     <div className="flex items-center gap-2">
+                      {entry.approvalStatus === 'pending' && (
+                        <button
+                          onClick={() => handleApproveEarned(entry.id)}
+                          className="px-2 py-0.5 text-xs rounded bg-amber-100 text-amber-700 hover:bg-amber-200"
+                          title="Pending approval - click to approve"
+                        >
+                          Pending
+                        </button>
+                      )}
                       <span className="font-bold text-green-600">+{entry.hours}h</span>
                       <button
                         onClick={() => handleDeleteEarned(entry.id)}
@@ -721,6 +756,8 @@ export default function ProfilePage() {
             </div>
           </div>
         </div>
+
+        <CompHoursPolicyPanel onPolicyChange={(policy) => { setCompPolicy(policy); loadCompHours(); }} />
         </TabsContent>
       </Tabs>
 
@@ -740,6 +777,7 @@ export default function ProfilePage() {
         isOpen={showEarnModal}
         onClose={() => setShowEarnModal(false)}
         onSave={handleAddEarned}
+        policyExpirationMonths={compPolicy?.expirationMonths}
       />
 
       {/* Use Hours Modal */}
@@ -989,11 +1027,13 @@ function CertificationModal({
 function EarnHoursModal({
   isOpen,
   onClose,
-  onSave
+  onSave,
+  policyExpirationMonths
 }: {
   isOpen: boolean;
   onClose: () => void;
   onSave: (entry: CompHoursEarned) => void;
+  policyExpirationMonths?: number; // When set, the backend assigns the expiration date
 }) {
   const [formData, setFormData] = useState({
     date: new Date().toISOString().split('T')[0],
@@ -1074,19 +1114,25 @@ function EarnHoursModal({
           />
         </div>
 
-        <div>
-          <label className="block text-sm font-medium text-gray-700 mb-1">
-            Expiration Date
-            <span className="text-gray-500 font-normal"> (optional)</span>
-          </label>
-          <input
-            type="date"
-            value={formData.expirationDate}
-            onChange={(e) => setFormData(prev => ({ ...prev, expirationDate: e.target.value }))}
-            className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-indigo-600"
-          />
-          <p className="text-xs text-gray-500 mt-1">Usually end of fiscal year (June 30)</p>
-        </div>
+        {policyExpirationMonths ? (
+          <p className="text-sm text-gray-600">
+            These hours will expire {policyExpirationMonths} months after the date earned, per your comp hours policy.
+          </p>
+        ) : (
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">
+              Expiration Date
+              <span className="text-gray-500 font-normal"> (optional)</span>
+            </label>
+            <input
+              type="date"
+              value={formData.expirationDate}
+              onChange={(e) => setFormData(prev => ({ ...prev, expirationDate: e.target.value }))}
+              className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-indigo-600"
+            />
+            <p className="text-xs text-gray-500 mt-1">Usually end of fiscal year (June 30)</p>
+          </div>
+        )}
 
         <div>
           <label className="block text-sm font-medium text-gray-700 mb-1">Notes</label>
@@ -7,6 +7,7 @@ import { useInstructor } from '@/components/InstructorProvider';
 import { ClinicalSite, Preceptor, VrScenario } from '@/types';
 import { getAllClinicalSites, addClinicalSite, updateClinicalSite, deleteClinicalSite, getAllPreceptors, addPreceptor, updatePreceptor, deletePreceptor, getAllVrScenarios, addVrScenario, updateVrScenario, deleteVrScenario } from '@/lib/db';
 import { Modal, FormField, Input, Textarea } from '@/components';
+import ReminderSettingsPanel from '@/components/ReminderSettingsPanel';
 
 interface ModelOption {
   id: string;
@@ -520,6 +521,9 @@ export default function SettingsPage() {
           </div>
         </div>
 
+        {/* Reminders Section */}
+        <ReminderSettingsPanel />
+
         {/* Clinical Sites Section */}
         <div className="card p-6">
           <div className="flex items-center justify-between mb-4">
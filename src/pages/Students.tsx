@@ -3,7 +3,7 @@ import { useState, useEffect, useMemo } from 'react';
 import { Link } from 'react-router-dom';
 import { Student } from '@/types';
 import { useStudentData } from '@/contexts/StudentDataContext';
-import { setNewStudentModalTrigger, setImportDataModalTrigger } from '@/hooks/useMenuEvents';
+import { setNewStudentModalTrigger } from '@/hooks/useMenuEvents';
 import { parseStudentCSV, validateStudents, downloadCSVTemplate } from '@/lib/csv-import';
 import { exportStudentsToPDF } from '@/lib/pdf-export';
 import { exportStudentsToCSV } from '@/lib/csv-export';
@@ -47,7 +47,6 @@ export default function StudentsPage() {
   // Register menu event triggers
   useEffect(() => {
     setNewStudentModalTrigger(() => setShowModal(true));
-    setImportDataModalTrigger(() => setShowImportModal(true));
   }, []);
 
   const handleEnroll = async (e: React.FormEvent) => {
@@ -1,10 +1,12 @@
 import { useState, useEffect, useMemo } from 'react';
-import { Calendar, ChevronLeft, ChevronRight, FileText, Download } from 'lucide-react';
+import { Calendar, ChevronLeft, ChevronRight, FileText, Download, CalendarPlus, CalendarX } from 'lucide-react';
 import { getAllCourses, getAllLessonPlans } from '@/lib/db';
 import type { Course, LessonPlan } from '@/types';
 import { format, startOfWeek, addWeeks, addDays, parseISO } from 'date-fns';
 import { useToast } from '@/components/Toast';
 import { Modal } from '@/components/Modal';
+import SemesterScheduleGenerator from '@/components/SemesterScheduleGenerator';
+import CancelClassModal from '@/components/CancelClassModal';
 import { clsx } from 'clsx';
 
 export default function TeachingCalendar() {
@@ -14,6 +16,8 @@ export default function TeachingCalendar() {
   const [loading, setLoading] = useState(true);
   const [selectedPlan, setSelectedPlan] = useState<LessonPlan | null>(null);
   const [showDetailModal, setShowDetailModal] = useState(false);
+  const [showGenerator, setShowGenerator] = useState(false);
+  const [showCancelClass, setShowCancelClass] = useState(false);
   const toast = useToast();
 
   // Calculate semester weeks (16 weeks typical)
@@ -148,6 +152,13 @@ export default function TeachingCalendar() {
             </div>
           </div>
           <div className="flex items-center gap-3">
+            <button
+              onClick={() => setShowGenerator(true)}
+              className="btn btn-primary flex items-center gap-2"
+            >
+              <CalendarPlus className="w-4 h-4" />
+              Generate Semester
+            </button>
             <button
               onClick={handleExportPDF}
               className="btn btn-outline flex items-center gap-2"
@@ -271,6 +282,17 @@ export default function TeachingCalendar() {
         onClose={() => setShowDetailModal(false)}
         title={selectedPlan?.topic || 'Lesson Details'}
         size="md"
+        footer={selectedPlan?.courseId ? (
+          <div className="flex justify-end">
+            <button
+              onClick={() => { setShowDetailModal(false); setShowCancelClass(true); }}
+              className="btn btn-outline flex items-center gap-2"
+            >
+              <CalendarX className="w-4 h-4" />
+              Cancel Class
+            </button>
+          </div>
+        ) : undefined}
       >
         {selectedPlan && (
           <div className="space-y-4">
@@ -332,6 +354,20 @@ export default function TeachingCalendar() {
           </div>
         )}
       </Modal>
+
+      <SemesterScheduleGenerator
+        isOpen={showGenerator}
+        onClose={() => setShowGenerator(false)}
+        courses={courses}
+        onGenerated={loadData}
+      />
+
+      <CancelClassModal
+        isOpen={showCancelClass}
+        onClose={() => setShowCancelClass(false)}
+        plan={selectedPlan}
+        onRescheduled={loadData}
+      />
     </div>
   );
 }
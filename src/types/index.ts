@@ -44,6 +44,7 @@ export interface ClinicalLog {
   studentId: string;
   date: string;
   siteName: string;
+  siteId?: string; // Linked clinical site; resolved from siteName when not set
   patientDiagnosis: string; // HIPPA compliant (generic)
   mappedCompetencies: string[]; // IDs of standards
   status: "Pending" | "Approved" | "Rejected";
@@ -106,6 +107,97 @@ export interface TeachingMaterial {
   sortOrder: number;
 }
 
+// Semester Scheduling
+export interface AcademicHoliday {
+  id: string;
+  date: string;
+  name: string;
+  createdAt: string;
+}
+
+export interface CourseSchedule {
+  courseId: string;
+  startDate: string;
+  endDate?: string;
+  meetingDays: string; // Comma-separated weekdays, e.g. "Mon,Wed"
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CourseOutlineEntry {
+  weekNumber: number;
+  chapter?: string;
+  topic: string;
+  topicsCovered?: string;
+  assessmentMethod?: string;
+}
+
+export interface SemesterScheduleRequest {
+  courseId: string;
+  startDate: string;
+  endDate?: string;
+  meetingDays: string[];
+  outline: CourseOutlineEntry[];
+  holidays?: AcademicHoliday[];
+  createCalendarEvents: boolean;
+  replaceExisting: boolean;
+  preview: boolean;
+}
+
+export interface ScheduledSession {
+  lessonPlanId: string;
+  date: string;
+  weekNumber: number;
+  chapter?: string;
+  topic: string;
+}
+
+export interface SemesterScheduleResult {
+  sessions: ScheduledSession[];
+  skippedDates: Array<{ date: string; reason: string }>;
+  replacedCount: number;
+  eventsCreated: number;
+  warnings: string[];
+}
+
+export interface RescheduleRequest {
+  courseId: string;
+  date: string;
+  reason?: string;
+  recordClosure: boolean;
+  preview: boolean;
+}
+
+export interface SessionMove {
+  lessonPlanId: string;
+  topic: string;
+  fromDate: string;
+  toDate: string;
+}
+
+export interface RescheduleResult {
+  moves: SessionMove[];
+  eventsUpdated: number;
+  warnings: string[];
+}
+
+export interface CourseCloneRequest {
+  courseId: string;
+  semester: string;
+  year: number;
+  startDate: string; // First day of the new term
+  code?: string;
+  name?: string;
+}
+
+export interface CourseCloneResult {
+  course: Course;
+  lessonPlanCount: number;
+  materialCount: number;
+  regulationLinkCount: number;
+  warnings: string[];
+}
+
 export interface LessonPlanWithMaterials {
   plan: LessonPlan;
   materials: TeachingMaterial[];
@@ -123,6 +215,8 @@ export interface Attendance {
   attendanceType: 'classroom' | 'clinical';
   hoursAttended?: number;
   hoursRequired?: number;
+  minutesLate?: number;
+  courseId?: string;
 }
 
 export interface AttendanceSummary {
@@ -135,6 +229,9 @@ export interface AttendanceSummary {
   classroomTardies?: number;
   clinicalAbsences?: number;
   clinicalTardies?: number;
+  hoursRequired?: number;
+  hoursMissed?: number;
+  attendancePercent?: number;
 }
 
 // Makeup Hours Tracking
@@ -147,12 +244,23 @@ export interface MakeupHours {
   reason?: string;
   dueDate?: string;
   completionDate?: string;
-  status: 'pending' | 'in_progress' | 'completed';
+  status: 'pending' | 'in_progress' | 'completed' | 'cancelled';
   notes?: string;
   createdAt: string;
   updatedAt: string;
 }
 
+// Hours of an approved makeup clinical log credited to a makeup obligation
+export interface MakeupAllocation {
+  id: string;
+  makeupHoursId: string;
+  clinicalLogId: string;
+  hours: number;
+  allocatedAt: string;
+  logDate: string;
+  siteName: string;
+}
+
 export interface MakeupHoursSummary {
   studentId: string;
   studentName: string;
@@ -181,6 +289,37 @@ export interface CertificationAlert {
   daysUntilExpiry: number;
 }
 
+// Certification requirements a student must meet to be cleared for clinical
+export interface CertificationRequirement {
+  id: string;
+  name: string;
+  certificationType: string; // Matches StudentCertification.certificationType
+  nameKeyword?: string; // Case-insensitive match on the certification name, e.g. "TB"
+  cohort?: string; // Unset = every cohort
+  siteId?: string; // Unset = every site
+  validityMonths?: number; // Counted from the issue date; unset = valid until the expiry date
+  isActive: number;
+  notes?: string;
+  createdAt: string;
+}
+
+export interface RequirementClearance {
+  requirementId: string;
+  requirementName: string;
+  status: 'met' | 'missing' | 'expired' | 'pending';
+  certificationId?: string;
+  validUntil?: string;
+}
+
+export interface ClinicalClearance {
+  studentId: string;
+  studentName: string;
+  siteId: string;
+  date: string;
+  cleared: boolean;
+  requirements: RequirementClearance[];
+}
+
 // Preceptor Evaluations
 export interface PreceptorEvaluation {
   id: string;
@@ -199,13 +338,42 @@ export interface PreceptorEvaluation {
   submittedAt: string;
 }
 
+// One row of a Google/Microsoft Forms evaluation export
+export interface EvaluationFormResponse {
+  responseTimestamp: string;
+  studentName?: string;
+  studentEmail?: string;
+  preceptorName?: string;
+  evaluationDate: string;
+  overallRating?: number;
+  clinicalSkillsRating?: number;
+  professionalismRating?: number;
+  communicationRating?: number;
+  comments?: string;
+  areasForImprovement?: string;
+  strengths?: string;
+}
+
+// A form response that couldn't be matched to a student automatically
+export interface EvaluationImportQueueItem {
+  id: string;
+  responseTimestamp: string;
+  responseKey?: string;
+  response: EvaluationFormResponse;
+  reason: string;
+  status: 'pending' | 'resolved' | 'dismissed';
+  resolvedEvaluationId?: string;
+  createdAt: string;
+  resolvedAt?: string;
+}
+
 // Deadlines
 export interface Deadline {
   id: string;
   title: string;
   description?: string;
   dueDate: string;
-  deadlineType: 'clinical_eval' | 'skills_assessment' | 'certification' | 'paperwork' | 'exam' | 'other';
+  deadlineType: 'clinical_eval' | 'skills_assessment' | 'certification' | 'paperwork' | 'exam' | 'remediation' | 'other';
   relatedStudentId?: string;
   status: 'Pending' | 'Completed' | 'Overdue';
   priority: 'High' | 'Medium' | 'Low';
@@ -222,6 +390,7 @@ export interface CalendarEvent {
   proctor?: string;
   status?: string;
   description?: string;
+  lessonPlanId?: string; // Class session this event was generated for
 }
 
 // Skill Validations
@@ -263,6 +432,52 @@ export interface StudentFlags {
   }>;
 }
 
+// A flag raised by the configurable flag rules
+export interface StudentFlag {
+  ruleId: string; // With relatedId, identifies the flag
+  flagType: string;
+  message: string;
+  severity: 'error' | 'warning' | 'info';
+  relatedId?: string;
+  historyId?: string;
+  acknowledgedAt?: string;
+}
+
+export interface CohortFlagFilters {
+  flagTypes?: string[];
+  severities?: Array<StudentFlag['severity']>;
+  studentStatus?: string;
+  limit?: number; // Max at-risk students returned
+}
+
+export interface FlagTypeCount {
+  flagType: string;
+  errors: number;
+  warnings: number;
+  info: number;
+  total: number;
+}
+
+export interface AtRiskStudent {
+  studentId: string;
+  studentName: string;
+  status: string;
+  riskScore: number; // Errors weigh 3, warnings 1
+  errorCount: number;
+  warningCount: number;
+  flags: StudentFlag[];
+}
+
+// Flag totals for a cohort, with its students ranked by risk (highest first)
+export interface CohortFlagReport {
+  cohort: string;
+  studentCount: number;
+  flaggedStudentCount: number;
+  byType: FlagTypeCount[];
+  bySeverity: { error: number; warning: number; info: number };
+  atRisk: AtRiskStudent[];
+}
+
 // Preceptor Management
 export interface Preceptor {
   id: string;
@@ -462,6 +677,8 @@ export interface CompHoursEarned {
   notes?: string;
   expirationDate?: string; // When these hours expire (e.g., end of fiscal year)
   createdAt: string;
+  approvalStatus?: 'approved' | 'pending'; // Set from the comp hours policy when hours are earned
+  approvedAt?: string;
 }
 
 export interface CompHoursUsed {
@@ -482,6 +699,8 @@ export interface CompHoursSummary {
   expiringSoon: number; // Hours expiring within 6 months
   expiringDate?: string; // Nearest expiration date
   daysUntilExpiry?: number;
+  forfeited: number; // Earned hours that expired unused
+  unallocatedUsed: number; // Used hours with no unexpired earned hours to draw from
 }
 
 export interface CompHoursExpirationWarning {
@@ -491,11 +710,120 @@ export interface CompHoursExpirationWarning {
   alertLevel: 'green' | 'yellow' | 'red'; // green (> 6 months) | yellow (3-6 months) | red (< 3 months)
 }
 
+export interface CompHoursAllocation {
+  id: string;
+  usedId: string;
+  earnedId: string;
+  hours: number;
+  createdAt: string;
+}
+
+export interface CompHoursLot extends CompHoursEarned {
+  consumed: number;
+  forfeited: number;
+  remaining: number;
+  status: 'available' | 'consumed' | 'forfeited';
+}
+
+export interface CompHoursLedger {
+  lots: CompHoursLot[];
+  allocations: CompHoursAllocation[];
+  available: number;
+  forfeited: number;
+  unallocatedUsed: number;
+  forfeitureSchedule: CompHoursExpirationWarning[];
+}
+
+export interface CompHoursPolicy {
+  accrualCap?: number; // Most hours that can be banked (available plus pending approval)
+  expirationMonths?: number; // Earned hours expire this many months after the date earned
+  requiresApproval: boolean;
+  warningDaysRed: number;
+  warningDaysYellow: number;
+  updatedAt?: string;
+}
+
+// Header/footer details printed on exported PDF reports
+export interface ReportBranding {
+  programName: string;
+  institutionName?: string;
+  preparedBy?: string;
+  footerText?: string;
+}
+
+// A current reminder from one of the alert sources
+export interface Reminder {
+  key: string; // Stable per item and date; passed back to snooze it
+  category: string;
+  title: string;
+  body: string;
+  route: string;
+}
+
+export interface ReminderSetting {
+  category: string;
+  label: string;
+  enabled: boolean;
+}
+
+// What a reminder notification just announced
+export interface ReminderNotice {
+  title: string;
+  body: string;
+  route: string; // App route to open
+}
+
+export type ReportKind = 'student_transcript' | 'skills_checklist' | 'attendance_record' | 'vbon_compliance' | 'site_roster';
+
+export interface ReportRequest {
+  kind: ReportKind;
+  studentId?: string; // Required for the student reports
+  frameworkId?: string; // Compliance report framework; defaults to the current one
+  outputPath: string;
+  branding: ReportBranding;
+  signatureLines?: string[];
+}
+
+export interface CompTimesheetEntry {
+  date: string;
+  kind: 'Earned' | 'Used' | 'Forfeited';
+  description: string;
+  hours: number;
+  expirationDate?: string;
+  status: 'approved' | 'pending';
+  notes?: string;
+}
+
+export interface CompTimesheet {
+  startDate: string;
+  endDate: string;
+  openingBalance: number;
+  earned: number;
+  used: number;
+  forfeited: number;
+  closingBalance: number;
+  pendingApproval: number;
+  entries: CompTimesheetEntry[];
+}
+
 // ==================== VBON COMPLIANCE MAPPING ====================
 
-// VBON Regulation from Virginia Board of Nursing
+// Regulatory framework: a state board of nursing or an accreditor, each with its own regulation catalog
+export interface RegulatoryFramework {
+  id: string; // e.g., "vbon", "ncbon", "acen"
+  code: string; // e.g., "VBON"
+  name: string;
+  frameworkType: 'state_board' | 'accreditor';
+  jurisdiction?: string; // Two-letter state for state boards
+  description?: string;
+  isCurrent: boolean;
+  createdAt: string;
+}
+
+// VBON Regulation from Virginia Board of Nursing (or another framework's catalog)
 export interface VBONRegulation {
   id: string;
+  frameworkId?: string; // Defaults to "vbon"
   code: string; // e.g., "B.1", "B.2", "B.6"
   section: string; // e.g., "18VAC90-27-100", main section reference
   category: string; // e.g., "Curriculum Content", "Clinical Requirements", "Faculty Requirements"
@@ -503,6 +831,7 @@ export interface VBONRegulation {
   description: string; // Full description of the requirement
   sortOrder: number;
   isActive: number; // SQLite boolean
+  editionId?: string; // Catalog edition the wording comes from
   createdAt: string;
   updatedAt: string;
 }
@@ -520,6 +849,9 @@ export interface VBONMapping {
   coverageStatus: 'not_covered' | 'partial' | 'covered';
   lastReviewedDate?: string;
   reviewedBy?: string;
+  reviewedEditionId?: string; // Catalog edition the regulation was at when last reviewed
+  needsReview?: boolean; // Set when a new edition changes or removes the regulation
+  reviewReason?: string;
   createdAt: string;
   updatedAt: string;
 }
@@ -527,18 +859,64 @@ export interface VBONMapping {
 // Combined view for display
 export interface VBONRegulationWithMapping extends VBONRegulation {
   mapping?: VBONMapping;
-  linkedLessonPlans?: LessonPlan[];
+  lessonPlans: EvidenceLessonPlan[];
+  taughtDates: string[];
   materialLinksParsed?: Array<{ title: string; url: string; slideNumbers?: string }>;
 }
 
 // VBON Compliance Summary
+export interface EvidenceLessonPlan {
+  id: string;
+  date: string;
+  courseName: string;
+  weekNumber?: number;
+  chapter?: string;
+  topic: string;
+  assessmentMethod?: string;
+  taught: boolean; // Dated on or before the report date
+}
+
+export interface EvidenceMaterial {
+  title: string;
+  url?: string;
+  materialType: string; // Teaching material type, or "mapping" for links entered on the mapping
+  lessonPlanId?: string;
+  packagedFile?: string;
+}
+
+export interface RegulationEvidence extends VBONRegulation {
+  coverageStatus: 'not_covered' | 'partial' | 'covered';
+  syllabusReferences: string[];
+  lessonPlans: EvidenceLessonPlan[];
+  materials: EvidenceMaterial[];
+  assessmentMethods: string[];
+  clinicalExperience?: string;
+  lastReviewedDate?: string;
+  daysSinceReview?: number;
+  isStale: boolean;
+  gaps: string[];
+}
+
+export interface VBONEvidenceReport {
+  frameworkId: string;
+  frameworkCode: string;
+  frameworkName: string;
+  generatedOn: string;
+  reviewIntervalDays: number;
+  regulations: RegulationEvidence[];
+  gapCount: number;
+  staleCount: number;
+}
+
 export interface VBONComplianceSummary {
+  frameworkId: string;
   totalRegulations: number;
   coveredCount: number;
   partialCount: number;
   notCoveredCount: number;
   percentageComplete: number;
   lastAuditDate?: string;
+  needsReviewCount: number; // Mappings flagged by a catalog change
   regulationsByCategory: Array<{
     category: string;
     total: number;
@@ -547,3 +925,106 @@ export interface VBONComplianceSummary {
     notCovered: number;
   }>;
 }
+
+export interface FrameworkComplianceSummary {
+  framework: RegulatoryFramework;
+  summary: VBONComplianceSummary;
+}
+
+// Imported edition of a framework's regulation catalog
+export interface RegulationCatalogEdition {
+  id: string;
+  frameworkId: string;
+  edition: string; // e.g., "2024"
+  effectiveDate?: string;
+  sourceFile?: string;
+  notes?: string;
+  regulationCount: number;
+  isCurrent: boolean; // Applied to the live catalog
+  importedAt: string;
+}
+
+export interface RegulationSnapshot {
+  id: string;
+  code: string;
+  section: string;
+  category: string;
+  title: string;
+  description: string;
+  sortOrder: number;
+}
+
+export interface RegulationChange {
+  before: RegulationSnapshot;
+  after: RegulationSnapshot;
+  changedFields: Array<'code' | 'section' | 'category' | 'title' | 'description'>;
+  hasMapping: boolean;
+}
+
+export interface RegulationCatalogDiff {
+  frameworkId: string;
+  fromEditionId?: string; // Absent when compared against the live catalog
+  toEditionId: string;
+  added: RegulationSnapshot[];
+  removed: RegulationSnapshot[];
+  changed: RegulationChange[];
+  unchangedCount: number;
+  affectedMappingCount: number;
+}
+
+export interface RegulationCatalogImportRequest {
+  frameworkId?: string;
+  filePath: string; // Just the file name when `content` is given
+  content?: string;
+  edition?: string; // Required unless the JSON file names its edition
+  effectiveDate?: string;
+  notes?: string;
+  apply: boolean;
+}
+
+export interface RegulationCatalogImportResult {
+  edition: RegulationCatalogEdition;
+  diff: RegulationCatalogDiff;
+  mappingsFlagged: number;
+}
+
+// Spreadsheet (CSV/XLSX) import
+export type ImportTarget = 'students' | 'grades' | 'attendance';
+
+export interface ImportField {
+  name: string; // Key used in ImportMapping.columns, e.g. "first_name"
+  required: boolean;
+}
+
+export interface ImportMapping {
+  target: ImportTarget;
+  columns: Record<string, string>; // Target field -> spreadsheet column header
+  defaults?: Record<string, string>; // Constant values for unmapped fields (e.g. cohort, semester)
+  sheetName?: string; // XLSX only; defaults to the first sheet
+  dateFormat?: string; // chrono format tried first when parsing dates, e.g. "%d/%m/%Y"
+}
+
+export interface SpreadsheetInfo {
+  sheetNames: string[];
+  headers: string[];
+  rowCount: number;
+  sampleRows: string[][];
+}
+
+export interface ImportRowPreview {
+  rowNumber: number; // Spreadsheet row number (header is row 1)
+  values: Record<string, string>;
+  studentId?: string; // Matched student for grades/attendance
+  duplicateOf?: string; // Existing record this row would duplicate
+  errors: string[];
+  warnings: string[];
+}
+
+export interface ImportPreview {
+  target: ImportTarget;
+  totalRows: number;
+  validRows: number;
+  errorRows: number;
+  duplicateRows: number;
+  rows: ImportRowPreview[];
+}